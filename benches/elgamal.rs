@@ -1,9 +1,10 @@
-use ark_bls12_381::{Bls12_381 as BlsCurve, G1Affine};
+use ark_bls12_381::Bls12_381 as BlsCurve;
 use ark_ec::pairing::Pairing;
-use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
-use ark_std::{test_rng, UniformRand};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
 use criterion::{criterion_group, criterion_main, Criterion};
+use fde::encrypt::elgamal::KeyPair;
 use fde::encrypt::EncryptionEngine;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -19,9 +20,8 @@ fn bench_elgamal(c: &mut Criterion) {
     let mut group = c.benchmark_group("split-elgamal");
     group.sample_size(10);
 
-    let rng = &mut test_rng();
-    let encryption_sk = Scalar::rand(rng);
-    let encryption_pk = (G1Affine::generator() * encryption_sk).into_affine();
+    let rng = &mut StdRng::seed_from_u64(0);
+    let encryption_keys = KeyPair::<<BlsCurve as Pairing>::G1>::generate(rng);
 
     let scalars: Vec<Scalar> = (0..4096 * N).map(|_| Scalar::rand(rng)).collect();
 
@@ -35,10 +35,11 @@ fn bench_elgamal(c: &mut Criterion) {
             println!("{}/{}", i, 4096 * N);
         }
         let split_scalar = SplitScalar::from(*scalar);
-        let (split_cipher, randomness) = split_scalar.encrypt::<Elgamal, _>(&encryption_pk, rng);
+        let (split_cipher, randomness) =
+            split_scalar.encrypt::<Elgamal, _>(encryption_keys.pk(), rng);
         let long_cipher = <Elgamal as EncryptionEngine>::encrypt_with_randomness(
-            &scalar,
-            &encryption_pk,
+            scalar,
+            encryption_keys.pk(),
             &randomness,
         );
 
@@ -76,5 +77,27 @@ fn bench_elgamal(c: &mut Criterion) {
     group.finish()
 }
 
-criterion_group!(benches, bench_elgamal);
+fn bench_batch_encryption(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch-elgamal");
+    group.sample_size(10);
+
+    let rng = &mut StdRng::seed_from_u64(0);
+    let encryption_keys = KeyPair::<<BlsCurve as Pairing>::G1>::generate(rng);
+    let data: Vec<Scalar> = (0..4096u32).map(Scalar::from).collect();
+
+    group.bench_function("naive-encryption-4096", |b| {
+        b.iter(|| {
+            data.iter()
+                .map(|m| Elgamal::encrypt(m, encryption_keys.pk(), rng))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("batch-encryption-4096", |b| {
+        b.iter(|| Elgamal::encrypt_batch(&data, encryption_keys.pk(), rng))
+    });
+
+    group.finish()
+}
+
+criterion_group!(benches, bench_elgamal, bench_batch_encryption);
 criterion_main!(benches);
@@ -0,0 +1,43 @@
+use ark_ec::pairing::Pairing;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly_commit::DenseUVPolynomial;
+use ark_std::{test_rng, UniformRand};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fde::commit::kzg::Powers;
+
+const LOG_DEGREES: [usize; 3] = [10, 13, 16];
+
+type TestCurve = ark_bls12_381::Bls12_381;
+type Scalar = <TestCurve as Pairing>::ScalarField;
+type UniPoly = DensePolynomial<Scalar>;
+
+fn bench_commit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kzg");
+    group.sample_size(10);
+
+    let rng = &mut test_rng();
+    let tau = Scalar::rand(rng);
+    let max_degree = 1 << LOG_DEGREES[LOG_DEGREES.len() - 1];
+    let powers = Powers::<TestCurve>::unsafe_setup(tau, max_degree);
+    let mut precomputed = powers.clone();
+    precomputed.precompute(12).unwrap();
+
+    for log_degree in LOG_DEGREES {
+        let poly = UniPoly::rand((1 << log_degree) - 1, rng);
+        group.bench_with_input(
+            BenchmarkId::new("commit-g1", log_degree),
+            &poly,
+            |b, poly| b.iter(|| powers.commit_g1(poly).unwrap()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("commit-g1-precomputed", log_degree),
+            &poly,
+            |b, poly| b.iter(|| precomputed.commit_g1(poly).unwrap()),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_commit);
+criterion_main!(benches);
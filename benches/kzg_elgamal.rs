@@ -1,11 +1,12 @@
 use ark_ec::pairing::Pairing;
-use ark_ec::{CurveGroup, Group};
 use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::{EvaluationDomain, Evaluations, GeneralEvaluationDomain};
-use ark_std::{test_rng, UniformRand};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
 use criterion::{criterion_group, criterion_main, Criterion};
 use fde::commit::kzg::Powers;
+use fde::encrypt::elgamal::KeyPair;
 
 const DATA_LOG_SIZE: usize = 12; // 4096 = 2^12
 const N: usize = Scalar::MODULUS_BIT_SIZE as usize / fde::encrypt::elgamal::MAX_BITS + 1;
@@ -24,18 +25,17 @@ fn bench_proof(c: &mut Criterion) {
     let data_size = 1 << DATA_LOG_SIZE;
     assert_eq!(data_size, 4096);
 
-    let rng = &mut test_rng();
+    let rng = &mut StdRng::seed_from_u64(0);
     let tau = Scalar::rand(rng);
     let powers = Powers::<TestCurve>::unsafe_setup(tau, data_size + 1);
 
-    let encryption_sk = Scalar::rand(rng);
-    let encryption_pk = (<TestCurve as Pairing>::G1::generator() * encryption_sk).into_affine();
+    let encryption_keys = KeyPair::<<TestCurve as Pairing>::G1>::generate(rng);
 
     println!("Generating encryption proofs for 4096 * 8 split field elements...");
     println!("This might take a few minutes and it's not included in the actual benchmarks.");
     let t_start = std::time::Instant::now();
     let data: Vec<Scalar> = (0..data_size).map(|_| Scalar::rand(rng)).collect();
-    let encryption_proof = EncryptionProof::new(&data, &encryption_pk, &powers, rng);
+    let encryption_proof = EncryptionProof::new(&data, encryption_keys.pk(), &powers, rng);
     let elapsed = std::time::Instant::now().duration_since(t_start).as_secs();
     println!("Generated encryption proofs, elapsed time: {} [s]", elapsed);
 
@@ -44,7 +44,7 @@ fn bench_proof(c: &mut Criterion) {
 
     let evaluations = Evaluations::from_vec_and_domain(data, domain);
     let f_poly: UniPoly = evaluations.interpolate_by_ref();
-    let com_f_poly = powers.commit_g1(&f_poly);
+    let com_f_poly = powers.commit_g1(&f_poly).unwrap();
 
     for i in 0..=12 {
         let subset_size = 1 << i;
@@ -56,7 +56,7 @@ fn bench_proof(c: &mut Criterion) {
         let subset_evaluations = fde::veck::subset_evals(&evaluations, &subset_indices, subdomain);
 
         let f_s_poly: UniPoly = subset_evaluations.interpolate_by_ref();
-        let com_f_s_poly = powers.commit_g1(&f_s_poly);
+        let com_f_s_poly = powers.commit_g1(&f_s_poly).unwrap();
 
         let sub_encryption_proof = encryption_proof.subset(&subset_indices);
 
@@ -65,7 +65,7 @@ fn bench_proof(c: &mut Criterion) {
                 Proof::new(
                     &f_poly,
                     &f_s_poly,
-                    &encryption_sk,
+                    encryption_keys.sk(),
                     sub_encryption_proof.clone(),
                     &powers,
                     rng,
@@ -78,7 +78,7 @@ fn bench_proof(c: &mut Criterion) {
             let proof = Proof::new(
                 &f_poly,
                 &f_s_poly,
-                &encryption_sk,
+                encryption_keys.sk(),
                 sub_encryption_proof.clone(),
                 &powers,
                 rng,
@@ -86,7 +86,7 @@ fn bench_proof(c: &mut Criterion) {
             .unwrap();
             b.iter(|| {
                 assert!(proof
-                    .verify(com_f_poly, com_f_s_poly, encryption_pk, &powers)
+                    .verify(com_f_poly, com_f_s_poly, *encryption_keys.pk(), &powers)
                     .is_ok())
             })
         });
@@ -3,7 +3,8 @@ use ark_ec::pairing::Pairing;
 use ark_ff::{BigInteger, PrimeField};
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::{EvaluationDomain, Evaluations, GeneralEvaluationDomain};
-use ark_std::{test_rng, UniformRand};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+use ark_std::UniformRand;
 use criterion::{criterion_group, criterion_main, Criterion};
 use fde::commit::kzg::Powers;
 use fde::veck::kzg::paillier::Server;
@@ -24,7 +25,7 @@ fn bench_proof(c: &mut Criterion) {
     //let data_size = 1 << 12;
     //let data: Vec<Scalar> = (0..data_size).map(|_| Scalar::rand(rng)).collect();
     //let domain = GeneralEvaluationDomain::new(DATA_SIZE).unwrap();
-    let rng = &mut test_rng();
+    let rng = &mut StdRng::seed_from_u64(0);
     let tau = Scalar::rand(rng);
     let powers = Powers::<TestCurve>::unsafe_setup_eip_4844(tau, 1 << 12); // TODO data_size
     let server = Server::new(rng);
@@ -1,6 +1,7 @@
 use ark_ec::pairing::Pairing;
 use ark_ff::PrimeField;
-use ark_std::{rand::RngCore, test_rng, UniformRand};
+use ark_std::rand::{rngs::StdRng, RngCore, SeedableRng};
+use ark_std::UniformRand;
 use criterion::{criterion_group, criterion_main, Criterion};
 use fde::commit::kzg::Powers;
 #[cfg(feature = "parallel")]
@@ -17,7 +18,7 @@ type RangeProof = fde::range_proof::RangeProof<TestCurve, TestHash>;
 fn bench_proof(c: &mut Criterion) {
     let mut group = c.benchmark_group("range-proof");
 
-    let rng = &mut test_rng();
+    let rng = &mut StdRng::seed_from_u64(0);
     let tau = Scalar::rand(rng);
     let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
 
@@ -42,7 +43,7 @@ fn bench_multiple_proofs(c: &mut Criterion) {
     let mut group = c.benchmark_group("range-proof");
     group.sample_size(10);
 
-    let rng = &mut test_rng();
+    let rng = &mut StdRng::seed_from_u64(0);
     let tau = Scalar::rand(rng);
     let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
 
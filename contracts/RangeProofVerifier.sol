@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.13;
+
+import { BN254 } from "./BN254.sol";
+import { Types } from "./Types.sol";
+import { Constants } from "./Constants.sol";
+
+/// @notice Verifies `fde` range proofs over BN254 proving that the value committed to in `f` lies
+/// in [0, 2^n). The parameters of a trusted setup and a bit size are provided by a contract
+/// generated with `fde::range_proof::solidity::verifier_contract`.
+abstract contract RangeProofVerifier is BN254 {
+    uint256 internal constant HALF_Q = (Constants.PRIME_Q - 1) / 2;
+
+    /// @return the base two logarithm of the size of the evaluation domain
+    function logDomainSize() internal pure virtual returns (uint256);
+
+    /// @return generator the generator of the evaluation domain
+    /// @return last its inverse, i.e. the last element of the domain
+    function omega() internal pure virtual returns (uint256 generator, uint256 last);
+
+    /// @return tau * G2 from the trusted setup
+    function tauG2() internal pure virtual returns (Types.G2Point memory);
+
+    /// @return the domain separator, bit size and domain generator as serialized by the prover
+    function transcriptPrefix() internal pure virtual returns (bytes memory);
+
+    /// @param proof [g(rho), g(rho * omega), w_cap(rho), f, g, q, aggregate proof, shifted proof]
+    /// where every G1 point is given by its affine (x, y) coordinates
+    function verifyProof(uint256[13] calldata proof) external view returns (bool) {
+        for (uint256 i = 0; i < 3; i++) {
+            if (proof[i] >= Constants.PRIME_R) return false;
+        }
+        for (uint256 i = 3; i < 13; i += 2) {
+            if (!isOnCurve(proof[i], proof[i + 1])) return false;
+        }
+
+        (uint256 tau, uint256 rho, uint256 aggregation) = challenges(proof);
+        (uint256 rel1, uint256 rel2, uint256 invShift) = rhoRelations(rho);
+        if (evaluationSum(proof[0], proof[1], rho, tau, rel1, rel2, invShift) != proof[2]) {
+            return false;
+        }
+
+        Types.G1Point memory f = Types.G1Point(proof[3], proof[4]);
+        Types.G1Point memory g = Types.G1Point(proof[5], proof[6]);
+        Types.G1Point memory q = Types.G1Point(proof[7], proof[8]);
+        Types.G1Point memory wCap = plus(mul(f, rel1), mul(q, rel2));
+        Types.G1Point memory commitment = plus(g, mul(wCap, aggregation));
+        uint256 value = addmod(proof[0], mulmod(proof[2], aggregation, Constants.PRIME_R), Constants.PRIME_R);
+
+        (uint256 generator,) = omega();
+        return kzgCheck(commitment, value, rho, Types.G1Point(proof[9], proof[10]))
+            && kzgCheck(g, proof[1], mulmod(rho, generator, Constants.PRIME_R), Types.G1Point(proof[11], proof[12]));
+    }
+
+    function challenges(uint256[13] calldata proof)
+        internal
+        pure
+        returns (uint256 tau, uint256 rho, uint256 aggregation)
+    {
+        bytes memory transcript = abi.encodePacked(
+            transcriptPrefix(), compress(proof[3], proof[4]), compress(proof[5], proof[6]), "tau"
+        );
+        tau = challenge(transcript);
+        transcript = abi.encodePacked(transcript, "rho");
+        rho = challenge(transcript);
+        transcript = abi.encodePacked(transcript, "aggregation_challenge");
+        aggregation = challenge(transcript);
+    }
+
+    // returns (rho^n - 1) / (rho - 1), rho^n - 1 and 1 / (rho - omega^(n - 1))
+    function rhoRelations(uint256 rho) internal view returns (uint256 rel1, uint256 rel2, uint256 invShift) {
+        uint256 r = Constants.PRIME_R;
+        uint256 rhoN = rho;
+        for (uint256 i = 0; i < logDomainSize(); i++) {
+            rhoN = mulmod(rhoN, rhoN, r);
+        }
+        (, uint256 last) = omega();
+        rel2 = addmod(rhoN, r - 1, r);
+        uint256 d1 = addmod(rho, r - 1, r);
+        uint256 d2 = addmod(rho, r - last, r);
+        uint256 inv = inverse(mulmod(d1, d2, r));
+        rel1 = mulmod(rel2, mulmod(inv, d2, r), r);
+        invShift = mulmod(inv, d1, r);
+    }
+
+    // w1(rho) + tau * w2(rho) + tau^2 * w3(rho)
+    function evaluationSum(
+        uint256 g,
+        uint256 gOmega,
+        uint256 rho,
+        uint256 tau,
+        uint256 rel1,
+        uint256 rel2,
+        uint256 invShift
+    ) internal pure returns (uint256) {
+        uint256 r = Constants.PRIME_R;
+        (, uint256 last) = omega();
+        uint256 w1 = mulmod(g, rel1, r);
+        uint256 w2 = mulmod(mulmod(g, addmod(1, r - g, r), r), mulmod(rel2, invShift, r), r);
+        uint256 a = addmod(g, r - mulmod(2, gOmega, r), r);
+        uint256 w3 = mulmod(mulmod(a, addmod(1, r - a, r), r), addmod(rho, r - last, r), r);
+        return addmod(w1, mulmod(tau, addmod(w2, mulmod(tau, w3, r), r), r), r);
+    }
+
+    // e(commitment - value * G1 + point * proof, G2) * e(-proof, tau * G2) == 1, the third pair
+    // of the pairing check is the point at infinity
+    function kzgCheck(
+        Types.G1Point memory commitment,
+        uint256 value,
+        uint256 point,
+        Types.G1Point memory proof
+    ) internal view returns (bool) {
+        Types.G1Point memory lhs =
+            plus(plus(commitment, mul(P1(), Constants.PRIME_R - value)), mul(proof, point));
+        return pairingCheck(lhs, P2(), negate(proof), tauG2(), Types.G1Point(0, 0), P2());
+    }
+
+    // arkworks compressed encoding: little-endian x with the sign of y and the infinity flag stored
+    // in the two most significant bits
+    function compress(uint256 x, uint256 y) internal pure returns (bytes32) {
+        if (x == 0 && y == 0) return bytes32(uint256(0x40));
+        uint256 encoded = reverseBytes(x);
+        if (y > HALF_Q) encoded |= 0x80;
+        return bytes32(encoded);
+    }
+
+    // Fiat-Shamir challenges are the little-endian keccak256 digests reduced modulo r
+    function challenge(bytes memory transcript) internal pure returns (uint256) {
+        return reverseBytes(uint256(keccak256(transcript))) % Constants.PRIME_R;
+    }
+
+    function isOnCurve(uint256 x, uint256 y) internal pure returns (bool) {
+        uint256 q = Constants.PRIME_Q;
+        if (x >= q || y >= q) return false;
+        if (x == 0 && y == 0) return true;
+        return mulmod(y, y, q) == addmod(mulmod(mulmod(x, x, q), x, q), 3, q);
+    }
+
+    function inverse(uint256 a) internal view returns (uint256) {
+        uint256[6] memory input = [uint256(0x20), 0x20, 0x20, a, Constants.PRIME_R - 2, Constants.PRIME_R];
+        uint256[1] memory out;
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x05, input, 0xc0, out, 0x20)
+        }
+        require(success, "RangeProofVerifier: modexp failed");
+        return out[0];
+    }
+
+    function reverseBytes(uint256 v) internal pure returns (uint256) {
+        v = ((v & 0xff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00) >> 8)
+            | ((v & 0x00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff) << 8);
+        v = ((v & 0xffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000) >> 16)
+            | ((v & 0x0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff) << 16);
+        v = ((v & 0xffffffff00000000ffffffff00000000ffffffff00000000ffffffff00000000) >> 32)
+            | ((v & 0x00000000ffffffff00000000ffffffff00000000ffffffff00000000ffffffff) << 32);
+        v = ((v & 0xffffffffffffffff0000000000000000ffffffffffffffff0000000000000000) >> 64)
+            | ((v & 0x0000000000000000ffffffffffffffff0000000000000000ffffffffffffffff) << 64);
+        return (v >> 128) | (v << 128);
+    }
+}
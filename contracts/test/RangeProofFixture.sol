@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.13;
+
+import { RangeProofVerifier } from "contracts/RangeProofVerifier.sol";
+import { Types } from "contracts/Types.sol";
+
+/// @notice Verifies `fde` range proofs over BN254 proving that the value committed to in `f` lies
+/// in [0, 2^8). Generated by `fde::range_proof::solidity`, do not edit by hand.
+contract RangeProofVerifier8 is RangeProofVerifier {
+    function logDomainSize() internal pure override returns (uint256) {
+        return 3;
+    }
+
+    function omega() internal pure override returns (uint256, uint256) {
+        return (0x2b337de1c8c14f22ec9b9e2f96afef3652627366f8170a0a948dad4ac1bd5e80, 0x130b17119778465cfb3acaee30f81dee20710ead41671f568b11d9ab07b95a9b);
+    }
+
+    // tau * G2 from the trusted setup, coordinates are encoded as `x0 * i + x1`
+    function tauG2() internal pure override returns (Types.G2Point memory) {
+        return Types.G2Point({
+            x0: 0x2e99d08a8067c9b3a676fc036189692c35018295651adf2f4bd8131ada006be9,
+            x1: 0x073544a7faa45857386f4d40063f6df57b8447599f53e94adb8de3d1bf23dd8c,
+            y0: 0x305ba413b77c578cdb63d30ee75b4ff90841756efaf642ee9f61a07cf8cbf28a,
+            y1: 0x23516fbcad5dd180b5d88db543167ff199b371bba13f78b4b68ab455efd44991
+        });
+    }
+
+    function transcriptPrefix() internal pure override returns (bytes memory) {
+        return hex"0f000000000000006664652072616e67652070726f6f660800000000000000805ebdc14aad8d940a0a17f86673625236efaf962f9e9bec224fc1c8e17d332b";
+    }
+}
+
+/// @notice A proof of 0 <= 100 < 2^8 generated by the Rust prover for the verifier above.
+library RangeProofFixture {
+    function proof() internal pure returns (uint256[13] memory) {
+        return [
+            uint256(0x2015682750f6fcbcb63ec49985be59f54a79745bd9acfb87d50143a7ada38b37),
+            0x2c9d5bc74802f78b45935aded49db930ca3203efd5968681117c24166914851d,
+            0x1ba725e235532c80238b99c6f94c0a5d6598b754b6c3fe303896e9e72d8c4394,
+            0x1f6f809034ca7b3dd56aff75dd58d57a3ca23cf73ba494ab42a9dd7ec0452537,
+            0x2c82134c6b1bb3f324377ef8ded104b2e30aa57ebee881f94a07783b7cb4ac0a,
+            0x25fb2f7ef66557ba354ea4b293bd804cc25802d03b81e627a61d92c329466939,
+            0x1c3c79ab238841f7e64cd48b0352d7333004b925d7f20b1b3c2c0de7d47959aa,
+            0x1e2b7e4ce5c1c4990bef240cf4585883f98745b63f4a3ca06793181a6b1a043d,
+            0x0934a13f5c71cbc08595b6fa0459b06e7bd52cf8fbbef745b536da86532c0d4f,
+            0x2d6813fb045d84192aa0d01c5f4ecc0182a5a14c6944d59fc92084cc68e55f7d,
+            0x2c73c32e17addec51ec18560f8954d112d0740e3ac15a2116f4e6b37ad9bb322,
+            0x022677e176819711d96eaa31a49897ac09a6b981edfa51d5e25fd9ea566950fb,
+            0x1224d7f90f8dd900dbd75639e15a47220b3f1d1dc360a19a21f492ae42bd0c6b
+        ];
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.13;
+
+import { Constants } from "contracts/Constants.sol";
+import { RangeProofVerifier8, RangeProofFixture } from "./RangeProofFixture.sol";
+
+/// @notice Runs the generated verifier on a proof emitted by the Rust prover, see
+/// `foundry_fixture_is_up_to_date` in `src/range_proof/solidity/mod.rs`.
+contract RangeProofVerifierTest {
+    RangeProofVerifier8 internal verifier;
+
+    function setUp() public {
+        verifier = new RangeProofVerifier8();
+    }
+
+    function testAcceptsProofOfRustProver() public view {
+        require(verifier.verifyProof(RangeProofFixture.proof()), "valid proof rejected");
+    }
+
+    function testRejectsTamperedEvaluation() public view {
+        uint256[13] memory proof = RangeProofFixture.proof();
+        proof[1] = addmod(proof[1], 1, Constants.PRIME_R);
+        require(!verifier.verifyProof(proof), "tampered evaluation accepted");
+    }
+
+    function testRejectsSwappedWitnesses() public view {
+        uint256[13] memory proof = RangeProofFixture.proof();
+        (proof[9], proof[10], proof[11], proof[12]) = (proof[11], proof[12], proof[9], proof[10]);
+        require(!verifier.verifyProof(proof), "swapped witnesses accepted");
+    }
+
+    function testRejectsPointsOffTheCurve() public view {
+        uint256[13] memory proof = RangeProofFixture.proof();
+        proof[4] ^= 1;
+        require(!verifier.verifyProof(proof), "point off the curve accepted");
+    }
+}
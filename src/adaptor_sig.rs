@@ -4,14 +4,14 @@ use ark_crypto_primitives::Error;
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::Field;
 use ark_serialize::CanonicalSerialize;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
 use ark_std::UniformRand;
 use digest::Digest;
 
 pub trait AdaptorSignatureScheme: SignatureScheme {
     type PreSignature;
 
-    fn pre_sign<R: Rng>(
+    fn pre_sign<R: Rng + CryptoRng>(
         adaptor_pk: &Self::PublicKey,
         signer_sk: &Self::SecretKey,
         message: &[u8],
@@ -39,7 +39,7 @@ pub trait AdaptorSignatureScheme: SignatureScheme {
 
 impl<C: CurveGroup, D: Digest + Send + Sync> AdaptorSignatureScheme for Schnorr<C, D> {
     type PreSignature = Signature<C>;
-    fn pre_sign<R: Rng>(
+    fn pre_sign<R: Rng + CryptoRng>(
         adaptor_pk: &Self::PublicKey,
         signer_sk: &Self::SecretKey,
         message: &[u8],
@@ -139,14 +139,14 @@ fn hash_challenge<C: CurveGroup, D: Digest>(
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::tests::test_rng;
     use ark_ec::Group;
     use ark_secp256k1::Projective as Secp256k1;
-    use ark_std::test_rng;
     use sha3::Keccak256;
 
     type Scheme = Schnorr<Secp256k1, Keccak256>;
 
-    fn keygen<R: Rng>(
+    fn keygen<R: Rng + CryptoRng>(
         rng: &mut R,
     ) -> (
         <Scheme as SignatureScheme>::PublicKey,
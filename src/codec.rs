@@ -0,0 +1,102 @@
+//! Reversible encoding of byte strings as field elements.
+use ark_ff::{BigInteger, PrimeField};
+use thiserror::Error as ErrorT;
+
+/// Number of bytes packed into a single field element.
+pub const CHUNK_SIZE: usize = 31;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("field with {0} bits is too small to hold {CHUNK_SIZE} bytes")]
+    FieldTooSmall(u32),
+    #[error("missing length element")]
+    MissingLength,
+    #[error("encoded length {length} doesn't match {elements} data elements")]
+    LengthMismatch { length: u64, elements: usize },
+    #[error("element {0} is out of range")]
+    InvalidElement(usize),
+}
+
+/// Returns the number of field elements [`encode`] produces for `len` bytes.
+pub fn encoded_len(len: usize) -> usize {
+    1 + len.div_ceil(CHUNK_SIZE)
+}
+
+/// Encodes `bytes` as their length followed by chunks of [`CHUNK_SIZE`] bytes, each interpreted as
+/// a little-endian integer. Since 31 bytes always fit into the scalar fields of the supported
+/// curves, encoding never reduces modulo the field order and [`decode`] can check that every
+/// element is in the expected range.
+pub fn encode<S: PrimeField>(bytes: &[u8]) -> Result<Vec<S>, Error> {
+    if (S::MODULUS_BIT_SIZE as usize) <= 8 * CHUNK_SIZE {
+        return Err(Error::FieldTooSmall(S::MODULUS_BIT_SIZE));
+    }
+    let mut elements = Vec::with_capacity(encoded_len(bytes.len()));
+    elements.push(S::from(bytes.len() as u64));
+    elements.extend(bytes.chunks(CHUNK_SIZE).map(S::from_le_bytes_mod_order));
+    Ok(elements)
+}
+
+pub fn decode<S: PrimeField>(elements: &[S]) -> Result<Vec<u8>, Error> {
+    let (length, data) = elements.split_first().ok_or(Error::MissingLength)?;
+    let length_bytes = length.into_bigint().to_bytes_le();
+    if length_bytes[8..].iter().any(|&b| b != 0) {
+        return Err(Error::InvalidElement(0));
+    }
+    let length = u64::from_le_bytes(length_bytes[..8].try_into().expect("8 bytes"));
+    if usize::try_from(length).map(encoded_len) != Ok(elements.len()) {
+        return Err(Error::LengthMismatch {
+            length,
+            elements: data.len(),
+        });
+    }
+
+    let mut bytes = Vec::with_capacity(data.len() * CHUNK_SIZE);
+    for (i, element) in data.iter().enumerate() {
+        let chunk = element.into_bigint().to_bytes_le();
+        if chunk[CHUNK_SIZE..].iter().any(|&b| b != 0) {
+            return Err(Error::InvalidElement(i + 1));
+        }
+        bytes.extend_from_slice(&chunk[..CHUNK_SIZE]);
+    }
+    // the padding of the last chunk has to be zero as well
+    let length = length as usize;
+    if bytes[length..].iter().any(|&b| b != 0) {
+        return Err(Error::InvalidElement(data.len()));
+    }
+    bytes.truncate(length);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::Scalar;
+    use ark_ff::Field;
+
+    #[test]
+    fn bytes_roundtrip() {
+        for len in [0, 1, 30, 31, 32, 62, 100] {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 7 + 255) as u8).collect();
+            let elements = encode::<Scalar>(&bytes).unwrap();
+            assert_eq!(elements.len(), encoded_len(len));
+            assert_eq!(decode(&elements).unwrap(), bytes);
+        }
+
+        let mut elements = encode::<Scalar>(&[0xff; 40]).unwrap();
+        assert_eq!(
+            decode(&elements[..2]),
+            Err(Error::LengthMismatch {
+                length: 40,
+                elements: 1
+            })
+        );
+        elements[1] += Scalar::from(2u8).pow([8 * CHUNK_SIZE as u64]);
+        assert_eq!(decode(&elements), Err(Error::InvalidElement(1)));
+        assert_eq!(decode::<Scalar>(&[]), Err(Error::MissingLength));
+        // non-zero padding
+        assert_eq!(
+            decode(&[Scalar::from(1u8), Scalar::from(256u16)]),
+            Err(Error::InvalidElement(1))
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Commitments to EIP-4844 blobs, compatible with the
+//! [polynomial commitments](https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/polynomial-commitments.md)
+//! of the consensus specs.
+use super::encoding;
+use super::ethereum::bit_reverse;
+use super::kzg::{Kzg, Powers};
+use crate::Error as CrateError;
+use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+use ark_ec::CurveGroup;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly_commit::DenseUVPolynomial;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error as ErrorT;
+
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+pub const FIELD_ELEMENTS_PER_BLOB: usize = 4096;
+pub const BYTES_PER_BLOB: usize = BYTES_PER_FIELD_ELEMENT * FIELD_ELEMENTS_PER_BLOB;
+/// Version byte of versioned hashes of KZG commitments.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("blob has {0} bytes, expected {BYTES_PER_BLOB}")]
+    InvalidLength(usize),
+    #[error("field element {0} is not canonical")]
+    NonCanonicalFieldElement(usize),
+    #[error(transparent)]
+    Commitment(#[from] CrateError),
+}
+
+/// Decodes a blob of 4096 big-endian encoded field elements, i.e. the evaluations of the blob
+/// polynomial over the roots of unity in bit-reversed order.
+pub fn blob_to_field_elements(blob: &[u8]) -> Result<Vec<Fr>, Error> {
+    if blob.len() != BYTES_PER_BLOB {
+        return Err(Error::InvalidLength(blob.len()));
+    }
+    blob.chunks_exact(BYTES_PER_FIELD_ELEMENT)
+        .enumerate()
+        .map(|(i, bytes)| {
+            encoding::scalar_from_bytes_be(bytes).map_err(|_| Error::NonCanonicalFieldElement(i))
+        })
+        .collect()
+}
+
+/// Encodes field elements as a blob, missing elements are filled with zeros.
+pub fn field_elements_to_blob(elements: &[Fr]) -> Result<Vec<u8>, Error> {
+    if elements.len() > FIELD_ELEMENTS_PER_BLOB {
+        return Err(Error::InvalidLength(
+            elements.len() * BYTES_PER_FIELD_ELEMENT,
+        ));
+    }
+    let mut blob = vec![0u8; BYTES_PER_BLOB];
+    for (element, bytes) in elements
+        .iter()
+        .zip(blob.chunks_exact_mut(BYTES_PER_FIELD_ELEMENT))
+    {
+        bytes.copy_from_slice(&encoding::scalar_to_bytes_be(element));
+    }
+    Ok(blob)
+}
+
+/// Returns the blob polynomial in coefficient form.
+pub fn blob_to_polynomial(blob: &[u8]) -> Result<DensePolynomial<Fr>, Error> {
+    let mut evaluations = blob_to_field_elements(blob)?;
+    bit_reverse(&mut evaluations);
+    let domain = domain();
+    Ok(DensePolynomial::from_coefficients_vec(
+        domain.ifft(&evaluations),
+    ))
+}
+
+/// Computes the KZG commitment of a blob like `blob_to_kzg_commitment` of the specs.
+///
+/// Since the roots of unity of the arkworks BLS12-381 scalar field are derived from the same
+/// generator as in the specs, the commitment equals the one of the specs when using the powers of
+/// the Ethereum KZG ceremony (see [`super::ethereum`]).
+///
+/// This is an MSM with the Lagrange basis if it was precomputed via
+/// [`Powers::add_lagrange_basis`] for a domain of size [`FIELD_ELEMENTS_PER_BLOB`].
+pub fn blob_to_kzg_commitment(blob: &[u8], powers: &Powers<Bls12_381>) -> Result<G1Affine, Error> {
+    let mut evaluations = blob_to_field_elements(blob)?;
+    bit_reverse(&mut evaluations);
+    Ok(powers.commit_evals(&evaluations, &domain())?.into_affine())
+}
+
+/// Opens the blob polynomial at `z` like `compute_kzg_proof` of the specs, returns the proof and
+/// the evaluation.
+///
+/// The proof is computed from the evaluation form (see [`Kzg::open_evals`]), which avoids an
+/// inverse FFT if the Lagrange basis was precomputed.
+pub fn compute_kzg_proof(
+    blob: &[u8],
+    z: Fr,
+    powers: &Powers<Bls12_381>,
+) -> Result<(G1Affine, Fr), Error> {
+    let mut evaluations = blob_to_field_elements(blob)?;
+    bit_reverse(&mut evaluations);
+    Ok(Kzg::open_evals(&evaluations, &domain(), z, powers)?)
+}
+
+/// Computes the versioned hash of a commitment as used in blob transactions, i.e. the SHA-256
+/// hash of the compressed commitment with its first byte replaced by the version.
+pub fn kzg_to_versioned_hash(commitment: &G1Affine) -> [u8; 32] {
+    let mut bytes = Vec::with_capacity(48);
+    commitment
+        .serialize_compressed(&mut bytes)
+        .expect("should not fail");
+    let mut hash: [u8; 32] = Sha256::digest(&bytes).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+fn domain() -> GeneralEvaluationDomain<Fr> {
+    GeneralEvaluationDomain::new(FIELD_ELEMENTS_PER_BLOB).expect("valid domain")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hex;
+    use crate::tests::test_rng;
+    use ark_poly::Polynomial;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn blob_commitment() {
+        let rng = &mut test_rng();
+        let mut powers = Powers::<Bls12_381>::unsafe_setup(Fr::rand(rng), FIELD_ELEMENTS_PER_BLOB);
+
+        let zero = vec![0u8; BYTES_PER_BLOB];
+        let commitment = blob_to_kzg_commitment(&zero, &powers).unwrap();
+        assert_eq!(
+            hex::encode(&kzg_to_versioned_hash(&commitment)),
+            "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+        );
+
+        // element i is the evaluation at the bit-reversed i-th root of unity
+        let elements: Vec<Fr> = (0..100).map(|_| Fr::rand(rng)).collect();
+        let blob = field_elements_to_blob(&elements).unwrap();
+        assert_eq!(blob_to_field_elements(&blob).unwrap()[..100], elements);
+        let poly = blob_to_polynomial(&blob).unwrap();
+        let domain = domain();
+        assert_eq!(poly.evaluate(&domain.element(0)), elements[0]);
+        assert_eq!(
+            poly.evaluate(&domain.element(FIELD_ELEMENTS_PER_BLOB / 2)),
+            elements[1]
+        );
+        assert_eq!(
+            poly.evaluate(&domain.element(FIELD_ELEMENTS_PER_BLOB / 4)),
+            elements[2]
+        );
+
+        let commitment = blob_to_kzg_commitment(&blob, &powers).unwrap();
+        assert_eq!(commitment, powers.commit_g1(&poly).unwrap().into_affine());
+        powers.add_lagrange_basis(&domain).unwrap();
+        assert_eq!(blob_to_kzg_commitment(&blob, &powers).unwrap(), commitment);
+
+        let z = Fr::rand(rng);
+        let (proof, y) = compute_kzg_proof(&blob, z, &powers).unwrap();
+        assert!(Kzg::verify_scalar(proof, commitment, z, y, &powers));
+
+        let mut invalid = blob;
+        invalid[..32].fill(0xff);
+        assert_eq!(
+            blob_to_kzg_commitment(&invalid, &powers),
+            Err(Error::NonCanonicalFieldElement(0))
+        );
+        assert_eq!(
+            blob_to_field_elements(&invalid[1..]),
+            Err(Error::InvalidLength(BYTES_PER_BLOB - 1))
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Powers-of-tau ceremony for generating a KZG setup among several parties, see [`Transcript`].
+use super::kzg::Powers;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Field;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::{UniformRand, Zero};
+use zeroize::Zeroizing;
+
+/// Public record of a contribution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contribution<C: Pairing> {
+    /// `s * g2` for the secret `s` of the contribution.
+    pub pubkey: C::G2Affine,
+    /// `tau * g1` after the contribution.
+    pub g1_tau: C::G1Affine,
+}
+
+/// The current powers together with all contributions so far.
+///
+/// The ceremony starts with the powers of `tau = 1`, i.e. the generators. Every participant
+/// samples a secret `s`, multiplies the `i`-th powers by `s^i` and publishes `s * g2` as the
+/// public key of the contribution. The resulting `tau` is the product of all secrets, so the
+/// setup is secure as long as a single participant discarded their secret.
+#[derive(Clone, Debug)]
+pub struct Transcript<C: Pairing> {
+    pub powers: Powers<C>,
+    pub contributions: Vec<Contribution<C>>,
+}
+
+impl<C: Pairing> Transcript<C> {
+    /// Starts a ceremony for `num_g1` G1 and `num_g2` G2 powers.
+    pub fn new(num_g1: usize, num_g2: usize) -> Self {
+        Self {
+            powers: Powers::new(
+                vec![C::G1Affine::generator(); num_g1],
+                vec![C::G2Affine::generator(); num_g2],
+            ),
+            contributions: Vec::new(),
+        }
+    }
+
+    /// Updates the powers with a fresh secret which is wiped from memory afterwards.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> Contribution<C> {
+        let secret = Zeroizing::new(loop {
+            let secret = C::ScalarField::rand(rng);
+            if !secret.is_zero() {
+                break secret;
+            }
+        });
+        let (powers, contribution) = contribute(&self.powers, *secret);
+        self.powers = powers;
+        self.contributions.push(contribution);
+        contribution
+    }
+
+    /// Checks and applies a contribution computed elsewhere, e.g. by a remote participant.
+    pub fn apply<R: Rng + CryptoRng>(
+        &mut self,
+        powers: Powers<C>,
+        contribution: Contribution<C>,
+        rng: &mut R,
+    ) -> Result<(), CrateError> {
+        verify_contribution(&self.powers, &powers, &contribution, rng)?;
+        self.powers = powers;
+        self.contributions.push(contribution);
+        Ok(())
+    }
+
+    /// Verifies the chain of contributions and the consistency of the final powers.
+    pub fn verify<R: Rng + CryptoRng>(&self, rng: &mut R) -> Result<(), CrateError> {
+        if self.contributions.is_empty() {
+            return Err(CrateError::InconsistentPowers);
+        }
+        let mut g1_tau = C::G1Affine::generator();
+        for contribution in &self.contributions {
+            if !is_update(g1_tau, contribution) {
+                return Err(CrateError::InconsistentPowers);
+            }
+            g1_tau = contribution.g1_tau;
+        }
+        if self.powers.g1.get(1) != Some(&g1_tau) {
+            return Err(CrateError::InconsistentPowers);
+        }
+        self.powers.validate(rng)
+    }
+}
+
+/// Multiplies the `i`-th powers by `secret^i`.
+pub fn contribute<C: Pairing>(
+    powers: &Powers<C>,
+    secret: C::ScalarField,
+) -> (Powers<C>, Contribution<C>) {
+    let g1 = scale(&powers.g1, secret);
+    let g2 = scale(&powers.g2, secret);
+    let contribution = Contribution {
+        pubkey: (C::G2Affine::generator() * secret).into_affine(),
+        g1_tau: g1.get(1).copied().unwrap_or_else(C::G1Affine::zero),
+    };
+    (Powers::new(g1, g2), contribution)
+}
+
+/// Checks that `next` is the result of applying `contribution` to `previous`, i.e. that the new
+/// powers are consistent powers of the same secret (see [`Powers::validate`]) and that
+/// `e(tau' * g1, g2) = e(tau * g1, s * g2)` for the new `tau' * g1` and the public key `s * g2`.
+pub fn verify_contribution<C: Pairing, R: Rng + CryptoRng>(
+    previous: &Powers<C>,
+    next: &Powers<C>,
+    contribution: &Contribution<C>,
+    rng: &mut R,
+) -> Result<(), CrateError> {
+    if previous.g1.len() != next.g1.len() || previous.g2.len() != next.g2.len() {
+        return Err(CrateError::InconsistentPowers);
+    }
+    next.validate(rng)?;
+    if next.g1[1] != contribution.g1_tau || !is_update(previous.g1[1], contribution) {
+        return Err(CrateError::InconsistentPowers);
+    }
+    Ok(())
+}
+
+// e(tau' * g1, g2) = e(tau * g1, s * g2) for a non-trivial secret s
+fn is_update<C: Pairing>(previous_g1_tau: C::G1Affine, contribution: &Contribution<C>) -> bool {
+    !contribution.pubkey.is_zero()
+        && contribution.pubkey != C::G2Affine::generator()
+        && C::pairing(contribution.g1_tau, C::G2Affine::generator())
+            == C::pairing(previous_g1_tau, contribution.pubkey)
+}
+
+fn scale<G: AffineRepr>(points: &[G], secret: G::ScalarField) -> Vec<G> {
+    let mut exponent = G::ScalarField::ONE;
+    let scaled: Vec<G::Group> = points
+        .iter()
+        .map(|point| {
+            let scaled = *point * exponent;
+            exponent *= secret;
+            scaled
+        })
+        .collect();
+    G::Group::normalize_batch(&scaled)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commit::kzg::Kzg;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, UniPoly};
+    use ark_poly::Polynomial;
+    use ark_poly_commit::DenseUVPolynomial;
+
+    #[test]
+    fn ceremony() {
+        let rng = &mut test_rng();
+        let mut transcript = Transcript::<TestCurve>::new(8, 3);
+        assert!(transcript.verify(rng).is_err());
+        transcript.contribute(rng);
+        transcript.contribute(rng);
+
+        // a remote contribution with a known secret
+        let secret = Scalar::rand(rng);
+        let (powers, contribution) = contribute(&transcript.powers, secret);
+        transcript.apply(powers, contribution, rng).unwrap();
+        assert_eq!(transcript.contributions.len(), 3);
+        assert!(transcript.verify(rng).is_ok());
+
+        // the result is a working setup
+        let powers = &transcript.powers;
+        let poly = UniPoly::rand(7, rng);
+        let commitment = powers.commit_g1(&poly).unwrap().into_affine();
+        let point = Scalar::rand(rng);
+        let value = poly.evaluate(&point);
+        let proof = Kzg::proof(&poly, point, value, powers).unwrap();
+        assert!(Kzg::verify_scalar(proof, commitment, point, value, powers));
+
+        // contribution whose public key doesn't match the update
+        let (powers, mut contribution) = contribute(&transcript.powers, secret);
+        contribution.pubkey = (contribution.pubkey + contribution.pubkey).into_affine();
+        assert_eq!(
+            transcript.apply(powers, contribution, rng),
+            Err(CrateError::InconsistentPowers)
+        );
+        // contribution that resets the powers
+        let fresh = Transcript::<TestCurve>::new(8, 3);
+        let (powers, contribution) = contribute(&fresh.powers, secret);
+        assert!(transcript.apply(powers, contribution, rng).is_err());
+        assert!(transcript.verify(rng).is_ok());
+    }
+}
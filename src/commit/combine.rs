@@ -0,0 +1,121 @@
+//! Computing commitments to linear combinations of polynomials from their commitments, i.e.
+//! `com(a * f + b * g) = a * com(f) + b * com(g)` and `com(f + c) = com(f) + c * g1`.
+use super::kzg::Kzg;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, VariableBaseMSM};
+
+impl<C: Pairing> Kzg<C> {
+    /// Returns the commitment to `sum(coeffs[i] * f_i)` given the commitments to `f_i`.
+    ///
+    /// The same holds for everything that commits to a linear function of the polynomial with the
+    /// same setup points, e.g. the shifted commitments `com(X^k * f)` of
+    /// [`Self::prove_degree_bound`] for the same bound, or opening witnesses at the same point,
+    /// where the combined witness opens the combined polynomial to the same combination of the
+    /// values.
+    pub fn linear_combination(
+        commitments: &[C::G1Affine],
+        coeffs: &[C::ScalarField],
+    ) -> Result<C::G1, CrateError> {
+        if commitments.len() != coeffs.len() {
+            return Err(CrateError::LengthMismatch {
+                expected: commitments.len(),
+                actual: coeffs.len(),
+            });
+        }
+        Ok(C::G1::msm_unchecked(commitments, coeffs))
+    }
+
+    /// Returns the commitment to `a * f + b * g` given the commitments to `f` and `g`.
+    pub fn combine(
+        a: C::ScalarField,
+        commitment_f: C::G1Affine,
+        b: C::ScalarField,
+        commitment_g: C::G1Affine,
+    ) -> C::G1 {
+        commitment_f * a + commitment_g * b
+    }
+
+    /// Returns the commitment to `f + constant` given the commitment to `f`.
+    pub fn add_constant(commitment: C::G1Affine, constant: C::ScalarField) -> C::G1 {
+        commitment.into_group() + C::G1Affine::generator() * constant
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commit::kzg::Powers;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, UniPoly};
+    use ark_ec::CurveGroup;
+    use ark_poly::{DenseUVPolynomial, Polynomial};
+    use ark_std::UniformRand;
+
+    #[test]
+    fn homomorphic_combinations() {
+        let rng = &mut test_rng();
+        let powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 12);
+        let commit = |poly: &UniPoly| powers.commit_g1(poly).unwrap().into_affine();
+        let f = UniPoly::rand(7, rng);
+        let g = UniPoly::rand(5, rng);
+        let (a, b, c) = (Scalar::rand(rng), Scalar::rand(rng), Scalar::rand(rng));
+        let combined = &(&f * a) + &(&g * b);
+
+        assert_eq!(
+            Kzg::<TestCurve>::combine(a, commit(&f), b, commit(&g)).into_affine(),
+            commit(&combined)
+        );
+        assert_eq!(
+            Kzg::<TestCurve>::linear_combination(&[commit(&f), commit(&g)], &[a, b])
+                .unwrap()
+                .into_affine(),
+            commit(&combined)
+        );
+        let shifted = &f + &UniPoly::from_coefficients_slice(&[c]);
+        assert_eq!(
+            Kzg::<TestCurve>::add_constant(commit(&f), c).into_affine(),
+            commit(&shifted)
+        );
+
+        // degree-bound proofs combine to a proof for the combination
+        let bound = 8;
+        let proofs = [f.clone(), g.clone()]
+            .map(|poly| Kzg::prove_degree_bound(&poly, bound, &powers).unwrap());
+        let proof = Kzg::<TestCurve>::linear_combination(&proofs, &[a, b])
+            .unwrap()
+            .into_affine();
+        assert!(Kzg::verify_degree_bound(
+            proof,
+            commit(&combined),
+            bound,
+            &powers
+        ));
+
+        // so do opening witnesses at the same point
+        let point = Scalar::rand(rng);
+        let (value_f, value_g) = (f.evaluate(&point), g.evaluate(&point));
+        let witnesses = [
+            Kzg::proof(&f, point, value_f, &powers).unwrap(),
+            Kzg::proof(&g, point, value_g, &powers).unwrap(),
+        ];
+        let witness = Kzg::<TestCurve>::linear_combination(&witnesses, &[a, b])
+            .unwrap()
+            .into_affine();
+        assert!(Kzg::verify_scalar(
+            witness,
+            commit(&combined),
+            point,
+            a * value_f + b * value_g,
+            &powers
+        ));
+
+        assert_eq!(
+            Kzg::<TestCurve>::linear_combination(&[commit(&f)], &[a, b]),
+            Err(CrateError::LengthMismatch {
+                expected: 1,
+                actual: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Proving that a committed polynomial agrees with a value vector on a coset of a subgroup of its
+//! evaluation domain, e.g. on every `n/m`-th element of the data stored as its evaluations.
+use super::kzg::{Kzg, MultiProof, Powers};
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use ark_std::Zero;
+
+impl<C: Pairing> Kzg<C> {
+    /// Returns the coset of the subgroup of size `size` which is shifted by the `offset`-th
+    /// element of `domain`. Its `k`-th element is the `offset + k * domain.size() / size`-th
+    /// element of `domain`.
+    pub fn coset(
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+        size: usize,
+        offset: usize,
+    ) -> Result<GeneralEvaluationDomain<C::ScalarField>, CrateError> {
+        if offset >= domain.size() {
+            return Err(CrateError::IndexOutOfBounds(offset));
+        }
+        GeneralEvaluationDomain::new(size)
+            .filter(|subgroup| subgroup.size() == size && domain.size() % size == 0)
+            .and_then(|subgroup| subgroup.get_coset(domain.element(offset)))
+            .ok_or(CrateError::InvalidFftDomain(size))
+    }
+
+    /// Opens `poly` on every element of `coset` (see [`Self::coset`]) with a single witness
+    /// committing to `poly / (X^m - c)` where `c` is the `m`-th power of the coset offset.
+    ///
+    /// The vanishing polynomial `X^m - c` is sparse, so the quotient and the remainder, which
+    /// interpolates the values on the coset, are computed in linear time.
+    pub fn open_coset(
+        poly: &DensePolynomial<C::ScalarField>,
+        coset: &GeneralEvaluationDomain<C::ScalarField>,
+        powers: &Powers<C>,
+    ) -> Result<MultiProof<C>, CrateError> {
+        let (quotient, remainder) = divide_by_vanishing(poly, coset);
+        Ok(MultiProof {
+            witness: powers.commit_g1(&quotient)?.into(),
+            values: coset.fft(&remainder.coeffs),
+        })
+    }
+
+    /// Verifies that the polynomial committed to in `commitment` evaluates to `proof.values` on
+    /// `coset`, i.e. `e(com - I(tau) * g1, g2) = e(witness, tau^m * g2 - c * g2)`.
+    ///
+    /// Requires `m` G1 powers for committing to the interpolation `I` and `m + 1` G2 powers.
+    pub fn verify_coset(
+        proof: &MultiProof<C>,
+        commitment: C::G1Affine,
+        coset: &GeneralEvaluationDomain<C::ScalarField>,
+        powers: &Powers<C>,
+    ) -> bool {
+        let size = coset.size();
+        if proof.values.len() != size || powers.g2.len() <= size {
+            return false;
+        }
+        let interpolation = DensePolynomial::from_coefficients_vec(coset.ifft(&proof.values));
+        let Ok(com_interpolation) = powers.commit_g1(&interpolation) else {
+            return false;
+        };
+        let com_vanishing =
+            powers.g2[size].into_group() - C::G2Affine::generator() * coset.coset_offset_pow_size();
+        Self::pairing_check(
+            commitment.into_group() - com_interpolation,
+            proof.witness.into_group(),
+            com_vanishing,
+        )
+    }
+}
+
+// divides by the sparse vanishing polynomial X^m - c, returns the quotient and the remainder
+fn divide_by_vanishing<S: PrimeField>(
+    poly: &DensePolynomial<S>,
+    coset: &GeneralEvaluationDomain<S>,
+) -> (DensePolynomial<S>, DensePolynomial<S>) {
+    let m = coset.size();
+    let c = coset.coset_offset_pow_size();
+    let mut remainder = poly.coeffs.clone();
+    if remainder.len() <= m {
+        return (DensePolynomial::zero(), poly.clone());
+    }
+    let mut quotient = vec![S::zero(); remainder.len() - m];
+    for i in (m..remainder.len()).rev() {
+        let coeff = remainder[i];
+        quotient[i - m] = coeff;
+        remainder[i - m] += c * coeff;
+    }
+    remainder.truncate(m);
+    (
+        DensePolynomial::from_coefficients_vec(quotient),
+        DensePolynomial::from_coefficients_vec(remainder),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, UniPoly};
+    use ark_ec::CurveGroup;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn coset_agreement() {
+        let rng = &mut test_rng();
+        let powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 17);
+        let domain = GeneralEvaluationDomain::<Scalar>::new(16).unwrap();
+        let data: Vec<Scalar> = (0..16).map(|_| Scalar::rand(rng)).collect();
+        let poly = UniPoly::from_coefficients_vec(domain.ifft(&data));
+        let commitment = powers.commit_g1(&poly).unwrap().into_affine();
+
+        let coset = Kzg::<TestCurve>::coset(&domain, 4, 3).unwrap();
+        let proof = Kzg::open_coset(&poly, &coset, &powers).unwrap();
+        let sample: Vec<Scalar> = (0..4).map(|k| data[3 + 4 * k]).collect();
+        assert_eq!(proof.values, sample);
+        assert!(Kzg::verify_coset(&proof, commitment, &coset, &powers));
+
+        // values that don't match the data
+        let mut invalid = proof.clone();
+        invalid.values[2] += Scalar::from(1u8);
+        assert!(!Kzg::verify_coset(&invalid, commitment, &coset, &powers));
+        // another coset
+        let other = Kzg::<TestCurve>::coset(&domain, 4, 2).unwrap();
+        assert!(!Kzg::verify_coset(&proof, commitment, &other, &powers));
+        // the whole domain and a single point are cosets as well
+        for size in [1, 16] {
+            let coset = Kzg::<TestCurve>::coset(&domain, size, 0).unwrap();
+            let proof = Kzg::open_coset(&poly, &coset, &powers).unwrap();
+            assert!(Kzg::verify_coset(&proof, commitment, &coset, &powers));
+        }
+
+        assert_eq!(
+            Kzg::<TestCurve>::coset(&domain, 32, 0),
+            Err(CrateError::InvalidFftDomain(32))
+        );
+        assert_eq!(
+            Kzg::<TestCurve>::coset(&domain, 4, 16),
+            Err(CrateError::IndexOutOfBounds(16))
+        );
+    }
+}
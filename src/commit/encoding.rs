@@ -0,0 +1,326 @@
+//! Fixed-size compressed encodings of commitments, proofs and scalars. Hex strings are `0x`
+//! prefixed like in Ethereum tooling, but decoding accepts them without the prefix as well.
+use crate::hex;
+use ark_ff::{BigInteger, PrimeField};
+#[cfg(any(feature = "bls12-381", feature = "bn254"))]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use thiserror::Error as ErrorT;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("expected {expected} bytes, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+    #[error("bytes don't encode a valid point")]
+    InvalidPoint,
+    #[error("scalar is not smaller than the field order")]
+    NonCanonicalScalar,
+    #[error(transparent)]
+    Hex(#[from] hex::Error),
+}
+
+/// BLS12-381 points use the compressed ZCash format (48 bytes in G1, 96 bytes in G2), which is the
+/// encoding of `KZGCommitment` and `KZGProof` in c-kzg and the consensus specs. BN254 points use
+/// the compressed arkworks format (32 bytes in G1, 64 bytes in G2).
+pub trait CompressedPoint: Sized {
+    /// Length of the compressed encoding in bytes.
+    const SIZE: usize;
+    type Bytes: AsRef<[u8]>;
+
+    fn to_compressed(&self) -> Self::Bytes;
+
+    /// Decodes a compressed point, checking that it is on the curve and in the prime order
+    /// subgroup.
+    fn from_compressed(bytes: &[u8]) -> Result<Self, Error>;
+
+    fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.to_compressed().as_ref()))
+    }
+
+    fn from_hex(input: &str) -> Result<Self, Error> {
+        Self::from_compressed(&hex::decode(input)?)
+    }
+}
+
+#[cfg(any(feature = "bls12-381", feature = "bn254"))]
+macro_rules! impl_compressed_point {
+    ($point:ty, $size:literal) => {
+        impl CompressedPoint for $point {
+            const SIZE: usize = $size;
+            type Bytes = [u8; $size];
+
+            fn to_compressed(&self) -> Self::Bytes {
+                let mut bytes = [0u8; $size];
+                self.serialize_compressed(&mut bytes[..])
+                    .expect("compressed size is fixed");
+                bytes
+            }
+
+            fn from_compressed(bytes: &[u8]) -> Result<Self, Error> {
+                if bytes.len() != Self::SIZE {
+                    return Err(Error::InvalidLength {
+                        expected: Self::SIZE,
+                        actual: bytes.len(),
+                    });
+                }
+                Self::deserialize_compressed(bytes).map_err(|_| Error::InvalidPoint)
+            }
+        }
+    };
+}
+
+// the concrete configs are named since coherence can't tell apart the projections behind
+// `ark_bls12_381::G1Affine` and `ark_bls12_381::G2Affine`
+#[cfg(feature = "bls12-381")]
+impl_compressed_point!(
+    ark_ec::short_weierstrass::Affine<ark_bls12_381::g1::Config>,
+    48
+);
+#[cfg(feature = "bls12-381")]
+impl_compressed_point!(
+    ark_ec::short_weierstrass::Affine<ark_bls12_381::g2::Config>,
+    96
+);
+#[cfg(feature = "bn254")]
+impl_compressed_point!(ark_ec::short_weierstrass::Affine<ark_bn254::g1::Config>, 32);
+#[cfg(feature = "bn254")]
+impl_compressed_point!(ark_ec::short_weierstrass::Affine<ark_bn254::g2::Config>, 64);
+
+/// Length of the canonical encoding of elements of `S`, i.e. `ceil(log2(r) / 8)` bytes for the
+/// field order `r`.
+pub fn scalar_size<S: PrimeField>() -> usize {
+    (S::MODULUS_BIT_SIZE as usize).div_ceil(8)
+}
+
+/// Big-endian canonical encoding, the `Bytes32` of c-kzg for BLS12-381.
+pub fn scalar_to_bytes_be<S: PrimeField>(scalar: &S) -> Vec<u8> {
+    let mut bytes = scalar.into_bigint().to_bytes_be();
+    bytes.drain(..bytes.len() - scalar_size::<S>());
+    bytes
+}
+
+/// Little-endian canonical encoding, the layout of `blst_scalar` for BLS12-381.
+pub fn scalar_to_bytes_le<S: PrimeField>(scalar: &S) -> Vec<u8> {
+    let mut bytes = scalar.into_bigint().to_bytes_le();
+    bytes.truncate(scalar_size::<S>());
+    bytes
+}
+
+/// Decodes a big-endian canonical encoding. Non-canonical encodings are rejected instead of
+/// reduced, like in c-kzg.
+pub fn scalar_from_bytes_be<S: PrimeField>(bytes: &[u8]) -> Result<S, Error> {
+    check_scalar_length::<S>(bytes)?;
+    let scalar = S::from_be_bytes_mod_order(bytes);
+    // reducing modulo the field order must not change the encoding
+    if scalar_to_bytes_be(&scalar) != bytes {
+        return Err(Error::NonCanonicalScalar);
+    }
+    Ok(scalar)
+}
+
+/// Decodes a little-endian canonical encoding, see [`scalar_from_bytes_be`].
+pub fn scalar_from_bytes_le<S: PrimeField>(bytes: &[u8]) -> Result<S, Error> {
+    check_scalar_length::<S>(bytes)?;
+    let scalar = S::from_le_bytes_mod_order(bytes);
+    if scalar_to_bytes_le(&scalar) != bytes {
+        return Err(Error::NonCanonicalScalar);
+    }
+    Ok(scalar)
+}
+
+fn check_scalar_length<S: PrimeField>(bytes: &[u8]) -> Result<(), Error> {
+    let expected = scalar_size::<S>();
+    if bytes.len() != expected {
+        return Err(Error::InvalidLength {
+            expected,
+            actual: bytes.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    #[cfg(any(feature = "bls12-381", feature = "bn254"))]
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_std::UniformRand;
+
+    #[cfg(any(feature = "bls12-381", feature = "bn254"))]
+    fn roundtrip<P: CompressedPoint + AffineRepr>() {
+        let point = (P::generator() * P::ScalarField::rand(&mut test_rng())).into_affine();
+        let bytes = point.to_compressed();
+        assert_eq!(bytes.as_ref().len(), P::SIZE);
+        assert_eq!(P::from_compressed(bytes.as_ref()).unwrap(), point);
+        assert_eq!(P::from_hex(&point.to_hex()).unwrap(), point);
+        assert_eq!(P::from_hex(&point.to_hex()[2..]).unwrap(), point);
+        assert_eq!(
+            P::from_compressed(&bytes.as_ref()[1..]),
+            Err(Error::InvalidLength {
+                expected: P::SIZE,
+                actual: P::SIZE - 1
+            })
+        );
+    }
+
+    #[cfg(any(feature = "bls12-381", feature = "bn254"))]
+    #[test]
+    fn compressed_roundtrip() {
+        #[cfg(feature = "bn254")]
+        {
+            roundtrip::<ark_bn254::G1Affine>();
+            roundtrip::<ark_bn254::G2Affine>();
+        }
+        #[cfg(feature = "bls12-381")]
+        {
+            roundtrip::<ark_bls12_381::G1Affine>();
+            roundtrip::<ark_bls12_381::G2Affine>();
+        }
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn invalid_points() {
+        assert_eq!(
+            ark_bn254::G1Affine::from_compressed(&[0xff; 32]),
+            Err(Error::InvalidPoint)
+        );
+        assert_eq!(
+            ark_bn254::G1Affine::from_hex("0x0g"),
+            Err(Error::Hex(hex::Error::InvalidCharacter('g')))
+        );
+    }
+
+    #[cfg(feature = "bls12-381")]
+    #[test]
+    fn c_kzg_encodings() {
+        use ark_bls12_381::G1Affine;
+        // generator and point at infinity (the commitment to the zero polynomial)
+        let generator = "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+        assert_eq!(G1Affine::generator().to_hex(), generator);
+        assert_eq!(
+            G1Affine::from_hex(generator).unwrap(),
+            G1Affine::generator()
+        );
+        let mut infinity = [0u8; 48];
+        infinity[0] = 0xc0;
+        assert_eq!(G1Affine::zero().to_compressed(), infinity);
+        assert_eq!(
+            G1Affine::from_compressed(&infinity).unwrap(),
+            G1Affine::zero()
+        );
+    }
+
+    // The `verify_kzg_proof` cases of the consensus specs with a constant polynomial, for which the
+    // proof is the point at infinity and which therefore verify against any setup:
+    // `correct_proof_point_at_infinity_for_zero_poly` and `..._for_twos_poly`.
+    #[cfg(feature = "bls12-381")]
+    #[test]
+    fn c_kzg_verify_kzg_proof_vectors() {
+        use crate::commit::kzg::{Kzg, Powers};
+        use ark_bls12_381::{Bls12_381, Fr, G1Affine};
+
+        let infinity = "0xc00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+        let twos = "0xa572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e";
+        assert_eq!(
+            G1Affine::from_hex(twos).unwrap(),
+            (G1Affine::generator() * Fr::from(2u8)).into_affine()
+        );
+        let zs = [
+            "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000",
+        ];
+        let zero = "0x0000000000000000000000000000000000000000000000000000000000000000";
+        let two = "0x0000000000000000000000000000000000000000000000000000000000000002";
+
+        let powers = Powers::<Bls12_381>::unsafe_setup(Fr::rand(&mut test_rng()), 2);
+        let verify = |commitment: &str, z: &str, y: &str, proof: &str| {
+            let scalar = |s: &str| scalar_from_bytes_be(&hex::decode(&s[2..]).unwrap()).unwrap();
+            Kzg::verify_scalar(
+                G1Affine::from_hex(proof).unwrap(),
+                G1Affine::from_hex(commitment).unwrap(),
+                scalar(z),
+                scalar(y),
+                &powers,
+            )
+        };
+        for z in zs {
+            assert!(verify(infinity, z, zero, infinity));
+            assert!(verify(twos, z, two, infinity));
+            assert!(!verify(twos, z, zero, infinity));
+        }
+    }
+
+    #[test]
+    fn scalar_encodings() {
+        let scalar = ark_bn254::Fr::rand(&mut test_rng());
+        let be = scalar_to_bytes_be(&scalar);
+        let le = scalar_to_bytes_le(&scalar);
+        assert_eq!(be.len(), 32);
+        assert_eq!(
+            be.iter().rev().collect::<Vec<_>>(),
+            le.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(scalar_from_bytes_be(&be), Ok(scalar));
+        assert_eq!(scalar_from_bytes_le(&le), Ok(scalar));
+        assert_eq!(
+            scalar_from_bytes_be::<ark_bn254::Fr>(&be[1..]),
+            Err(Error::InvalidLength {
+                expected: 32,
+                actual: 31
+            })
+        );
+        assert_eq!(
+            scalar_from_bytes_le::<ark_bn254::Fr>(&[0xff; 32]),
+            Err(Error::NonCanonicalScalar)
+        );
+    }
+
+    // a KZG proof that passes through the byte encodings of c-kzg still verifies
+    #[cfg(feature = "bls12-381")]
+    #[test]
+    fn c_kzg_bundle_roundtrip() {
+        use crate::commit::kzg::{Kzg, Powers};
+        use crate::tests::{Scalar, TestCurve, UniPoly};
+        use ark_ec::pairing::Pairing;
+        use ark_poly::{DenseUVPolynomial, Polynomial};
+        type G1Affine = <TestCurve as Pairing>::G1Affine;
+
+        let modulus = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+        let mut modulus = hex::decode(modulus).unwrap();
+        assert_eq!(
+            scalar_from_bytes_be::<Scalar>(&modulus),
+            Err(Error::NonCanonicalScalar)
+        );
+        modulus.reverse();
+        assert_eq!(
+            scalar_from_bytes_le::<Scalar>(&modulus),
+            Err(Error::NonCanonicalScalar)
+        );
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(scalar_from_bytes_be(&one), Ok(Scalar::from(1u8)));
+
+        let rng = &mut test_rng();
+        let powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 8);
+        let poly = UniPoly::rand(7, rng);
+        let commitment = powers.commit_g1(&poly).unwrap().into_affine();
+        let z = Scalar::rand(rng);
+        let y = poly.evaluate(&z);
+        let proof = Kzg::proof(&poly, z, y, &powers).unwrap();
+
+        let commitment_bytes = commitment.to_compressed();
+        let proof_bytes = proof.to_compressed();
+        let z_bytes = scalar_to_bytes_be(&z);
+        let y_bytes = scalar_to_bytes_be(&y);
+        assert!(Kzg::verify_scalar(
+            G1Affine::from_compressed(&proof_bytes).unwrap(),
+            G1Affine::from_compressed(&commitment_bytes).unwrap(),
+            scalar_from_bytes_be(&z_bytes).unwrap(),
+            scalar_from_bytes_be(&y_bytes).unwrap(),
+            &powers
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+//! Loader for the trusted setup of the Ethereum KZG ceremony (EIP-4844). Points are encoded in
+//! the compressed ZCash format, which is also used by the arkworks BLS12-381 implementation.
+use super::kzg::Powers;
+use crate::hex;
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_serialize::CanonicalDeserialize;
+use thiserror::Error as ErrorT;
+
+/// Number of G1 points in the Ethereum mainnet setup.
+pub const MAINNET_G1_POINTS: usize = 4096;
+/// Number of G2 points in the Ethereum mainnet setup.
+pub const MAINNET_G2_POINTS: usize = 65;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid trusted setup format: {0}")]
+    InvalidFormat(&'static str),
+    #[error(transparent)]
+    Hex(#[from] hex::Error),
+    #[error("invalid point encoding at index {0}")]
+    InvalidPoint(usize),
+    #[error("G1 and G2 points are not powers of the same secret")]
+    InconsistentSetup,
+}
+
+/// Parses the text format of [c-kzg](https://github.com/ethereum/c-kzg-4844)
+/// (`trusted_setup.txt`): the number of G1 and G2 points followed by the hex encoded G1 points in
+/// Lagrange form, the G2 points in monomial form and, in recent versions, the G1 points in
+/// monomial form. Missing monomial G1 points are recovered from the Lagrange form via an FFT in
+/// the group.
+pub fn from_trusted_setup_txt(input: &str) -> Result<Powers<Bls12_381>, Error> {
+    let mut tokens = input.split_whitespace();
+    let mut count = || -> Result<usize, Error> {
+        tokens
+            .next()
+            .and_then(|token| token.parse().ok())
+            .ok_or(Error::InvalidFormat("missing point count"))
+    };
+    let g1_count = count()?;
+    let g2_count = count()?;
+
+    let hex_points: Vec<&str> = input.split_whitespace().skip(2).collect();
+    if hex_points.len() != g1_count + g2_count && hex_points.len() != 2 * g1_count + g2_count {
+        return Err(Error::InvalidFormat("unexpected number of points"));
+    }
+    let g1_lagrange = &hex_points[..g1_count];
+    let g2_monomial = &hex_points[g1_count..g1_count + g2_count];
+    let g1_monomial = &hex_points[g1_count + g2_count..];
+    let g1_monomial = (!g1_monomial.is_empty()).then_some(g1_monomial);
+
+    load(g1_lagrange, g2_monomial, g1_monomial)
+}
+
+/// Parses the JSON format of the consensus specs (`trusted_setup_4096.json`) with the
+/// `g1_lagrange`, `g2_monomial` and optional `g1_monomial` arrays, see
+/// [`from_trusted_setup_txt`].
+pub fn from_trusted_setup_json(input: &str) -> Result<Powers<Bls12_381>, Error> {
+    let g1_lagrange =
+        json_array(input, "g1_lagrange")?.ok_or(Error::InvalidFormat("missing g1_lagrange"))?;
+    let g2_monomial =
+        json_array(input, "g2_monomial")?.ok_or(Error::InvalidFormat("missing g2_monomial"))?;
+    let g1_monomial = json_array(input, "g1_monomial")?;
+    load(&g1_lagrange, &g2_monomial, g1_monomial.as_deref())
+}
+
+fn load(
+    g1_lagrange: &[&str],
+    g2_monomial: &[&str],
+    g1_monomial: Option<&[&str]>,
+) -> Result<Powers<Bls12_381>, Error> {
+    if g2_monomial.len() < 2 {
+        return Err(Error::InvalidFormat("at least two G2 points are required"));
+    }
+    let g2 = decode_points::<G2Affine>(g2_monomial)?;
+    let g1 = match g1_monomial {
+        Some(points) => {
+            if points.len() != g1_lagrange.len() {
+                return Err(Error::InvalidFormat("G1 point counts don't match"));
+            }
+            let g1 = decode_points::<G1Affine>(points)?;
+            if !is_consistent(&g1, &g2) {
+                return Err(Error::InconsistentSetup);
+            }
+            g1
+        }
+        None => monomial_from_lagrange(decode_points::<G1Affine>(g1_lagrange)?, &g2)?,
+    };
+    Ok(Powers::new(g1, g2))
+}
+
+// the Lagrange points may be stored in natural or bit-reversed order, the right one is found by
+// checking the recovered powers against the G2 powers
+fn monomial_from_lagrange(
+    mut lagrange: Vec<G1Affine>,
+    g2: &[G2Affine],
+) -> Result<Vec<G1Affine>, Error> {
+    let domain = GeneralEvaluationDomain::<Fr>::new(lagrange.len())
+        .filter(|domain| domain.size() == lagrange.len())
+        .ok_or(Error::InvalidFormat("G1 point count is not a power of two"))?;
+    for _ in 0..2 {
+        let points: Vec<G1Projective> = lagrange.iter().map(|p| p.into_group()).collect();
+        let monomial = G1Projective::normalize_batch(&domain.fft(&points));
+        if is_consistent(&monomial, g2) {
+            return Ok(monomial);
+        }
+        bit_reverse(&mut lagrange);
+    }
+    Err(Error::InconsistentSetup)
+}
+
+pub(super) fn bit_reverse<T>(values: &mut [T]) {
+    let bits = values.len().trailing_zeros();
+    for i in 0..values.len() {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+// e(tau * g1, g2) = e(g1, tau * g2) and the first points are the generators
+fn is_consistent(g1: &[G1Affine], g2: &[G2Affine]) -> bool {
+    g1.len() >= 2
+        && g1[0] == G1Affine::generator()
+        && g2[0] == G2Affine::generator()
+        && Bls12_381::pairing(g1[1], g2[0]) == Bls12_381::pairing(g1[0], g2[1])
+}
+
+fn decode_points<P: CanonicalDeserialize>(encoded: &[&str]) -> Result<Vec<P>, Error> {
+    encoded
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let bytes = hex::decode(point)?;
+            P::deserialize_compressed(bytes.as_slice()).map_err(|_| Error::InvalidPoint(i))
+        })
+        .collect()
+}
+
+// returns the string elements of the array stored under `key`, without quotes
+fn json_array<'a>(input: &'a str, key: &str) -> Result<Option<Vec<&'a str>>, Error> {
+    let Some(start) = input.find(&format!("\"{key}\"")) else {
+        return Ok(None);
+    };
+    let rest = &input[start + key.len() + 2..];
+    let open = rest
+        .find('[')
+        .ok_or(Error::InvalidFormat("expected an array"))?;
+    let close = rest
+        .find(']')
+        .ok_or(Error::InvalidFormat("unterminated array"))?;
+    if rest[..open].trim() != ":" {
+        return Err(Error::InvalidFormat("expected an array"));
+    }
+    Ok(Some(
+        rest[open + 1..close]
+            .split(',')
+            .map(|element| element.trim().trim_matches('"'))
+            .filter(|element| !element.is_empty())
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::UniformRand;
+
+    const SIZE: usize = 16;
+
+    fn encode<P: CanonicalSerialize>(points: &[P]) -> Vec<String> {
+        points
+            .iter()
+            .map(|point| {
+                let mut bytes = Vec::new();
+                point.serialize_compressed(&mut bytes).unwrap();
+                hex::encode(&bytes)
+            })
+            .collect()
+    }
+
+    fn setup() -> (Powers<Bls12_381>, Vec<String>, Vec<String>, Vec<String>) {
+        let tau = Fr::rand(&mut test_rng());
+        let powers = Powers::<Bls12_381>::unsafe_setup(tau, SIZE);
+        let lagrange = Powers::<Bls12_381>::unsafe_setup_eip_4844(tau, SIZE).g1;
+        let g1 = encode(&powers.g1);
+        let g2 = encode(&powers.g2[..3]);
+        (powers, encode(&lagrange), g2, g1)
+    }
+
+    #[test]
+    fn load_text_format() {
+        let (powers, lagrange, g2, g1) = setup();
+
+        let text = [
+            vec![SIZE.to_string(), "3".to_string()],
+            lagrange.clone(),
+            g2.clone(),
+            g1,
+        ]
+        .concat()
+        .join("\n");
+        let loaded = from_trusted_setup_txt(&text).unwrap();
+        assert_eq!(loaded.g1, powers.g1);
+        assert_eq!(loaded.g2, powers.g2[..3]);
+
+        // legacy format without the monomial G1 points
+        let text = [vec![SIZE.to_string(), "3".to_string()], lagrange, g2]
+            .concat()
+            .join("\n");
+        assert_eq!(from_trusted_setup_txt(&text).unwrap().g1, powers.g1);
+
+        assert_eq!(
+            from_trusted_setup_txt("16\n3\n").unwrap_err(),
+            Error::InvalidFormat("unexpected number of points")
+        );
+    }
+
+    #[test]
+    fn load_json_format() {
+        let (powers, mut lagrange, g2, g1) = setup();
+        let quoted = |points: &[String]| {
+            points
+                .iter()
+                .map(|p| format!("\"0x{p}\""))
+                .collect::<Vec<_>>()
+                .join(",\n    ")
+        };
+
+        let json = format!(
+            "{{\n  \"g1_monomial\": [{}],\n  \"g1_lagrange\": [{}],\n  \"g2_monomial\": [{}]\n}}",
+            quoted(&g1),
+            quoted(&lagrange),
+            quoted(&g2)
+        );
+        let loaded = from_trusted_setup_json(&json).unwrap();
+        assert_eq!(loaded.g1, powers.g1);
+        assert_eq!(loaded.g2, powers.g2[..3]);
+
+        // bit-reversed Lagrange points only
+        bit_reverse(&mut lagrange);
+        let json = format!(
+            "{{\"g1_lagrange\": [{}], \"g2_monomial\": [{}]}}",
+            quoted(&lagrange),
+            quoted(&g2)
+        );
+        assert_eq!(from_trusted_setup_json(&json).unwrap().g1, powers.g1);
+
+        // G2 points of another setup
+        let other = encode(&Powers::<Bls12_381>::unsafe_setup(Fr::from(2u8), 3).g2);
+        let json = format!(
+            "{{\"g1_monomial\": [{}], \"g1_lagrange\": [{}], \"g2_monomial\": [{}]}}",
+            quoted(&g1),
+            quoted(&lagrange),
+            quoted(&other)
+        );
+        assert_eq!(
+            from_trusted_setup_json(&json).unwrap_err(),
+            Error::InconsistentSetup
+        );
+    }
+}
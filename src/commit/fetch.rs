@@ -0,0 +1,205 @@
+//! Downloading public SRS files into a local cache, see [`Fetcher::fetch`]. Only the `curl`
+//! binary is required by default, so no HTTP client is compiled in.
+use super::kzg::Powers;
+use crate::hex;
+use ark_ec::pairing::Pairing;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error as ErrorT;
+
+/// The setup of the Ethereum KZG ceremony in the text format of c-kzg, see
+/// [`ethereum::from_trusted_setup_txt`](super::ethereum::from_trusted_setup_txt).
+pub const ETHEREUM_TRUSTED_SETUP_URL: &str =
+    "https://raw.githubusercontent.com/ethereum/c-kzg-4844/main/src/trusted_setup.txt";
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("download of {url} failed: {reason}")]
+    Download { url: String, reason: String },
+    #[error("hash mismatch, expected {expected}, got {actual}")]
+    HashMismatch { expected: String, actual: String },
+    #[error("cache error: {0}")]
+    Cache(String),
+    #[error("invalid SHA-256 hash")]
+    InvalidHash,
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Cache(error.to_string())
+    }
+}
+
+/// A file to download together with its expected SHA-256 hash. The hashes of well-known files are
+/// not bundled, callers should pin the hash of the file they audited, e.g. the one published
+/// alongside the ceremony.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Source<'a> {
+    pub url: &'a str,
+    pub sha256: [u8; 32],
+}
+
+impl<'a> Source<'a> {
+    /// Creates a source with the hash given as a hex string.
+    pub fn new(url: &'a str, sha256: &str) -> Result<Self, Error> {
+        let sha256 = hex::decode(sha256)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(Error::InvalidHash)?;
+        Ok(Self { url, sha256 })
+    }
+}
+
+/// HTTP client used by a [`Fetcher`], e.g. for plugging in a client other than [`Curl`].
+pub trait Transport {
+    fn get(&self, url: &str) -> Result<Vec<u8>, Error>;
+}
+
+/// Downloads files with the `curl` binary.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Curl;
+
+impl Transport for Curl {
+    fn get(&self, url: &str) -> Result<Vec<u8>, Error> {
+        let error = |reason: String| Error::Download {
+            url: url.to_string(),
+            reason,
+        };
+        let output = Command::new("curl")
+            .args(["--fail", "--silent", "--show-error", "--location", url])
+            .output()
+            .map_err(|e| error(e.to_string()))?;
+        if !output.status.success() {
+            return Err(error(String::from_utf8_lossy(&output.stderr).into_owned()));
+        }
+        Ok(output.stdout)
+    }
+}
+
+pub struct Fetcher<T = Curl> {
+    cache_dir: PathBuf,
+    transport: T,
+}
+
+impl Fetcher<Curl> {
+    pub fn new<P: Into<PathBuf>>(cache_dir: P) -> Self {
+        Self::with_transport(cache_dir, Curl)
+    }
+}
+
+impl<T: Transport> Fetcher<T> {
+    pub fn with_transport<P: Into<PathBuf>>(cache_dir: P, transport: T) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+            transport,
+        }
+    }
+
+    /// Path of the cached file of `source`, which may not exist yet.
+    pub fn cache_path(&self, source: &Source) -> PathBuf {
+        self.cache_dir.join(hex::encode(&source.sha256))
+    }
+
+    /// Returns the contents of `source`, downloading it only if the cache doesn't contain a file
+    /// with the expected hash. Downloads are checked against the hash and stored under the hex
+    /// encoded hash in the cache directory, corrupted cache entries are replaced.
+    pub fn fetch(&self, source: &Source) -> Result<Vec<u8>, Error> {
+        let path = self.cache_path(source);
+        if let Ok(bytes) = fs::read(&path) {
+            if check_hash(&bytes, &source.sha256).is_ok() {
+                return Ok(bytes);
+            }
+        }
+        let bytes = self.transport.get(source.url)?;
+        check_hash(&bytes, &source.sha256)?;
+        write_atomic(&self.cache_dir, &path, &bytes)?;
+        Ok(bytes)
+    }
+
+    /// Fetches `source` and parses it with `parse`, e.g. one of the loaders of
+    /// [`ethereum`](super::ethereum) or [`ptau`](super::ptau).
+    pub fn fetch_powers<C: Pairing, E, F>(&self, source: &Source, parse: F) -> Result<Powers<C>, E>
+    where
+        E: From<Error>,
+        F: FnOnce(&[u8]) -> Result<Powers<C>, E>,
+    {
+        let bytes = self.fetch(source)?;
+        parse(&bytes)
+    }
+}
+
+fn check_hash(bytes: &[u8], expected: &[u8; 32]) -> Result<(), Error> {
+    let actual: [u8; 32] = Sha256::digest(bytes).into();
+    if &actual != expected {
+        return Err(Error::HashMismatch {
+            expected: hex::encode(expected),
+            actual: hex::encode(&actual),
+        });
+    }
+    Ok(())
+}
+
+// writes to a temporary file first, so a concurrent reader never sees a partial file
+fn write_atomic(dir: &Path, path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    fs::create_dir_all(dir)?;
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    struct Mock<'a> {
+        body: &'a [u8],
+        calls: Cell<usize>,
+    }
+
+    impl Transport for Mock<'_> {
+        fn get(&self, _url: &str) -> Result<Vec<u8>, Error> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.body.to_vec())
+        }
+    }
+
+    #[test]
+    fn fetch_and_cache() {
+        let dir = std::env::temp_dir().join(format!("fde-srs-fetch-{}", std::process::id()));
+        let body = b"not really a trusted setup";
+        let sha256: [u8; 32] = Sha256::digest(body).into();
+        let source = Source::new("https://example.com/srs", &hex::encode(&sha256)).unwrap();
+        let mock = Mock {
+            body,
+            calls: Cell::new(0),
+        };
+        let fetcher = Fetcher::with_transport(&dir, mock);
+
+        assert_eq!(fetcher.fetch(&source).unwrap(), body);
+        assert_eq!(fetcher.fetch(&source).unwrap(), body);
+        assert_eq!(fetcher.transport.calls.get(), 1);
+
+        // corrupted cache entries are downloaded again
+        fs::write(fetcher.cache_path(&source), b"corrupted").unwrap();
+        assert_eq!(fetcher.fetch(&source).unwrap(), body);
+        assert_eq!(fetcher.transport.calls.get(), 2);
+
+        // downloads with another hash are rejected and not cached
+        let other = Source {
+            sha256: [0; 32],
+            ..source
+        };
+        assert!(matches!(
+            fetcher.fetch(&other),
+            Err(Error::HashMismatch { .. })
+        ));
+        assert!(!fetcher.cache_path(&other).exists());
+
+        assert_eq!(Source::new("", "abcd"), Err(Error::InvalidHash));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,110 @@
+//! Computing the KZG opening proofs at all points of an evaluation domain in `O(n log n)` with
+//! the technique of Feist and Khovratovich ("Fast amortized KZG proofs",
+//! <https://eprint.iacr.org/2023/033>).
+use super::kzg::{Kzg, Powers};
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
+use ark_std::Zero;
+
+impl<C: Pairing> Kzg<C> {
+    /// Computes the opening proofs of `poly` at every element of `domain`, i.e. the `i`-th proof
+    /// is the same as the one returned by [`Self::proof`] for the point `domain.element(i)`.
+    ///
+    /// For `f(X) = sum(f_i * X^i)` of degree `d`, the witness for an opening at `z` is
+    /// `sum(z^k * h_(k + 1))` for `k < d` with `h_i = sum(f_j * tau^(j - i) * g1)` over `j >= i`.
+    /// The `h_i` are a Toeplitz matrix-vector product of the coefficients and the powers, which is
+    /// computed by embedding it into a circulant matrix and using FFTs. The witnesses at all domain
+    /// points are then the FFT of the `h_i`.
+    pub fn open_all(
+        poly: &DensePolynomial<C::ScalarField>,
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+        powers: &Powers<C>,
+    ) -> Result<Vec<C::G1Affine>, CrateError> {
+        let n = domain.size();
+        if poly.is_zero() || poly.degree() == 0 {
+            return Ok(vec![C::G1Affine::zero(); n]);
+        }
+        let d = poly.degree();
+        if d > powers.g1.len() {
+            return Err(CrateError::InsufficientPowers {
+                required: d,
+                available: powers.g1.len(),
+            });
+        }
+
+        let h = toeplitz_product::<C>(&poly.coeffs, &powers.g1[..d])?;
+
+        // the opening at w^m is sum(h_(k + 1) * w^(mk)), indices are reduced modulo n if the
+        // degree exceeds the domain size
+        let mut folded = vec![C::G1::zero(); n];
+        for (k, h) in h.into_iter().enumerate() {
+            folded[k % n] += h;
+        }
+        Ok(C::G1::normalize_batch(&domain.fft(&folded)))
+    }
+}
+
+// returns h_1, ..., h_d where h_i = sum(coeffs[j] * powers[j - i]) for i <= j <= d
+fn toeplitz_product<C: Pairing>(
+    coeffs: &[C::ScalarField],
+    powers: &[C::G1Affine],
+) -> Result<Vec<C::G1>, CrateError> {
+    let d = powers.len();
+    let size = 2 * d;
+    let domain = GeneralEvaluationDomain::<C::ScalarField>::new(size)
+        .ok_or(CrateError::InvalidFftDomain(size))?;
+
+    // h_(k + 1) is the (k + d - 1)-th entry of the convolution of the reversed powers and the
+    // coefficients without the constant term, the domain is large enough to avoid wrapping around
+    let mut reversed: Vec<C::G1> = powers.iter().rev().map(|p| p.into_group()).collect();
+    reversed.resize(domain.size(), C::G1::zero());
+    let mut shifted = coeffs[1..].to_vec();
+    shifted.resize(domain.size(), C::ScalarField::zero());
+
+    let reversed = domain.fft(&reversed);
+    let shifted = domain.fft(&shifted);
+    let product: Vec<C::G1> = reversed
+        .into_iter()
+        .zip(shifted)
+        .map(|(p, c)| p * c)
+        .collect();
+    let convolution = domain.ifft(&product);
+    Ok(convolution[d - 1..2 * d - 1].to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve};
+    use ark_poly_commit::DenseUVPolynomial;
+    use ark_std::UniformRand;
+
+    type UniPoly = DensePolynomial<Scalar>;
+
+    #[test]
+    fn all_openings() {
+        let rng = &mut test_rng();
+        let powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 32);
+
+        // degree below, equal to and above the domain size
+        for (degree, domain_size) in [(5, 8), (7, 8), (8, 8), (20, 8), (0, 4)] {
+            let poly = UniPoly::rand(degree, rng);
+            let domain = GeneralEvaluationDomain::new(domain_size).unwrap();
+            let proofs = Kzg::open_all(&poly, &domain, &powers).unwrap();
+            assert_eq!(proofs.len(), domain_size);
+            for (i, proof) in proofs.into_iter().enumerate() {
+                let point = domain.element(i);
+                let value = poly.evaluate(&point);
+                assert_eq!(
+                    proof,
+                    Kzg::proof(&poly, point, value, &powers).unwrap(),
+                    "degree {degree}, index {i}"
+                );
+            }
+        }
+    }
+}
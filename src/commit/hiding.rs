@@ -0,0 +1,309 @@
+//! Hiding KZG commitments as in the original KZG10 construction, which keep commitments to
+//! low-entropy data from being opened by trying every candidate.
+use super::kzg::{Kzg, Powers};
+use crate::hash::Hasher;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM as Msm};
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::Polynomial;
+use ark_poly_commit::DenseUVPolynomial;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+use zeroize::{Zeroize, Zeroizing};
+
+const PROOF_DOMAIN_SEP: &[u8] = b"fde zk kzg opening";
+
+/// The powers `gamma * tau^i * g1` of the hiding generator.
+#[derive(Clone, Debug)]
+pub struct HidingPowers<C: Pairing> {
+    pub gamma_g1: Vec<C::G1Affine>,
+}
+
+impl<C: Pairing> HidingPowers<C> {
+    /// Generates powers for blinding polynomials of degree up to `hiding_degree` from known
+    /// secrets.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn unsafe_setup(tau: C::ScalarField, gamma: C::ScalarField, hiding_degree: usize) -> Self {
+        let mut exponent = gamma;
+        let gamma_g1 = (0..=hiding_degree)
+            .map(|_| {
+                let point = C::G1Affine::generator() * exponent;
+                exponent *= tau;
+                point
+            })
+            .collect::<Vec<_>>();
+        Self {
+            gamma_g1: C::G1::normalize_batch(&gamma_g1),
+        }
+    }
+
+    pub fn hiding_degree(&self) -> usize {
+        self.gamma_g1.len().saturating_sub(1)
+    }
+}
+
+/// Random blinding polynomial of a hiding commitment, required for opening it. The coefficients
+/// are wiped from memory when it is dropped.
+pub struct Blinding<S: PrimeField>(pub DensePolynomial<S>);
+
+impl<S: PrimeField> Drop for Blinding<S> {
+    fn drop(&mut self) {
+        self.0.coeffs.zeroize();
+    }
+}
+
+/// Opening of a hiding commitment. Besides the witness, it contains the evaluation of the
+/// blinding polynomial at the opened point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HidingProof<C: Pairing> {
+    pub witness: C::G1Affine,
+    pub blinding_value: C::ScalarField,
+}
+
+/// Zero-knowledge opening of a hiding commitment, see [`HidingKzg::open_zk`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZkHidingProof<C: Pairing> {
+    pub witness: C::G1Affine,
+    /// `r(point) * gamma * g1` for the blinding polynomial `r`.
+    pub blinding_commitment: C::G1Affine,
+    pub challenge: C::ScalarField,
+    pub claim: C::ScalarField,
+}
+
+/// A random blinding polynomial `r` is committed to under a second generator `gamma * g1` whose
+/// discrete logarithm is unknown, i.e. the commitment is `f(tau) * g1 + r(tau) * gamma * g1`.
+/// Every opening reveals one evaluation of `r`, thus a blinding polynomial of degree `d` keeps the
+/// commitment hiding for up to `d` openings. The openings of [`Self::open_zk`] don't reveal the
+/// evaluation of `r`.
+pub struct HidingKzg<C: Pairing>(PhantomData<C>);
+
+impl<C: Pairing> HidingKzg<C> {
+    /// Commits to `poly` with a random blinding polynomial of the maximum degree supported by
+    /// `hiding`.
+    pub fn commit<R: Rng + CryptoRng>(
+        poly: &DensePolynomial<C::ScalarField>,
+        powers: &Powers<C>,
+        hiding: &HidingPowers<C>,
+        rng: &mut R,
+    ) -> Result<(C::G1, Blinding<C::ScalarField>), CrateError> {
+        let blinding = Blinding(DensePolynomial::rand(hiding.hiding_degree(), rng));
+        let commitment = powers.commit_g1(poly)? + Self::commit_blinding(&blinding.0, hiding)?;
+        Ok((commitment, blinding))
+    }
+
+    /// Opens the commitment to `poly` at `point`, the opened value is `poly(point)`.
+    pub fn open(
+        poly: &DensePolynomial<C::ScalarField>,
+        blinding: &Blinding<C::ScalarField>,
+        point: C::ScalarField,
+        powers: &Powers<C>,
+        hiding: &HidingPowers<C>,
+    ) -> Result<HidingProof<C>, CrateError> {
+        let witness_poly = Kzg::<C>::witness(poly, point);
+        let blinding_witness_poly = Blinding(Kzg::<C>::witness(&blinding.0, point));
+        let witness = powers.commit_g1(&witness_poly)?
+            + Self::commit_blinding(&blinding_witness_poly.0, hiding)?;
+        Ok(HidingProof {
+            witness: witness.into_affine(),
+            blinding_value: blinding.0.evaluate(&point),
+        })
+    }
+
+    /// Checks `e(com - value * g1 - r(point) * gamma * g1, g2) = e(witness, (tau - point) * g2)`.
+    pub fn verify(
+        proof: &HidingProof<C>,
+        commitment: C::G1Affine,
+        point: C::ScalarField,
+        value: C::ScalarField,
+        powers: &Powers<C>,
+        hiding: &HidingPowers<C>,
+    ) -> bool {
+        let Some(gamma_g1) = hiding.gamma_g1.first() else {
+            return false;
+        };
+        let lhs = commitment.into_group()
+            - C::G1Affine::generator() * value
+            - *gamma_g1 * proof.blinding_value;
+        let rhs_g2 = powers.g2_tau().into_group() - C::G2Affine::generator() * point;
+        Kzg::<C>::pairing_check(lhs, proof.witness.into_group(), rhs_g2)
+    }
+
+    /// Same as [`Self::open`] but the evaluation of the blinding polynomial is replaced by its
+    /// commitment `r(point) * gamma * g1` and a proof of knowledge of `r(point)`, so that the
+    /// opening reveals nothing beyond `poly(point)`.
+    pub fn open_zk<D: Digest, R: Rng + CryptoRng>(
+        poly: &DensePolynomial<C::ScalarField>,
+        blinding: &Blinding<C::ScalarField>,
+        commitment: C::G1Affine,
+        point: C::ScalarField,
+        powers: &Powers<C>,
+        hiding: &HidingPowers<C>,
+        rng: &mut R,
+    ) -> Result<ZkHidingProof<C>, CrateError> {
+        let proof = Self::open(poly, blinding, point, powers, hiding)?;
+        let gamma_g1 = hiding.gamma_g1[0];
+        let blinding_value = Zeroizing::new(proof.blinding_value);
+        let rand = Zeroizing::new(C::ScalarField::rand(rng));
+        let points = C::G1::normalize_batch(&[gamma_g1 * *blinding_value, gamma_g1 * *rand]);
+
+        let challenge = Self::challenge::<D>(
+            commitment,
+            point,
+            poly.evaluate(&point),
+            proof.witness,
+            points[0],
+            points[1],
+        );
+        Ok(ZkHidingProof {
+            witness: proof.witness,
+            blinding_commitment: points[0],
+            challenge,
+            claim: *rand - challenge * *blinding_value,
+        })
+    }
+
+    /// Checks the proof of knowledge and
+    /// `e(com - value * g1 - blinding_commitment, g2) = e(witness, (tau - point) * g2)`.
+    pub fn verify_zk<D: Digest>(
+        proof: &ZkHidingProof<C>,
+        commitment: C::G1Affine,
+        point: C::ScalarField,
+        value: C::ScalarField,
+        powers: &Powers<C>,
+        hiding: &HidingPowers<C>,
+    ) -> bool {
+        let Some(gamma_g1) = hiding.gamma_g1.first() else {
+            return false;
+        };
+        let k = *gamma_g1 * proof.claim + proof.blinding_commitment * proof.challenge;
+        let challenge = Self::challenge::<D>(
+            commitment,
+            point,
+            value,
+            proof.witness,
+            proof.blinding_commitment,
+            k.into_affine(),
+        );
+        if challenge != proof.challenge {
+            return false;
+        }
+        let lhs =
+            commitment.into_group() - C::G1Affine::generator() * value - proof.blinding_commitment;
+        let rhs_g2 = powers.g2_tau().into_group() - C::G2Affine::generator() * point;
+        Kzg::<C>::pairing_check(lhs, proof.witness.into_group(), rhs_g2)
+    }
+
+    fn challenge<D: Digest>(
+        commitment: C::G1Affine,
+        point: C::ScalarField,
+        value: C::ScalarField,
+        witness: C::G1Affine,
+        blinding_commitment: C::G1Affine,
+        k: C::G1Affine,
+    ) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(&PROOF_DOMAIN_SEP);
+        hasher.update(&commitment);
+        hasher.update(&point);
+        hasher.update(&value);
+        hasher.update(&witness);
+        hasher.update(&blinding_commitment);
+        hasher.update(&k);
+        C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+    }
+
+    fn commit_blinding(
+        blinding: &DensePolynomial<C::ScalarField>,
+        hiding: &HidingPowers<C>,
+    ) -> Result<C::G1, CrateError> {
+        let coeffs = blinding.coeffs();
+        if coeffs.len() > hiding.gamma_g1.len() {
+            return Err(CrateError::InsufficientPowers {
+                required: coeffs.len(),
+                available: hiding.gamma_g1.len(),
+            });
+        }
+        Ok(Msm::msm_unchecked(&hiding.gamma_g1[..coeffs.len()], coeffs))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_std::{One, UniformRand};
+
+    type UniPoly = DensePolynomial<Scalar>;
+
+    #[test]
+    fn hiding_commitment() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 16);
+        let hiding = HidingPowers::<TestCurve>::unsafe_setup(tau, Scalar::rand(rng), 2);
+
+        let poly = UniPoly::rand(15, rng);
+        let (commitment, blinding) = HidingKzg::commit(&poly, &powers, &hiding, rng).unwrap();
+        let commitment = commitment.into_affine();
+        // a low-entropy polynomial can't be recovered by recomputing the plain commitment
+        assert_ne!(commitment, powers.commit_g1(&poly).unwrap().into_affine());
+        let (other, _) = HidingKzg::commit(&poly, &powers, &hiding, rng).unwrap();
+        assert_ne!(commitment, other.into_affine());
+
+        let point = Scalar::rand(rng);
+        let value = poly.evaluate(&point);
+        let proof = HidingKzg::open(&poly, &blinding, point, &powers, &hiding).unwrap();
+        assert!(HidingKzg::verify(
+            &proof, commitment, point, value, &powers, &hiding
+        ));
+        assert!(!HidingKzg::verify(
+            &proof,
+            commitment,
+            point,
+            value + Scalar::one(),
+            &powers,
+            &hiding
+        ));
+        let mut invalid = proof;
+        invalid.blinding_value += Scalar::one();
+        assert!(!HidingKzg::verify(
+            &invalid, commitment, point, value, &powers, &hiding
+        ));
+    }
+
+    #[test]
+    fn zero_knowledge_opening() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 8);
+        let hiding = HidingPowers::<TestCurve>::unsafe_setup(tau, Scalar::rand(rng), 2);
+
+        let poly = UniPoly::rand(7, rng);
+        let (commitment, blinding) = HidingKzg::commit(&poly, &powers, &hiding, rng).unwrap();
+        let commitment = commitment.into_affine();
+        let point = Scalar::rand(rng);
+        let value = poly.evaluate(&point);
+        let proof = HidingKzg::open_zk::<TestHash, _>(
+            &poly, &blinding, commitment, point, &powers, &hiding, rng,
+        )
+        .unwrap();
+        let verify = |proof: &ZkHidingProof<TestCurve>, value: Scalar| {
+            HidingKzg::verify_zk::<TestHash>(proof, commitment, point, value, &powers, &hiding)
+        };
+        assert!(verify(&proof, value));
+        assert!(!verify(&proof, value + Scalar::one()));
+
+        // the blinding commitment is bound by the proof of knowledge
+        let mut invalid = proof;
+        invalid.blinding_commitment = (invalid.blinding_commitment + hiding.gamma_g1[0]).into();
+        assert!(!verify(&invalid, value));
+        let mut invalid = proof;
+        invalid.claim += Scalar::one();
+        assert!(!verify(&invalid, value));
+    }
+}
@@ -0,0 +1,276 @@
+//! Transparent polynomial commitments based on the inner product argument of
+//! [Bulletproofs](https://eprint.iacr.org/2017/1066), as used by Halo.
+use super::scheme::PolynomialCommitment;
+use crate::hash::{hash_to_curve, Hasher};
+use crate::Error as CrateError;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Field;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::Polynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::marker::PhantomData;
+use ark_std::rand::Rng;
+use ark_std::{One, Zero};
+use digest::Digest;
+
+const PROOF_DOMAIN_SEP: &[u8] = b"fde ipa opening";
+/// Label from which [`Ipa::setup`] derives the generators.
+pub const GENERATORS_LABEL: &[u8] = b"fde ipa generators";
+
+/// Generators for committing to polynomials with up to `g.len()` coefficients, `g.len()` is a
+/// power of two.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Params<G: CurveGroup> {
+    pub g: Vec<G::Affine>,
+    pub u: G::Affine,
+}
+
+impl<G: CurveGroup> Params<G> {
+    /// Derives generators for polynomials of degree up to `max_degree` from `label`.
+    pub fn new<D: Digest>(label: &[u8], max_degree: usize) -> Self {
+        let size = (max_degree + 1).next_power_of_two();
+        let mut generators = (0..=size as u64).map(|i| hash_to_curve::<G, D>(label, i));
+        let u = generators.next().expect("at least one generator");
+        Self {
+            g: generators.collect(),
+            u,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Proof<G: CurveGroup> {
+    pub l: Vec<G::Affine>,
+    pub r: Vec<G::Affine>,
+    /// The coefficient vector folded down to a single element.
+    pub a: G::ScalarField,
+}
+
+/// A polynomial with coefficients `a` is committed to as `sum(a_i * g_i)`. The evaluation at `z`
+/// is the inner product of `a` and `b = (1, z, z^2, ...)`, which is proven by halving both vectors
+/// in every round. The generators are derived by hashing a public label to the curve, so nobody
+/// knows discrete logarithms between them and no trusted setup is required.
+pub struct Ipa<G, D>(PhantomData<(G, D)>);
+
+impl<G: CurveGroup, D: Digest> PolynomialCommitment<G::ScalarField> for Ipa<G, D> {
+    type Params = Params<G>;
+    type Commitment = G::Affine;
+    type Proof = Proof<G>;
+
+    /// Deterministically derives the generators from [`GENERATORS_LABEL`], `rng` is unused.
+    fn setup<R: Rng>(max_degree: usize, _rng: &mut R) -> Result<Self::Params, CrateError> {
+        Ok(Params::new::<D>(GENERATORS_LABEL, max_degree))
+    }
+
+    fn commit(
+        params: &Self::Params,
+        poly: &DensePolynomial<G::ScalarField>,
+    ) -> Result<Self::Commitment, CrateError> {
+        check_size(poly, params)?;
+        Ok(G::msm_unchecked(&params.g[..poly.coeffs.len()], &poly.coeffs).into_affine())
+    }
+
+    fn open(
+        params: &Self::Params,
+        poly: &DensePolynomial<G::ScalarField>,
+        point: G::ScalarField,
+    ) -> Result<(G::ScalarField, Self::Proof), CrateError> {
+        check_size(poly, params)?;
+        let n = params.g.len();
+        let mut a = poly.coeffs.clone();
+        a.resize(n, G::ScalarField::zero());
+        let mut b = powers_of(point, n);
+        let mut g: Vec<G> = params.g.iter().map(|p| p.into_group()).collect();
+
+        let value = poly.evaluate(&point);
+        let commitment = G::msm_unchecked(&params.g[..poly.coeffs.len()], &poly.coeffs);
+        let mut hasher = transcript::<G, D>(&commitment, point, value);
+        let u = params.u * hasher.next_scalar::<G::ScalarField>(b"u");
+
+        let mut l_vec = Vec::new();
+        let mut r_vec = Vec::new();
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+            let g_affine = G::normalize_batch(&g);
+            let l = G::msm_unchecked(&g_affine[half..], a_lo) + u * inner_product(a_lo, b_hi);
+            let r = G::msm_unchecked(&g_affine[..half], a_hi) + u * inner_product(a_hi, b_lo);
+            let lr = G::normalize_batch(&[l, r]);
+            hasher.update(&lr[0]);
+            hasher.update(&lr[1]);
+            l_vec.push(lr[0]);
+            r_vec.push(lr[1]);
+
+            let x: G::ScalarField = hasher.next_scalar(b"x");
+            // NOTE the challenge is zero only with negligible probability
+            let x_inv = x.inverse().expect("non-zero challenge");
+            a = fold(a_lo, a_hi, x, x_inv);
+            b = fold(b_lo, b_hi, x_inv, x);
+            g = g_lo
+                .iter()
+                .zip(g_hi)
+                .map(|(lo, hi)| *lo * x_inv + *hi * x)
+                .collect();
+        }
+
+        Ok((
+            value,
+            Proof {
+                l: l_vec,
+                r: r_vec,
+                a: a[0],
+            },
+        ))
+    }
+
+    /// Verification takes time linear in the number of generators since the folded generator
+    /// is an MSM over all of them.
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: G::ScalarField,
+        value: G::ScalarField,
+        proof: &Self::Proof,
+    ) -> bool {
+        let n = params.g.len();
+        let rounds = n.trailing_zeros() as usize;
+        if proof.l.len() != rounds || proof.r.len() != rounds {
+            return false;
+        }
+
+        let commitment = commitment.into_group();
+        let mut hasher = transcript::<G, D>(&commitment, point, value);
+        let u = params.u * hasher.next_scalar::<G::ScalarField>(b"u");
+
+        let mut challenges = Vec::with_capacity(rounds);
+        let mut folded = commitment + u * value;
+        for (l, r) in proof.l.iter().zip(&proof.r) {
+            hasher.update(l);
+            hasher.update(r);
+            let x: G::ScalarField = hasher.next_scalar(b"x");
+            let Some(x_inv) = x.inverse() else {
+                return false;
+            };
+            folded += *l * x.square() + *r * x_inv.square();
+            challenges.push((x, x_inv));
+        }
+
+        // the i-th generator is scaled by x_j if bit j of i (from the top) is set, else by 1/x_j
+        let mut s = vec![G::ScalarField::one(); n];
+        for (j, (x, x_inv)) in challenges.iter().enumerate() {
+            let bit = 1 << (rounds - 1 - j);
+            for (i, s) in s.iter_mut().enumerate() {
+                *s *= if i & bit != 0 { x } else { x_inv };
+            }
+        }
+        let g_final = G::msm_unchecked(&params.g, &s);
+        let b_final = inner_product(&s, &powers_of(point, n));
+
+        folded == g_final * proof.a + u * (proof.a * b_final)
+    }
+}
+
+fn check_size<G: CurveGroup>(
+    poly: &DensePolynomial<G::ScalarField>,
+    params: &Params<G>,
+) -> Result<(), CrateError> {
+    if poly.coeffs.len() > params.g.len() {
+        return Err(CrateError::InsufficientPowers {
+            required: poly.coeffs.len(),
+            available: params.g.len(),
+        });
+    }
+    Ok(())
+}
+
+fn transcript<G: CurveGroup, D: Digest>(
+    commitment: &G,
+    point: G::ScalarField,
+    value: G::ScalarField,
+) -> Hasher<D> {
+    let mut hasher = Hasher::<D>::new();
+    hasher.update(&PROOF_DOMAIN_SEP);
+    hasher.update(commitment);
+    hasher.update(&point);
+    hasher.update(&value);
+    hasher
+}
+
+fn powers_of<S: Field>(x: S, n: usize) -> Vec<S> {
+    let mut powers = Vec::with_capacity(n);
+    let mut acc = S::one();
+    for _ in 0..n {
+        powers.push(acc);
+        acc *= x;
+    }
+    powers
+}
+
+fn inner_product<S: Field>(a: &[S], b: &[S]) -> S {
+    a.iter().zip(b).map(|(a, b)| *a * b).sum()
+}
+
+fn fold<S: Field>(lo: &[S], hi: &[S], x_lo: S, x_hi: S) -> Vec<S> {
+    lo.iter()
+        .zip(hi)
+        .map(|(lo, hi)| *lo * x_lo + *hi * x_hi)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+    use ark_poly_commit::DenseUVPolynomial;
+    use ark_std::UniformRand;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Scheme = Ipa<G1, TestHash>;
+
+    #[test]
+    fn ipa_opening() {
+        let rng = &mut test_rng();
+        let params = Scheme::setup(12, rng).unwrap();
+        assert_eq!(params.g.len(), 16);
+        assert_eq!(params, Scheme::setup(12, rng).unwrap());
+
+        for degree in [0, 5, 15] {
+            let poly = DensePolynomial::<Scalar>::rand(degree, rng);
+            let commitment = Scheme::commit(&params, &poly).unwrap();
+            let point = Scalar::rand(rng);
+            let (value, proof) = Scheme::open(&params, &poly, point).unwrap();
+            assert_eq!(value, poly.evaluate(&point));
+            assert_eq!(proof.l.len(), 4);
+            assert!(Scheme::verify(&params, &commitment, point, value, &proof));
+            assert!(!Scheme::verify(
+                &params,
+                &commitment,
+                point,
+                value + Scalar::one(),
+                &proof
+            ));
+            assert!(!Scheme::verify(
+                &params,
+                &commitment,
+                point + Scalar::one(),
+                value,
+                &proof
+            ));
+            let mut invalid = proof.clone();
+            invalid.a += Scalar::one();
+            assert!(!Scheme::verify(
+                &params,
+                &commitment,
+                point,
+                value,
+                &invalid
+            ));
+        }
+
+        assert!(Scheme::commit(&params, &DensePolynomial::rand(16, rng)).is_err());
+    }
+}
@@ -1,65 +1,309 @@
 // We need to commit to G2 as well, which arkworks' kzg10 implementation doesn't allow
+use super::msm::FixedBaseTable;
+use crate::codec;
+use crate::hash::Hasher;
+use crate::Error as CrateError;
 use ark_ec::pairing::Pairing;
 use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM as Msm};
 use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
-use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
 use ark_poly_commit::DenseUVPolynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::collections::BTreeMap;
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
 use ark_std::{One, UniformRand, Zero};
+use sha3::Keccak256;
 
+/// Number of coefficients per MSM in [`Powers::commit_g1_stream`].
+pub const STREAM_CHUNK_SIZE: usize = 1 << 16;
+
+const OPENINGS_DOMAIN_SEP: &[u8] = b"fde kzg openings";
+
+#[derive(Clone, Debug)]
 pub struct Powers<C: Pairing> {
     pub g1: Vec<C::G1Affine>,
     pub g2: Vec<C::G2Affine>,
+    // Lagrange basis G1 points keyed by domain size, see `add_lagrange_basis`
+    lagrange: BTreeMap<usize, Vec<C::G1Affine>>,
+    // prepared G2 points together with the `tau * g2` they were computed from, see `prepared`
+    prepared: Option<(C::G2Affine, PreparedPowers<C>)>,
+    // multiples of the G1 powers, see `precompute`
+    precomputed: Option<FixedBaseTable<C::G1>>,
 }
 
 impl<C: Pairing> Powers<C> {
+    /// Generates powers for a known `tau`, which breaks the binding property of commitments.
+    /// Only available for tests and with the `test-utils` feature, see [`super::ceremony`] for
+    /// generating a setup.
+    #[cfg(any(test, feature = "test-utils"))]
     pub fn unsafe_setup(tau: C::ScalarField, range: usize) -> Self {
-        let mut g1 = Vec::new();
-        let mut g2 = Vec::new();
+        let mut exponents = Vec::with_capacity(range);
         let mut exponent = C::ScalarField::one();
         for _ in 1..=range {
-            g1.push((<C::G1Affine as AffineRepr>::generator() * exponent).into_affine());
-            g2.push((<C::G2Affine as AffineRepr>::generator() * exponent).into_affine());
+            exponents.push(exponent);
             exponent *= tau;
         }
-        Self { g1, g2 }
+        Self::from_exponents(&exponents)
     }
 
+    #[cfg(any(test, feature = "test-utils"))]
     pub fn unsafe_setup_eip_4844(tau: C::ScalarField, range: usize) -> Self {
-        let mut g1 = Vec::new();
-        let mut g2 = Vec::new();
         let domain = GeneralEvaluationDomain::new(range).unwrap();
         let lagrange_evaluations = domain.evaluate_all_lagrange_coefficients(tau);
-        lagrange_evaluations.into_iter().for_each(|exponent| {
-            g1.push((<C::G1Affine as AffineRepr>::generator() * exponent).into_affine());
-            g2.push((<C::G2Affine as AffineRepr>::generator() * exponent).into_affine());
-        });
+        Self::from_exponents(&lagrange_evaluations)
+    }
+
+    // multiplies both generators by every exponent via fixed-base windowed multiplication
+    #[cfg(any(test, feature = "test-utils"))]
+    fn from_exponents(exponents: &[C::ScalarField]) -> Self {
+        Self::new(
+            fixed_base_mul(C::G1Affine::generator().into_group(), exponents),
+            fixed_base_mul(C::G2Affine::generator().into_group(), exponents),
+        )
+    }
+
+    pub fn new(g1: Vec<C::G1Affine>, g2: Vec<C::G2Affine>) -> Self {
+        let prepared = g2
+            .get(1)
+            .map(|&g2_tau| (g2_tau, PreparedPowers::new(g2_tau)));
+        Self {
+            g1,
+            g2,
+            lagrange: BTreeMap::new(),
+            prepared,
+            precomputed: None,
+        }
+    }
+
+    /// Precomputes a [`FixedBaseTable`] of the G1 powers with windows of `window_bits` bits,
+    /// which is used by all subsequent G1 commitments to monomial coefficients.
+    ///
+    /// The table takes `ceil(log2(r) / window_bits)` times the memory of the G1 powers for the
+    /// scalar field order `r`, thus this pays off only if the powers are reused for many
+    /// commitments. The table isn't updated if `g1` is modified directly, call this again or
+    /// [`Self::clear_precomputed`] afterwards.
+    pub fn precompute(&mut self, window_bits: usize) -> Result<(), CrateError> {
+        self.precomputed = Some(FixedBaseTable::new(&self.g1, window_bits)?);
+        Ok(())
+    }
+
+    /// Returns the table computed by [`Self::precompute`].
+    pub fn precomputed(&self) -> Option<&FixedBaseTable<C::G1>> {
+        self.precomputed.as_ref()
+    }
 
-        Self { g1, g2 }
+    pub fn clear_precomputed(&mut self) {
+        self.precomputed = None;
     }
 
+    /// Returns the [`PreparedPowers`] computed when the powers were created, which are used by
+    /// [`Kzg::verify_scalar`]. Returns `None` if `tau * g2` was modified since.
+    pub fn prepared(&self) -> Option<&PreparedPowers<C>> {
+        match &self.prepared {
+            Some((g2_tau, prepared)) if self.g2.get(1) == Some(g2_tau) => Some(prepared),
+            _ => None,
+        }
+    }
+
+    /// Precomputes the G1 points `L_i(tau) * g1` of the Lagrange basis of `domain`, which are used
+    /// by [`Self::commit_evals`].
+    ///
+    /// The points are the inverse FFT of the first `domain.size()` monomial powers, thus this only
+    /// makes sense for powers in the monomial basis (i.e. not the ones returned by
+    /// `unsafe_setup_eip_4844`).
+    pub fn add_lagrange_basis(
+        &mut self,
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+    ) -> Result<(), CrateError> {
+        let size = domain.size();
+        if self.g1.len() < size {
+            return Err(CrateError::InsufficientPowers {
+                required: size,
+                available: self.g1.len(),
+            });
+        }
+        let monomial: Vec<C::G1> = self.g1[..size].iter().map(|p| p.into_group()).collect();
+        let lagrange = C::G1::normalize_batch(&domain.ifft(&monomial));
+        self.lagrange.insert(size, lagrange);
+        Ok(())
+    }
+
+    /// Returns the Lagrange basis points of a domain of the given size if they were precomputed
+    /// via [`Self::add_lagrange_basis`].
+    pub fn lagrange_basis(&self, size: usize) -> Option<&[C::G1Affine]> {
+        self.lagrange.get(&size).map(Vec::as_slice)
+    }
+
+    /// Commits to the polynomial with the given evaluations over `domain`.
+    ///
+    /// If the Lagrange basis of `domain` was precomputed, this is a single MSM of the evaluations
+    /// and the basis points. Otherwise, the evaluations are interpolated via an inverse FFT first.
+    /// Missing evaluations are treated as zeros.
+    pub fn commit_evals(
+        &self,
+        evals: &[C::ScalarField],
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+    ) -> Result<C::G1, CrateError> {
+        check_capacity(evals.len(), domain.size())?;
+        match self.lagrange_basis(domain.size()) {
+            Some(basis) => Ok(Msm::msm_unchecked(&basis[..evals.len()], evals)),
+            None => {
+                check_capacity(domain.size(), self.g1.len())?;
+                Ok(self.commit_scalars_g1(&domain.ifft(evals)))
+            }
+        }
+    }
+
+    /// Encodes `bytes` via [`codec::encode`] and commits to the polynomial whose evaluations over
+    /// the smallest fitting domain are the encoded elements, see [`Self::commit_evals`].
+    pub fn commit_bytes(&self, bytes: &[u8]) -> Result<C::G1, CrateError> {
+        let elements = codec::encode(bytes)?;
+        let domain = GeneralEvaluationDomain::new(elements.len())
+            .ok_or(CrateError::InvalidFftDomain(elements.len()))?;
+        self.commit_evals(&elements, &domain)
+    }
+
+    /// Commits to the given coefficients via a Pippenger MSM, which runs in parallel with the
+    /// `parallel` feature, or via the table of [`Self::precompute`]. Panics if there are more
+    /// coefficients than G1 powers, see [`Self::commit_g1`] for a fallible version.
     pub fn commit_scalars_g1(&self, scalars: &[C::ScalarField]) -> C::G1 {
-        Msm::msm_unchecked(&self.g1[0..scalars.len()], scalars)
+        self.msm_g1(0, scalars)
+    }
+
+    // MSM of the scalars and the G1 powers starting at `offset`
+    fn msm_g1(&self, offset: usize, scalars: &[C::ScalarField]) -> C::G1 {
+        let end = offset + scalars.len();
+        match &self.precomputed {
+            Some(table) if end <= table.len() => table.msm(offset, scalars),
+            _ => Msm::msm_unchecked(&self.g1[offset..end], scalars),
+        }
     }
 
+    /// Commits to the given coefficients. Panics if there are more coefficients than G2 powers,
+    /// see [`Self::commit_g2`] for a fallible version.
     pub fn commit_scalars_g2(&self, scalars: &[C::ScalarField]) -> C::G2 {
         Msm::msm_unchecked(&self.g2[0..scalars.len()], scalars)
     }
 
+    /// Keeps the G1 powers for polynomials of degree up to `degree` and drops the precomputed
+    /// Lagrange bases of larger domains as well as the precomputed multiples of removed powers.
+    /// The G2 powers are kept, the G1 powers can be loaded again via
+    /// [`Self::extend_from_reader`].
+    pub fn truncate(&mut self, degree: usize) {
+        self.g1.truncate(degree.saturating_add(1));
+        let len = self.g1.len();
+        self.lagrange.retain(|&size, _| size <= len);
+        if let Some(table) = &mut self.precomputed {
+            table.truncate(len);
+        }
+    }
+
+    /// The maximum degree of polynomials that can be committed to in G1.
+    pub fn max_degree(&self) -> usize {
+        self.g1.len().saturating_sub(1)
+    }
+
+    /// Commits to `poly` in G1, returns [`CrateError::InsufficientPowers`] if its degree exceeds
+    /// [`Self::max_degree`].
     pub fn commit_g1<P: DenseUVPolynomial<C::ScalarField, Point = C::ScalarField>>(
         &self,
         poly: &P,
-    ) -> C::G1 {
-        self.commit_scalars_g1(poly.coeffs())
+    ) -> Result<C::G1, CrateError> {
+        check_capacity(poly.coeffs().len(), self.g1.len())?;
+        Ok(self.commit_scalars_g1(poly.coeffs()))
     }
 
+    /// Same as [`Self::commit_g1`] but takes the coefficients from an iterator, so the polynomial
+    /// never has to be held in memory as a whole.
+    ///
+    /// The coefficients are collected in chunks of [`STREAM_CHUNK_SIZE`] and every chunk is
+    /// committed with a separate MSM against the corresponding powers.
+    pub fn commit_g1_stream<I: IntoIterator<Item = C::ScalarField>>(
+        &self,
+        coeffs: I,
+    ) -> Result<C::G1, CrateError> {
+        let mut coeffs = coeffs.into_iter();
+        let mut chunk = Vec::with_capacity(STREAM_CHUNK_SIZE);
+        let mut commitment = C::G1::zero();
+        let mut offset = 0;
+        loop {
+            chunk.clear();
+            chunk.extend(coeffs.by_ref().take(STREAM_CHUNK_SIZE));
+            if chunk.is_empty() {
+                return Ok(commitment);
+            }
+            let end = offset + chunk.len();
+            if end > self.g1.len() {
+                return Err(CrateError::InsufficientPowers {
+                    required: end + coeffs.count(),
+                    available: self.g1.len(),
+                });
+            }
+            commitment += self.msm_g1(offset, &chunk);
+            offset = end;
+        }
+    }
+
+    /// Commits to `poly` in G2, returns [`CrateError::InsufficientPowers`] if it has more
+    /// coefficients than there are G2 powers.
     pub fn commit_g2<P: DenseUVPolynomial<C::ScalarField, Point = C::ScalarField>>(
         &self,
         poly: &P,
-    ) -> C::G2 {
-        self.commit_scalars_g2(poly.coeffs())
+    ) -> Result<C::G2, CrateError> {
+        check_capacity(poly.coeffs().len(), self.g2.len())?;
+        Ok(self.commit_scalars_g2(poly.coeffs()))
+    }
+
+    /// Checks that the G1 and G2 points are consistent powers of the same `tau`, i.e. that
+    /// `g1[i] = tau^i * g1` and `g2[i] = tau^i * g2` for the group generators.
+    ///
+    /// Checking every pair `e(g1[i + 1], g2) = e(g1[i], g2[1])` separately would require two
+    /// pairings per point. Instead, both sequences are folded with random 128-bit weights into a
+    /// single multi-pairing, which accepts inconsistent powers only with negligible probability.
+    /// The points are expected to be valid group elements, e.g. deserialized with validation.
+    /// Powers in the Lagrange basis (see `unsafe_setup_eip_4844`) don't pass this check.
+    pub fn validate<R: Rng + CryptoRng>(&self, rng: &mut R) -> Result<(), CrateError> {
+        let available = self.g1.len().min(self.g2.len());
+        if available < 2 {
+            return Err(CrateError::InsufficientPowers {
+                required: 2,
+                available,
+            });
+        }
+        if self.g1[0] != C::G1Affine::generator() || self.g2[0] != C::G2Affine::generator() {
+            return Err(CrateError::InconsistentPowers);
+        }
+
+        // sum(r_i * g1[i + 1]) and sum(r_i * g1[i]) differ by a factor of tau
+        let g1_weights: Vec<C::ScalarField> =
+            (1..self.g1.len()).map(|_| u128::rand(rng).into()).collect();
+        let g1_shifted: C::G1 = Msm::msm_unchecked(&self.g1[1..], &g1_weights);
+        let g1_base: C::G1 = Msm::msm_unchecked(&self.g1[..self.g1.len() - 1], &g1_weights);
+        let g2_weights: Vec<C::ScalarField> =
+            (1..self.g2.len()).map(|_| u128::rand(rng).into()).collect();
+        let g2_shifted: C::G2 = Msm::msm_unchecked(&self.g2[1..], &g2_weights);
+        let g2_base: C::G2 = Msm::msm_unchecked(&self.g2[..self.g2.len() - 1], &g2_weights);
+
+        // e(A, g2) * e(-B, tau * g2) * e(g1, C) * e(-tau * g1, D) = 1
+        let g1_points = C::G1::normalize_batch(&[
+            g1_shifted,
+            -g1_base,
+            self.g1[0].into_group(),
+            -self.g1[1].into_group(),
+        ]);
+        let g2_points = C::G2::normalize_batch(&[
+            self.g2[0].into_group(),
+            self.g2[1].into_group(),
+            g2_shifted,
+            g2_base,
+        ]);
+        if C::multi_pairing(g1_points, g2_points).0.is_one() {
+            Ok(())
+        } else {
+            Err(CrateError::InconsistentPowers)
+        }
     }
 
     pub fn g1_tau(&self) -> C::G1Affine {
@@ -75,6 +319,122 @@ impl<C: Pairing> Powers<C> {
     }
 }
 
+#[cfg(any(test, feature = "test-utils"))]
+fn fixed_base_mul<G: CurveGroup>(base: G, scalars: &[G::ScalarField]) -> Vec<G::Affine> {
+    use ark_ec::scalar_mul::fixed_base::FixedBase;
+    let scalar_size = G::ScalarField::MODULUS_BIT_SIZE as usize;
+    let window = FixedBase::get_mul_window_size(scalars.len());
+    let table = FixedBase::get_window_table(scalar_size, window, base);
+    G::normalize_batch(&FixedBase::msm(scalar_size, window, &table, scalars))
+}
+
+pub(super) fn check_capacity(required: usize, available: usize) -> Result<(), CrateError> {
+    if required > available {
+        return Err(CrateError::InsufficientPowers {
+            required,
+            available,
+        });
+    }
+    Ok(())
+}
+
+/// The G2 elements needed for verifying KZG openings in the [`Pairing::G2Prepared`] form.
+///
+/// Preparing a G2 point precomputes the line coefficients used by the Miller loop. Verifiers
+/// that check many openings against the same setup should build this once and reuse it instead of
+/// repeating the preprocessing in every pairing.
+#[derive(Clone, Debug)]
+pub struct PreparedPowers<C: Pairing> {
+    pub g2: C::G2Prepared,
+    pub g2_tau: C::G2Prepared,
+}
+
+impl<C: Pairing> PreparedPowers<C> {
+    pub fn new(g2_tau: C::G2Affine) -> Self {
+        Self {
+            g2: C::G2Affine::generator().into(),
+            g2_tau: g2_tau.into(),
+        }
+    }
+}
+
+impl<C: Pairing> From<&Powers<C>> for PreparedPowers<C> {
+    fn from(powers: &Powers<C>) -> Self {
+        Self::new(powers.g2_tau())
+    }
+}
+
+/// The full setup is all a prover needs, see [`VerifierKey`] for the verifier's part.
+pub type ProverKey<C> = Powers<C>;
+
+/// The part of the setup needed for verifying KZG openings.
+///
+/// Verifiers only need the generators and `tau * g2`, along with the first few G1 powers if
+/// commitments are computed on their side. Compared to the full [`Powers`], this is small enough
+/// to embed into WASM binaries or contracts.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifierKey<C: Pairing> {
+    pub g1: Vec<C::G1Affine>,
+    pub g2: C::G2Affine,
+    pub g2_tau: C::G2Affine,
+}
+
+impl<C: Pairing> VerifierKey<C> {
+    pub fn g1_tau(&self) -> C::G1Affine {
+        self.g1[1]
+    }
+
+    pub fn prepare(&self) -> PreparedPowers<C> {
+        PreparedPowers {
+            g2: self.g2.into(),
+            g2_tau: self.g2_tau.into(),
+        }
+    }
+}
+
+impl<C: Pairing> Powers<C> {
+    /// Extracts the [`VerifierKey`] with the first `num_g1` G1 powers (at least the generator and
+    /// `tau * g1`).
+    pub fn verifier_key(&self, num_g1: usize) -> Result<VerifierKey<C>, CrateError> {
+        let required = num_g1.max(2);
+        if self.g1.len() < required || self.g2.len() < 2 {
+            return Err(CrateError::InsufficientPowers {
+                required,
+                available: self.g1.len().min(self.g2.len()),
+            });
+        }
+        Ok(VerifierKey {
+            g1: self.g1[..required].to_vec(),
+            g2: self.g2[0],
+            g2_tau: self.g2[1],
+        })
+    }
+}
+
+/// Evaluations of a polynomial at several points together with a single opening witness, see
+/// [`Kzg::open_multi`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiProof<C: Pairing> {
+    pub witness: C::G1Affine,
+    pub values: Vec<C::ScalarField>,
+}
+
+/// Evaluations of several polynomials at the same point together with a single opening witness,
+/// see [`Kzg::open_batch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchProof<C: Pairing> {
+    pub witness: C::G1Affine,
+    pub values: Vec<C::ScalarField>,
+}
+
+/// A single opening `(commitment, point, value, proof)`, see [`Kzg::verify_openings`].
+pub type Opening<C> = (
+    <C as Pairing>::G1Affine,
+    <C as Pairing>::ScalarField,
+    <C as Pairing>::ScalarField,
+    <C as Pairing>::G1Affine,
+);
+
 pub struct Kzg<C: Pairing>(PhantomData<C>);
 
 impl<C: Pairing> Kzg<C> {
@@ -100,10 +460,10 @@ impl<C: Pairing> Kzg<C> {
         point: C::ScalarField,
         value: C::ScalarField,
         powers: &Powers<C>,
-    ) -> C::G1Affine {
+    ) -> Result<C::G1Affine, CrateError> {
         let numerator = poly + &DensePolynomial::from_coefficients_slice(&[-value]);
         let quotient = Self::witness(&numerator, point);
-        powers.commit_g1(&quotient).into()
+        Ok(powers.commit_g1(&quotient)?.into())
     }
 
     pub fn verify_scalar(
@@ -113,6 +473,9 @@ impl<C: Pairing> Kzg<C> {
         value: C::ScalarField,
         powers: &Powers<C>,
     ) -> bool {
+        if let Some(prepared) = powers.prepared() {
+            return Self::verify_prepared(proof, commitment.into(), point, value, prepared);
+        }
         let point_g2 = C::G2Affine::generator() * point;
         let value_g1 = C::G1Affine::generator() * value;
         Self::verify(proof, commitment, point_g2, value_g1, powers)
@@ -133,13 +496,258 @@ impl<C: Pairing> Kzg<C> {
         Self::pairing_check(com_over_g_value, proof.into_group(), g_tau_over_g_point)
     }
 
+    /// Verifies the opening of a commitment in G2 (see [`Powers::commit_g2`]), where the witness
+    /// is still computed in G1 via [`Self::proof`].
+    ///
+    /// The pairing is arranged as `e(g1, com - value * g2) = e(proof, (tau - point) * g2)`.
+    pub fn verify_g2(
+        proof: C::G1Affine,
+        commitment: C::G2Affine,
+        point: C::ScalarField,
+        value: C::ScalarField,
+        powers: &Powers<C>,
+    ) -> bool {
+        let com_over_g_value = commitment.into_group() - C::G2Affine::generator() * value;
+        let g_tau_over_g_point = powers.g2_tau().into_group() - C::G2Affine::generator() * point;
+        C::pairing(C::G1Affine::generator(), com_over_g_value)
+            == C::pairing(proof, g_tau_over_g_point)
+    }
+
+    /// Proves that `poly` has degree less than `bound`.
+    ///
+    /// The proof is a commitment to `X^k * poly` with `k = powers.g1.len() - bound`. Since the
+    /// setup doesn't contain G1 powers beyond `tau^(g1.len() - 1)`, this commitment can only be
+    /// computed if the degree of `poly` is at most `bound - 1`. Note that the verifier has to
+    /// use a setup with the same number of G1 powers as the original one.
+    pub fn prove_degree_bound(
+        poly: &DensePolynomial<C::ScalarField>,
+        bound: usize,
+        powers: &Powers<C>,
+    ) -> Result<C::G1Affine, CrateError> {
+        if bound == 0 || bound > powers.g1.len() {
+            return Err(CrateError::InvalidDegreeBound(bound));
+        }
+        if poly.coeffs.len() > bound {
+            return Err(CrateError::DegreeBoundExceeded {
+                degree: poly.degree(),
+                bound,
+            });
+        }
+        let shift = powers.g1.len() - bound;
+        let shifted: C::G1 =
+            Msm::msm_unchecked(&powers.g1[shift..shift + poly.coeffs.len()], &poly.coeffs);
+        Ok(shifted.into_affine())
+    }
+
+    /// Verifies a proof generated by [`Self::prove_degree_bound`] by checking
+    /// `e(proof, g2) = e(commitment, tau^k * g2)`, which requires the G2 power `tau^k`.
+    pub fn verify_degree_bound(
+        proof: C::G1Affine,
+        commitment: C::G1Affine,
+        bound: usize,
+        powers: &Powers<C>,
+    ) -> bool {
+        if bound == 0 || bound > powers.g1.len() {
+            return false;
+        }
+        let Some(g2_shift) = powers.g2.get(powers.g1.len() - bound) else {
+            return false;
+        };
+        C::multi_pairing(
+            [proof, (-commitment.into_group()).into_affine()],
+            [C::G2Affine::generator(), *g2_shift],
+        )
+        .0
+        .is_one()
+    }
+
+    /// Checks that a G1 and a G2 commitment commit to the same polynomial, i.e.
+    /// `e(com_g1, g2) = e(g1, com_g2)`.
+    pub fn cross_group_check(commitment_g1: C::G1Affine, commitment_g2: C::G2Affine) -> bool {
+        C::multi_pairing(
+            [
+                commitment_g1,
+                (-C::G1Affine::generator().into_group()).into_affine(),
+            ],
+            [C::G2Affine::generator(), commitment_g2],
+        )
+        .0
+        .is_one()
+    }
+
+    /// Same as [`Self::verify_scalar`] but only requires the [`VerifierKey`].
+    pub fn verify_with_key(
+        proof: C::G1Affine,
+        commitment: C::G1Affine,
+        point: C::ScalarField,
+        value: C::ScalarField,
+        key: &VerifierKey<C>,
+    ) -> bool {
+        let com_over_g_value = commitment.into_group() - key.g1[0] * value;
+        let g_tau_over_g_point = key.g2_tau.into_group() - key.g2 * point;
+        C::pairing(com_over_g_value, key.g2) == C::pairing(proof, g_tau_over_g_point)
+    }
+
+    /// Same as [`Self::verify_scalar`] but uses the [`PreparedPowers`] of the setup.
+    ///
+    /// The check `e(com - value * g1, g2) = e(proof, (tau - point) * g2)` is rearranged to
+    /// `e(com - value * g1 + point * proof, g2) * e(-proof, tau * g2) = 1`, so that only the fixed
+    /// G2 elements appear in the pairings.
+    pub fn verify_prepared(
+        proof: C::G1Affine,
+        commitment: C::G1,
+        point: C::ScalarField,
+        value: C::ScalarField,
+        prepared: &PreparedPowers<C>,
+    ) -> bool {
+        let lhs = commitment - C::G1Affine::generator() * value + proof * point;
+        let g1 = C::G1::normalize_batch(&[lhs, -proof.into_group()]);
+        C::multi_pairing(g1, [prepared.g2.clone(), prepared.g2_tau.clone()])
+            .0
+            .is_one()
+    }
+
+    /// Opens every polynomial in `polys` at `point` with a single witness.
+    ///
+    /// The witness is computed for the linear combination `sum(challenge^i * polys[i])`, where
+    /// `challenge` should be derived from the commitments and `point` via Fiat-Shamir, otherwise
+    /// the prover could cancel out invalid evaluations.
+    pub fn open_batch(
+        polys: &[&DensePolynomial<C::ScalarField>],
+        point: C::ScalarField,
+        challenge: C::ScalarField,
+        powers: &Powers<C>,
+    ) -> Result<BatchProof<C>, CrateError> {
+        let values = polys.iter().map(|poly| poly.evaluate(&point)).collect();
+        let mut acc = C::ScalarField::one();
+        let mut aggregated = DensePolynomial::zero();
+        for poly in polys {
+            aggregated += (acc, *poly);
+            acc *= challenge;
+        }
+        let witness_poly = Self::witness(&aggregated, point);
+        Ok(BatchProof {
+            witness: powers.commit_g1(&witness_poly)?.into_affine(),
+            values,
+        })
+    }
+
+    /// Verifies a [`BatchProof`] for `commitments` generated with the same `challenge`.
+    pub fn verify_batch(
+        proof: &BatchProof<C>,
+        commitments: &[C::G1Affine],
+        point: C::ScalarField,
+        challenge: C::ScalarField,
+        powers: &Powers<C>,
+    ) -> bool {
+        if commitments.len() != proof.values.len() {
+            return false;
+        }
+        let mut acc = C::ScalarField::one();
+        let mut commitment = C::G1::zero();
+        let mut value = C::ScalarField::zero();
+        for (com, v) in commitments.iter().zip(&proof.values) {
+            commitment += *com * acc;
+            value += acc * v;
+            acc *= challenge;
+        }
+        Self::verify_scalar(
+            proof.witness,
+            commitment.into_affine(),
+            point,
+            value,
+            powers,
+        )
+    }
+
+    /// Opens `poly` at every point in `points` with a single witness.
+    ///
+    /// With `I` interpolating the evaluations and `Z` vanishing on `points`, the witness commits to
+    /// `(poly - I) / Z`. Returns [`CrateError::RepeatedOpeningPoint`] if `points` are not
+    /// distinct.
+    pub fn open_multi(
+        poly: &DensePolynomial<C::ScalarField>,
+        points: &[C::ScalarField],
+        powers: &Powers<C>,
+    ) -> Result<MultiProof<C>, CrateError> {
+        let values: Vec<C::ScalarField> = points.iter().map(|point| poly.evaluate(point)).collect();
+        let interpolation = interpolate(points, &values).ok_or(CrateError::RepeatedOpeningPoint)?;
+        let witness_poly = &(poly - &interpolation) / &vanishing_poly(points);
+        Ok(MultiProof {
+            witness: powers.commit_g1(&witness_poly)?.into_affine(),
+            values,
+        })
+    }
+
+    /// Verifies a [`MultiProof`] by checking `e(com - I(tau) * g1, g2) = e(witness, Z(tau) * g2)`.
+    ///
+    /// Requires `points.len() + 1` G2 powers for committing to the vanishing polynomial `Z`.
+    pub fn verify_multi(
+        proof: &MultiProof<C>,
+        commitment: C::G1Affine,
+        points: &[C::ScalarField],
+        powers: &Powers<C>,
+    ) -> bool {
+        if points.len() != proof.values.len() {
+            return false;
+        }
+        let Some(interpolation) = interpolate(points, &proof.values) else {
+            return false;
+        };
+        let (Ok(com_interpolation), Ok(com_vanishing)) = (
+            powers.commit_g1(&interpolation),
+            powers.commit_g2(&vanishing_poly(points)),
+        ) else {
+            return false;
+        };
+        Self::pairing_check(
+            commitment.into_group() - com_interpolation,
+            proof.witness.into_group(),
+            com_vanishing,
+        )
+    }
+
     pub fn pairing_check(lhs_g1: C::G1, rhs_g1: C::G1, rhs_g2: C::G2) -> bool {
         let lhs = C::pairing(lhs_g1, C::G2Affine::generator());
         let rhs = C::pairing(rhs_g1, rhs_g2);
         lhs == rhs
     }
 
-    pub fn batch_verify<R: Rng>(
+    /// Verifies several unrelated openings `(commitment, point, value, proof)` at once.
+    ///
+    /// Every opening satisfies `e(com - value * g1 + point * proof, g2) = e(proof, tau * g2)`.
+    /// The openings are folded with 128-bit weights `r_i` into the single check
+    /// `e(sum(r_i * (com_i - value_i * g1 + point_i * proof_i)), g2) * e(-sum(r_i * proof_i),
+    /// tau * g2) = 1`, which requires two MSMs and a single multi-pairing.
+    ///
+    /// The weights are derived from a Keccak256 hash of all openings, so they are fixed only after
+    /// the openings are. This is the heterogeneous counterpart of [`Self::verify_batch`], which
+    /// checks a [`BatchProof`] of several polynomials at the same point.
+    pub fn verify_openings(openings: &[Opening<C>], powers: &Powers<C>) -> bool {
+        let mut hasher = Hasher::<Keccak256>::new();
+        hasher.update(&OPENINGS_DOMAIN_SEP);
+        for (commitment, point, value, proof) in openings {
+            hasher.update(commitment);
+            hasher.update(point);
+            hasher.update(value);
+            hasher.update(proof);
+        }
+        let seed: [u8; 32] = hasher.finalize().into();
+        let weights: Vec<C::ScalarField> = (0..openings.len() as u64)
+            .map(|i| {
+                let mut hasher = Hasher::<Keccak256>::new();
+                hasher.update(&seed);
+                hasher.update(&i);
+                let digest = hasher.finalize();
+                u128::from_le_bytes(digest[..16].try_into().unwrap()).into()
+            })
+            .collect();
+        Self::check_openings(openings, &weights, powers)
+    }
+
+    /// Same as [`Self::verify_openings`] with the openings given as separate slices and random
+    /// weights drawn from `rng`.
+    pub fn batch_verify<R: Rng + CryptoRng>(
         proofs: &[C::G1Affine],
         commitments: &[C::G1Affine],
         points: &[C::ScalarField],
@@ -147,43 +755,126 @@ impl<C: Pairing> Kzg<C> {
         powers: &Powers<C>,
         rng: &mut R,
     ) -> bool {
-        // NOTE copied (and slightly modified) from
-        // https://docs.rs/ark-poly-commit/latest/src/ark_poly_commit/kzg10/mod.rs.html#334-353
-        // because we need a more flexible KZG implementation
-        let mut total_c = <C::G1>::zero();
-        let mut total_w = <C::G1>::zero();
-
-        let mut randomizer = C::ScalarField::one();
-        // Instead of multiplying g and gamma_g in each turn, we simply accumulate
-        // their coefficients and perform a final multiplication at the end.
-        let mut g_multiplier = C::ScalarField::zero();
-        let gamma_g_multiplier = C::ScalarField::zero();
-        for (((c, &z), v), &w) in commitments.iter().zip(points).zip(values).zip(proofs) {
-            let mut temp = w * z;
-            temp += c;
-            let c = temp;
-            g_multiplier += &(randomizer * v);
-            total_c += c * randomizer;
-            total_w += w * randomizer;
-            // We don't need to sample randomizers from the full field,
-            // only from 128-bit strings.
-            randomizer = u128::rand(rng).into();
+        let openings: Vec<_> = commitments
+            .iter()
+            .zip(points)
+            .zip(values)
+            .zip(proofs)
+            .map(|(((&c, &z), &v), &w)| (c, z, v, w))
+            .collect();
+        let weights: Vec<C::ScalarField> =
+            openings.iter().map(|_| u128::rand(rng).into()).collect();
+        Self::check_openings(&openings, &weights, powers)
+    }
+
+    fn check_openings(
+        openings: &[Opening<C>],
+        weights: &[C::ScalarField],
+        powers: &Powers<C>,
+    ) -> bool {
+        let mut bases = Vec::with_capacity(2 * openings.len() + 1);
+        let mut scalars = Vec::with_capacity(2 * openings.len() + 1);
+        let mut proofs = Vec::with_capacity(openings.len());
+        let mut value_sum = C::ScalarField::zero();
+        for (&(commitment, point, value, proof), &weight) in openings.iter().zip(weights) {
+            bases.extend([commitment, proof]);
+            scalars.extend([weight, weight * point]);
+            value_sum += weight * value;
+            proofs.push(proof);
         }
-        total_c -= C::G1Affine::generator() * g_multiplier;
-        total_c -= powers.g1_tau() * gamma_g_multiplier;
+        bases.push(C::G1Affine::generator());
+        scalars.push(-value_sum);
 
-        let affine_points = C::G1::normalize_batch(&[-total_w, total_c]);
-        let (total_w, total_c) = (affine_points[0], affine_points[1]);
+        let lhs: C::G1 = Msm::msm_unchecked(&bases, &scalars);
+        let proof_sum: C::G1 = Msm::msm_unchecked(&proofs, weights);
+        let g1 = C::G1::normalize_batch(&[lhs, -proof_sum]);
+        C::multi_pairing(g1, [C::G2Affine::generator(), powers.g2_tau()])
+            .0
+            .is_one()
+    }
 
-        C::multi_pairing(
-            [total_w, total_c],
-            [powers.g2_tau(), C::G2Affine::generator()],
-        )
-        .0
-        .is_one()
+    /// Updates a commitment to evaluations over `domain` (see [`Powers::commit_evals`]) after the
+    /// evaluation at `index` changed from `old_value` to `new_value`.
+    ///
+    /// The commitment changes by `(new_value - old_value) * L_index(tau) * g1`, which is a single
+    /// scalar multiplication if the Lagrange basis of `domain` was precomputed via
+    /// [`Powers::add_lagrange_basis`]. Otherwise, the basis point is computed from the monomial
+    /// powers with an MSM of size `domain.size()`.
+    pub fn update_commitment(
+        old: C::G1,
+        index: usize,
+        old_value: C::ScalarField,
+        new_value: C::ScalarField,
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+        powers: &Powers<C>,
+    ) -> Result<C::G1, CrateError> {
+        let size = domain.size();
+        if index >= size {
+            return Err(CrateError::IndexOutOfBounds(index));
+        }
+        let delta = new_value - old_value;
+        let basis_point = match powers.lagrange_basis(size) {
+            Some(basis) => basis[index].into_group(),
+            None => {
+                check_capacity(size, powers.g1.len())?;
+                // L_i(X) = 1/n * sum_j (omega^-i)^j * X^j
+                let step = domain.element((size - index) % size);
+                let mut coeff = domain.size_inv();
+                let coeffs: Vec<C::ScalarField> = (0..size)
+                    .map(|_| {
+                        let current = coeff;
+                        coeff *= step;
+                        current
+                    })
+                    .collect();
+                powers.commit_scalars_g1(&coeffs)
+            }
+        };
+        Ok(old + basis_point * delta)
     }
 }
 
+/// Returns the polynomial `(X - points[0]) * .. * (X - points[k-1])`.
+pub fn vanishing_poly<S: PrimeField>(points: &[S]) -> DensePolynomial<S> {
+    points.iter().fold(
+        DensePolynomial::from_coefficients_vec(vec![S::one()]),
+        |acc, point| &acc * &DensePolynomial::from_coefficients_slice(&[-*point, S::one()]),
+    )
+}
+
+/// Returns the polynomial of degree less than `points.len()` that evaluates to `values` at
+/// `points`, or `None` if `points` are not distinct.
+pub fn interpolate<S: PrimeField>(points: &[S], values: &[S]) -> Option<DensePolynomial<S>> {
+    let distinct = points
+        .iter()
+        .enumerate()
+        .all(|(i, x_i)| !points[i + 1..].contains(x_i));
+    if !distinct {
+        return None;
+    }
+
+    let vanishing = vanishing_poly(points);
+    // the Lagrange denominators are the derivative of the vanishing polynomial at the points
+    let mut denominators: Vec<S> = points
+        .iter()
+        .map(|x_i| {
+            points
+                .iter()
+                .filter(|x_j| *x_j != x_i)
+                .map(|x_j| *x_i - x_j)
+                .product()
+        })
+        .collect();
+    ark_ff::batch_inversion(&mut denominators);
+
+    let mut result = DensePolynomial::zero();
+    for ((x_i, value), denominator) in points.iter().zip(values).zip(denominators) {
+        let basis = &vanishing / &DensePolynomial::from_coefficients_slice(&[-*x_i, S::one()]);
+        result += (*value * denominator, &basis);
+    }
+    Some(result)
+}
+
 pub fn aggregate_polys<S: PrimeField>(values: &[DensePolynomial<S>], by: S) -> DensePolynomial<S> {
     let mut acc = S::one();
     let mut result = DensePolynomial::zero();
@@ -200,11 +891,12 @@ pub fn aggregate_polys<S: PrimeField>(values: &[DensePolynomial<S>], by: S) -> D
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::tests::test_rng;
     use ark_bls12_381::Bls12_381 as BlsCurve;
     use ark_ec::CurveGroup;
     use ark_poly::univariate::DensePolynomial;
     use ark_poly::Polynomial;
-    use ark_std::{test_rng, One};
+    use ark_std::One;
 
     type Scalar = <BlsCurve as Pairing>::ScalarField;
     type UniPoly = DensePolynomial<Scalar>;
@@ -219,8 +911,8 @@ mod test {
         assert_eq!(poly_tau, Scalar::from(3));
         // kzg
         let powers = Powers::<BlsCurve>::unsafe_setup(tau, 10);
-        let com_g1 = powers.commit_g1(&poly);
-        let com_g2 = powers.commit_g2(&poly);
+        let com_g1 = powers.commit_g1(&poly).unwrap();
+        let com_g2 = powers.commit_g2(&poly).unwrap();
 
         assert_eq!(com_g1, (powers.g1[0] * poly_tau).into_affine());
         assert_eq!(com_g2, (powers.g2[0] * poly_tau).into_affine());
@@ -244,10 +936,10 @@ mod test {
             let mut proofs = Vec::new();
             for _ in 0..10 {
                 let poly = UniPoly::rand(degree, rng);
-                let comm = powers.commit_g1(&poly).into_affine();
+                let comm = powers.commit_g1(&poly).unwrap().into_affine();
                 let point = Scalar::rand(rng);
                 let value = poly.evaluate(&point);
-                let proof = Kzg::proof(&poly, point, value, &powers);
+                let proof = Kzg::proof(&poly, point, value, &powers).unwrap();
                 assert!(Kzg::verify_scalar(proof, comm, point, value, &powers));
 
                 comms.push(comm);
@@ -261,6 +953,138 @@ mod test {
         }
     }
 
+    #[test]
+    fn prepared_verification() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<BlsCurve>::unsafe_setup(tau, 8);
+        let prepared = PreparedPowers::from(&powers);
+
+        let poly = UniPoly::rand(7, rng);
+        let comm = powers.commit_g1(&poly).unwrap();
+        let point = Scalar::rand(rng);
+        let value = poly.evaluate(&point);
+        let proof = Kzg::proof(&poly, point, value, &powers).unwrap();
+        assert!(Kzg::verify_prepared(proof, comm, point, value, &prepared));
+        assert!(!Kzg::verify_prepared(
+            proof,
+            comm,
+            point,
+            value + Scalar::one(),
+            &prepared
+        ));
+        assert!(!Kzg::verify_prepared(
+            proof,
+            comm,
+            point + Scalar::one(),
+            value,
+            &prepared
+        ));
+    }
+
+    #[test]
+    fn batch_opening() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<BlsCurve>::unsafe_setup(tau, 16);
+
+        let polys: Vec<UniPoly> = (0..4).map(|i| UniPoly::rand(8 + i, rng)).collect();
+        let comms: Vec<_> = polys
+            .iter()
+            .map(|poly| powers.commit_g1(poly).unwrap().into_affine())
+            .collect();
+        let point = Scalar::rand(rng);
+        let challenge = Scalar::rand(rng);
+        let borrowed: Vec<&UniPoly> = polys.iter().collect();
+        let proof = Kzg::open_batch(&borrowed, point, challenge, &powers).unwrap();
+        assert_eq!(proof.values[1], polys[1].evaluate(&point));
+        assert!(Kzg::verify_batch(&proof, &comms, point, challenge, &powers));
+
+        // another challenge
+        let other = challenge + Scalar::one();
+        assert!(!Kzg::verify_batch(&proof, &comms, point, other, &powers));
+        // swapped commitments
+        let swapped = [comms[1], comms[0], comms[2], comms[3]];
+        assert!(!Kzg::verify_batch(
+            &proof, &swapped, point, challenge, &powers
+        ));
+        // invalid value
+        let mut invalid = proof.clone();
+        invalid.values[0] += Scalar::one();
+        assert!(!Kzg::verify_batch(
+            &invalid, &comms, point, challenge, &powers
+        ));
+    }
+
+    #[test]
+    fn multi_point_opening() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<BlsCurve>::unsafe_setup(tau, 16);
+
+        let poly = UniPoly::rand(12, rng);
+        let comm = powers.commit_g1(&poly).unwrap().into_affine();
+        let points: Vec<Scalar> = (0..5).map(|_| Scalar::rand(rng)).collect();
+        let proof = Kzg::open_multi(&poly, &points, &powers).unwrap();
+        assert_eq!(proof.values[2], poly.evaluate(&points[2]));
+        assert!(Kzg::verify_multi(&proof, comm, &points, &powers));
+
+        // invalid value
+        let mut invalid = proof.clone();
+        invalid.values[3] += Scalar::one();
+        assert!(!Kzg::verify_multi(&invalid, comm, &points, &powers));
+        // subset of the points
+        assert!(!Kzg::verify_multi(&proof, comm, &points[1..], &powers));
+        // duplicate points
+        let duplicate = [points[0], points[1], points[0]];
+        assert_eq!(
+            Kzg::open_multi(&poly, &duplicate, &powers),
+            Err(CrateError::RepeatedOpeningPoint)
+        );
+    }
+
+    #[test]
+    fn interpolation() {
+        let rng = &mut test_rng();
+        let points: Vec<Scalar> = (0..7).map(|_| Scalar::rand(rng)).collect();
+        let values: Vec<Scalar> = (0..7).map(|_| Scalar::rand(rng)).collect();
+        let poly = interpolate(&points, &values).unwrap();
+        assert!(poly.degree() < points.len());
+        for (point, value) in points.iter().zip(&values) {
+            assert_eq!(poly.evaluate(point), *value);
+            assert!(vanishing_poly(&points).evaluate(point).is_zero());
+        }
+    }
+
+    #[test]
+    fn commitment_from_evaluations() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let mut powers = Powers::<BlsCurve>::unsafe_setup(tau, 16);
+        let domain = GeneralEvaluationDomain::new(16).unwrap();
+        let poly = UniPoly::rand(15, rng);
+        let evals = poly.evaluate_over_domain_by_ref(domain).evals;
+
+        let expected = powers.commit_g1(&poly).unwrap();
+        assert!(powers.lagrange_basis(16).is_none());
+        assert_eq!(powers.commit_evals(&evals, &domain).unwrap(), expected);
+        powers.add_lagrange_basis(&domain).unwrap();
+        assert_eq!(powers.commit_evals(&evals, &domain).unwrap(), expected);
+
+        // the basis matches the powers computed directly in Lagrange form
+        let powers_eip = Powers::<BlsCurve>::unsafe_setup_eip_4844(tau, 16);
+        assert_eq!(powers.lagrange_basis(16).unwrap(), powers_eip.g1);
+
+        let large = GeneralEvaluationDomain::new(32).unwrap();
+        assert_eq!(
+            powers.add_lagrange_basis(&large),
+            Err(CrateError::InsufficientPowers {
+                required: 32,
+                available: 16
+            })
+        );
+    }
+
     #[test]
     fn commitment_equality() {
         let rng = &mut test_rng();
@@ -274,9 +1098,288 @@ mod test {
         let poly = UniPoly { coeffs };
 
         let evals = poly.evaluate_over_domain_by_ref(domain);
-        let com_p = powers.commit_g1(&poly);
+        let com_p = powers.commit_g1(&poly).unwrap();
         let com_p_eip = powers_eip.commit_scalars_g1(&evals.evals);
 
         assert_eq!(com_p, com_p_eip);
     }
+
+    #[test]
+    fn powers_validation() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<BlsCurve>::unsafe_setup(tau, 16);
+        assert!(powers.validate(rng).is_ok());
+
+        // a single point of another secret
+        let mut invalid = powers.clone();
+        invalid.g1[7] = (invalid.g1[7] + invalid.g1[0]).into_affine();
+        assert_eq!(invalid.validate(rng), Err(CrateError::InconsistentPowers));
+        let mut invalid = powers.clone();
+        invalid.g2[15] = (invalid.g2[15] + invalid.g2[0]).into_affine();
+        assert_eq!(invalid.validate(rng), Err(CrateError::InconsistentPowers));
+        // G1 and G2 powers of different secrets
+        let other = Powers::<BlsCurve>::unsafe_setup(tau + Scalar::one(), 16);
+        let mixed = Powers::<BlsCurve>::new(powers.g1.clone(), other.g2);
+        assert_eq!(mixed.validate(rng), Err(CrateError::InconsistentPowers));
+
+        let lagrange = Powers::<BlsCurve>::unsafe_setup_eip_4844(tau, 16);
+        assert!(lagrange.validate(rng).is_err());
+        assert_eq!(
+            Powers::<BlsCurve>::unsafe_setup(tau, 1).validate(rng),
+            Err(CrateError::InsufficientPowers {
+                required: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn verification_with_verifier_key() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<BlsCurve>::unsafe_setup(tau, 16);
+        let key = powers.verifier_key(0).unwrap();
+        assert_eq!(key.g1.len(), 2);
+        assert_eq!(key.g1_tau(), powers.g1_tau());
+
+        let mut bytes = Vec::new();
+        key.serialize_compressed(&mut bytes).unwrap();
+        let key = VerifierKey::<BlsCurve>::deserialize_compressed(bytes.as_slice()).unwrap();
+        let prepared = key.prepare();
+
+        let poly = UniPoly::rand(10, rng);
+        let commitment = powers.commit_g1(&poly).unwrap().into_affine();
+        let point = Scalar::rand(rng);
+        let value = poly.evaluate(&point);
+        let proof = Kzg::proof(&poly, point, value, &powers).unwrap();
+        assert!(Kzg::verify_with_key(proof, commitment, point, value, &key));
+        assert!(Kzg::verify_prepared(
+            proof,
+            commitment.into(),
+            point,
+            value,
+            &prepared
+        ));
+        assert!(!Kzg::verify_with_key(
+            proof,
+            commitment,
+            point,
+            value + Scalar::one(),
+            &key
+        ));
+
+        assert_eq!(
+            powers.verifier_key(17).unwrap_err(),
+            CrateError::InsufficientPowers {
+                required: 17,
+                available: 16
+            }
+        );
+    }
+
+    #[test]
+    fn commitment_degree_bound() {
+        let rng = &mut test_rng();
+        let powers = Powers::<BlsCurve>::unsafe_setup(Scalar::rand(rng), 8);
+        assert_eq!(powers.max_degree(), 7);
+
+        assert!(powers.commit_g1(&UniPoly::rand(7, rng)).is_ok());
+        assert_eq!(
+            powers.commit_g1(&UniPoly::rand(8, rng)),
+            Err(CrateError::InsufficientPowers {
+                required: 9,
+                available: 8
+            })
+        );
+        assert!(powers.commit_g2(&UniPoly::rand(8, rng)).is_err());
+
+        let domain = GeneralEvaluationDomain::new(16).unwrap();
+        assert!(powers.commit_evals(&[Scalar::one()], &domain).is_err());
+    }
+
+    #[test]
+    fn g2_commitment_opening() {
+        let rng = &mut test_rng();
+        let powers = Powers::<BlsCurve>::unsafe_setup(Scalar::rand(rng), 8);
+        let poly = UniPoly::rand(7, rng);
+        let com_g1 = powers.commit_g1(&poly).unwrap().into_affine();
+        let com_g2 = powers.commit_g2(&poly).unwrap().into_affine();
+        assert!(Kzg::<BlsCurve>::cross_group_check(com_g1, com_g2));
+        let other = powers.commit_g2(&UniPoly::rand(7, rng)).unwrap();
+        assert!(!Kzg::<BlsCurve>::cross_group_check(
+            com_g1,
+            other.into_affine()
+        ));
+
+        let point = Scalar::rand(rng);
+        let value = poly.evaluate(&point);
+        let proof = Kzg::proof(&poly, point, value, &powers).unwrap();
+        assert!(Kzg::verify_g2(proof, com_g2, point, value, &powers));
+        assert!(!Kzg::verify_g2(
+            proof,
+            com_g2,
+            point,
+            value + Scalar::one(),
+            &powers
+        ));
+        assert!(!Kzg::verify_g2(
+            proof,
+            com_g2,
+            point + Scalar::one(),
+            value,
+            &powers
+        ));
+    }
+
+    #[test]
+    fn degree_bound_proof() {
+        let rng = &mut test_rng();
+        let powers = Powers::<BlsCurve>::unsafe_setup(Scalar::rand(rng), 16);
+        let poly = UniPoly::rand(9, rng);
+        let commitment = powers.commit_g1(&poly).unwrap().into_affine();
+
+        for bound in [10, 11, 16] {
+            let proof = Kzg::prove_degree_bound(&poly, bound, &powers).unwrap();
+            assert!(Kzg::verify_degree_bound(proof, commitment, bound, &powers));
+        }
+        assert_eq!(
+            Kzg::prove_degree_bound(&poly, 9, &powers),
+            Err(CrateError::DegreeBoundExceeded {
+                degree: 9,
+                bound: 9
+            })
+        );
+        assert_eq!(
+            Kzg::prove_degree_bound(&poly, 17, &powers),
+            Err(CrateError::InvalidDegreeBound(17))
+        );
+
+        // a proof for a higher bound doesn't prove a lower one
+        let proof = Kzg::prove_degree_bound(&poly, 10, &powers).unwrap();
+        assert!(!Kzg::verify_degree_bound(proof, commitment, 9, &powers));
+        assert!(!Kzg::verify_degree_bound(proof, commitment, 11, &powers));
+    }
+
+    #[test]
+    fn heterogeneous_batch_verification() {
+        let rng = &mut test_rng();
+        let powers = Powers::<BlsCurve>::unsafe_setup(Scalar::rand(rng), 16);
+        let mut openings: Vec<_> = [3, 8, 15, 1]
+            .into_iter()
+            .map(|degree| {
+                let poly = UniPoly::rand(degree, rng);
+                let commitment = powers.commit_g1(&poly).unwrap().into_affine();
+                let point = Scalar::rand(rng);
+                let value = poly.evaluate(&point);
+                let proof = Kzg::proof(&poly, point, value, &powers).unwrap();
+                (commitment, point, value, proof)
+            })
+            .collect();
+        assert!(Kzg::verify_openings(&openings, &powers));
+        assert!(Kzg::verify_openings(&[], &powers));
+
+        openings[2].2 += Scalar::one();
+        assert!(!Kzg::verify_openings(&openings, &powers));
+        openings[2].2 -= Scalar::one();
+        openings.swap(0, 1);
+        openings[0].3 = openings[1].3;
+        assert!(!Kzg::verify_openings(&openings, &powers));
+    }
+
+    #[test]
+    fn streaming_commitment() {
+        let rng = &mut test_rng();
+        let size = STREAM_CHUNK_SIZE + 10;
+        // the commitment doesn't depend on the G2 powers, which are expensive to compute
+        let exponents: Vec<Scalar> = (0..size).map(|_| Scalar::rand(rng)).collect();
+        let g1 = fixed_base_mul(
+            <BlsCurve as Pairing>::G1Affine::generator().into_group(),
+            &exponents,
+        );
+        let powers = Powers::<BlsCurve>::new(g1, Vec::new());
+        let poly = UniPoly::rand(size - 1, rng);
+        let expected = powers.commit_g1(&poly).unwrap();
+        assert_eq!(
+            powers.commit_g1_stream(poly.coeffs.iter().copied()),
+            Ok(expected)
+        );
+        assert_eq!(
+            powers.commit_g1_stream(poly.coeffs[..5].iter().copied()),
+            powers.commit_g1(&UniPoly::from_coefficients_slice(&poly.coeffs[..5]))
+        );
+        assert!(powers.commit_g1_stream([]).unwrap().is_zero());
+        assert_eq!(
+            powers.commit_g1_stream((0..size + 3).map(|_| Scalar::one())),
+            Err(CrateError::InsufficientPowers {
+                required: size + 3,
+                available: size
+            })
+        );
+    }
+
+    #[test]
+    fn cached_prepared_powers() {
+        let rng = &mut test_rng();
+        let mut powers = Powers::<BlsCurve>::unsafe_setup(Scalar::rand(rng), 8);
+        assert!(powers.prepared().is_some());
+
+        let poly = UniPoly::rand(7, rng);
+        let commitment = powers.commit_g1(&poly).unwrap().into_affine();
+        let point = Scalar::rand(rng);
+        let value = poly.evaluate(&point);
+        let proof = Kzg::proof(&poly, point, value, &powers).unwrap();
+        assert!(Kzg::verify_scalar(proof, commitment, point, value, &powers));
+
+        // the stale cache isn't used after replacing tau * g2
+        powers.g2[1] = powers.g2[2];
+        assert!(powers.prepared().is_none());
+        assert!(!Kzg::verify_scalar(
+            proof, commitment, point, value, &powers
+        ));
+        assert!(Powers::<BlsCurve>::new(powers.g1, Vec::new())
+            .prepared()
+            .is_none());
+    }
+
+    #[test]
+    fn commitment_to_bytes() {
+        let rng = &mut test_rng();
+        let powers = Powers::<BlsCurve>::unsafe_setup(Scalar::rand(rng), 8);
+        let bytes = b"the quick brown fox jumps over the lazy dog";
+        let elements = codec::encode::<Scalar>(bytes).unwrap();
+        let domain = GeneralEvaluationDomain::<Scalar>::new(elements.len()).unwrap();
+        let poly = DensePolynomial::from_coefficients_vec(domain.ifft(&elements));
+        assert_eq!(powers.commit_bytes(bytes), powers.commit_g1(&poly));
+        assert!(powers.commit_bytes(&[0; 8 * codec::CHUNK_SIZE]).is_err());
+    }
+
+    #[test]
+    fn commitment_update() {
+        let rng = &mut test_rng();
+        let mut powers = Powers::<BlsCurve>::unsafe_setup(Scalar::rand(rng), 8);
+        let domain = GeneralEvaluationDomain::<Scalar>::new(8).unwrap();
+        let mut evals: Vec<Scalar> = (0..8).map(|_| Scalar::rand(rng)).collect();
+        let commitment = powers.commit_evals(&evals, &domain).unwrap();
+
+        // without the precomputed Lagrange basis
+        let new_value = Scalar::rand(rng);
+        let updated =
+            Kzg::update_commitment(commitment, 3, evals[3], new_value, &domain, &powers).unwrap();
+        evals[3] = new_value;
+        assert_eq!(updated, powers.commit_evals(&evals, &domain).unwrap());
+
+        // with the precomputed Lagrange basis
+        powers.add_lagrange_basis(&domain).unwrap();
+        let new_value = Scalar::rand(rng);
+        let updated =
+            Kzg::update_commitment(updated, 0, evals[0], new_value, &domain, &powers).unwrap();
+        evals[0] = new_value;
+        assert_eq!(updated, powers.commit_evals(&evals, &domain).unwrap());
+
+        assert_eq!(
+            Kzg::update_commitment(updated, 8, evals[0], new_value, &domain, &powers),
+            Err(CrateError::IndexOutOfBounds(8))
+        );
+    }
 }
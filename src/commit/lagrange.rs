@@ -0,0 +1,131 @@
+//! KZG openings of polynomials given by their evaluations over a domain, without interpolating
+//! them first.
+use super::kzg::{check_capacity, Kzg, Powers};
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ff::{FftField, Field};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_std::Zero;
+
+impl<C: Pairing> Kzg<C> {
+    /// Evaluates the polynomial with the given evaluations over `domain` at `point`. Missing
+    /// evaluations are treated as zeros.
+    ///
+    /// For evaluations `f_i` over a domain with generator `w`, this is the barycentric formula
+    /// `f(z) = (z^n - 1) / n * sum(f_i * w^i / (z - w^i))`.
+    pub fn evaluate_evals(
+        evals: &[C::ScalarField],
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+        point: C::ScalarField,
+    ) -> Result<C::ScalarField, CrateError> {
+        check_capacity(evals.len(), domain.size())?;
+        if let Some(index) = domain_index(domain, point) {
+            return Ok(evals.get(index).copied().unwrap_or_default());
+        }
+        let mut denominators: Vec<C::ScalarField> = domain
+            .elements()
+            .take(evals.len())
+            .map(|element| point - element)
+            .collect();
+        ark_ff::batch_inversion(&mut denominators);
+        let sum: C::ScalarField = evals
+            .iter()
+            .zip(domain.elements())
+            .zip(&denominators)
+            .map(|((eval, element), denominator)| *eval * element * denominator)
+            .sum();
+        Ok(domain.evaluate_vanishing_polynomial(point) * domain.size_inv() * sum)
+    }
+
+    /// Opens the polynomial with the given evaluations over `domain` at `point`, returns the proof
+    /// and the evaluation. The result is the same as [`Self::proof`] for the interpolated
+    /// polynomial, but no inverse FFT is needed if the Lagrange basis of `domain` was precomputed
+    /// via [`Powers::add_lagrange_basis`].
+    ///
+    /// The quotient `(f(X) - y) / (X - z)` evaluates to `(f_i - y) / (w^i - z)` at `w^i`. If
+    /// `z = w^m` is in the domain, its `m`-th evaluation is
+    /// `sum((f_i - y) * w^i / (z * (z - w^i)))` over `i != m` instead. The quotient is committed
+    /// to via [`Powers::commit_evals`].
+    pub fn open_evals(
+        evals: &[C::ScalarField],
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+        point: C::ScalarField,
+        powers: &Powers<C>,
+    ) -> Result<(C::G1Affine, C::ScalarField), CrateError> {
+        let value = Self::evaluate_evals(evals, domain, point)?;
+        let size = domain.size();
+        let mut shifted: Vec<C::ScalarField> = evals.iter().map(|eval| *eval - value).collect();
+        shifted.resize(size, -value);
+
+        let index = domain_index(domain, point);
+        let mut denominators: Vec<C::ScalarField> =
+            domain.elements().map(|element| element - point).collect();
+        ark_ff::batch_inversion(&mut denominators);
+        let mut quotient: Vec<C::ScalarField> = shifted
+            .iter()
+            .zip(&denominators)
+            .map(|(numerator, denominator)| *numerator * denominator)
+            .collect();
+        if let Some(m) = index {
+            // the denominators w^i - z are inverted above, thus (f_i - y) * w^i / (z * (z - w^i))
+            // equals -q_i * w^i / z
+            let point_inv = point.inverse().expect("domain elements are non-zero");
+            let mut sum = C::ScalarField::zero();
+            for (i, element) in domain.elements().enumerate() {
+                if i != m {
+                    sum -= quotient[i] * element;
+                }
+            }
+            quotient[m] = sum * point_inv;
+        }
+        let proof = powers.commit_evals(&quotient, domain)?;
+        Ok((proof.into(), value))
+    }
+}
+
+// returns `i` if `point` is the `i`-th element of the domain
+fn domain_index<F: FftField>(domain: &GeneralEvaluationDomain<F>, point: F) -> Option<usize> {
+    if !domain.evaluate_vanishing_polynomial(point).is_zero() {
+        return None;
+    }
+    domain.elements().position(|element| element == point)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, UniPoly};
+    use ark_ec::CurveGroup;
+    use ark_poly::{DenseUVPolynomial, Polynomial};
+    use ark_std::UniformRand;
+
+    #[test]
+    fn openings_from_evaluations() {
+        let rng = &mut test_rng();
+        let mut powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 16);
+        let domain = GeneralEvaluationDomain::<Scalar>::new(16).unwrap();
+        let evals: Vec<Scalar> = (0..13).map(|_| Scalar::rand(rng)).collect();
+        let poly = UniPoly::from_coefficients_vec(domain.ifft(&evals));
+        let commitment = powers.commit_evals(&evals, &domain).unwrap().into_affine();
+
+        for _ in 0..2 {
+            for point in [Scalar::rand(rng), domain.element(5), domain.element(14)] {
+                let value = poly.evaluate(&point);
+                assert_eq!(
+                    Kzg::<TestCurve>::evaluate_evals(&evals, &domain, point),
+                    Ok(value)
+                );
+                let (proof, opened) = Kzg::open_evals(&evals, &domain, point, &powers).unwrap();
+                assert_eq!(opened, value);
+                assert_eq!(proof, Kzg::proof(&poly, point, value, &powers).unwrap());
+                assert!(Kzg::verify_scalar(proof, commitment, point, value, &powers));
+            }
+            // the same openings via the precomputed Lagrange basis
+            powers.add_lagrange_basis(&domain).unwrap();
+        }
+
+        let too_many = vec![Scalar::zero(); 17];
+        assert!(Kzg::open_evals(&too_many, &domain, Scalar::rand(rng), &powers).is_err());
+    }
+}
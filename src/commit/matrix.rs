@@ -0,0 +1,213 @@
+//! Commitments to matrices with openings of single cells, rows or columns.
+use super::kzg::{BatchProof, Kzg, Powers};
+use crate::hash::Hasher;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
+use ark_poly_commit::DenseUVPolynomial;
+use digest::Digest;
+
+const COLUMN_DOMAIN_SEP: &[u8] = b"fde matrix column opening";
+
+/// KZG commitments to the rows of an `m x k` matrix, each interpolated over a domain of size `k`,
+/// i.e. the cell `(i, j)` is the evaluation of the row polynomial at the `j`-th root of unity.
+///
+/// A cell is opened with an ordinary KZG proof, a row by revealing its values, and a column by
+/// opening all row polynomials at the same point with a single witness (see
+/// [`Kzg::open_batch`]), where the batching challenge is derived from the row commitments and the
+/// column index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatrixCommitment<C: Pairing> {
+    pub rows: Vec<C::G1Affine>,
+    pub columns: usize,
+}
+
+/// The prover's side of a [`MatrixCommitment`], holding the row polynomials.
+#[derive(Clone, Debug)]
+pub struct CommittedMatrix<C: Pairing> {
+    pub commitment: MatrixCommitment<C>,
+    polys: Vec<DensePolynomial<C::ScalarField>>,
+    domain: GeneralEvaluationDomain<C::ScalarField>,
+}
+
+impl<C: Pairing> CommittedMatrix<C> {
+    pub fn new(rows: &[Vec<C::ScalarField>], powers: &Powers<C>) -> Result<Self, CrateError> {
+        let columns = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != columns) {
+            return Err(CrateError::RaggedMatrix);
+        }
+        let domain = domain::<C>(columns)?;
+        let polys: Vec<_> = rows
+            .iter()
+            .map(|row| DensePolynomial::from_coefficients_vec(domain.ifft(row)))
+            .collect();
+        let commitments = polys
+            .iter()
+            .map(|poly| powers.commit_g1(poly))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            commitment: MatrixCommitment {
+                rows: C::G1::normalize_batch(&commitments),
+                columns,
+            },
+            polys,
+            domain,
+        })
+    }
+
+    pub fn open_cell(
+        &self,
+        row: usize,
+        column: usize,
+        powers: &Powers<C>,
+    ) -> Result<(C::ScalarField, C::G1Affine), CrateError> {
+        let poly = self
+            .polys
+            .get(row)
+            .ok_or(CrateError::IndexOutOfBounds(row))?;
+        let point = self.point(column)?;
+        let value = poly.evaluate(&point);
+        Ok((value, Kzg::proof(poly, point, value, powers)?))
+    }
+
+    pub fn open_row(&self, row: usize) -> Result<Vec<C::ScalarField>, CrateError> {
+        let poly = self
+            .polys
+            .get(row)
+            .ok_or(CrateError::IndexOutOfBounds(row))?;
+        let mut values = self.domain.fft(poly);
+        values.truncate(self.commitment.columns);
+        Ok(values)
+    }
+
+    /// Opens the column, the values of the returned proof are the cells from top to bottom.
+    pub fn open_column<D: Digest>(
+        &self,
+        column: usize,
+        powers: &Powers<C>,
+    ) -> Result<BatchProof<C>, CrateError> {
+        let point = self.point(column)?;
+        let challenge = self.commitment.column_challenge::<D>(column);
+        let polys: Vec<_> = self.polys.iter().collect();
+        Kzg::open_batch(&polys, point, challenge, powers)
+    }
+
+    fn point(&self, column: usize) -> Result<C::ScalarField, CrateError> {
+        if column >= self.commitment.columns {
+            return Err(CrateError::IndexOutOfBounds(column));
+        }
+        Ok(self.domain.element(column))
+    }
+}
+
+impl<C: Pairing> MatrixCommitment<C> {
+    pub fn verify_cell(
+        &self,
+        row: usize,
+        column: usize,
+        value: C::ScalarField,
+        proof: C::G1Affine,
+        powers: &Powers<C>,
+    ) -> bool {
+        let (Some(&commitment), Some(point)) = (self.rows.get(row), self.point(column)) else {
+            return false;
+        };
+        Kzg::verify_scalar(proof, commitment, point, value, powers)
+    }
+
+    /// Recomputes the commitment to the row from its values.
+    pub fn verify_row(&self, row: usize, values: &[C::ScalarField], powers: &Powers<C>) -> bool {
+        let Some(&commitment) = self.rows.get(row) else {
+            return false;
+        };
+        if values.len() != self.columns {
+            return false;
+        }
+        let Ok(domain) = domain::<C>(self.columns) else {
+            return false;
+        };
+        powers
+            .commit_evals(values, &domain)
+            .is_ok_and(|c| c.into_affine() == commitment)
+    }
+
+    pub fn verify_column<D: Digest>(
+        &self,
+        column: usize,
+        proof: &BatchProof<C>,
+        powers: &Powers<C>,
+    ) -> bool {
+        let Some(point) = self.point(column) else {
+            return false;
+        };
+        let challenge = self.column_challenge::<D>(column);
+        Kzg::verify_batch(proof, &self.rows, point, challenge, powers)
+    }
+
+    fn point(&self, column: usize) -> Option<C::ScalarField> {
+        if column >= self.columns {
+            return None;
+        }
+        domain::<C>(self.columns).ok().map(|d| d.element(column))
+    }
+
+    fn column_challenge<D: Digest>(&self, column: usize) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(&COLUMN_DOMAIN_SEP);
+        hasher.update(&self.rows);
+        hasher.update(&(column as u64));
+        hasher.next_scalar(b"challenge")
+    }
+}
+
+fn domain<C: Pairing>(size: usize) -> Result<GeneralEvaluationDomain<C::ScalarField>, CrateError> {
+    GeneralEvaluationDomain::new(size).ok_or(CrateError::InvalidFftDomain(size))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_std::{One, UniformRand};
+
+    #[test]
+    fn matrix_openings() {
+        let rng = &mut test_rng();
+        let powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 8);
+        // the number of columns doesn't need to be a power of two
+        let rows: Vec<Vec<Scalar>> = (0..3)
+            .map(|_| (0..6).map(|_| Scalar::rand(rng)).collect())
+            .collect();
+        let matrix = CommittedMatrix::new(&rows, &powers).unwrap();
+        let commitment = &matrix.commitment;
+
+        let (value, proof) = matrix.open_cell(2, 5, &powers).unwrap();
+        assert_eq!(value, rows[2][5]);
+        assert!(commitment.verify_cell(2, 5, value, proof, &powers));
+        assert!(!commitment.verify_cell(1, 5, value, proof, &powers));
+        assert!(!commitment.verify_cell(2, 6, value, proof, &powers));
+
+        let row = matrix.open_row(1).unwrap();
+        assert_eq!(row, rows[1]);
+        assert!(commitment.verify_row(1, &row, &powers));
+        assert!(!commitment.verify_row(0, &row, &powers));
+
+        let proof = matrix.open_column::<TestHash>(3, &powers).unwrap();
+        let column: Vec<Scalar> = rows.iter().map(|row| row[3]).collect();
+        assert_eq!(proof.values, column);
+        assert!(commitment.verify_column::<TestHash>(3, &proof, &powers));
+        assert!(!commitment.verify_column::<TestHash>(2, &proof, &powers));
+        let mut invalid = proof;
+        invalid.values[0] += Scalar::one();
+        assert!(!commitment.verify_column::<TestHash>(3, &invalid, &powers));
+
+        assert!(matrix.open_cell(3, 0, &powers).is_err());
+        assert_eq!(
+            CommittedMatrix::new(&[vec![Scalar::one()], vec![]], &powers).unwrap_err(),
+            CrateError::RaggedMatrix
+        );
+    }
+}
@@ -1 +1,22 @@
+#[cfg(feature = "bls12-381")]
+pub mod blob;
+pub mod ceremony;
+mod combine;
+mod coset;
+pub mod encoding;
+#[cfg(feature = "bls12-381")]
+pub mod ethereum;
+#[cfg(feature = "srs-download")]
+pub mod fetch;
+mod fk;
+pub mod hiding;
+pub mod ipa;
 pub mod kzg;
+mod lagrange;
+pub mod matrix;
+pub mod msm;
+pub mod poly_commit;
+#[cfg(feature = "bn254")]
+pub mod ptau;
+pub mod scheme;
+pub mod storage;
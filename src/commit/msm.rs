@@ -0,0 +1,184 @@
+//! Fixed-base precomputation for the multi-scalar multiplications in commitments, see
+//! [`Powers::precompute`](super::kzg::Powers::precompute).
+use crate::Error as CrateError;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// The largest supported window size of a [`FixedBaseTable`], which needs `2^w` buckets per MSM.
+pub const MAX_WINDOW_BITS: usize = 20;
+
+/// Precomputed multiples of a fixed list of bases for repeated MSMs, see
+/// [`Powers::precompute`](super::kzg::Powers::precompute).
+///
+/// The table stores the multiples `2^(j * w) * base` of every base, so all windows of all scalars
+/// share a single set of buckets and the per-window bucket aggregation and doublings of
+/// Pippenger's algorithm disappear. With `w`-bit windows, the table holds `ceil(log2(r) / w)`
+/// points per base for the scalar field order `r` and an MSM of `n` scalars costs about
+/// `n * log2(r) / w` mixed additions plus `2^(w+1)` additions for the buckets.
+#[derive(Clone, Debug)]
+pub struct FixedBaseTable<G: CurveGroup> {
+    window_bits: usize,
+    num_windows: usize,
+    // the multiples of the i-th base start at index `i * num_windows`
+    points: Vec<G::Affine>,
+}
+
+impl<G: CurveGroup> FixedBaseTable<G> {
+    pub fn new(bases: &[G::Affine], window_bits: usize) -> Result<Self, CrateError> {
+        if !(1..=MAX_WINDOW_BITS).contains(&window_bits) {
+            return Err(CrateError::InvalidWindowSize(window_bits));
+        }
+        let num_windows = (G::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(window_bits);
+        let multiples = |base: &G::Affine| {
+            let mut point = base.into_group();
+            let mut multiples = Vec::with_capacity(num_windows);
+            for _ in 0..num_windows {
+                multiples.push(point);
+                for _ in 0..window_bits {
+                    point.double_in_place();
+                }
+            }
+            multiples
+        };
+        #[cfg(not(feature = "parallel"))]
+        let points: Vec<G> = bases.iter().flat_map(multiples).collect();
+        #[cfg(feature = "parallel")]
+        let points: Vec<G> = bases.par_iter().flat_map_iter(multiples).collect();
+        Ok(Self {
+            window_bits,
+            num_windows,
+            points: G::normalize_batch(&points),
+        })
+    }
+
+    /// Number of bases in the table.
+    pub fn len(&self) -> usize {
+        self.points.len() / self.num_windows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn window_bits(&self) -> usize {
+        self.window_bits
+    }
+
+    /// Drops the multiples of all bases after the first `len` ones.
+    pub fn truncate(&mut self, len: usize) {
+        self.points.truncate(len.saturating_mul(self.num_windows));
+    }
+
+    /// Computes `sum(scalars[i] * bases[offset + i])`. Panics if the table doesn't contain
+    /// enough bases.
+    pub fn msm(&self, offset: usize, scalars: &[G::ScalarField]) -> G {
+        assert!(offset + scalars.len() <= self.len(), "not enough bases");
+        let points = &self.points[offset * self.num_windows..];
+        #[cfg(not(feature = "parallel"))]
+        return self.msm_chunk(points, scalars);
+        #[cfg(feature = "parallel")]
+        {
+            // every thread needs its own buckets, thus the scalars are split evenly
+            let chunk_size = scalars.len().div_ceil(rayon::current_num_threads()).max(1);
+            scalars
+                .par_chunks(chunk_size)
+                .enumerate()
+                .map(|(i, chunk)| {
+                    self.msm_chunk(&points[i * chunk_size * self.num_windows..], chunk)
+                })
+                .sum()
+        }
+    }
+
+    fn msm_chunk(&self, points: &[G::Affine], scalars: &[G::ScalarField]) -> G {
+        let mut buckets = vec![G::zero(); (1 << self.window_bits) - 1];
+        for (scalar, multiples) in scalars.iter().zip(points.chunks(self.num_windows)) {
+            let bigint = scalar.into_bigint();
+            for (j, multiple) in multiples.iter().enumerate() {
+                let digit = window(bigint.as_ref(), j * self.window_bits, self.window_bits);
+                if digit != 0 {
+                    buckets[digit - 1] += multiple;
+                }
+            }
+        }
+        // sum(d * buckets[d - 1]) via running sums
+        let mut running = G::zero();
+        let mut sum = G::zero();
+        for bucket in buckets.iter().rev() {
+            running += bucket;
+            sum += running;
+        }
+        sum
+    }
+}
+
+// returns the `bits` bits of the little-endian limbs starting at bit `start`
+fn window(limbs: &[u64], start: usize, bits: usize) -> usize {
+    let (limb, shift) = (start / 64, start % 64);
+    let mut value = limbs[limb] >> shift;
+    if shift + bits > 64 && limb + 1 < limbs.len() {
+        value |= limbs[limb + 1] << (64 - shift);
+    }
+    (value & ((1 << bits) - 1)) as usize
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commit::kzg::Powers;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve};
+    use ark_ec::pairing::Pairing;
+    use ark_ec::VariableBaseMSM;
+    use ark_poly::univariate::DensePolynomial;
+    use ark_poly::DenseUVPolynomial;
+    use ark_std::{UniformRand, Zero};
+
+    type G1 = <TestCurve as Pairing>::G1;
+
+    #[test]
+    fn precomputed_commitments() {
+        let rng = &mut test_rng();
+        let mut powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 8);
+        let poly = DensePolynomial::<Scalar>::rand(7, rng);
+        let expected = powers.commit_g1(&poly).unwrap();
+        let scalars = [Scalar::rand(rng), -Scalar::from(1u8), Scalar::zero()];
+        let expected_offset = G1::msm_unchecked(&powers.g1[4..7], &scalars);
+
+        for window_bits in [1, 5, 13] {
+            powers.precompute(window_bits).unwrap();
+            let table = powers.precomputed().unwrap();
+            assert_eq!(table.len(), 8);
+            assert_eq!(powers.commit_g1(&poly).unwrap(), expected);
+            assert_eq!(
+                powers.commit_g1_stream(poly.coeffs.iter().copied()),
+                Ok(expected)
+            );
+            assert_eq!(table.msm(4, &scalars), expected_offset);
+        }
+
+        // commitments beyond the truncated table fall back to Pippenger
+        let mut truncated = powers.precomputed().unwrap().clone();
+        truncated.truncate(3);
+        assert_eq!(truncated.len(), 3);
+        powers.truncate(5);
+        assert_eq!(powers.precomputed().unwrap().len(), 6);
+        powers.g1.push(powers.g1[0]);
+        let poly = DensePolynomial::<Scalar>::rand(6, rng);
+        assert_eq!(
+            powers.commit_g1(&poly).unwrap(),
+            G1::msm_unchecked(&powers.g1, &poly.coeffs)
+        );
+        powers.clear_precomputed();
+        assert!(powers.precomputed().is_none());
+
+        for window_bits in [0, MAX_WINDOW_BITS + 1] {
+            assert_eq!(
+                powers.precompute(window_bits),
+                Err(CrateError::InvalidWindowSize(window_bits))
+            );
+        }
+    }
+}
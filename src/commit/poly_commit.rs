@@ -0,0 +1,365 @@
+//! Implementation of the [`ark_poly_commit::PolynomialCommitment`] trait for [`Kzg`], so that
+//! arkworks projects can use it as their polynomial commitment scheme.
+use super::kzg::{self, Kzg, Powers};
+use ark_crypto_primitives::sponge::CryptographicSponge;
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::Polynomial;
+use ark_poly_commit::challenge::ChallengeGenerator;
+use ark_poly_commit::kzg10::{Commitment, PreparedCommitment, Proof, Randomness};
+use ark_poly_commit::{
+    Error, LabeledCommitment, LabeledPolynomial, PCCommitterKey, PCPreparedVerifierKey,
+    PCRandomness, PCUniversalParams, PCVerifierKey, PolynomialCommitment,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::RngCore;
+
+/// Verifier key of the arkworks interface, i.e. the [`kzg::VerifierKey`] together with the
+/// degrees of the setup it was trimmed from.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifierKey<C: Pairing> {
+    pub key: kzg::VerifierKey<C>,
+    pub max_degree: usize,
+    pub supported_degree: usize,
+}
+
+impl<C: Pairing> PCUniversalParams for Powers<C> {
+    fn max_degree(&self) -> usize {
+        Powers::max_degree(self)
+    }
+}
+
+impl<C: Pairing> PCCommitterKey for Powers<C> {
+    fn max_degree(&self) -> usize {
+        Powers::max_degree(self)
+    }
+
+    fn supported_degree(&self) -> usize {
+        Powers::max_degree(self)
+    }
+}
+
+impl<C: Pairing> PCVerifierKey for VerifierKey<C> {
+    fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    fn supported_degree(&self) -> usize {
+        self.supported_degree
+    }
+}
+
+impl<C: Pairing> PCPreparedVerifierKey<VerifierKey<C>> for kzg::PreparedPowers<C> {
+    fn prepare(vk: &VerifierKey<C>) -> Self {
+        vk.key.prepare()
+    }
+}
+
+/// Commitments, proofs and randomness are the `kzg10` types of `ark-poly-commit`, the universal
+/// parameters and the committer key are [`Powers`]. Several polynomials opened at the same point
+/// are combined with one sponge challenge per polynomial, like in the Marlin variant of KZG.
+///
+/// Hiding commitments and degree bounds are not supported, see [`super::hiding`] and
+/// [`Kzg::prove_degree_bound`] for those.
+impl<C, S> PolynomialCommitment<C::ScalarField, DensePolynomial<C::ScalarField>, S> for Kzg<C>
+where
+    C: Pairing,
+    S: CryptographicSponge,
+{
+    type UniversalParams = Powers<C>;
+    type CommitterKey = Powers<C>;
+    type VerifierKey = VerifierKey<C>;
+    type PreparedVerifierKey = kzg::PreparedPowers<C>;
+    type Commitment = Commitment<C>;
+    type PreparedCommitment = PreparedCommitment<C>;
+    type Randomness = Randomness<C::ScalarField, DensePolynomial<C::ScalarField>>;
+    type Proof = Proof<C>;
+    type BatchProof = Vec<Proof<C>>;
+    type Error = Error;
+
+    /// Same as the setup of [`super::scheme::PolynomialCommitment`], see there for the trust
+    /// assumptions.
+    fn setup<R: RngCore>(
+        max_degree: usize,
+        num_vars: Option<usize>,
+        rng: &mut R,
+    ) -> Result<Self::UniversalParams, Self::Error> {
+        if num_vars.is_some() {
+            return Err(Error::InvalidNumberOfVariables);
+        }
+        if max_degree == 0 {
+            return Err(Error::DegreeIsZero);
+        }
+        <Self as super::scheme::PolynomialCommitment<C::ScalarField>>::setup(max_degree, rng)
+            .map_err(map_error)
+    }
+
+    fn trim(
+        pp: &Self::UniversalParams,
+        supported_degree: usize,
+        supported_hiding_bound: usize,
+        enforced_degree_bounds: Option<&[usize]>,
+    ) -> Result<(Self::CommitterKey, Self::VerifierKey), Self::Error> {
+        check_hiding_bound(Some(supported_hiding_bound).filter(|&bound| bound > 0))?;
+        if let Some(&bound) = enforced_degree_bounds.and_then(|bounds| bounds.first()) {
+            return Err(Error::UnsupportedDegreeBound(bound));
+        }
+        if supported_degree > pp.max_degree() {
+            return Err(Error::TrimmingDegreeTooLarge);
+        }
+        let key = pp.verifier_key(2).map_err(map_error)?;
+        let mut ck = pp.clone();
+        ck.truncate(supported_degree);
+        let vk = VerifierKey {
+            key,
+            max_degree: pp.max_degree(),
+            supported_degree,
+        };
+        Ok((ck, vk))
+    }
+
+    fn commit<'a>(
+        ck: &Self::CommitterKey,
+        polynomials: impl IntoIterator<
+            Item = &'a LabeledPolynomial<C::ScalarField, DensePolynomial<C::ScalarField>>,
+        >,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<
+        (
+            Vec<LabeledCommitment<Self::Commitment>>,
+            Vec<Self::Randomness>,
+        ),
+        Self::Error,
+    >
+    where
+        DensePolynomial<C::ScalarField>: 'a,
+    {
+        let mut commitments = Vec::new();
+        let mut randomness = Vec::new();
+        for poly in polynomials {
+            check_labeled(poly)?;
+            let commitment = commit(ck, poly.polynomial())?;
+            commitments.push(LabeledCommitment::new(
+                poly.label().clone(),
+                Commitment(commitment),
+                None,
+            ));
+            randomness.push(Self::Randomness::empty());
+        }
+        Ok((commitments, randomness))
+    }
+
+    fn open<'a>(
+        ck: &Self::CommitterKey,
+        labeled_polynomials: impl IntoIterator<
+            Item = &'a LabeledPolynomial<C::ScalarField, DensePolynomial<C::ScalarField>>,
+        >,
+        _commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a C::ScalarField,
+        challenge_generator: &mut ChallengeGenerator<C::ScalarField, S>,
+        _rands: impl IntoIterator<Item = &'a Self::Randomness>,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Self::Error>
+    where
+        DensePolynomial<C::ScalarField>: 'a,
+        Self::Randomness: 'a,
+        Self::Commitment: 'a,
+    {
+        let mut combined = DensePolynomial::default();
+        for poly in labeled_polynomials {
+            check_labeled(poly)?;
+            let challenge = challenge_generator.next_challenge();
+            combined += (challenge, poly.polynomial());
+        }
+        let value = combined.evaluate(point);
+        let w = Kzg::proof(&combined, *point, value, ck).map_err(map_error)?;
+        Ok(Proof { w, random_v: None })
+    }
+
+    fn check<'a>(
+        vk: &Self::VerifierKey,
+        commitments: impl IntoIterator<Item = &'a LabeledCommitment<Self::Commitment>>,
+        point: &'a C::ScalarField,
+        values: impl IntoIterator<Item = C::ScalarField>,
+        proof: &Self::Proof,
+        challenge_generator: &mut ChallengeGenerator<C::ScalarField, S>,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<bool, Self::Error>
+    where
+        Self::Commitment: 'a,
+    {
+        let commitments: Vec<C::G1Affine> = commitments
+            .into_iter()
+            .map(|commitment| commitment.commitment().0)
+            .collect();
+        let values: Vec<C::ScalarField> = values.into_iter().collect();
+        if commitments.len() != values.len() {
+            return Err(Error::IncorrectInputLength(format!(
+                "{} commitments, but {} values",
+                commitments.len(),
+                values.len()
+            )));
+        }
+        if proof.random_v.is_some() {
+            return Ok(false);
+        }
+        let challenges: Vec<C::ScalarField> = (0..commitments.len())
+            .map(|_| challenge_generator.next_challenge())
+            .collect();
+        let value = challenges.iter().zip(&values).map(|(c, v)| *c * v).sum();
+        let commitment = Kzg::<C>::linear_combination(&commitments, &challenges)
+            .expect("lengths are equal")
+            .into_affine();
+        Ok(Kzg::verify_with_key(
+            proof.w, commitment, *point, value, &vk.key,
+        ))
+    }
+}
+
+fn commit<C: Pairing>(
+    ck: &Powers<C>,
+    poly: &DensePolynomial<C::ScalarField>,
+) -> Result<C::G1Affine, Error> {
+    ck.commit_g1(poly).map(Into::into).map_err(map_error)
+}
+
+// translates the errors of the native scheme into their arkworks counterparts
+fn map_error(error: crate::Error) -> Error {
+    match error {
+        crate::Error::InsufficientPowers {
+            required,
+            available,
+        } => Error::TooManyCoefficients {
+            num_coefficients: required,
+            num_powers: available,
+        },
+        crate::Error::InvalidDegreeBound(bound) => Error::UnsupportedDegreeBound(bound),
+        crate::Error::DegreeBoundExceeded { degree, bound } => Error::IncorrectDegreeBound {
+            poly_degree: degree,
+            degree_bound: bound,
+            supported_degree: bound,
+            label: String::new(),
+        },
+        error => Error::IncorrectInputLength(error.to_string()),
+    }
+}
+
+fn check_labeled<S: ark_ff::PrimeField>(
+    poly: &LabeledPolynomial<S, DensePolynomial<S>>,
+) -> Result<(), Error> {
+    check_hiding_bound(poly.hiding_bound())?;
+    if let Some(bound) = poly.degree_bound() {
+        return Err(Error::UnsupportedDegreeBound(bound));
+    }
+    Ok(())
+}
+
+fn check_hiding_bound(bound: Option<usize>) -> Result<(), Error> {
+    match bound {
+        Some(bound) => Err(Error::HidingBoundToolarge {
+            hiding_poly_degree: bound,
+            num_powers: 0,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, UniPoly};
+    use ark_crypto_primitives::sponge::poseidon::{
+        find_poseidon_ark_and_mds, PoseidonConfig, PoseidonSponge,
+    };
+    use ark_ff::PrimeField;
+    use ark_poly::DenseUVPolynomial;
+    use ark_std::One;
+
+    type Pcs = Kzg<TestCurve>;
+    type Sponge = PoseidonSponge<Scalar>;
+
+    fn new_sponge() -> Sponge {
+        let (ark, mds) =
+            find_poseidon_ark_and_mds::<Scalar>(Scalar::MODULUS_BIT_SIZE as u64, 2, 8, 31, 0);
+        Sponge::new(&PoseidonConfig::new(8, 31, 17, mds, ark, 2, 1))
+    }
+
+    #[test]
+    fn arkworks_interface() {
+        let rng = &mut test_rng();
+        let pp =
+            <Pcs as PolynomialCommitment<Scalar, UniPoly, Sponge>>::setup(15, None, rng).unwrap();
+        let (ck, vk) =
+            <Pcs as PolynomialCommitment<Scalar, UniPoly, Sponge>>::trim(&pp, 7, 0, None).unwrap();
+        assert_eq!(PCCommitterKey::supported_degree(&ck), 7);
+        assert_eq!(vk.max_degree(), 15);
+
+        let polys: Vec<_> = (0..3)
+            .map(|i| LabeledPolynomial::new(format!("f{i}"), UniPoly::rand(7, rng), None, None))
+            .collect();
+        let (commitments, rands) =
+            <Pcs as PolynomialCommitment<Scalar, UniPoly, Sponge>>::commit(&ck, &polys, Some(rng))
+                .unwrap();
+        assert_eq!(
+            commitments[1].commitment().0,
+            ck.commit_g1(polys[1].polynomial()).unwrap().into_affine()
+        );
+
+        let point = Scalar::from(42u8);
+        let values: Vec<Scalar> = polys.iter().map(|p| p.evaluate(&point)).collect();
+        let mut sponge = new_sponge();
+        let proof = Pcs::open(
+            &ck,
+            &polys,
+            &commitments,
+            &point,
+            &mut ChallengeGenerator::new_univariate(&mut sponge),
+            &rands,
+            None,
+        )
+        .unwrap();
+        let check = |values: &[Scalar]| {
+            let mut sponge = new_sponge();
+            Pcs::check(
+                &vk,
+                &commitments,
+                &point,
+                values.iter().copied(),
+                &proof,
+                &mut ChallengeGenerator::new_univariate(&mut sponge),
+                None,
+            )
+        };
+        assert!(check(&values).unwrap());
+        let mut invalid = values.clone();
+        invalid[2] += Scalar::one();
+        assert!(!check(&invalid).unwrap());
+        assert!(check(&values[1..]).is_err());
+
+        // polynomials beyond the trimmed degree and unsupported features
+        let large = LabeledPolynomial::new("g".into(), UniPoly::rand(8, rng), None, None);
+        assert!(matches!(
+            <Pcs as PolynomialCommitment<Scalar, UniPoly, Sponge>>::commit(&ck, [&large], None),
+            Err(Error::TooManyCoefficients {
+                num_coefficients: 9,
+                num_powers: 8
+            })
+        ));
+        let bounded = LabeledPolynomial::new("h".into(), UniPoly::rand(3, rng), Some(4), None);
+        assert!(matches!(
+            <Pcs as PolynomialCommitment<Scalar, UniPoly, Sponge>>::commit(&ck, [&bounded], None),
+            Err(Error::UnsupportedDegreeBound(4))
+        ));
+        assert!(
+            <Pcs as PolynomialCommitment<Scalar, UniPoly, Sponge>>::trim(&pp, 16, 0, None).is_err()
+        );
+
+        // the setup round-trips through the arkworks serialization
+        let mut bytes = Vec::new();
+        pp.serialize_compressed(&mut bytes).unwrap();
+        let deserialized = Powers::<TestCurve>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!((deserialized.g1, deserialized.g2), (pp.g1, pp.g2));
+    }
+}
@@ -0,0 +1,316 @@
+//! Loader for the `.ptau` files of [snarkjs](https://github.com/iden3/snarkjs), e.g. the ones
+//! produced by the Perpetual Powers of Tau ceremony over BN254.
+use super::kzg::Powers;
+use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInt, BigInteger, PrimeField};
+use ark_serialize::{Read, Valid};
+use thiserror::Error as ErrorT;
+
+const MAGIC: &[u8; 4] = b"ptau";
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+// byte size of a base field element
+const N8: usize = 32;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid ptau file: {0}")]
+    InvalidFormat(&'static str),
+    #[error("i/o error: {0}")]
+    Io(String),
+    #[error("file contains {available} powers, but {requested} were requested")]
+    NotEnoughPowers { requested: usize, available: usize },
+    #[error("invalid point at index {0}")]
+    InvalidPoint(usize),
+    #[error("G1 and G2 points are not powers of the same secret")]
+    InconsistentSetup,
+}
+
+/// Reads the first `num_powers` G1 and G2 powers of tau from a `.ptau` file.
+///
+/// The file starts with the magic `ptau`, a version and the number of sections, followed by
+/// sections consisting of a type, a byte length and the payload, all integers being little-endian.
+/// The header section contains the byte size and the modulus of the base field and the power `p`
+/// of the ceremony. The `tau * G1` section contains `2^(p+1) - 1` points and the `tau * G2` section
+/// contains `2^p` points, stored uncompressed with coordinates in little-endian Montgomery form.
+/// Only the requested points are read, the remaining part of a section is skipped, so large
+/// ceremony files can be used for small setups without keeping them in memory.
+///
+/// Since the file contains only half as many G2 points as G1 points, fewer G2 powers are returned
+/// if `num_powers` exceeds their number.
+pub fn from_ptau<R: Read>(mut reader: R, num_powers: usize) -> Result<Powers<Bn254>, Error> {
+    let mut magic = [0u8; 4];
+    read_exact(&mut reader, &mut magic)?;
+    if &magic != MAGIC {
+        return Err(Error::InvalidFormat("missing magic bytes"));
+    }
+    let _version = read_u32(&mut reader)?;
+    let sections = read_u32(&mut reader)?;
+
+    let mut power = None;
+    let mut g1 = None;
+    let mut g2 = None;
+    for _ in 0..sections {
+        let section = read_u32(&mut reader)?;
+        let size = read_u64(&mut reader)? as usize;
+        let mut section_reader = Section {
+            reader: &mut reader,
+            remaining: size as u64,
+        };
+        match section {
+            HEADER_SECTION => power = Some(read_header(&mut section_reader)?),
+            TAU_G1_SECTION | TAU_G2_SECTION => {
+                let power = power.ok_or(Error::InvalidFormat("points before the header"))?;
+                if section == TAU_G1_SECTION {
+                    let available = (1 << (power + 1)) - 1;
+                    if num_powers > available {
+                        return Err(Error::NotEnoughPowers {
+                            requested: num_powers,
+                            available,
+                        });
+                    }
+                    g1 = Some(read_points(&mut section_reader, num_powers, read_g1)?);
+                } else {
+                    let count = num_powers.min(1 << power);
+                    g2 = Some(read_points(&mut section_reader, count, read_g2)?);
+                }
+            }
+            _ => {}
+        }
+        // skip the unread part of the section
+        skip(&mut section_reader)?;
+        if g1.is_some() && g2.is_some() {
+            break;
+        }
+    }
+
+    let g1 = g1.ok_or(Error::InvalidFormat("missing tau * G1 section"))?;
+    let g2 = g2.ok_or(Error::InvalidFormat("missing tau * G2 section"))?;
+    if g1.len() >= 2 && g2.len() >= 2 {
+        let consistent = g1[0] == G1Affine::generator()
+            && g2[0] == G2Affine::generator()
+            && Bn254::pairing(g1[1], g2[0]) == Bn254::pairing(g1[0], g2[1]);
+        if !consistent {
+            return Err(Error::InconsistentSetup);
+        }
+    }
+    Ok(Powers::new(g1, g2))
+}
+
+// returns the power of the ceremony after checking the base field
+fn read_header<R: Read>(reader: &mut R) -> Result<u32, Error> {
+    let n8 = read_u32(reader)? as usize;
+    if n8 != N8 {
+        return Err(Error::InvalidFormat("unsupported field size"));
+    }
+    let mut modulus = [0u8; N8];
+    read_exact(reader, &mut modulus)?;
+    if modulus.as_slice() != Fq::MODULUS.to_bytes_le().as_slice() {
+        return Err(Error::InvalidFormat("not a BN254 setup"));
+    }
+    let power = read_u32(reader)?;
+    if power >= usize::BITS - 1 {
+        return Err(Error::InvalidFormat("invalid power"));
+    }
+    Ok(power)
+}
+
+fn read_points<R: Read, P: Valid>(
+    reader: &mut R,
+    count: usize,
+    read_point: fn(&mut R) -> Result<Option<P>, Error>,
+) -> Result<Vec<P>, Error> {
+    (0..count)
+        .map(|i| match read_point(reader)? {
+            Some(point) if point.check().is_ok() => Ok(point),
+            _ => Err(Error::InvalidPoint(i)),
+        })
+        .collect()
+}
+
+fn read_g1<R: Read>(reader: &mut R) -> Result<Option<G1Affine>, Error> {
+    let (x, y) = (read_fq(reader)?, read_fq(reader)?);
+    Ok(x.zip(y).map(|(x, y)| G1Affine::new_unchecked(x, y)))
+}
+
+fn read_g2<R: Read>(reader: &mut R) -> Result<Option<G2Affine>, Error> {
+    let coordinates = [
+        read_fq(reader)?,
+        read_fq(reader)?,
+        read_fq(reader)?,
+        read_fq(reader)?,
+    ];
+    let [Some(x0), Some(x1), Some(y0), Some(y1)] = coordinates else {
+        return Ok(None);
+    };
+    Ok(Some(G2Affine::new_unchecked(
+        Fq2::new(x0, x1),
+        Fq2::new(y0, y1),
+    )))
+}
+
+// reads a base field element in little-endian Montgomery form, `None` if it is not reduced
+fn read_fq<R: Read>(reader: &mut R) -> Result<Option<Fq>, Error> {
+    let mut bytes = [0u8; N8];
+    read_exact(reader, &mut bytes)?;
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("8 bytes"));
+    }
+    let montgomery = BigInt(limbs);
+    Ok((montgomery < Fq::MODULUS).then(|| Fq::new_unchecked(montgomery)))
+}
+
+// limits reads to the remaining bytes of a section
+struct Section<'a, R> {
+    reader: &'a mut R,
+    remaining: u64,
+}
+
+impl<R: Read> Read for Section<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> ark_std::io::Result<usize> {
+        let len = buf
+            .len()
+            .min(usize::try_from(self.remaining).unwrap_or(usize::MAX));
+        let read = self.reader.read(&mut buf[..len])?;
+        self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+
+fn skip<R: Read>(reader: &mut R) -> Result<(), Error> {
+    let mut buf = [0u8; 4096];
+    while reader
+        .read(&mut buf)
+        .map_err(|e| Error::Io(e.to_string()))?
+        > 0
+    {}
+    Ok(())
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), Error> {
+    reader.read_exact(buf).map_err(|e| Error::Io(e.to_string()))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, Error> {
+    let mut bytes = [0u8; 4];
+    read_exact(reader, &mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, Error> {
+    let mut bytes = [0u8; 8];
+    read_exact(reader, &mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    const POWER: u32 = 3;
+
+    fn fq_bytes(x: &Fq, out: &mut Vec<u8>) {
+        out.extend(x.0 .0.iter().flat_map(|limb| limb.to_le_bytes()));
+    }
+
+    fn section(kind: u32, payload: &[u8], out: &mut Vec<u8>) {
+        out.extend(kind.to_le_bytes());
+        out.extend((payload.len() as u64).to_le_bytes());
+        out.extend(payload);
+    }
+
+    // writes a ptau file with a section that has to be skipped before the points
+    fn ptau_file(powers: &Powers<Bn254>) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend((N8 as u32).to_le_bytes());
+        header.extend(Fq::MODULUS.to_bytes_le());
+        header.extend(POWER.to_le_bytes());
+        header.extend(POWER.to_le_bytes());
+
+        let mut g1 = Vec::new();
+        for point in &powers.g1 {
+            fq_bytes(&point.x, &mut g1);
+            fq_bytes(&point.y, &mut g1);
+        }
+        let mut g2 = Vec::new();
+        for point in &powers.g2[..1 << POWER] {
+            for coordinate in [point.x.c0, point.x.c1, point.y.c0, point.y.c1] {
+                fq_bytes(&coordinate, &mut g2);
+            }
+        }
+
+        let mut file = MAGIC.to_vec();
+        file.extend(1u32.to_le_bytes());
+        file.extend(4u32.to_le_bytes());
+        section(HEADER_SECTION, &header, &mut file);
+        section(7, &[1, 2, 3], &mut file);
+        section(TAU_G1_SECTION, &g1, &mut file);
+        section(TAU_G2_SECTION, &g2, &mut file);
+        file
+    }
+
+    #[test]
+    fn load_ptau_file() {
+        let tau = Fr::rand(&mut test_rng());
+        let powers = Powers::<Bn254>::unsafe_setup(tau, (1 << (POWER + 1)) - 1);
+        let file = ptau_file(&powers);
+
+        let loaded = from_ptau(file.as_slice(), 15).unwrap();
+        assert_eq!(loaded.g1, powers.g1);
+        assert_eq!(loaded.g2, powers.g2[..8]);
+
+        // partial read
+        let loaded = from_ptau(file.as_slice(), 5).unwrap();
+        assert_eq!(loaded.g1, powers.g1[..5]);
+        assert_eq!(loaded.g2, powers.g2[..5]);
+
+        assert_eq!(
+            from_ptau(file.as_slice(), 16).unwrap_err(),
+            Error::NotEnoughPowers {
+                requested: 16,
+                available: 15
+            }
+        );
+        assert!(matches!(
+            from_ptau(&file[..file.len() - 1], 15),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn load_invalid_ptau_file() {
+        let tau = Fr::rand(&mut test_rng());
+        let powers = Powers::<Bn254>::unsafe_setup(tau, (1 << (POWER + 1)) - 1);
+
+        let mut file = ptau_file(&powers);
+        file[0] = b'x';
+        assert_eq!(
+            from_ptau(file.as_slice(), 4).unwrap_err(),
+            Error::InvalidFormat("missing magic bytes")
+        );
+
+        // G2 powers of another tau
+        let mut other = Powers::<Bn254>::unsafe_setup(tau + Fr::from(1u8), 15);
+        other.g1 = powers.g1.clone();
+        assert_eq!(
+            from_ptau(ptau_file(&other).as_slice(), 4).unwrap_err(),
+            Error::InconsistentSetup
+        );
+
+        // point that is not on the curve, the y coordinate of the third point is changed
+        let mut file = ptau_file(&powers);
+        let offset = 4 + 4 + 4 + (12 + 32 + 8 + 12) + (12 + 3) + 12 + 2 * 64 + 32;
+        file[offset] ^= 1;
+        assert_eq!(
+            from_ptau(file.as_slice(), 4).unwrap_err(),
+            Error::InvalidPoint(2)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Abstraction over univariate polynomial commitment schemes.
+//!
+//! [`Kzg`] requires a trusted setup but has constant-size proofs and verification. The
+//! [`Ipa`](super::ipa::Ipa) scheme is transparent, i.e. its parameters are derived from a public
+//! label, at the cost of logarithmic proofs and linear verification.
+use super::ceremony::Transcript;
+use super::kzg::{Kzg, Powers};
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::Polynomial;
+use ark_std::rand::Rng;
+
+pub trait PolynomialCommitment<S: PrimeField> {
+    type Params;
+    type Commitment: Clone + PartialEq;
+    type Proof: Clone;
+
+    /// Generates parameters for polynomials of degree up to `max_degree`.
+    fn setup<R: Rng>(max_degree: usize, rng: &mut R) -> Result<Self::Params, CrateError>;
+
+    fn commit(
+        params: &Self::Params,
+        poly: &DensePolynomial<S>,
+    ) -> Result<Self::Commitment, CrateError>;
+
+    /// Opens `poly` at `point`, returns the evaluation and the proof.
+    fn open(
+        params: &Self::Params,
+        poly: &DensePolynomial<S>,
+        point: S,
+    ) -> Result<(S, Self::Proof), CrateError>;
+
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: S,
+        value: S,
+        proof: &Self::Proof,
+    ) -> bool;
+}
+
+impl<C: Pairing> PolynomialCommitment<C::ScalarField> for Kzg<C> {
+    type Params = Powers<C>;
+    type Commitment = C::G1Affine;
+    type Proof = C::G1Affine;
+
+    /// Runs a [`Transcript`] with a single contribution sampled from `rng`, thus whoever controls
+    /// `rng` learns the trapdoor. Production deployments should run a ceremony with several
+    /// participants or load the powers of an existing one instead.
+    fn setup<R: Rng>(max_degree: usize, rng: &mut R) -> Result<Self::Params, CrateError> {
+        let mut transcript = Transcript::new(max_degree + 1, max_degree + 1);
+        transcript.contribute(rng);
+        Ok(transcript.powers)
+    }
+
+    fn commit(
+        params: &Self::Params,
+        poly: &DensePolynomial<C::ScalarField>,
+    ) -> Result<Self::Commitment, CrateError> {
+        Ok(params.commit_g1(poly)?.into_affine())
+    }
+
+    fn open(
+        params: &Self::Params,
+        poly: &DensePolynomial<C::ScalarField>,
+        point: C::ScalarField,
+    ) -> Result<(C::ScalarField, Self::Proof), CrateError> {
+        let value = poly.evaluate(&point);
+        Ok((value, Kzg::proof(poly, point, value, params)?))
+    }
+
+    fn verify(
+        params: &Self::Params,
+        commitment: &Self::Commitment,
+        point: C::ScalarField,
+        value: C::ScalarField,
+        proof: &Self::Proof,
+    ) -> bool {
+        Kzg::verify_scalar(*proof, *commitment, point, value, params)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commit::ipa::Ipa;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_poly_commit::DenseUVPolynomial;
+    use ark_std::UniformRand;
+
+    fn commit_and_open<P: PolynomialCommitment<Scalar>>() {
+        let rng = &mut test_rng();
+        let params = P::setup(7, rng).unwrap();
+        let poly = DensePolynomial::rand(7, rng);
+        let commitment = P::commit(&params, &poly).unwrap();
+        let point = Scalar::rand(rng);
+        let (value, proof) = P::open(&params, &poly, point).unwrap();
+        assert!(P::verify(&params, &commitment, point, value, &proof));
+        assert!(!P::verify(&params, &commitment, point, point, &proof));
+    }
+
+    #[test]
+    fn commitment_schemes() {
+        commit_and_open::<Kzg<TestCurve>>();
+        commit_and_open::<Ipa<<TestCurve as Pairing>::G1, TestHash>>();
+    }
+}
@@ -0,0 +1,582 @@
+//! Binary on-disk format for [`Powers`], see [`Powers::write_to`] and [`PowersView`]. Besides the
+//! file format, [`Powers`] implement the arkworks serialization traits, which encode the G1 points
+//! followed by the G2 points without a header, e.g. for embedding the setup into other arkworks
+//! structures.
+use super::kzg::{Powers, STREAM_CHUNK_SIZE};
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, VariableBaseMSM};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::marker::PhantomData;
+use ark_std::Zero;
+use sha3::{Digest, Sha3_256};
+use thiserror::Error as ErrorT;
+
+const MAGIC: &[u8; 8] = b"FDE-SRS\0";
+const VERSION: u32 = 1;
+/// Size of the header in bytes.
+pub const HEADER_SIZE: usize = 8 + 4 + 32 + 8 + 8 + 32;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("not an SRS file")]
+    InvalidMagic,
+    #[error("unsupported SRS file version {0}")]
+    UnsupportedVersion(u32),
+    #[error("SRS file was generated for another curve")]
+    CurveMismatch,
+    #[error("SRS file is corrupted, hash mismatch")]
+    HashMismatch,
+    #[error("SRS file has {available} G1 powers, but {required} are required")]
+    InsufficientPowers { required: usize, available: u64 },
+    #[error("SRS file has {actual} bytes, but the header requires {expected}")]
+    InvalidLength { expected: u64, actual: usize },
+    #[error("SRS file contains another setup")]
+    SetupMismatch,
+    #[error("serialization error: {0}")]
+    Serialization(String),
+}
+
+impl From<SerializationError> for Error {
+    fn from(error: SerializationError) -> Self {
+        Self::Serialization(error.to_string())
+    }
+}
+
+/// Header of an SRS file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub curve_id: [u8; 32],
+    pub g1_len: u64,
+    pub g2_len: u64,
+    pub hash: [u8; 32],
+}
+
+impl Header {
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, Error> {
+        let mut bytes = [0u8; HEADER_SIZE];
+        reader
+            .read_exact(&mut bytes)
+            .map_err(SerializationError::from)?;
+        if &bytes[..8] != MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+        let version = u32::from_le_bytes(bytes[8..12].try_into().expect("4 bytes"));
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        Ok(Self {
+            curve_id: bytes[12..44].try_into().expect("32 bytes"),
+            g1_len: u64::from_le_bytes(bytes[44..52].try_into().expect("8 bytes")),
+            g2_len: u64::from_le_bytes(bytes[52..60].try_into().expect("8 bytes")),
+            hash: bytes[60..92].try_into().expect("32 bytes"),
+        })
+    }
+
+    fn write<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.curve_id);
+        bytes.extend_from_slice(&self.g1_len.to_le_bytes());
+        bytes.extend_from_slice(&self.g2_len.to_le_bytes());
+        bytes.extend_from_slice(&self.hash);
+        writer.write_all(&bytes).map_err(SerializationError::from)?;
+        Ok(())
+    }
+}
+
+/// Identifier of the curve `C`, the hash of the uncompressed generators.
+pub fn curve_id<C: Pairing>() -> [u8; 32] {
+    let mut hasher = HashingWriter(Sha3_256::new());
+    C::G1Affine::generator()
+        .serialize_uncompressed(&mut hasher)
+        .expect("should not fail");
+    C::G2Affine::generator()
+        .serialize_uncompressed(&mut hasher)
+        .expect("should not fail");
+    hasher.0.finalize().into()
+}
+
+impl<C: Pairing> Powers<C> {
+    /// Writes the powers as a file consisting of a fixed-size header followed by the points:
+    /// - the magic `FDE-SRS\0` and a little-endian `u32` format version,
+    /// - a 32 byte curve identifier, see [`curve_id`],
+    /// - the number of G1 and G2 points as little-endian `u64`s,
+    /// - the SHA3-256 hash of the point data,
+    /// - the uncompressed G1 points followed by the uncompressed G2 points.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        let mut hasher = HashingWriter(Sha3_256::new());
+        self.write_points(&mut hasher)?;
+        let header = Header {
+            curve_id: curve_id::<C>(),
+            g1_len: self.g1.len() as u64,
+            g2_len: self.g2.len() as u64,
+            hash: hasher.0.finalize().into(),
+        };
+        header.write(&mut writer)?;
+        self.write_points(writer)
+    }
+
+    /// Reads powers written by [`Self::write_to`]. With [`Validate::Yes`] every point is checked
+    /// to be on the curve and in the prime order subgroup.
+    ///
+    /// Uncompressed points don't require square roots, and [`Validate::No`] skips the subgroup
+    /// checks, which is safe for files written by the same party, since the hash still protects
+    /// against corruption.
+    pub fn read_from<R: Read>(mut reader: R, validate: Validate) -> Result<Self, Error> {
+        let header = Header::read(&mut reader)?;
+        if header.curve_id != curve_id::<C>() {
+            return Err(Error::CurveMismatch);
+        }
+
+        let mut reader = HashingReader {
+            reader,
+            hasher: Sha3_256::new(),
+        };
+        let g1 = (0..header.g1_len)
+            .map(|_| C::G1Affine::deserialize_with_mode(&mut reader, Compress::No, validate))
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2 = (0..header.g2_len)
+            .map(|_| C::G2Affine::deserialize_with_mode(&mut reader, Compress::No, validate))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let hash: [u8; 32] = reader.hasher.finalize().into();
+        if hash != header.hash {
+            return Err(Error::HashMismatch);
+        }
+        Ok(Self::new(g1, g2))
+    }
+
+    /// Extends the G1 powers to `num_g1` points read from a file written by [`Self::write_to`],
+    /// e.g. after [`Powers::truncate`].
+    ///
+    /// The file has to contain the same setup, i.e. the points in memory have to match the
+    /// first points of the file, otherwise [`Error::SetupMismatch`] is returned. Only the missing
+    /// points are deserialized, the rest of the file is just hashed.
+    pub fn extend_from_reader<R: Read>(
+        &mut self,
+        mut reader: R,
+        num_g1: usize,
+        validate: Validate,
+    ) -> Result<(), Error> {
+        let header = Header::read(&mut reader)?;
+        if header.curve_id != curve_id::<C>() {
+            return Err(Error::CurveMismatch);
+        }
+        if header.g1_len < num_g1 as u64 || header.g2_len < self.g2.len() as u64 {
+            return Err(Error::InsufficientPowers {
+                required: num_g1,
+                available: header.g1_len,
+            });
+        }
+
+        let mut reader = HashingReader {
+            reader,
+            hasher: Sha3_256::new(),
+        };
+        let mut buffer = Vec::new();
+        let mut g1 = Vec::with_capacity(num_g1.saturating_sub(self.g1.len()));
+        for i in 0..header.g1_len as usize {
+            if self.g1.len() <= i && i < num_g1 {
+                g1.push(C::G1Affine::deserialize_with_mode(
+                    &mut reader,
+                    Compress::No,
+                    validate,
+                )?);
+            } else {
+                skip_point(&mut reader, self.g1.get(i), &mut buffer)?;
+            }
+        }
+        for i in 0..header.g2_len as usize {
+            skip_point(&mut reader, self.g2.get(i), &mut buffer)?;
+        }
+
+        let hash: [u8; 32] = reader.hasher.finalize().into();
+        if hash != header.hash {
+            return Err(Error::HashMismatch);
+        }
+        self.g1.extend(g1);
+        Ok(())
+    }
+
+    /// Same as [`Self::read_from`] but reads from a byte slice. This copies all points into
+    /// memory, use [`PowersView`] to access them in place, e.g. in a memory-mapped file.
+    pub fn from_bytes(bytes: &[u8], validate: Validate) -> Result<Self, Error> {
+        let view = PowersView::<C, _>::from_bytes(bytes)?;
+        view.check_hash()?;
+        view.to_powers(view.g1_len(), validate)
+    }
+
+    fn write_points<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        for point in &self.g1 {
+            point.serialize_uncompressed(&mut writer)?;
+        }
+        for point in &self.g2 {
+            point.serialize_uncompressed(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Powers in the format of [`Powers::write_to`] backed by a byte buffer, e.g. a memory-mapped
+/// file. Only the header is parsed up front, points are deserialized when accessed.
+pub struct PowersView<C: Pairing, B = Vec<u8>> {
+    bytes: B,
+    header: Header,
+    _curve: PhantomData<C>,
+}
+
+impl<C: Pairing, B: AsRef<[u8]>> PowersView<C, B> {
+    /// Checks the header and the length of the buffer without reading the points. The hash is
+    /// not checked, since this requires reading the whole buffer, see [`Self::check_hash`].
+    pub fn from_bytes(bytes: B) -> Result<Self, Error> {
+        let header = Header::read(bytes.as_ref())?;
+        if header.curve_id != curve_id::<C>() {
+            return Err(Error::CurveMismatch);
+        }
+        let expected = header
+            .g1_len
+            .checked_mul(g1_size::<C>() as u64)
+            .zip(header.g2_len.checked_mul(g2_size::<C>() as u64))
+            .and_then(|(g1, g2)| g1.checked_add(g2))
+            .and_then(|points| points.checked_add(HEADER_SIZE as u64))
+            .unwrap_or(u64::MAX);
+        let actual = bytes.as_ref().len();
+        if actual as u64 != expected {
+            return Err(Error::InvalidLength { expected, actual });
+        }
+        Ok(Self {
+            bytes,
+            header,
+            _curve: PhantomData,
+        })
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    pub fn g1_len(&self) -> usize {
+        self.header.g1_len as usize
+    }
+
+    pub fn g2_len(&self) -> usize {
+        self.header.g2_len as usize
+    }
+
+    /// Checks the hash of the point data against the header.
+    pub fn check_hash(&self) -> Result<(), Error> {
+        let hash: [u8; 32] = Sha3_256::digest(&self.bytes.as_ref()[HEADER_SIZE..]).into();
+        if hash != self.header.hash {
+            return Err(Error::HashMismatch);
+        }
+        Ok(())
+    }
+
+    /// Deserializes the `index`-th G1 point.
+    pub fn g1(&self, index: usize, validate: Validate) -> Result<C::G1Affine, Error> {
+        let point = self.g1_bytes(index, 1)?;
+        Ok(C::G1Affine::deserialize_with_mode(
+            point,
+            Compress::No,
+            validate,
+        )?)
+    }
+
+    /// Deserializes the `index`-th G2 point.
+    pub fn g2(&self, index: usize, validate: Validate) -> Result<C::G2Affine, Error> {
+        if index >= self.g2_len() {
+            return Err(Error::InsufficientPowers {
+                required: index + 1,
+                available: self.header.g2_len,
+            });
+        }
+        let offset = HEADER_SIZE + self.g1_len() * g1_size::<C>() + index * g2_size::<C>();
+        let point = &self.bytes.as_ref()[offset..offset + g2_size::<C>()];
+        Ok(C::G2Affine::deserialize_with_mode(
+            point,
+            Compress::No,
+            validate,
+        )?)
+    }
+
+    /// Deserializes the first `num_g1` G1 points and all G2 points.
+    pub fn to_powers(&self, num_g1: usize, validate: Validate) -> Result<Powers<C>, Error> {
+        let g1 = self.read_g1(0, num_g1, validate)?;
+        let g2 = (0..self.g2_len())
+            .map(|i| self.g2(i, validate))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Powers::new(g1, g2))
+    }
+
+    /// Commits to the polynomial with the coefficients `coeffs` in G1. The powers are
+    /// deserialized in chunks of [`STREAM_CHUNK_SIZE`], so at most one chunk is held in memory.
+    pub fn commit_g1(&self, coeffs: &[C::ScalarField], validate: Validate) -> Result<C::G1, Error> {
+        let mut commitment = C::G1::zero();
+        for (i, chunk) in coeffs.chunks(STREAM_CHUNK_SIZE).enumerate() {
+            let bases = self.read_g1(i * STREAM_CHUNK_SIZE, chunk.len(), validate)?;
+            commitment += C::G1::msm_unchecked(&bases, chunk);
+        }
+        Ok(commitment)
+    }
+
+    fn read_g1(
+        &self,
+        offset: usize,
+        len: usize,
+        validate: Validate,
+    ) -> Result<Vec<C::G1Affine>, Error> {
+        let mut points = self.g1_bytes(offset, len)?;
+        (0..len)
+            .map(|_| C::G1Affine::deserialize_with_mode(&mut points, Compress::No, validate))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::from)
+    }
+
+    // the bytes of `len` G1 points starting at `offset`
+    fn g1_bytes(&self, offset: usize, len: usize) -> Result<&[u8], Error> {
+        let end = offset.saturating_add(len);
+        if end > self.g1_len() {
+            return Err(Error::InsufficientPowers {
+                required: end,
+                available: self.header.g1_len,
+            });
+        }
+        let start = HEADER_SIZE + offset * g1_size::<C>();
+        Ok(&self.bytes.as_ref()[start..start + len * g1_size::<C>()])
+    }
+}
+
+fn g1_size<C: Pairing>() -> usize {
+    C::G1Affine::generator().uncompressed_size()
+}
+
+fn g2_size<C: Pairing>() -> usize {
+    C::G2Affine::generator().uncompressed_size()
+}
+
+// reads an uncompressed point without deserializing it, checks that it matches `expected` if given
+fn skip_point<P: AffineRepr, R: Read>(
+    mut reader: R,
+    expected: Option<&P>,
+    buffer: &mut Vec<u8>,
+) -> Result<(), Error> {
+    let size = P::generator().uncompressed_size();
+    buffer.resize(size, 0);
+    reader
+        .read_exact(buffer)
+        .map_err(SerializationError::from)?;
+    if let Some(point) = expected {
+        let mut bytes = Vec::with_capacity(size);
+        point.serialize_uncompressed(&mut bytes)?;
+        if bytes != *buffer {
+            return Err(Error::SetupMismatch);
+        }
+    }
+    Ok(())
+}
+
+impl<C: Pairing> CanonicalSerialize for Powers<C> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.g1.serialize_with_mode(&mut writer, compress)?;
+        self.g2.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.g1.serialized_size(compress) + self.g2.serialized_size(compress)
+    }
+}
+
+impl<C: Pairing> Valid for Powers<C> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.g1.check()?;
+        self.g2.check()
+    }
+}
+
+impl<C: Pairing> CanonicalDeserialize for Powers<C> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let g1 = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+        let g2 = Vec::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self::new(g1, g2))
+    }
+}
+
+struct HashingWriter<D>(D);
+
+impl<D: Digest> Write for HashingWriter<D> {
+    fn write(&mut self, buf: &[u8]) -> ark_std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> ark_std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// hashes the bytes as they are read
+struct HashingReader<R, D> {
+    reader: R,
+    hasher: D,
+}
+
+impl<R: Read, D: Digest> Read for HashingReader<R, D> {
+    fn read(&mut self, buf: &mut [u8]) -> ark_std::io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve};
+    use ark_bn254::Bn254;
+    use ark_poly::univariate::DensePolynomial;
+    use ark_poly::DenseUVPolynomial;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn powers_file_roundtrip() {
+        let tau = Scalar::rand(&mut test_rng());
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 10);
+
+        let mut file = Vec::new();
+        powers.write_to(&mut file).unwrap();
+        assert_eq!(file.len(), HEADER_SIZE + 10 * 96 + 10 * 192);
+        let header = Header::read(file.as_slice()).unwrap();
+        assert_eq!(header.g1_len, 10);
+        assert_eq!(header.curve_id, curve_id::<TestCurve>());
+
+        for validate in [Validate::Yes, Validate::No] {
+            let loaded = Powers::<TestCurve>::from_bytes(&file, validate).unwrap();
+            assert_eq!(loaded.g1, powers.g1);
+            assert_eq!(loaded.g2, powers.g2);
+        }
+
+        assert_eq!(
+            Powers::<Bn254>::from_bytes(&file, Validate::Yes).unwrap_err(),
+            Error::CurveMismatch
+        );
+
+        // corrupted payload
+        let mut corrupted = file.clone();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 1;
+        assert!(Powers::<TestCurve>::from_bytes(&corrupted, Validate::Yes).is_err());
+        // the coordinates are still valid field elements, only the hash catches this
+        let mut corrupted = file.clone();
+        corrupted[HEADER_SIZE + 95] ^= 1;
+        assert_eq!(
+            Powers::<TestCurve>::from_bytes(&corrupted, Validate::No).unwrap_err(),
+            Error::HashMismatch
+        );
+
+        let mut corrupted = file;
+        corrupted[0] = 0;
+        assert_eq!(
+            Powers::<TestCurve>::from_bytes(&corrupted, Validate::No).unwrap_err(),
+            Error::InvalidMagic
+        );
+    }
+
+    #[test]
+    fn powers_view() {
+        let rng = &mut test_rng();
+        let powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 10);
+        let mut file = Vec::new();
+        powers.write_to(&mut file).unwrap();
+
+        let view = PowersView::<TestCurve, _>::from_bytes(file.as_slice()).unwrap();
+        view.check_hash().unwrap();
+        assert_eq!((view.g1_len(), view.g2_len()), (10, 10));
+        assert_eq!(view.g1(7, Validate::Yes).unwrap(), powers.g1[7]);
+        assert_eq!(view.g2(9, Validate::No).unwrap(), powers.g2[9]);
+        assert_eq!(
+            view.g1(10, Validate::No).unwrap_err(),
+            Error::InsufficientPowers {
+                required: 11,
+                available: 10
+            }
+        );
+        let loaded = view.to_powers(4, Validate::Yes).unwrap();
+        assert_eq!(loaded.g1, powers.g1[..4]);
+        assert_eq!(loaded.g2, powers.g2);
+
+        let coeffs = (0..8).map(|_| Scalar::rand(rng)).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeffs.clone());
+        assert_eq!(
+            view.commit_g1(&coeffs, Validate::No).unwrap(),
+            powers.commit_g1(&poly).unwrap()
+        );
+
+        // the hash is only checked on request
+        let mut corrupted = file.clone();
+        corrupted[HEADER_SIZE + 95] ^= 1;
+        let view = PowersView::<TestCurve, _>::from_bytes(corrupted).unwrap();
+        assert_eq!(view.check_hash().unwrap_err(), Error::HashMismatch);
+
+        file.pop();
+        assert_eq!(
+            PowersView::<TestCurve, _>::from_bytes(&file).err(),
+            Some(Error::InvalidLength {
+                expected: file.len() as u64 + 1,
+                actual: file.len()
+            })
+        );
+    }
+
+    #[test]
+    fn truncate_and_extend() {
+        let rng = &mut test_rng();
+        let powers = Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 16);
+        let mut file = Vec::new();
+        powers.write_to(&mut file).unwrap();
+
+        let mut small = powers.clone();
+        small.truncate(3);
+        assert_eq!(small.g1, powers.g1[..4]);
+        assert_eq!(small.max_degree(), 3);
+
+        small
+            .extend_from_reader(file.as_slice(), 12, Validate::Yes)
+            .unwrap();
+        assert_eq!(small.g1, powers.g1[..12]);
+        // extending to fewer powers is a no-op
+        small
+            .extend_from_reader(file.as_slice(), 8, Validate::No)
+            .unwrap();
+        assert_eq!(small.g1.len(), 12);
+
+        assert_eq!(
+            small.extend_from_reader(file.as_slice(), 17, Validate::No),
+            Err(Error::InsufficientPowers {
+                required: 17,
+                available: 16
+            })
+        );
+        let mut other = Vec::new();
+        Powers::<TestCurve>::unsafe_setup(Scalar::rand(rng), 16)
+            .write_to(&mut other)
+            .unwrap();
+        assert_eq!(
+            small.extend_from_reader(other.as_slice(), 16, Validate::No),
+            Err(Error::SetupMismatch)
+        );
+        assert_eq!(small.g1.len(), 12);
+    }
+}
@@ -0,0 +1,104 @@
+//! Instantiations over the BN254 (a.k.a. alt_bn128) pairing-friendly curve of `ark-bn254`, as
+//! used by the Ethereum [EIP-196](https://eips.ethereum.org/EIPS/eip-196) and
+//! [EIP-197](https://eips.ethereum.org/EIPS/eip-197) precompiles.
+pub use ark_bn254::{Bn254, Fq, Fq12, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+
+/// Number of chunks a BN254 scalar is split into for exponential ElGamal encryption.
+pub const ELGAMAL_CHUNKS: usize =
+    <Fr as ark_ff::PrimeField>::MODULUS_BIT_SIZE as usize / crate::encrypt::elgamal::MAX_BITS + 1;
+
+// the protocols of this crate instantiated over BN254
+pub type Powers = crate::commit::kzg::Powers<Bn254>;
+pub type Kzg = crate::commit::kzg::Kzg<Bn254>;
+pub type RangeProof<D> = crate::range_proof::RangeProof<Bn254, D>;
+pub type Elgamal = crate::encrypt::elgamal::ExponentialElgamal<G1Projective>;
+pub type DleqProof<D> = crate::dleq::Proof<G1Projective, D>;
+pub type ElgamalEncryptionProof<D> =
+    crate::veck::kzg::elgamal::EncryptionProof<ELGAMAL_CHUNKS, Bn254, D>;
+pub type KzgElgamalProof<D> = crate::veck::kzg::elgamal::Proof<ELGAMAL_CHUNKS, Bn254, D>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_std::UniformRand;
+
+    #[test]
+    fn protocols_over_bn254() {
+        use crate::encrypt::EncryptionEngine;
+        use crate::hash::Hasher;
+        use crate::tests::TestHash;
+        use ark_poly::univariate::DensePolynomial;
+        use ark_poly::{EvaluationDomain, Evaluations, GeneralEvaluationDomain, Polynomial};
+        use ark_poly_commit::DenseUVPolynomial;
+
+        const DATA_SIZE: usize = 4;
+        const SUBSET_SIZE: usize = 2;
+
+        let rng = &mut test_rng();
+        let powers = Powers::unsafe_setup(Fr::rand(rng), 4 * crate::encrypt::elgamal::MAX_BITS);
+
+        // KZG
+        let poly = DensePolynomial::<Fr>::rand(15, rng);
+        let commitment = powers.commit_g1(&poly).unwrap().into_affine();
+        let point = Fr::rand(rng);
+        let value = poly.evaluate(&point);
+        let proof = Kzg::proof(&poly, point, value, &powers).unwrap();
+        assert!(Kzg::verify_scalar(proof, commitment, point, value, &powers));
+
+        // range proof
+        let proof = RangeProof::<TestHash>::new(Fr::from(255u8), 8, &powers, rng).unwrap();
+        assert!(proof.verify(8, &powers).is_ok());
+        assert!(RangeProof::<TestHash>::new(Fr::from(256u16), 8, &powers, rng).is_err());
+
+        // transcripts
+        let mut hasher = Hasher::<TestHash>::new();
+        hasher.update(&commitment);
+        assert_ne!(
+            hasher.next_scalar::<Fr>(b"a"),
+            hasher.next_scalar::<Fr>(b"b")
+        );
+        let secret = Fr::rand(rng);
+        let g = G1Affine::generator();
+        let h = (g * Fr::rand(rng)).into_affine();
+        let dleq = DleqProof::<TestHash>::new(&secret, g, h, rng);
+        assert!(dleq.verify(g, g * secret, h, h * secret));
+
+        // ElGamal
+        let sk = Fr::rand(rng);
+        let pk = (g * sk).into_affine();
+        let cipher = Elgamal::encrypt(&Fr::from(42u8), &pk, rng);
+        assert_eq!(Elgamal::decrypt(cipher, &sk), Ok(Fr::from(42u8)));
+
+        // verifiable encryption of a KZG committed subset
+        let data: Vec<Fr> = (0..DATA_SIZE).map(|_| Fr::rand(rng)).collect();
+        let encryption_proof = ElgamalEncryptionProof::<TestHash>::new(&data, &pk, &powers, rng);
+        assert!(encryption_proof.verify_range_proofs(&powers));
+        let domain = GeneralEvaluationDomain::new(DATA_SIZE).unwrap();
+        let index_map = crate::veck::index_map(domain);
+        let evaluations = Evaluations::from_vec_and_domain(data, domain);
+        let f_poly = evaluations.interpolate_by_ref();
+        let subdomain = GeneralEvaluationDomain::new(SUBSET_SIZE).unwrap();
+        let subset_indices = crate::veck::subset_indices(&index_map, &subdomain);
+        let f_s_poly = crate::veck::subset_evals(&evaluations, &subset_indices, subdomain)
+            .interpolate_by_ref();
+        let proof = KzgElgamalProof::<TestHash>::new(
+            &f_poly,
+            &f_s_poly,
+            &sk,
+            encryption_proof.subset(&subset_indices),
+            &powers,
+            rng,
+        )
+        .unwrap();
+        assert!(proof
+            .verify(
+                powers.commit_g1(&f_poly).unwrap(),
+                powers.commit_g1(&f_s_poly).unwrap(),
+                pk,
+                &powers
+            )
+            .is_ok());
+    }
+}
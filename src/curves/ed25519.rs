@@ -0,0 +1,135 @@
+//! Instantiations over the twisted Edwards form of Curve25519 of `ark-ed25519`, i.e. the curve of
+//! Ed25519 signatures, for integrations with non-pairing ecosystems where KZG commitments are not
+//! needed.
+//!
+//! The curve has cofactor 8. The arkworks serialization checks that deserialized points lie in the
+//! prime order subgroup, but its compressed encoding stores the sign of `x` differently from
+//! RFC 8032, see [`compress`] for the standard encoding.
+use crate::encrypt::elgamal;
+use ark_ec::twisted_edwards::TECurveConfig;
+use ark_ff::{BigInteger, Field, PrimeField};
+use thiserror::Error as ErrorT;
+
+pub use ark_ed25519::{EdwardsAffine, EdwardsConfig, EdwardsProjective, Fq, Fr};
+
+pub type Elgamal = elgamal::ExponentialElgamal<EdwardsProjective>;
+pub type Cipher = elgamal::Cipher<EdwardsProjective>;
+pub type KeyPair = elgamal::KeyPair<EdwardsProjective>;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid point encoding")]
+    InvalidEncoding,
+}
+
+/// Encodes a point as in section 5.1.2 of RFC 8032: `y` in little-endian with the lowest bit of `x`
+/// in the most significant bit.
+pub fn compress(point: &EdwardsAffine) -> [u8; 32] {
+    let mut bytes = to_bytes(point.y);
+    bytes[31] |= (point.x.into_bigint().is_odd() as u8) << 7;
+    bytes
+}
+
+/// Decodes a point as in section 5.1.3 of RFC 8032, rejecting non-canonical `y` coordinates.
+///
+/// As in RFC 8032, the point isn't checked to lie in the prime order subgroup, use
+/// [`ark_ec::AffineRepr::clear_cofactor`] where this matters.
+pub fn decompress(bytes: &[u8; 32]) -> Result<EdwardsAffine, Error> {
+    let mut y_bytes = *bytes;
+    y_bytes[31] &= 0x7f;
+    let y = Fq::from_le_bytes_mod_order(&y_bytes);
+    if to_bytes(y) != y_bytes {
+        return Err(Error::InvalidEncoding);
+    }
+    let y2 = y.square();
+    // x^2 = (y^2 - 1) / (d y^2 + 1), the denominator is non-zero since d is not a square
+    let x2 = (y2 - Fq::ONE)
+        * (EdwardsConfig::COEFF_D * y2 + Fq::ONE)
+            .inverse()
+            .expect("non-zero");
+    let x = x2.sqrt().ok_or(Error::InvalidEncoding)?;
+    let odd = bytes[31] >> 7 == 1;
+    if x == Fq::ZERO && odd {
+        return Err(Error::InvalidEncoding);
+    }
+    let x = if x.into_bigint().is_odd() == odd {
+        x
+    } else {
+        -x
+    };
+    Ok(EdwardsAffine::new_unchecked(x, y))
+}
+
+fn to_bytes(element: Fq) -> [u8; 32] {
+    element
+        .into_bigint()
+        .to_bytes_le()
+        .try_into()
+        .expect("32 bytes")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::BabyStepGiantStep;
+    use crate::encrypt::EncryptionEngine;
+    use crate::hex;
+    use crate::tests::test_rng;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_std::One;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn rfc8032_encoding() {
+        // the base point is (x, 4/5) with a non-negative x
+        assert_eq!(
+            hex::encode(&compress(&EdwardsAffine::generator())),
+            "5866666666666666666666666666666666666666666666666666666666666666"
+        );
+
+        // section 7.1, test 1: the public key of the secret key
+        // 9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60, whose clamped
+        // SHA-512 hash is the scalar below
+        let scalar =
+            hex::decode("307c83864f2833cb427a2ef1c00a013cfdff2768d980c0a3a520f006904de94f")
+                .unwrap();
+        let public = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+        let point =
+            (EdwardsAffine::generator() * Fr::from_le_bytes_mod_order(&scalar)).into_affine();
+        assert_eq!(hex::encode(&compress(&point)), public);
+        let bytes = hex::decode(public).unwrap().try_into().unwrap();
+        assert_eq!(decompress(&bytes).unwrap(), point);
+
+        let rng = &mut test_rng();
+        for _ in 0..8 {
+            let point = EdwardsAffine::rand(rng);
+            let bytes = compress(&point);
+            assert_eq!(decompress(&bytes).unwrap(), point);
+            assert_eq!(bytes[31] >> 7, point.x.into_bigint().is_odd() as u8);
+        }
+        // the torsion point (0, -1) is accepted, but not with the sign bit set
+        let mut torsion = compress(&EdwardsAffine::new_unchecked(Fq::ZERO, -Fq::ONE));
+        assert!(decompress(&torsion).is_ok());
+        torsion[31] |= 0x80;
+        assert_eq!(decompress(&torsion), Err(Error::InvalidEncoding));
+        // y = p is not canonical
+        let mut modulus = [0xff; 32];
+        modulus[0] = 0xed;
+        modulus[31] = 0x7f;
+        assert_eq!(decompress(&modulus), Err(Error::InvalidEncoding));
+    }
+
+    #[test]
+    fn exponential_elgamal() {
+        let rng = &mut test_rng();
+        let keys = KeyPair::generate(rng);
+        let plaintext = Fr::from(987654u32);
+        let cipher = Elgamal::encrypt(&plaintext, keys.pk(), rng);
+        let sum = cipher + Elgamal::encrypt(&Fr::from(1u8), keys.pk(), rng);
+        let solver = BabyStepGiantStep::<EdwardsProjective>::new(10);
+        assert_eq!(
+            Elgamal::decrypt_range(sum, keys.sk(), 20, &solver),
+            Ok(plaintext + Fr::one())
+        );
+    }
+}
@@ -0,0 +1,8 @@
+//! Protocol instantiations over the arkworks curves used by payment layers and non-pairing
+//! ecosystems, each behind the feature of the same name.
+#[cfg(feature = "bn254")]
+pub mod bn254;
+#[cfg(feature = "ed25519")]
+pub mod ed25519;
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
@@ -0,0 +1,101 @@
+//! Instantiations over secp256k1 for integrations with payment layers, e.g. adaptor signatures
+//! and HTLCs on Ethereum or Bitcoin. All Fiat-Shamir transcripts use Keccak256.
+//!
+//! secp256k1 is not pairing-friendly, so only the curve-generic encryption engines and sigma
+//! protocols are available, KZG-based range proofs and VECK proofs are not.
+use crate::encrypt::{elgamal, hybrid};
+use sha3::Keccak256;
+
+pub use ark_secp256k1::{Affine, Fq, Fr, Projective};
+
+pub type Elgamal = elgamal::ExponentialElgamal<Projective>;
+pub type Cipher = elgamal::Cipher<Projective>;
+pub type MultiCipher = elgamal::MultiCipher<Projective>;
+pub type KeyPair = elgamal::KeyPair<Projective>;
+pub type PublicKey = elgamal::PublicKey<Projective>;
+pub type SplitEncryption<const N: usize> = elgamal::SplitEncryption<N, Projective, Keccak256>;
+pub type HashedElgamal = elgamal::HashedElgamal<Projective, Keccak256>;
+pub type Hybrid = hybrid::Hybrid<Projective, Keccak256>;
+
+pub type PlaintextEqualityProof = elgamal::PlaintextEqualityProof<Projective, Keccak256>;
+pub type DecryptionProof = elgamal::DecryptionProof<Projective, Keccak256>;
+pub type CommitmentEqualityProof = elgamal::CommitmentEqualityProof<Projective, Keccak256>;
+pub type PlaintextKnowledgeProof = elgamal::PlaintextKnowledgeProof<Projective, Keccak256>;
+pub type DleqProof = crate::dleq::Proof<Projective, Keccak256>;
+pub type EqualityProof = crate::equality::Proof<Projective, Keccak256>;
+pub type SumProof = crate::sum::Proof<Projective, Keccak256>;
+pub type Bases = crate::equality::Bases<Projective>;
+pub type Schnorr = ark_crypto_primitives::signature::schnorr::Schnorr<Projective, Keccak256>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::BabyStepGiantStep;
+    use crate::encrypt::EncryptionEngine;
+    use crate::tests::test_rng;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_std::UniformRand;
+
+    #[test]
+    fn encryption_engines() {
+        let rng = &mut test_rng();
+        let keys = KeyPair::generate(rng);
+
+        let plaintext = Fr::from(123456u32);
+        let cipher = Elgamal::encrypt(&plaintext, keys.pk(), rng);
+        let solver = BabyStepGiantStep::<Projective>::new(10);
+        assert_eq!(
+            Elgamal::decrypt_range(cipher, keys.sk(), 20, &solver),
+            Ok(plaintext)
+        );
+
+        let scalar = Fr::rand(rng);
+        let split = SplitEncryption::<8>::new(&scalar, keys.pk(), rng);
+        let commitment = (Affine::generator() * scalar).into_affine();
+        assert!(split.verify(&commitment, keys.pk()));
+        assert!(!split.verify(&Affine::generator(), keys.pk()));
+
+        let data = b"htlc preimage".to_vec();
+        let cipher = HashedElgamal::encrypt(&data, keys.pk(), rng);
+        assert_eq!(HashedElgamal::decrypt(&cipher, keys.sk()).unwrap(), data);
+        let cipher = Hybrid::encrypt(&data, keys.pk(), rng);
+        assert_eq!(Hybrid::decrypt(cipher, keys.sk()), Ok(data));
+    }
+
+    #[test]
+    fn sigma_protocols() {
+        let rng = &mut test_rng();
+        let keys = KeyPair::generate(rng);
+        let secret = Fr::rand(rng);
+        let h = (Affine::generator() * Fr::rand(rng)).into_affine();
+        let proof = DleqProof::new(&secret, Affine::generator(), h, rng);
+        assert!(proof.verify(
+            Affine::generator(),
+            Affine::generator() * secret,
+            h,
+            h * secret
+        ));
+
+        let bases = Bases::new(Affine::generator(), h);
+        let value = Fr::from(42u8);
+        let blinding = Fr::rand(rng);
+        let commitment = bases.commit(&value, &blinding).into_affine();
+        let (cipher, randomness) = Elgamal::encrypt_returning_randomness(&value, keys.pk(), rng);
+        let proof = CommitmentEqualityProof::new(
+            &value,
+            &blinding,
+            &randomness,
+            &bases,
+            keys.pk(),
+            &commitment,
+            &cipher,
+            rng,
+        );
+        assert!(proof.verify(&bases, keys.pk(), &commitment, &cipher));
+
+        let (plaintext_point, proof) = DecryptionProof::new(&cipher, keys.sk(), rng);
+        assert!(proof.verify(&cipher, &plaintext_point, keys.pk()));
+        assert!(proof.verify_plaintext(&cipher, &value, keys.pk()));
+        assert!(!proof.verify_plaintext(&cipher, &Fr::from(43u8), keys.pk()));
+    }
+}
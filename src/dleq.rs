@@ -2,7 +2,7 @@ use crate::hash::Hasher;
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
 use ark_std::UniformRand;
 use digest::Digest;
 
@@ -17,7 +17,12 @@ where
     C: CurveGroup,
     D: Digest,
 {
-    pub fn new<R: Rng>(secret: &C::ScalarField, g1: C::Affine, g2: C::Affine, rng: &mut R) -> Self {
+    pub fn new<R: Rng + CryptoRng>(
+        secret: &C::ScalarField,
+        g1: C::Affine,
+        g2: C::Affine,
+        rng: &mut R,
+    ) -> Self {
         let rand = C::ScalarField::rand(rng);
         let k1 = g1 * rand;
         let k2 = g2 * rand;
@@ -61,10 +66,11 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::tests::test_rng;
     use crate::tests::{G1Affine, Scalar, TestCurve, TestHash};
     use ark_ec::pairing::Pairing;
     use ark_ec::{AffineRepr, CurveGroup};
-    use ark_std::{test_rng, UniformRand};
+    use ark_std::UniformRand;
 
     type DleqProof = Proof<<TestCurve as Pairing>::G1, TestHash>;
 
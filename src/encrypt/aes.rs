@@ -0,0 +1,51 @@
+//! AES-128 in counter mode as used by [`super::ecies`], on top of the `aes` and `ctr` crates.
+use ctr::cipher::{KeyIvInit, StreamCipher};
+
+pub const KEY_SIZE: usize = 16;
+pub const BLOCK_SIZE: usize = 16;
+
+type Aes128Ctr = ctr::Ctr128BE<::aes::Aes128>;
+
+/// XORs `data` with the AES-128 keystream of the big-endian 128-bit counter starting at `iv`.
+pub fn apply_ctr(key: &[u8; KEY_SIZE], iv: &[u8; BLOCK_SIZE], data: &mut [u8]) {
+    Aes128Ctr::new(key.into(), iv.into()).apply_keystream(data);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hex;
+    use ::aes::cipher::{BlockEncrypt, KeyInit};
+
+    #[test]
+    fn fips197_test_vectors() {
+        // appendix C.1
+        let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let mut block: [u8; 16] = core::array::from_fn(|i| (i as u8) * 0x11);
+        ::aes::Aes128::new(&key.into()).encrypt_block((&mut block).into());
+        assert_eq!(hex::encode(&block), "69c4e0d86a7b0430d8cdb78070b4c55a");
+    }
+
+    #[test]
+    fn sp800_38a_ctr() {
+        // appendix F.5.1, the first two blocks
+        let key = hex::decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let iv = hex::decode("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+        let mut data =
+            hex::decode("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51")
+                .unwrap();
+        apply_ctr(&key.try_into().unwrap(), &iv.try_into().unwrap(), &mut data);
+        assert_eq!(
+            hex::encode(&data),
+            "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff"
+        );
+
+        // the counter wraps around after 2^128 blocks
+        let key = [0u8; KEY_SIZE];
+        let mut wrapped = [0u8; 2 * BLOCK_SIZE];
+        apply_ctr(&key, &[0xff; BLOCK_SIZE], &mut wrapped);
+        let mut first = [0u8; BLOCK_SIZE];
+        apply_ctr(&key, &[0u8; BLOCK_SIZE], &mut first);
+        assert_eq!(wrapped[BLOCK_SIZE..], first);
+    }
+}
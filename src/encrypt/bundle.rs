@@ -0,0 +1,126 @@
+//! Authentication tags over ordered lists of ciphertexts, which a buyer checks before the more
+//! expensive encryption proofs. Seller and buyer agree on the key via [`session_key`] without
+//! further interaction.
+use crate::encrypt::constant_time;
+use crate::hash::{constant_time_eq, expand};
+use ark_ec::CurveGroup;
+use ark_serialize::CanonicalSerialize;
+use digest::{Digest, Output};
+use thiserror::Error as ErrorT;
+use zeroize::{Zeroize, Zeroizing};
+
+const TAG_DOMAIN_SEP: &[u8] = b"fde ciphertext bundle";
+const KDF_DOMAIN_SEP: &[u8] = b"fde bundle session key";
+pub const KEY_SIZE: usize = 32;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid bundle tag")]
+    InvalidTag,
+}
+
+/// Derives the symmetric session key from the own secret key and the peer's public key, i.e. the
+/// Diffie-Hellman secret `x_seller * x_buyer * g`, which both parties compute alike.
+pub fn session_key<C: CurveGroup, D: Digest + Clone>(
+    secret: &C::ScalarField,
+    peer: &C::Affine,
+) -> Zeroizing<[u8; KEY_SIZE]> {
+    let mut shared_secret = constant_time::mul::<C>(peer, secret).into_affine();
+    let mut bytes = Zeroizing::new(Vec::new());
+    shared_secret
+        .serialize_compressed(&mut *bytes)
+        .expect("should not fail");
+    shared_secret.zeroize();
+    let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+    expand::<D>(KDF_DOMAIN_SEP, &bytes, &mut *key);
+    key
+}
+
+/// Computes the tag of `ciphers` in the given order.
+///
+/// The tag is the keyed hash `D(domain || len(key) || key || len(context) || context || n ||
+/// len(c_1) || c_1 || ... || len(c_n) || c_n)` over the compressed ciphertexts, thus reordering,
+/// truncating, extending or substituting any ciphertext invalidates it.
+pub fn tag<D: Digest, T: CanonicalSerialize>(
+    key: &[u8],
+    context: &[u8],
+    ciphers: &[T],
+) -> Output<D> {
+    let mut digest = D::new();
+    digest.update(TAG_DOMAIN_SEP);
+    for part in [key, context] {
+        digest.update((part.len() as u64).to_le_bytes());
+        digest.update(part);
+    }
+    digest.update((ciphers.len() as u64).to_le_bytes());
+    let mut bytes = Vec::new();
+    for cipher in ciphers {
+        bytes.clear();
+        cipher
+            .serialize_compressed(&mut bytes)
+            .expect("should not fail");
+        digest.update((bytes.len() as u64).to_le_bytes());
+        digest.update(&bytes);
+    }
+    digest.finalize()
+}
+
+/// Checks the tag of `ciphers` in constant time.
+pub fn verify<D: Digest, T: CanonicalSerialize>(
+    key: &[u8],
+    context: &[u8],
+    ciphers: &[T],
+    expected: &[u8],
+) -> Result<(), Error> {
+    if constant_time_eq(&tag::<D, T>(key, context, ciphers), expected) {
+        Ok(())
+    } else {
+        Err(Error::InvalidTag)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::{ExponentialElgamal, KeyPair};
+    use crate::encrypt::EncryptionEngine;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+
+    #[test]
+    fn bundle_tag() {
+        let rng = &mut test_rng();
+        let seller = KeyPair::<G1>::generate(rng);
+        let buyer = KeyPair::<G1>::generate(rng);
+        let key = session_key::<G1, TestHash>(seller.sk(), buyer.pk());
+        assert_eq!(*key, *session_key::<G1, TestHash>(buyer.sk(), seller.pk()));
+
+        let data: Vec<Scalar> = (0..4u8).map(Scalar::from).collect();
+        let ciphers = ExponentialElgamal::<G1>::encrypt_batch(&data, seller.pk(), rng);
+        let tag = tag::<TestHash, _>(&*key, b"order 1", &ciphers);
+        assert_eq!(
+            verify::<TestHash, _>(&*key, b"order 1", &ciphers, &tag),
+            Ok(())
+        );
+
+        let mut reordered = ciphers.clone();
+        reordered.swap(0, 1);
+        let mut substituted = ciphers.clone();
+        substituted[2] = substituted[2].rerandomize(seller.pk(), rng);
+        let mut extended = ciphers.clone();
+        extended.push(ciphers[0]);
+        for tampered in [reordered, substituted, extended, ciphers[..3].to_vec()] {
+            assert_eq!(
+                verify::<TestHash, _>(&*key, b"order 1", &tampered, &tag),
+                Err(Error::InvalidTag)
+            );
+        }
+        assert!(verify::<TestHash, _>(&*key, b"order 2", &ciphers, &tag).is_err());
+        let other = KeyPair::<G1>::generate(rng);
+        let other_key = session_key::<G1, TestHash>(other.sk(), seller.pk());
+        assert!(verify::<TestHash, _>(&*other_key, b"order 1", &ciphers, &tag).is_err());
+    }
+}
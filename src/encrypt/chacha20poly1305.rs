@@ -0,0 +1,111 @@
+//! ChaCha20-Poly1305 authenticated encryption as specified in RFC 8439, on top of the
+//! `chacha20poly1305` crate.
+//!
+//! Used for encrypting bulk data under keys derived by [`super::hybrid`].
+use ::chacha20poly1305::aead::{AeadInPlace, KeyInit};
+use ::chacha20poly1305::ChaCha20Poly1305;
+use thiserror::Error as ErrorT;
+
+pub const KEY_SIZE: usize = 32;
+pub const NONCE_SIZE: usize = 12;
+pub const TAG_SIZE: usize = 16;
+const BLOCK_SIZE: usize = 64;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid authentication tag")]
+    InvalidTag,
+    #[error("plaintext of {0} bytes is too long")]
+    PlaintextTooLong(usize),
+}
+
+/// Encrypts `plaintext` in place and returns the tag authenticating it together with `aad`.
+pub fn seal(
+    key: &[u8; KEY_SIZE],
+    nonce: &[u8; NONCE_SIZE],
+    aad: &[u8],
+    plaintext: &mut [u8],
+) -> Result<[u8; TAG_SIZE], Error> {
+    // the 32-bit block counter starts at 1
+    if plaintext.len() as u64 > (u32::MAX as u64) * BLOCK_SIZE as u64 {
+        return Err(Error::PlaintextTooLong(plaintext.len()));
+    }
+    let tag = ChaCha20Poly1305::new(key.into())
+        .encrypt_in_place_detached(nonce.into(), aad, plaintext)
+        .map_err(|_| Error::PlaintextTooLong(plaintext.len()))?;
+    Ok(tag.into())
+}
+
+/// Checks the tag and decrypts `ciphertext` in place, which is left untouched if the tag is
+/// invalid.
+pub fn open(
+    key: &[u8; KEY_SIZE],
+    nonce: &[u8; NONCE_SIZE],
+    aad: &[u8],
+    ciphertext: &mut [u8],
+    expected_tag: &[u8; TAG_SIZE],
+) -> Result<(), Error> {
+    ChaCha20Poly1305::new(key.into())
+        .decrypt_in_place_detached(nonce.into(), aad, ciphertext, expected_tag.into())
+        .map_err(|_| Error::InvalidTag)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hex;
+
+    fn array<const N: usize>(input: &str) -> [u8; N] {
+        hex::decode(input).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn rfc8439_test_vectors() {
+        // section 2.8.2
+        let key: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        let nonce = array("070000004041424344454647");
+        let aad = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one \
+                          tip for the future, sunscreen would be it.";
+        let mut data = plaintext.to_vec();
+        let tag = seal(&key, &nonce, &aad, &mut data).unwrap();
+        assert_eq!(
+            hex::encode(&data),
+            "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6\
+             3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36\
+             92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc\
+             3ff4def08e4b7a9de576d26586cec64b6116"
+        );
+        assert_eq!(hex::encode(&tag), "1ae10b594f09e26a7e902ecbd0600691");
+
+        open(&key, &nonce, &aad, &mut data, &tag).unwrap();
+        assert_eq!(data, plaintext);
+    }
+
+    #[test]
+    fn authentication() {
+        let key = [7u8; KEY_SIZE];
+        let nonce = [1u8; NONCE_SIZE];
+        let mut data = vec![42u8; 1000];
+        let tag = seal(&key, &nonce, b"aad", &mut data).unwrap();
+
+        let mut tampered = data.clone();
+        tampered[999] ^= 1;
+        let before = tampered.clone();
+        assert_eq!(
+            open(&key, &nonce, b"aad", &mut tampered, &tag),
+            Err(Error::InvalidTag)
+        );
+        assert_eq!(tampered, before);
+        assert_eq!(
+            open(&key, &nonce, b"aaD", &mut data.clone(), &tag),
+            Err(Error::InvalidTag)
+        );
+        assert_eq!(
+            open(&[8u8; KEY_SIZE], &nonce, b"aad", &mut data.clone(), &tag),
+            Err(Error::InvalidTag)
+        );
+        open(&key, &nonce, b"aad", &mut data, &tag).unwrap();
+        assert_eq!(data, vec![42u8; 1000]);
+    }
+}
@@ -0,0 +1,123 @@
+//! Scalar multiplication by secret scalars, e.g. decryption keys, without secret-dependent
+//! branching. Note that the field and curve arithmetic of arkworks itself does not guarantee
+//! constant time execution.
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Computes `scalar * base` for a `base` in the prime order subgroup.
+///
+/// The generic multiplication of arkworks skips leading zeros and branches on every bit of the
+/// scalar. Here, the scalar `k` is replaced by `k + n` or `k + 2n` for the group order `n`,
+/// whichever has its top bit at position `log2(n)`, and processed by a Montgomery ladder, which
+/// performs one addition and one doubling per bit.
+pub fn mul<C: CurveGroup>(base: &C::Affine, scalar: &C::ScalarField) -> C {
+    ladder::<C>(base, &fixed_length::<C::ScalarField>(scalar))
+}
+
+/// Computes `scalar * base` for every base, recoding the scalar only once.
+pub fn mul_batch<C: CurveGroup>(bases: &[C::Affine], scalar: &C::ScalarField) -> Vec<C> {
+    let limbs = fixed_length::<C::ScalarField>(scalar);
+    #[cfg(not(feature = "parallel"))]
+    return bases.iter().map(|base| ladder::<C>(base, &limbs)).collect();
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        bases
+            .par_iter()
+            .map(|base| ladder::<C>(base, &limbs))
+            .collect()
+    }
+}
+
+// `k + n` or `k + 2n`, whichever has its top bit at position `log2(n)`
+fn fixed_length<S: PrimeField>(scalar: &S) -> Zeroizing<Vec<u64>> {
+    let bits = S::MODULUS_BIT_SIZE as usize;
+    let mut modulus = S::MODULUS.as_ref().to_vec();
+    modulus.push(0);
+    let mut bigint = scalar.into_bigint();
+    let mut once = Zeroizing::new(bigint.as_ref().to_vec());
+    bigint.as_mut().zeroize();
+    once.push(0);
+    add_limbs(&mut once, &modulus);
+    let mut twice = once.clone();
+    add_limbs(&mut twice, &modulus);
+
+    // k + n < 2^bits implies 2^bits <= k + 2n < 2^(bits + 1)
+    let mask = 0u64.wrapping_sub(bit(&once, bits));
+    for (o, t) in once.iter_mut().zip(twice.iter()) {
+        *o = (*o & mask) | (*t & !mask);
+    }
+    once
+}
+
+fn ladder<C: CurveGroup>(base: &C::Affine, limbs: &[u64]) -> C {
+    let bits = C::ScalarField::MODULUS_BIT_SIZE as usize;
+    // invariant: ladder[1] = ladder[0] + base
+    let mut ladder = [base.into_group(), base.into_group().double()];
+    for i in (0..bits).rev() {
+        let b = bit(limbs, i) as usize;
+        ladder[1 - b] = ladder[0] + ladder[1];
+        ladder[b].double_in_place();
+    }
+    let result = ladder[0];
+    ladder.zeroize();
+    result
+}
+
+fn bit(limbs: &[u64], i: usize) -> u64 {
+    (limbs[i / 64] >> (i % 64)) & 1
+}
+
+// a += b for little-endian limbs of the same length, the final carry is dropped
+fn add_limbs(a: &mut [u64], b: &[u64]) {
+    let mut carry = 0u128;
+    for (x, y) in a.iter_mut().zip(b) {
+        let sum = *x as u128 + *y as u128 + carry;
+        *x = sum as u64;
+        carry = sum >> 64;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::TestCurve;
+    use ark_ec::pairing::Pairing;
+    use ark_ed25519::EdwardsProjective;
+    use ark_std::{One, UniformRand, Zero};
+
+    fn check<C: CurveGroup>() {
+        let rng = &mut test_rng();
+        let base = (C::Affine::generator() * C::ScalarField::rand(rng)).into_affine();
+        let scalars = [
+            C::ScalarField::zero(),
+            C::ScalarField::one(),
+            -C::ScalarField::one(),
+            C::ScalarField::from(2u8),
+            C::ScalarField::rand(rng),
+            C::ScalarField::rand(rng),
+        ];
+        for scalar in scalars {
+            assert_eq!(mul::<C>(&base, &scalar), base * scalar);
+        }
+        assert!(mul::<C>(&C::Affine::zero(), &C::ScalarField::rand(rng)).is_zero());
+
+        let bases = [base, C::Affine::zero(), C::Affine::generator()];
+        let scalar = C::ScalarField::rand(rng);
+        let products = mul_batch::<C>(&bases, &scalar);
+        assert!(products
+            .iter()
+            .zip(bases)
+            .all(|(p, base)| *p == base * scalar));
+    }
+
+    #[test]
+    fn constant_time_mul() {
+        check::<<TestCurve as Pairing>::G1>();
+        check::<EdwardsProjective>();
+        // the modulus fills all limbs
+        check::<ark_secp256k1::Projective>();
+    }
+}
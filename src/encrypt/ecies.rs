@@ -0,0 +1,272 @@
+//! ECIES over secp256k1 with the parameters of Ethereum's devp2p and go-ethereum's `ecies`
+//! package, so that data can be encrypted to the key of a wallet.
+use super::aes;
+use super::constant_time;
+use crate::hash::{constant_time_eq, hmac};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use ark_secp256k1::{Affine, Fq, Fr, Projective};
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use sha2::{Digest, Sha256};
+use thiserror::Error as ErrorT;
+use zeroize::{Zeroize, Zeroizing};
+
+const COORDINATE_SIZE: usize = 32;
+/// Size of an uncompressed SEC1 point.
+pub const PUBLIC_KEY_SIZE: usize = 2 * COORDINATE_SIZE + 1;
+pub const TAG_SIZE: usize = 32;
+/// Size of a ciphertext of the empty message.
+pub const OVERHEAD: usize = PUBLIC_KEY_SIZE + aes::BLOCK_SIZE + TAG_SIZE;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid public key")]
+    InvalidPublicKey,
+    #[error("invalid secret key")]
+    InvalidSecretKey,
+    #[error("ciphertext of {0} bytes is too short")]
+    CiphertextTooShort(usize),
+    #[error("invalid authentication tag")]
+    InvalidTag,
+}
+
+/// Parses a 32-byte big-endian secret key as exported by wallets.
+pub fn secret_key_from_bytes(bytes: &[u8]) -> Result<Fr, Error> {
+    let key = Fr::from_be_bytes_mod_order(bytes);
+    if bytes.len() != COORDINATE_SIZE || key.is_zero() || key.into_bigint().to_bytes_be() != bytes {
+        return Err(Error::InvalidSecretKey);
+    }
+    Ok(key)
+}
+
+/// Parses a compressed (33 bytes) or uncompressed (65 bytes) SEC1 public key.
+pub fn public_key_from_bytes(bytes: &[u8]) -> Result<Affine, Error> {
+    let coordinate = |bytes: &[u8]| {
+        let element = Fq::from_be_bytes_mod_order(bytes);
+        (element.into_bigint().to_bytes_be() == bytes)
+            .then_some(element)
+            .ok_or(Error::InvalidPublicKey)
+    };
+    let point = match (bytes.first(), bytes.len()) {
+        (Some(0x04), PUBLIC_KEY_SIZE) => Affine::new_unchecked(
+            coordinate(&bytes[1..COORDINATE_SIZE + 1])?,
+            coordinate(&bytes[COORDINATE_SIZE + 1..])?,
+        ),
+        (Some(prefix @ (0x02 | 0x03)), 33) => {
+            let x = coordinate(&bytes[1..])?;
+            let (y, negated) = Affine::get_ys_from_x_unchecked(x).ok_or(Error::InvalidPublicKey)?;
+            let odd = *prefix == 0x03;
+            let y = if y.into_bigint().is_odd() == odd {
+                y
+            } else {
+                negated
+            };
+            Affine::new_unchecked(x, y)
+        }
+        _ => return Err(Error::InvalidPublicKey),
+    };
+    // secp256k1 has cofactor one
+    if !point.is_on_curve() || point.is_zero() {
+        return Err(Error::InvalidPublicKey);
+    }
+    Ok(point)
+}
+
+/// Uncompressed SEC1 encoding `0x04 || x || y` of a point.
+pub fn public_key_to_bytes(point: &Affine) -> [u8; PUBLIC_KEY_SIZE] {
+    let mut bytes = [0u8; PUBLIC_KEY_SIZE];
+    bytes[0] = 0x04;
+    bytes[1..COORDINATE_SIZE + 1].copy_from_slice(&point.x.into_bigint().to_bytes_be());
+    bytes[COORDINATE_SIZE + 1..].copy_from_slice(&point.y.into_bigint().to_bytes_be());
+    bytes
+}
+
+/// Encrypts `data` to `key` with a fresh ephemeral key `R`.
+///
+/// The x-coordinate of the shared point `r * P` is expanded with the NIST SP 800-56 concatenation
+/// KDF over SHA-256 into a 16-byte AES-128-CTR key and a 16-byte MAC key, which is hashed again
+/// with SHA-256 to key HMAC-SHA-256. The ciphertext is laid out as
+/// `R || iv || AES-128-CTR(m) || HMAC(iv || AES-128-CTR(m))` with the uncompressed 65-byte
+/// ephemeral key `R`, and no shared info is used on either side.
+pub fn encrypt<R: Rng + CryptoRng>(data: &[u8], key: &Affine, rng: &mut R) -> Vec<u8> {
+    let random_nonce = Zeroizing::new(Fr::rand(rng));
+    let iv: [u8; aes::BLOCK_SIZE] = rng.gen();
+    encrypt_with_randomness(data, key, &random_nonce, &iv)
+}
+
+pub fn encrypt_with_randomness(
+    data: &[u8],
+    key: &Affine,
+    randomness: &Fr,
+    iv: &[u8; aes::BLOCK_SIZE],
+) -> Vec<u8> {
+    let ephemeral = (Affine::generator() * randomness).into_affine();
+    let (encryption_key, mac_key) = derive_keys(&(*key * randomness).into_affine());
+
+    let mut output = Vec::with_capacity(OVERHEAD + data.len());
+    output.extend_from_slice(&public_key_to_bytes(&ephemeral));
+    output.extend_from_slice(iv);
+    output.extend_from_slice(data);
+    aes::apply_ctr(
+        &encryption_key,
+        iv,
+        &mut output[PUBLIC_KEY_SIZE + aes::BLOCK_SIZE..],
+    );
+    let tag = hmac::<Sha256>(&*mac_key, &output[PUBLIC_KEY_SIZE..]);
+    output.extend_from_slice(&tag);
+    output
+}
+
+pub fn decrypt(cipher: &[u8], key: &Fr) -> Result<Vec<u8>, Error> {
+    if cipher.len() < OVERHEAD {
+        return Err(Error::CiphertextTooShort(cipher.len()));
+    }
+    // only uncompressed ephemeral keys are used in practice
+    if cipher[0] != 0x04 {
+        return Err(Error::InvalidPublicKey);
+    }
+    let ephemeral = public_key_from_bytes(&cipher[..PUBLIC_KEY_SIZE])?;
+    let mut shared_secret = constant_time::mul::<Projective>(&ephemeral, key).into_affine();
+    let (encryption_key, mac_key) = derive_keys(&shared_secret);
+    shared_secret.zeroize();
+
+    let (body, tag) = cipher[PUBLIC_KEY_SIZE..].split_at(cipher.len() - OVERHEAD + aes::BLOCK_SIZE);
+    if !constant_time_eq(&hmac::<Sha256>(&*mac_key, body), tag) {
+        return Err(Error::InvalidTag);
+    }
+    let (iv, ciphertext) = body.split_at(aes::BLOCK_SIZE);
+    let mut data = ciphertext.to_vec();
+    aes::apply_ctr(
+        &encryption_key,
+        &iv.try_into().expect("checked length"),
+        &mut data,
+    );
+    Ok(data)
+}
+
+// concatenation KDF `SHA-256(1_be32 || x)`, split into the encryption key and the hashed MAC key
+fn derive_keys(
+    shared_secret: &Affine,
+) -> (Zeroizing<[u8; aes::KEY_SIZE]>, Zeroizing<[u8; TAG_SIZE]>) {
+    let mut hasher = Sha256::new();
+    hasher.update(1u32.to_be_bytes());
+    hasher.update(shared_secret.x.into_bigint().to_bytes_be());
+    let keys = Zeroizing::new(<[u8; 32]>::from(hasher.finalize()));
+    let mut encryption_key = Zeroizing::new([0u8; aes::KEY_SIZE]);
+    encryption_key.copy_from_slice(&keys[..aes::KEY_SIZE]);
+    let mac_key = Zeroizing::new(Sha256::digest(&keys[aes::KEY_SIZE..]).into());
+    (encryption_key, mac_key)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hex;
+    use crate::tests::test_rng;
+
+    #[test]
+    fn hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hex::encode(&hmac::<Sha256>(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn wallet_keys() {
+        // the secret key 1 belongs to the generator
+        let mut bytes = [0u8; 32];
+        bytes[31] = 1;
+        let key = secret_key_from_bytes(&bytes).unwrap();
+        assert_eq!(key, Fr::from(1u8));
+        let generator =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        assert_eq!(
+            public_key_from_bytes(&generator).unwrap(),
+            Affine::generator()
+        );
+        let uncompressed = public_key_to_bytes(&Affine::generator());
+        assert_eq!(
+            hex::encode(&uncompressed),
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+        );
+        assert_eq!(
+            public_key_from_bytes(&uncompressed).unwrap(),
+            Affine::generator()
+        );
+        let odd = -Affine::generator();
+        let mut compressed = generator.clone();
+        compressed[0] = 0x03;
+        assert_eq!(public_key_from_bytes(&compressed).unwrap(), odd);
+
+        assert_eq!(
+            secret_key_from_bytes(&[0u8; 32]),
+            Err(Error::InvalidSecretKey)
+        );
+        assert_eq!(
+            secret_key_from_bytes(&[0xff; 32]),
+            Err(Error::InvalidSecretKey)
+        );
+        let mut off_curve = uncompressed;
+        off_curve[64] ^= 1;
+        assert_eq!(
+            public_key_from_bytes(&off_curve),
+            Err(Error::InvalidPublicKey)
+        );
+        assert_eq!(
+            public_key_from_bytes(&generator[1..]),
+            Err(Error::InvalidPublicKey)
+        );
+    }
+
+    // A ciphertext of go-ethereum's `ecies.Encrypt` layout with a fixed ephemeral key and IV,
+    // computed with OpenSSL's secp256k1 ECDH, AES-128-CTR and HMAC-SHA-256.
+    #[test]
+    fn known_ciphertext() {
+        let key = |hex: &str| secret_key_from_bytes(&hex::decode(hex).unwrap()).unwrap();
+        let secret_key = key("4b50fa71f5c3eeb8fdc452224b2395af2fcc3d125e06c32c82e048c0559db03f");
+        let randomness = key("d0b043b4c5d657670778242d82d68a29d25d7d711127d17b8e299f156dad361a");
+        let cipher = hex::decode(
+            "04bd27a63c91fe3233c5777e6d3d7b39204d398c8f92655947eb5a373d46e1688f022a1632d264725cbc7\
+             dc43ee1cfebde42fa0a86d08b55d2acfbb5e9b3b48dc5000102030405060708090a0b0c0d0e0f800ca4\
+             ef4e0e460415e5864b85335ea3b51562e20500793692f7a21f313c2b1ff1af9db2831e9a84d993f29271",
+        )
+        .unwrap();
+        assert_eq!(decrypt(&cipher, &secret_key).unwrap(), b"Hello, world.");
+
+        let public_key = (Affine::generator() * secret_key).into_affine();
+        let iv = core::array::from_fn(|i| i as u8);
+        assert_eq!(
+            encrypt_with_randomness(b"Hello, world.", &public_key, &randomness, &iv),
+            cipher
+        );
+    }
+
+    #[test]
+    fn ecies() {
+        let rng = &mut test_rng();
+        let secret_key = Fr::rand(rng);
+        let public_key = (Affine::generator() * secret_key).into_affine();
+
+        for data in [&[][..], b"decryption key", &[0x5a; 1000]] {
+            let cipher = encrypt(data, &public_key, rng);
+            assert_eq!(cipher.len(), data.len() + OVERHEAD);
+            assert_eq!(cipher[0], 0x04);
+            assert_eq!(decrypt(&cipher, &secret_key).unwrap(), data);
+        }
+
+        let cipher = encrypt(b"decryption key", &public_key, rng);
+        assert_eq!(decrypt(&cipher, &Fr::rand(rng)), Err(Error::InvalidTag));
+        let mut tampered = cipher.clone();
+        tampered[PUBLIC_KEY_SIZE + aes::BLOCK_SIZE] ^= 1;
+        assert_eq!(decrypt(&tampered, &secret_key), Err(Error::InvalidTag));
+        assert_eq!(
+            decrypt(&cipher[..OVERHEAD - 1], &secret_key),
+            Err(Error::CiphertextTooShort(OVERHEAD - 1))
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Oblivious decryption of exponential Elgamal ciphertexts, where the seller decrypts a cipher
+//! for the buyer without learning which one.
+use super::{Cipher, CipherError, DecryptError, DiscreteLog, MAX_BITS};
+use crate::dleq::Proof as DleqProof;
+use crate::encrypt::constant_time;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Field;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::{UniformRand, Zero};
+use digest::Digest;
+use thiserror::Error as ErrorT;
+use zeroize::Zeroize;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid decryption proof")]
+    InvalidProof,
+    #[error(transparent)]
+    Cipher(#[from] CipherError),
+    #[error(transparent)]
+    Decrypt(#[from] DecryptError),
+}
+
+/// The secret blinding factor `b` of a request, which only the buyer knows.
+pub struct BlindingFactor<C: CurveGroup>(C::ScalarField);
+
+impl<C: CurveGroup> Drop for BlindingFactor<C> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// The seller's response `x * b * c0` together with a proof of its correctness.
+pub struct BlindResponse<C: CurveGroup, D> {
+    pub point: C::Affine,
+    pub proof: DleqProof<C, D>,
+}
+
+/// The buyer holds a cipher `(c0, c1)` under the seller's key `h = x * g` and sends the blinded
+/// point `b * c0` for a random `b`. Since `c0` is a uniformly random point, so is `b * c0`. The
+/// seller responds with `x * b * c0` and a DLEQ proof against `h`, the buyer checks the proof,
+/// unblinds the shared secret `x * c0 = b^-1 * x * b * c0` and recovers `m * g = c1 - x * c0`.
+///
+/// The seller can neither link requests to ciphers nor restrict which ciphers are decrypted, so
+/// payment has to be per request, e.g. one request per purchased item.
+pub struct BlindDecryption<C, D>(PhantomData<(C, D)>);
+
+impl<C: CurveGroup, D: Digest> BlindDecryption<C, D> {
+    /// Blinds the first component of `cipher`, the returned point is sent to the seller.
+    pub fn blind<R: Rng + CryptoRng>(
+        cipher: &Cipher<C>,
+        rng: &mut R,
+    ) -> (C::Affine, BlindingFactor<C>) {
+        let mut factor = C::ScalarField::rand(rng);
+        while factor.is_zero() {
+            factor = C::ScalarField::rand(rng);
+        }
+        let request = (cipher.c0() * factor).into_affine();
+        (request, BlindingFactor(factor))
+    }
+
+    /// Decrypts a blinded request with the decryption key.
+    pub fn respond<R: Rng + CryptoRng>(
+        request: &C::Affine,
+        key: &C::ScalarField,
+        rng: &mut R,
+    ) -> BlindResponse<C, D> {
+        let point = constant_time::mul::<C>(request, key).into_affine();
+        let proof = DleqProof::new(key, C::Affine::generator(), *request, rng);
+        BlindResponse { point, proof }
+    }
+
+    /// Checks the response against the seller's `encryption_key` and returns the plaintext point
+    /// `m * g` of `cipher`.
+    pub fn unblind(
+        cipher: &Cipher<C>,
+        request: &C::Affine,
+        response: &BlindResponse<C, D>,
+        factor: &BlindingFactor<C>,
+        encryption_key: &C::Affine,
+    ) -> Result<C::Affine, Error> {
+        cipher.validate()?;
+        if !response.proof.verify(
+            C::Affine::generator(),
+            encryption_key.into_group(),
+            *request,
+            response.point.into_group(),
+        ) {
+            return Err(Error::InvalidProof);
+        }
+        let inverse = factor.0.inverse().expect("blinding factor is non-zero");
+        let mut shared_secret = constant_time::mul::<C>(&response.point, &inverse);
+        let plaintext_point = (cipher.c1().into_group() - shared_secret).into_affine();
+        shared_secret.zeroize();
+        Ok(plaintext_point)
+    }
+
+    /// Same as [`Self::unblind`], but solves the discrete logarithm of a plaintext smaller than
+    /// `2^MAX_BITS` with the given solver.
+    pub fn unblind_with<S: DiscreteLog<C>>(
+        cipher: &Cipher<C>,
+        request: &C::Affine,
+        response: &BlindResponse<C, D>,
+        factor: &BlindingFactor<C>,
+        encryption_key: &C::Affine,
+        solver: &S,
+    ) -> Result<C::ScalarField, Error> {
+        let bits = MAX_BITS as u32;
+        let plaintext_point = Self::unblind(cipher, request, response, factor, encryption_key)?;
+        let plaintext = solver
+            .solve(plaintext_point, bits)
+            .ok_or(DecryptError::BruteForceExceeded { bits })?;
+        Ok(C::ScalarField::from(plaintext))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::{BabyStepGiantStep, ExponentialElgamal, KeyPair};
+    use crate::encrypt::EncryptionEngine;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Elgamal = ExponentialElgamal<G1>;
+    type Blind = BlindDecryption<G1, TestHash>;
+
+    #[test]
+    fn blind_decryption() {
+        let rng = &mut test_rng();
+        let seller = KeyPair::<G1>::generate(rng);
+        let solver = BabyStepGiantStep::<G1>::new(10);
+        let items: Vec<Scalar> = (0..3u32).map(|i| Scalar::from(1000 + i)).collect();
+        let ciphers = Elgamal::encrypt_batch(&items, seller.pk(), rng);
+
+        let (request, factor) = Blind::blind(&ciphers[1], rng);
+        // the request differs from the first components of all ciphers
+        assert!(ciphers.iter().all(|cipher| cipher.c0() != request));
+        let response = Blind::respond(&request, seller.sk(), rng);
+        assert_eq!(
+            Blind::unblind_with(
+                &ciphers[1],
+                &request,
+                &response,
+                &factor,
+                seller.pk(),
+                &solver
+            ),
+            Ok(items[1])
+        );
+        // blinding the same cipher twice yields unrelated requests
+        let (other_request, _) = Blind::blind(&ciphers[1], rng);
+        assert_ne!(request, other_request);
+
+        // a response with another key is rejected
+        let other = KeyPair::<G1>::generate(rng);
+        let response = Blind::respond(&request, other.sk(), rng);
+        assert_eq!(
+            Blind::unblind(&ciphers[1], &request, &response, &factor, seller.pk()),
+            Err(Error::InvalidProof)
+        );
+        // as well as a response to another request
+        let response = Blind::respond(&other_request, seller.sk(), rng);
+        assert!(Blind::unblind(&ciphers[1], &request, &response, &factor, seller.pk()).is_err());
+    }
+}
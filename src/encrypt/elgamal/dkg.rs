@@ -0,0 +1,431 @@
+//! Pedersen's distributed generation of a threshold Elgamal key without a trusted dealer. Every
+//! party runs a [`Participant`] and ends up with a [`VerificationKey`] and a [`KeyShare`], usable
+//! with [`threshold::combine`](super::threshold::combine) like the ones of a trusted dealer.
+use super::threshold::{KeyShare, VerificationKey};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_std::collections::{BTreeMap, BTreeSet};
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::{UniformRand, Zero};
+use thiserror::Error as ErrorT;
+use zeroize::Zeroize;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid threshold {threshold} for {parties} parties")]
+    InvalidThreshold { threshold: usize, parties: usize },
+    #[error("party {0} is not a valid party index")]
+    InvalidIndex(u32),
+    #[error("malformed commitments of dealer {0}")]
+    InvalidCommitments(u32),
+    #[error("invalid share of dealer {0}")]
+    InvalidShare(u32),
+    #[error("no valid share of qualified dealer {0}")]
+    MissingShare(u32),
+    #[error("only {qualified} dealers qualified, but {threshold} are required")]
+    TooFewQualified { qualified: usize, threshold: usize },
+}
+
+/// Broadcast commitments `a_k * g` to the coefficients of a dealer's polynomial.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Commitments<C: CurveGroup> {
+    pub dealer: u32,
+    pub commitments: Vec<C::Affine>,
+}
+
+/// Share `f_dealer(recipient)`, wiped from memory when dropped.
+#[derive(Clone)]
+pub struct SecretShare<C: CurveGroup> {
+    pub dealer: u32,
+    pub recipient: u32,
+    value: C::ScalarField,
+}
+
+impl<C: CurveGroup> Drop for SecretShare<C> {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+/// Broadcast by `complainer` if it got no valid share from `dealer`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Complaint {
+    pub complainer: u32,
+    pub dealer: u32,
+}
+
+/// State of a single party, with index in `1..=parties`.
+///
+/// Each party acts as a dealer of Feldman's verifiable secret sharing (see
+/// [`threshold`](super::threshold)) for a random secret of its own, and the joint decryption key
+/// is the sum of the secrets of all qualified dealers. The protocol runs in three rounds:
+///
+/// 1. Every party broadcasts the [`Commitments`] to its polynomial and privately sends the
+///    [`SecretShare`] `f_i(j)` to each party `j`.
+/// 2. Every party checks the received shares against the commitments and broadcasts a
+///    [`Complaint`] against each dealer whose share is invalid or missing.
+/// 3. Accused dealers broadcast the disputed share. A dealer that does not answer a complaint
+///    with a valid share is disqualified by everyone.
+pub struct Participant<C: CurveGroup> {
+    index: u32,
+    threshold: usize,
+    parties: usize,
+    coefficients: Vec<C::ScalarField>,
+    commitments: BTreeMap<u32, VerificationKey<C>>,
+    shares: BTreeMap<u32, C::ScalarField>,
+    disqualified: BTreeSet<u32>,
+}
+
+impl<C: CurveGroup> Participant<C> {
+    /// Draws the polynomial of party `index` for a `threshold`-out-of-`parties` sharing.
+    pub fn new<R: Rng + CryptoRng>(
+        index: u32,
+        threshold: usize,
+        parties: usize,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        if threshold == 0 || threshold > parties || u32::try_from(parties).is_err() {
+            return Err(Error::InvalidThreshold { threshold, parties });
+        }
+        if index == 0 || index as usize > parties {
+            return Err(Error::InvalidIndex(index));
+        }
+        let coefficients: Vec<C::ScalarField> =
+            (0..threshold).map(|_| C::ScalarField::rand(rng)).collect();
+        let commitments: Vec<C> = coefficients
+            .iter()
+            .map(|a| C::Affine::generator() * a)
+            .collect();
+        let mut participant = Self {
+            index,
+            threshold,
+            parties,
+            coefficients,
+            commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            disqualified: BTreeSet::new(),
+        };
+        let own_commitments = VerificationKey {
+            commitments: C::normalize_batch(&commitments),
+        };
+        participant.commitments.insert(index, own_commitments);
+        participant
+            .shares
+            .insert(index, participant.evaluate(index));
+        Ok(participant)
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The commitments to broadcast in the first round.
+    pub fn commitments(&self) -> Commitments<C> {
+        Commitments {
+            dealer: self.index,
+            commitments: self.commitments[&self.index].commitments.clone(),
+        }
+    }
+
+    /// The share to send privately to party `recipient` in the first round.
+    pub fn share_for(&self, recipient: u32) -> Result<SecretShare<C>, Error> {
+        self.check_index(recipient)?;
+        Ok(SecretShare {
+            dealer: self.index,
+            recipient,
+            value: self.evaluate(recipient),
+        })
+    }
+
+    /// Records the broadcast commitments of another dealer. A dealer whose commitments are
+    /// malformed is disqualified.
+    pub fn receive_commitments(&mut self, commitments: &Commitments<C>) -> Result<(), Error> {
+        let dealer = commitments.dealer;
+        self.check_index(dealer)?;
+        if dealer == self.index {
+            return Ok(());
+        }
+        if commitments.commitments.len() != self.threshold || self.commitments.contains_key(&dealer)
+        {
+            self.disqualified.insert(dealer);
+            return Err(Error::InvalidCommitments(dealer));
+        }
+        let verification_key = VerificationKey {
+            commitments: commitments.commitments.clone(),
+        };
+        self.commitments.insert(dealer, verification_key);
+        Ok(())
+    }
+
+    /// Checks a share received from its dealer against the dealer's commitments and keeps it if
+    /// valid. Invalid shares are rejected and lead to a complaint.
+    pub fn receive_share(&mut self, share: &SecretShare<C>) -> Result<(), Error> {
+        if share.recipient != self.index || share.dealer == self.index {
+            return Err(Error::InvalidIndex(share.dealer));
+        }
+        if !self.is_valid(share) {
+            return Err(Error::InvalidShare(share.dealer));
+        }
+        self.shares.insert(share.dealer, share.value);
+        Ok(())
+    }
+
+    /// Complaints to broadcast in the second round, against every dealer that committed but
+    /// did not deliver a valid share.
+    pub fn complaints(&self) -> Vec<Complaint> {
+        self.commitments
+            .keys()
+            .filter(|dealer| !self.shares.contains_key(dealer))
+            .map(|&dealer| Complaint {
+                complainer: self.index,
+                dealer,
+            })
+            .collect()
+    }
+
+    /// The share to broadcast in the third round if the complaint is against this party.
+    pub fn answer(&self, complaint: &Complaint) -> Option<SecretShare<C>> {
+        if complaint.dealer != self.index {
+            return None;
+        }
+        self.share_for(complaint.complainer).ok()
+    }
+
+    /// Resolves a complaint with the dealer's answer, if any. The dealer is disqualified unless
+    /// the revealed share is valid, which the complainer then adopts.
+    pub fn resolve(&mut self, complaint: &Complaint, answer: Option<&SecretShare<C>>) {
+        if self.check_index(complaint.complainer).is_err() {
+            return;
+        }
+        match answer {
+            Some(share)
+                if share.dealer == complaint.dealer
+                    && share.recipient == complaint.complainer
+                    && self.is_valid(share) =>
+            {
+                if share.recipient == self.index {
+                    self.shares.insert(share.dealer, share.value);
+                }
+            }
+            _ => {
+                self.disqualified.insert(complaint.dealer);
+            }
+        }
+    }
+
+    /// The dealers that committed and answered all complaints.
+    pub fn qualified(&self) -> Vec<u32> {
+        self.commitments
+            .keys()
+            .filter(|dealer| !self.disqualified.contains(dealer))
+            .copied()
+            .collect()
+    }
+
+    /// Combines the contributions of the qualified dealers into the joint verification key and
+    /// the key share of this party.
+    ///
+    /// At least `threshold` dealers must qualify, such that the joint key contains the secret of
+    /// an honest party as long as fewer than `threshold` parties are corrupted. As in the original
+    /// protocol, a rushing adversary can bias the distribution of the public key, which does not
+    /// affect the security of Elgamal encryption.
+    pub fn finish(self) -> Result<(VerificationKey<C>, KeyShare<C>), Error> {
+        let qualified = self.qualified();
+        if qualified.len() < self.threshold {
+            return Err(Error::TooFewQualified {
+                qualified: qualified.len(),
+                threshold: self.threshold,
+            });
+        }
+        let mut secret = C::ScalarField::zero();
+        let mut commitments = vec![C::zero(); self.threshold];
+        for dealer in &qualified {
+            let share = self
+                .shares
+                .get(dealer)
+                .ok_or(Error::MissingShare(*dealer))?;
+            secret += share;
+            for (sum, commitment) in commitments
+                .iter_mut()
+                .zip(&self.commitments[dealer].commitments)
+            {
+                *sum += commitment;
+            }
+        }
+        let verification_key = VerificationKey {
+            commitments: C::normalize_batch(&commitments),
+        };
+        let key_share = KeyShare::new(self.index, secret);
+        secret.zeroize();
+        Ok((verification_key, key_share))
+    }
+
+    fn check_index(&self, index: u32) -> Result<(), Error> {
+        if index == 0 || index as usize > self.parties {
+            return Err(Error::InvalidIndex(index));
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, index: u32) -> C::ScalarField {
+        let x = C::ScalarField::from(index);
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(C::ScalarField::zero(), |acc, a| acc * x + a)
+    }
+
+    fn is_valid(&self, share: &SecretShare<C>) -> bool {
+        match self.commitments.get(&share.dealer) {
+            Some(commitments) => {
+                C::Affine::generator() * share.value
+                    == commitments.share_public_key(share.recipient)
+            }
+            None => false,
+        }
+    }
+}
+
+impl<C: CurveGroup> Drop for Participant<C> {
+    fn drop(&mut self) {
+        self.coefficients.zeroize();
+        self.shares.values_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::threshold::{combine, DecryptionShare};
+    use crate::encrypt::elgamal::{BabyStepGiantStep, DiscreteLog, ExponentialElgamal};
+    use crate::encrypt::EncryptionEngine;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Elgamal = ExponentialElgamal<G1>;
+
+    #[test]
+    fn distributed_key_generation() {
+        let rng = &mut test_rng();
+        assert_eq!(
+            Participant::<G1>::new(1, 4, 3, rng).err(),
+            Some(Error::InvalidThreshold {
+                threshold: 4,
+                parties: 3
+            })
+        );
+        assert_eq!(
+            Participant::<G1>::new(4, 2, 3, rng).err(),
+            Some(Error::InvalidIndex(4))
+        );
+
+        let (threshold, parties) = (3, 5);
+        let mut participants: Vec<Participant<G1>> = (1..=parties as u32)
+            .map(|index| Participant::new(index, threshold, parties, rng).unwrap())
+            .collect();
+
+        // round 1: dealer 2 sends a bad share to party 1 and dealer 5 one to party 3
+        let broadcasts: Vec<Commitments<G1>> =
+            participants.iter().map(Participant::commitments).collect();
+        let mut shares = Vec::new();
+        for dealer in &participants {
+            for recipient in 1..=parties as u32 {
+                if recipient != dealer.index() {
+                    shares.push(dealer.share_for(recipient).unwrap());
+                }
+            }
+        }
+        shares
+            .iter_mut()
+            .filter(|s| (s.dealer, s.recipient) == (2, 1) || (s.dealer, s.recipient) == (5, 3))
+            .for_each(|s| s.value += Scalar::from(1u32));
+        for participant in participants.iter_mut() {
+            for commitments in &broadcasts {
+                participant.receive_commitments(commitments).unwrap();
+            }
+            let index = participant.index();
+            for share in shares.iter().filter(|s| s.recipient == index) {
+                let result = participant.receive_share(share);
+                if [(2, 1), (5, 3)].contains(&(share.dealer, share.recipient)) {
+                    assert_eq!(result, Err(Error::InvalidShare(share.dealer)));
+                } else {
+                    assert_eq!(result, Ok(()));
+                }
+            }
+        }
+
+        // round 2
+        let complaints: Vec<Complaint> = participants
+            .iter()
+            .flat_map(Participant::complaints)
+            .collect();
+        assert_eq!(
+            complaints,
+            vec![
+                Complaint {
+                    complainer: 1,
+                    dealer: 2
+                },
+                Complaint {
+                    complainer: 3,
+                    dealer: 5
+                }
+            ]
+        );
+
+        // round 3: dealer 2 reveals the share, while dealer 5 stays silent
+        let answers: Vec<Option<SecretShare<G1>>> = complaints
+            .iter()
+            .map(|complaint| match complaint.dealer {
+                5 => None,
+                dealer => participants[dealer as usize - 1].answer(complaint),
+            })
+            .collect();
+        for participant in participants.iter_mut() {
+            for (complaint, answer) in complaints.iter().zip(&answers) {
+                participant.resolve(complaint, answer.as_ref());
+            }
+            assert_eq!(participant.qualified(), vec![1, 2, 3, 4]);
+        }
+        let results: Vec<(VerificationKey<G1>, KeyShare<G1>)> = participants
+            .into_iter()
+            .map(|participant| participant.finish().unwrap())
+            .collect();
+        let verification_key = results[0].0.clone();
+        assert!(results
+            .iter()
+            .all(|(vk, key_share)| *vk == verification_key
+                && verification_key.verify_share(key_share)));
+
+        // threshold decryption with the joint key
+        let cipher = Elgamal::encrypt(&Scalar::from(31337u32), &verification_key.public_key(), rng);
+        let shares: Vec<DecryptionShare<G1, TestHash>> = [4, 0, 2]
+            .into_iter()
+            .map(|i| results[i].1.decrypt(&cipher, rng))
+            .collect();
+        let point = combine(&cipher, &shares, &verification_key).unwrap();
+        let solver = BabyStepGiantStep::<G1>::new(10);
+        assert_eq!(solver.solve(point, 20), Some(31337));
+
+        // too many disqualified dealers
+        let mut participant = Participant::<G1>::new(1, 2, 3, rng).unwrap();
+        for dealer in [2, 3] {
+            participant.resolve(
+                &Complaint {
+                    complainer: 1,
+                    dealer,
+                },
+                None,
+            );
+        }
+        assert_eq!(participant.qualified(), vec![1]);
+        assert_eq!(
+            participant.finish().err(),
+            Some(Error::TooFewQualified {
+                qualified: 1,
+                threshold: 2
+            })
+        );
+    }
+}
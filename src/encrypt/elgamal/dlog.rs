@@ -0,0 +1,708 @@
+//! Discrete logarithms of small exponents, which are needed for decrypting exponential ElGamal
+//! ciphers. [`BabyStepGiantStep`] and [`DlogTable`] trade memory for speed, while [`BruteForce`],
+//! [`Kangaroo`] and [`KangarooSearch`] work without a table.
+use super::GeneratorTable;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(not(feature = "std"))]
+use ark_std::collections::BTreeMap as HashMap;
+#[cfg(feature = "std")]
+use ark_std::collections::HashMap;
+use ark_std::io::{Read, Write};
+use ark_std::marker::PhantomData;
+use ark_std::ops::ControlFlow;
+use sha3::{Digest, Sha3_256};
+use thiserror::Error as ErrorT;
+
+const MAGIC: &[u8; 8] = b"FDE-DLOG";
+const VERSION: u32 = 1;
+/// Size of the header of a [`DlogTable`] in bytes.
+pub const HEADER_SIZE: usize = 8 + 4 + 32 + 4 + 8;
+const ENTRY_SIZE: usize = 8 + 4;
+/// Number of points that are normalized with a single field inversion.
+const BATCH_SIZE: usize = 256;
+/// Number of exponents a [`BruteForce`] task tries sequentially.
+const BRUTE_FORCE_CHUNK: u64 = 1 << 16;
+/// Number of kangaroo walks with different starting points before [`Kangaroo`] gives up.
+const KANGAROO_RETRIES: u64 = 16;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("not a discrete logarithm table")]
+    InvalidMagic,
+    #[error("unsupported table version {0}")]
+    UnsupportedVersion(u32),
+    #[error("table was generated for another curve")]
+    CurveMismatch,
+    #[error("expected {expected} bytes, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+    #[error("io error: {0}")]
+    Io(String),
+    #[error("invalid interval of width {width} starting at {lower}")]
+    InvalidInterval { lower: u64, width: u64 },
+}
+
+impl From<ark_std::io::Error> for Error {
+    fn from(error: ark_std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+/// Solvers of the discrete logarithm of points with small exponents.
+pub trait DiscreteLog<C: CurveGroup> {
+    /// Returns the exponent `x < 2^bits` with `x * g = point`, or `None` if there is no such
+    /// exponent. Panics if `bits` exceeds 63.
+    fn solve(&self, point: C::Affine, bits: u32) -> Option<u64>;
+}
+
+/// Baby steps `j * g` for `j < 2^t` in a hash map keyed by a 64-bit fingerprint of the point.
+///
+/// The exponent `x = i * 2^t + j` of `x * g` is found by subtracting giant steps `2^t * g` until a
+/// baby step is hit, thus exponents below `2^b` need at most `2^(b - t)` giant steps. Larger tables
+/// make every lookup faster at the cost of memory and a longer precomputation, which pays off if
+/// the solver is reused via
+/// [`ExponentialElgamal::decrypt_with`](super::ExponentialElgamal::decrypt_with).
+pub struct BabyStepGiantStep<C: CurveGroup> {
+    table_bits: u32,
+    // fingerprints of the baby steps `j * g` mapped to `j`
+    baby_steps: HashMap<u64, u32>,
+    _curve: PhantomData<C>,
+}
+
+impl<C: CurveGroup> BabyStepGiantStep<C> {
+    /// Precomputes the `2^table_bits` baby steps. Panics if `table_bits` exceeds 32.
+    pub fn new(table_bits: u32) -> Self {
+        let mut baby_steps = HashMap::new();
+        #[cfg(feature = "std")]
+        baby_steps.reserve(1 << table_bits.min(24));
+        for_each_baby_step::<C, _>(table_bits, |fingerprint, j| {
+            // keeps the smaller exponent in the unlikely case of a fingerprint collision
+            baby_steps.entry(fingerprint).or_insert(j);
+        });
+        Self {
+            table_bits,
+            baby_steps,
+            _curve: PhantomData,
+        }
+    }
+
+    pub fn table_bits(&self) -> u32 {
+        self.table_bits
+    }
+}
+
+impl<C: CurveGroup> DiscreteLog<C> for BabyStepGiantStep<C> {
+    fn solve(&self, point: C::Affine, bits: u32) -> Option<u64> {
+        giant_steps::<C, _>(point, bits, self.table_bits, |fingerprint| {
+            self.baby_steps.get(&fingerprint).copied()
+        })
+    }
+}
+
+/// The baby steps of [`BabyStepGiantStep`] as a sorted array, which is searched in place, so that
+/// large tables (e.g. `2^24` baby steps in 192 MiB) are built once and loaded without parsing.
+///
+/// The serialized table consists of
+/// - the magic `FDE-DLOG` and a little-endian `u32` format version,
+/// - a 32 byte curve identifier, i.e. the SHA3-256 hash of the uncompressed generator,
+/// - the table size `t` as a little-endian `u32` and the number of entries as a little-endian
+///   `u64`,
+/// - the entries sorted by fingerprint, each a little-endian `u64` fingerprint followed by the
+///   little-endian `u32` exponent.
+///
+/// Since [`Self::from_bytes`] accepts anything implementing `AsRef<[u8]>`, a memory-mapped file
+/// can be passed to it directly.
+pub struct DlogTable<C: CurveGroup, B = Vec<u8>> {
+    bytes: B,
+    table_bits: u32,
+    len: usize,
+    _curve: PhantomData<C>,
+}
+
+impl<C: CurveGroup> DlogTable<C> {
+    /// Precomputes the `2^table_bits` baby steps. Panics if `table_bits` exceeds 32.
+    pub fn build(table_bits: u32) -> Self {
+        let mut entries = Vec::with_capacity(1 << table_bits.min(24));
+        for_each_baby_step::<C, _>(table_bits, |fingerprint, j| entries.push((fingerprint, j)));
+        entries.sort_unstable();
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + entries.len() * ENTRY_SIZE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&curve_id::<C>());
+        bytes.extend_from_slice(&table_bits.to_le_bytes());
+        bytes.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+        for (fingerprint, j) in &entries {
+            bytes.extend_from_slice(&fingerprint.to_le_bytes());
+            bytes.extend_from_slice(&j.to_le_bytes());
+        }
+        Self {
+            bytes,
+            table_bits,
+            len: entries.len(),
+            _curve: PhantomData,
+        }
+    }
+
+    /// Reads a table written by [`Self::write_to`] into memory.
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, Error> {
+        let mut bytes = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let read = reader.read(&mut buf)?;
+            if read == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&buf[..read]);
+        }
+        Self::from_bytes(bytes)
+    }
+}
+
+impl<C: CurveGroup, B: AsRef<[u8]>> DlogTable<C, B> {
+    /// Checks the header and the length of a serialized table without copying it.
+    pub fn from_bytes(bytes: B) -> Result<Self, Error> {
+        let data = bytes.as_ref();
+        if data.len() < HEADER_SIZE {
+            return Err(Error::InvalidLength {
+                expected: HEADER_SIZE,
+                actual: data.len(),
+            });
+        }
+        if &data[..8] != MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+        let version = u32::from_le_bytes(data[8..12].try_into().expect("4 bytes"));
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        if data[12..44] != curve_id::<C>() {
+            return Err(Error::CurveMismatch);
+        }
+        let table_bits = u32::from_le_bytes(data[44..48].try_into().expect("4 bytes"));
+        let len = u64::from_le_bytes(data[48..56].try_into().expect("8 bytes"));
+        // tables have at most 2^32 entries, thus this doesn't overflow
+        let expected = if table_bits <= 32 && len == 1 << table_bits {
+            HEADER_SIZE as u64 + len * ENTRY_SIZE as u64
+        } else {
+            u64::MAX
+        };
+        if data.len() as u64 != expected {
+            return Err(Error::InvalidLength {
+                expected: usize::try_from(expected).unwrap_or(usize::MAX),
+                actual: data.len(),
+            });
+        }
+        Ok(Self {
+            bytes,
+            table_bits,
+            len: len as usize,
+            _curve: PhantomData,
+        })
+    }
+
+    pub fn table_bits(&self) -> u32 {
+        self.table_bits
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        writer.write_all(self.as_bytes())?;
+        Ok(())
+    }
+
+    fn entry(&self, index: usize) -> (u64, u32) {
+        let offset = HEADER_SIZE + index * ENTRY_SIZE;
+        let entry = &self.as_bytes()[offset..offset + ENTRY_SIZE];
+        (
+            u64::from_le_bytes(entry[..8].try_into().expect("8 bytes")),
+            u32::from_le_bytes(entry[8..].try_into().expect("4 bytes")),
+        )
+    }
+
+    // binary search for the first entry with the given fingerprint
+    fn lookup(&self, fingerprint: u64) -> Option<u32> {
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.entry(mid).0 < fingerprint {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        (low < self.len)
+            .then(|| self.entry(low))
+            .filter(|entry| entry.0 == fingerprint)
+            .map(|entry| entry.1)
+    }
+}
+
+impl<C: CurveGroup, B: AsRef<[u8]>> DiscreteLog<C> for DlogTable<C, B> {
+    fn solve(&self, point: C::Affine, bits: u32) -> Option<u64> {
+        giant_steps::<C, _>(point, bits, self.table_bits, |fingerprint| {
+            self.lookup(fingerprint)
+        })
+    }
+}
+
+/// Exhaustive search, which needs `2^bits` additions in the worst case but no precomputation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BruteForce;
+
+impl<C: CurveGroup> DiscreteLog<C> for BruteForce {
+    fn solve(&self, point: C::Affine, bits: u32) -> Option<u64> {
+        assert!(bits < 64, "exponents have to fit into 64 bits");
+        let bound = 1u64 << bits;
+        let target = point.into_group();
+        // stepping through the exponents in projective form avoids an inversion per candidate
+        let search = |chunk: u64| {
+            let start = chunk * BRUTE_FORCE_CHUNK;
+            let end = bound.min(start + BRUTE_FORCE_CHUNK);
+            let mut candidate = GeneratorTable::<C>::get().mul(&C::ScalarField::from(start));
+            for exponent in start..end {
+                if candidate == target {
+                    return Some(exponent);
+                }
+                candidate += C::generator();
+            }
+            None
+        };
+        let num_chunks = bound.div_ceil(BRUTE_FORCE_CHUNK);
+        #[cfg(not(feature = "parallel"))]
+        return (0..num_chunks).find_map(search);
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            (0..num_chunks).into_par_iter().find_map_first(search)
+        }
+    }
+}
+
+/// Pollard's kangaroo method with jumps of `2^i * g` for `i < k`, where `k` is chosen such that
+/// the mean jump is about `2^(bits/2 - 1)`.
+///
+/// A tame kangaroo starts at the upper end of the range and sets a trap after `2^(bits/2 + 1)`
+/// jumps. A wild kangaroo starts at `point` and jumps until it falls into the trap or passes it,
+/// which takes at most about `2^(bits/2 + 2)` jumps. If it passes the trap, the walk is repeated
+/// from `point + z * g` for the next `z`, thus the solver is deterministic but may return `None`
+/// for exponents in range with a negligible probability.
+///
+/// The walk needs `O(2^(bits/2))` group operations like a balanced table but only constant memory.
+/// It is sequential and normalizes every point, so it is slower than a table of the same range but
+/// scales to larger ranges.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Kangaroo;
+
+impl<C: CurveGroup> DiscreteLog<C> for Kangaroo {
+    fn solve(&self, point: C::Affine, bits: u32) -> Option<u64> {
+        assert!(bits < 60, "kangaroo exponents have to fit into 60 bits");
+        let mut search = KangarooSearch::<C>::new(point, 0, 1 << bits).expect("valid interval");
+        match search.run(u64::MAX, |_| ControlFlow::Continue(())) {
+            SearchStatus::Found(exponent) => Some(exponent),
+            _ => None,
+        }
+    }
+}
+
+/// Progress of a [`KangarooSearch`], reported to the callback of [`KangarooSearch::run`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of jumps so far, including previous runs.
+    pub jumps: u64,
+    /// Rough estimate of the number of jumps until the exponent is found.
+    pub expected_jumps: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchStatus {
+    Found(u64),
+    /// The interval doesn't contain the exponent, or the search failed with negligible
+    /// probability.
+    NotFound,
+    /// The callback stopped the search, which continues from the same state on the next
+    /// [`KangarooSearch::run`].
+    Interrupted,
+}
+
+/// State of an interrupted [`KangarooSearch`], which can be serialized to resume the search
+/// after a restart.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Checkpoint<C: CurveGroup> {
+    pub point: C::Affine,
+    pub lower: u64,
+    pub width: u64,
+    pub trap: C::Affine,
+    pub trap_distance: u64,
+    pub tame_jumps: u64,
+    pub wild: C::Affine,
+    pub wild_distance: u64,
+    pub shift: u64,
+    pub jumps: u64,
+}
+
+/// The walk of [`Kangaroo`] for an exponent in `[lower, lower + width)`, which can be monitored,
+/// interrupted and resumed from a [`Checkpoint`]. The walk is deterministic, thus a resumed
+/// search performs the same jumps as an uninterrupted one.
+pub struct KangarooSearch<C: CurveGroup> {
+    jumps: Vec<C::Affine>,
+    tame_jumps: u64,
+    state: Checkpoint<C>,
+}
+
+impl<C: CurveGroup> KangarooSearch<C> {
+    /// Prepares the search for the exponent of `point` in `[lower, lower + width)`, fails if the
+    /// interval is empty, wider than `2^60` or exceeds `u64::MAX`.
+    pub fn new(point: C::Affine, lower: u64, width: u64) -> Result<Self, Error> {
+        Self::check_interval(lower, width)?;
+        let generator = GeneratorTable::<C>::get();
+        // the trap is set beyond every starting point of the wild kangaroo
+        let tame_start = width - 1 + KANGAROO_RETRIES;
+        let state = Checkpoint {
+            point,
+            lower,
+            width,
+            trap: generator
+                .mul(&C::ScalarField::from(tame_start))
+                .into_affine(),
+            trap_distance: 0,
+            tame_jumps: 0,
+            wild: Self::wild_start(&point, lower, 0),
+            wild_distance: 0,
+            shift: 0,
+            jumps: 0,
+        };
+        Self::resume(state)
+    }
+
+    /// Continues the search from `checkpoint`.
+    pub fn resume(checkpoint: Checkpoint<C>) -> Result<Self, Error> {
+        Self::check_interval(checkpoint.lower, checkpoint.width)?;
+        let mean = ((checkpoint.width as f64).sqrt() / 2.0).max(1.0);
+        let num_jumps = (1..64u32)
+            .find(|&k| ((1u64 << k) as f64) / f64::from(k) >= mean)
+            .expect("the mean jump is below 2^30");
+        let generator = GeneratorTable::<C>::get();
+        let jumps = C::normalize_batch(
+            &(0..num_jumps)
+                .map(|i| generator.mul(&C::ScalarField::from(1u64 << i)))
+                .collect::<Vec<_>>(),
+        );
+        Ok(Self {
+            jumps,
+            tame_jumps: (4.0 * mean) as u64 + 1,
+            state: checkpoint,
+        })
+    }
+
+    pub fn checkpoint(&self) -> &Checkpoint<C> {
+        &self.state
+    }
+
+    pub fn progress(&self) -> Progress {
+        Progress {
+            jumps: self.state.jumps,
+            // the tame kangaroo travels about `width`, the wild one about `1.5 * width`
+            expected_jumps: self.tame_jumps * 5 / 2,
+        }
+    }
+
+    /// Jumps until the exponent is found or the search fails, calling `callback` every
+    /// `report_interval` jumps. The search is interrupted if the callback breaks.
+    pub fn run<F>(&mut self, report_interval: u64, mut callback: F) -> SearchStatus
+    where
+        F: FnMut(Progress) -> ControlFlow<()>,
+    {
+        let report_interval = report_interval.max(1);
+        loop {
+            let jumps = self.state.jumps;
+            if let Some(status) = self.step() {
+                return status;
+            }
+            // restarts of the wild kangaroo don't count as jumps
+            let jumped = self.state.jumps != jumps;
+            if jumped
+                && self.state.jumps.is_multiple_of(report_interval)
+                && callback(self.progress()).is_break()
+            {
+                return SearchStatus::Interrupted;
+            }
+        }
+    }
+
+    // moves the tame or the wild kangaroo, returns the result once the search ends
+    fn step(&mut self) -> Option<SearchStatus> {
+        let state = &mut self.state;
+        if state.tame_jumps < self.tame_jumps {
+            Self::jump(&self.jumps, &mut state.trap, &mut state.trap_distance);
+            state.tame_jumps += 1;
+        } else {
+            let trap_exponent = state.width - 1 + KANGAROO_RETRIES + state.trap_distance;
+            if state.wild_distance > trap_exponent {
+                state.shift += 1;
+                if state.shift == KANGAROO_RETRIES {
+                    return Some(SearchStatus::NotFound);
+                }
+                state.wild = Self::wild_start(&state.point, state.lower, state.shift);
+                state.wild_distance = 0;
+                return None;
+            }
+            if state.wild == state.trap {
+                // point - lower * g + shift * g + wild_distance * g = trap_exponent * g
+                let status = (trap_exponent - state.wild_distance)
+                    .checked_sub(state.shift)
+                    .filter(|&exponent| exponent < state.width)
+                    .map_or(SearchStatus::NotFound, |exponent| {
+                        SearchStatus::Found(state.lower + exponent)
+                    });
+                return Some(status);
+            }
+            Self::jump(&self.jumps, &mut state.wild, &mut state.wild_distance);
+        }
+        state.jumps += 1;
+        None
+    }
+
+    fn jump(jumps: &[C::Affine], position: &mut C::Affine, distance: &mut u64) {
+        let i = (fingerprint(position) % jumps.len() as u64) as usize;
+        *position = (*position + jumps[i]).into_affine();
+        *distance += 1 << i;
+    }
+
+    fn wild_start(point: &C::Affine, lower: u64, shift: u64) -> C::Affine {
+        let offset = C::ScalarField::from(shift) - C::ScalarField::from(lower);
+        (*point + GeneratorTable::<C>::get().mul(&offset)).into_affine()
+    }
+
+    fn check_interval(lower: u64, width: u64) -> Result<(), Error> {
+        if width == 0 || width > 1 << 60 || lower.checked_add(width - 1).is_none() {
+            return Err(Error::InvalidInterval { lower, width });
+        }
+        Ok(())
+    }
+}
+
+/// Identifier of the group `C`, the hash of the uncompressed generator.
+pub fn curve_id<C: CurveGroup>() -> [u8; 32] {
+    let mut bytes = Vec::new();
+    C::generator()
+        .into_affine()
+        .serialize_uncompressed(&mut bytes)
+        .expect("serializing into a vector doesn't fail");
+    Sha3_256::digest(&bytes).into()
+}
+
+// calls `f` with the fingerprint of `j * g` and `j` for every `j < 2^table_bits`
+fn for_each_baby_step<C: CurveGroup, F: FnMut(u64, u32)>(table_bits: u32, mut f: F) {
+    assert!(table_bits <= 32, "at most 2^32 baby steps are supported");
+    let table_size = 1u64 << table_bits;
+    let generator = C::generator();
+    let mut point = C::zero();
+    let mut j = 0;
+    while j < table_size {
+        let batch = (table_size - j).min(BATCH_SIZE as u64);
+        let points: Vec<C> = (0..batch)
+            .map(|_| {
+                let current = point;
+                point += generator;
+                current
+            })
+            .collect();
+        for (k, point) in (j..).zip(C::normalize_batch(&points)) {
+            f(fingerprint(&point), k as u32);
+        }
+        j += batch;
+    }
+}
+
+// subtracts giant steps from `point` until `baby_step` returns the exponent of a candidate
+fn giant_steps<C, F>(point: C::Affine, bits: u32, table_bits: u32, baby_step: F) -> Option<u64>
+where
+    C: CurveGroup,
+    F: Fn(u64) -> Option<u32>,
+{
+    assert!(bits < 64, "exponents have to fit into 64 bits");
+    let table_size = 1u64 << table_bits;
+    let bound = 1u64 << bits;
+    let num_giant_steps = bound.div_ceil(table_size);
+    let generator = GeneratorTable::<C>::get();
+    let giant_step = -generator.mul(&C::ScalarField::from(table_size));
+    let mut current = point.into_group();
+    let mut i = 0;
+    while i < num_giant_steps {
+        let batch = (num_giant_steps - i).min(BATCH_SIZE as u64);
+        let candidates: Vec<C> = (0..batch)
+            .map(|_| {
+                let candidate = current;
+                current += giant_step;
+                candidate
+            })
+            .collect();
+        for (k, candidate) in (i..).zip(C::normalize_batch(&candidates)) {
+            let Some(j) = baby_step(fingerprint(&candidate)) else {
+                continue;
+            };
+            let exponent = k * table_size + u64::from(j);
+            // fingerprints may collide, thus hits are checked
+            if exponent < bound
+                && generator.mul(&C::ScalarField::from(exponent)) == point.into_group()
+            {
+                return Some(exponent);
+            }
+        }
+        i += batch;
+    }
+    None
+}
+
+// xor of the 8-byte words of the compressed encoding, whose x-coordinate is close to uniform
+fn fingerprint<P: CanonicalSerialize>(point: &P) -> u64 {
+    let mut bytes = Vec::with_capacity(point.compressed_size());
+    point
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a vector doesn't fail");
+    bytes.chunks(8).fold(0, |acc, chunk| {
+        let mut word = [0u8; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        acc ^ u64::from_le_bytes(word)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{G1Affine, Scalar, TestCurve};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+
+    fn check_solver<D: DiscreteLog<G1>>(solver: &D) {
+        for exponent in [0u64, 1, 1023, 1024, 1025, 54321, (1 << 18) - 1] {
+            let point = (G1Affine::generator() * Scalar::from(exponent)).into_affine();
+            assert_eq!(solver.solve(point, 18), Some(exponent));
+        }
+        // exponents out of range
+        let point = (G1Affine::generator() * Scalar::from(1u64 << 18)).into_affine();
+        assert_eq!(solver.solve(point, 18), None);
+        assert_eq!(solver.solve(point, 19), Some(1 << 18));
+        // tables larger than the range
+        assert_eq!(solver.solve(G1Affine::generator(), 4), Some(1));
+    }
+
+    #[test]
+    fn brute_force_and_kangaroo() {
+        check_solver(&BruteForce);
+        check_solver(&Kangaroo);
+        let point = (G1Affine::generator() * Scalar::from(123456789u64)).into_affine();
+        let kangaroo: &dyn DiscreteLog<G1> = &Kangaroo;
+        assert_eq!(kangaroo.solve(point, 28), Some(123456789));
+        assert_eq!(kangaroo.solve(point, 20), None);
+    }
+
+    #[test]
+    fn resumable_kangaroo() {
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+        let lower = 1_000_000_000u64;
+        let exponent = lower + 12_345_678;
+        let point = (G1Affine::generator() * Scalar::from(exponent)).into_affine();
+        let mut search = KangarooSearch::<G1>::new(point, lower, 1 << 24).unwrap();
+        let mut reports = Vec::new();
+        let status = search.run(1000, |progress| {
+            reports.push(progress.jumps);
+            if reports.len() == 3 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(status, SearchStatus::Interrupted);
+        assert_eq!(reports, [1000, 2000, 3000]);
+        assert_eq!(search.progress().jumps, 3000);
+
+        let mut bytes = Vec::new();
+        search
+            .checkpoint()
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+        let checkpoint = Checkpoint::<G1>::deserialize_compressed(&bytes[..]).unwrap();
+        let mut resumed = KangarooSearch::resume(checkpoint).unwrap();
+        let mut jumps = 0;
+        assert_eq!(
+            resumed.run(1, |progress| {
+                jumps = progress.jumps;
+                ControlFlow::Continue(())
+            }),
+            SearchStatus::Found(exponent)
+        );
+        // resuming performs the same jumps as an uninterrupted search
+        assert_eq!(
+            search.run(u64::MAX, |_| ControlFlow::Continue(())),
+            SearchStatus::Found(exponent)
+        );
+        assert_eq!(search.progress().jumps, jumps);
+
+        let mut below = KangarooSearch::<G1>::new(point, exponent + 1, 1 << 10).unwrap();
+        assert_eq!(
+            below.run(u64::MAX, |_| ControlFlow::Continue(())),
+            SearchStatus::NotFound
+        );
+        for (lower, width) in [(0, 0), (0, (1 << 60) + 1), (u64::MAX, 2)] {
+            assert_eq!(
+                KangarooSearch::<G1>::new(point, lower, width).err(),
+                Some(Error::InvalidInterval { lower, width })
+            );
+        }
+    }
+
+    #[test]
+    fn baby_step_giant_step() {
+        let solver = BabyStepGiantStep::<G1>::new(10);
+        assert_eq!(solver.table_bits(), 10);
+        check_solver(&solver);
+    }
+
+    #[test]
+    fn persistent_table() {
+        let table = DlogTable::<G1>::build(10);
+        assert_eq!(table.as_bytes().len(), HEADER_SIZE + 1024 * ENTRY_SIZE);
+        check_solver(&table);
+
+        // loading from a borrowed slice, e.g. a memory-mapped file, and sharing across threads
+        let mut bytes = Vec::new();
+        table.write_to(&mut bytes).unwrap();
+        let loaded = DlogTable::<G1, &[u8]>::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.table_bits(), 10);
+        std::thread::scope(|scope| {
+            for exponent in [5u64, 50000] {
+                let loaded = &loaded;
+                scope.spawn(move || {
+                    let point = (G1Affine::generator() * Scalar::from(exponent)).into_affine();
+                    assert_eq!(loaded.solve(point, 20), Some(exponent));
+                });
+            }
+        });
+        let read = DlogTable::<G1>::read_from(&bytes[..]).unwrap();
+        assert_eq!(read.as_bytes(), table.as_bytes());
+
+        assert_eq!(
+            DlogTable::<G1, _>::from_bytes(&bytes[..bytes.len() - 1]).err(),
+            Some(Error::InvalidLength {
+                expected: bytes.len(),
+                actual: bytes.len() - 1
+            })
+        );
+        assert_eq!(
+            DlogTable::<ark_bn254::G1Projective, _>::from_bytes(&bytes[..]).err(),
+            Some(Error::CurveMismatch)
+        );
+        let mut invalid = bytes.clone();
+        invalid[0] ^= 1;
+        assert_eq!(
+            DlogTable::<G1, _>::from_bytes(&invalid).err(),
+            Some(Error::InvalidMagic)
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Escrow of an existing decryption key with a set of arbiters, who can reconstruct it if the
+//! seller fails to deliver.
+use super::threshold::{self, KeyShare, VerificationKey};
+use super::KeyPair;
+use ark_ec::CurveGroup;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::Zero;
+use zeroize::Zeroize;
+
+pub use threshold::Error;
+
+/// Shares the decryption key of `key_pair` among `parties` arbiters such that any `threshold` of
+/// them can reconstruct it. Arbiter `i` receives the share with index `i + 1`.
+///
+/// The key `x` is shared with Feldman's verifiable secret sharing as in [`threshold::deal`], but
+/// with `f(0) = x` instead of a fresh key, thus the constant term of the commitments is the
+/// encryption key of the seller.
+pub fn escrow<C: CurveGroup, R: Rng + CryptoRng>(
+    key_pair: &KeyPair<C>,
+    threshold: usize,
+    parties: usize,
+    rng: &mut R,
+) -> Result<(VerificationKey<C>, Vec<KeyShare<C>>), Error> {
+    threshold::share(*key_pair.sk(), threshold, parties, rng)
+}
+
+/// Checks that `share` is consistent with the commitments, and that these share the decryption
+/// key of `public_key`.
+pub fn audit<C: CurveGroup>(
+    share: &KeyShare<C>,
+    verification_key: &VerificationKey<C>,
+    public_key: &C::Affine,
+) -> bool {
+    verification_key.commitments.first() == Some(public_key) && verification_key.verify_share(share)
+}
+
+/// Verifies the shares and interpolates the decryption key from the first `threshold` of them.
+pub fn reconstruct<C: CurveGroup>(
+    shares: &[KeyShare<C>],
+    verification_key: &VerificationKey<C>,
+) -> Result<KeyPair<C>, Error> {
+    let threshold = verification_key.threshold();
+    if shares.len() < threshold {
+        return Err(Error::InsufficientShares {
+            required: threshold,
+            available: shares.len(),
+        });
+    }
+    let shares = &shares[..threshold];
+    for (i, share) in shares.iter().enumerate() {
+        if share.index == 0 {
+            return Err(Error::InvalidIndex(share.index));
+        }
+        if shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(Error::DuplicateShare(share.index));
+        }
+        if !verification_key.verify_share(share) {
+            return Err(Error::InvalidShare(share.index));
+        }
+    }
+
+    let indices: Vec<u32> = shares.iter().map(|share| share.index).collect();
+    let mut secret = shares.iter().fold(C::ScalarField::zero(), |acc, share| {
+        acc + threshold::lagrange_coefficient::<C::ScalarField>(share.index, &indices)
+            * share.secret()
+    });
+    let key_pair = KeyPair::from_secret_key(secret);
+    secret.zeroize();
+    debug_assert_eq!(*key_pair.pk(), verification_key.public_key());
+    Ok(key_pair)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::TestCurve;
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+
+    #[test]
+    fn key_escrow() {
+        let rng = &mut test_rng();
+        let key_pair = KeyPair::<G1>::generate(rng);
+        assert_eq!(
+            escrow(&key_pair, 0, 3, rng).err(),
+            Some(Error::InvalidThreshold {
+                threshold: 0,
+                parties: 3
+            })
+        );
+
+        let (verification_key, shares) = escrow(&key_pair, 3, 5, rng).unwrap();
+        assert_eq!(verification_key.public_key(), *key_pair.pk());
+        assert!(shares
+            .iter()
+            .all(|share| audit(share, &verification_key, key_pair.pk())));
+        // commitments to another key
+        let other = KeyPair::<G1>::generate(rng);
+        assert!(!audit(&shares[0], &verification_key, other.pk()));
+        let (other_key, other_shares) = escrow(&other, 3, 5, rng).unwrap();
+        assert!(!audit(&other_shares[0], &verification_key, key_pair.pk()));
+
+        let reconstructed = reconstruct(&shares[2..], &verification_key).unwrap();
+        assert_eq!(reconstructed.sk(), key_pair.sk());
+        assert_eq!(
+            reconstruct(&shares[..2], &verification_key).err(),
+            Some(Error::InsufficientShares {
+                required: 3,
+                available: 2
+            })
+        );
+
+        // a share of another escrow
+        let mixed = vec![
+            KeyShare::new(shares[0].index, *shares[0].secret()),
+            KeyShare::new(other_shares[1].index, *other_shares[1].secret()),
+            KeyShare::new(shares[2].index, *shares[2].secret()),
+        ];
+        assert_eq!(
+            reconstruct(&mixed, &verification_key).err(),
+            Some(Error::InvalidShare(2))
+        );
+        assert_eq!(
+            reconstruct(&other_shares, &other_key).unwrap().sk(),
+            other.sk()
+        );
+        let duplicate = vec![
+            KeyShare::new(shares[0].index, *shares[0].secret()),
+            KeyShare::new(shares[0].index, *shares[0].secret()),
+            KeyShare::new(shares[2].index, *shares[2].secret()),
+        ];
+        assert_eq!(
+            reconstruct(&duplicate, &verification_key).err(),
+            Some(Error::DuplicateShare(1))
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Window tables of the generator shared by all operations on the same curve.
+use ark_ec::scalar_mul::fixed_base::FixedBase;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_std::sync::Arc;
+#[cfg(feature = "std")]
+use ark_std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+};
+#[cfg(feature = "std")]
+use std::sync::{OnceLock, RwLock};
+
+const WINDOW: usize = 8;
+
+#[cfg(feature = "std")]
+type Cache = RwLock<BTreeMap<TypeId, Arc<dyn Any + Send + Sync>>>;
+
+#[cfg(feature = "std")]
+static TABLES: OnceLock<Cache> = OnceLock::new();
+
+/// Holds `k * 2^(w * i) * g` for every window `i` and digit `k < 2^w`, thus a multiplication of
+/// the generator takes one mixed addition per window instead of a full double-and-add pass. With
+/// `w = 8`, a 255-bit scalar takes 32 additions and the table of BLS12-381 G1 occupies about
+/// 800 KiB.
+///
+/// Lookups depend on the digits of the scalar, so the table must not be used for long-term
+/// secrets such as decryption keys, see [`constant_time`](crate::encrypt::constant_time).
+pub struct GeneratorTable<C: CurveGroup> {
+    table: Vec<Vec<C::Affine>>,
+}
+
+impl<C: CurveGroup> GeneratorTable<C> {
+    /// Returns the table of `C`. With the `std` feature, the table is built on the first use of
+    /// the curve and shared by all later calls, otherwise every call builds a new table.
+    #[cfg(feature = "std")]
+    pub fn get() -> Arc<Self> {
+        let cache = TABLES.get_or_init(Default::default);
+        let cached = cache
+            .read()
+            .expect("table cache poisoned")
+            .get(&TypeId::of::<C>())
+            .cloned();
+        let table = match cached {
+            Some(table) => table,
+            None => cache
+                .write()
+                .expect("table cache poisoned")
+                .entry(TypeId::of::<C>())
+                .or_insert_with(|| Arc::new(Self::new()))
+                .clone(),
+        };
+        table.downcast().expect("keyed by the curve type")
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn get() -> Arc<Self> {
+        Arc::new(Self::new())
+    }
+
+    fn new() -> Self {
+        let table = FixedBase::get_window_table(
+            C::ScalarField::MODULUS_BIT_SIZE as usize,
+            WINDOW,
+            C::Affine::generator().into_group(),
+        );
+        Self { table }
+    }
+
+    /// Computes `scalar * g`.
+    pub fn mul(&self, scalar: &C::ScalarField) -> C {
+        FixedBase::windowed_mul::<C>(self.table.len(), WINDOW, &self.table, scalar)
+    }
+
+    /// Computes `scalar * g` for every scalar, in parallel with the `parallel` feature.
+    pub fn batch_mul(&self, scalars: &[C::ScalarField]) -> Vec<C> {
+        FixedBase::msm::<C>(
+            C::ScalarField::MODULUS_BIT_SIZE as usize,
+            WINDOW,
+            &self.table,
+            scalars,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve};
+    use ark_ec::pairing::Pairing;
+    use ark_std::{One, UniformRand, Zero};
+
+    type G1 = <TestCurve as Pairing>::G1;
+
+    #[test]
+    fn generator_table() {
+        let rng = &mut test_rng();
+        let table = GeneratorTable::<G1>::get();
+        #[cfg(feature = "std")]
+        assert!(Arc::ptr_eq(&table, &GeneratorTable::<G1>::get()));
+        let mut scalars: Vec<Scalar> = (0..8).map(|_| Scalar::rand(rng)).collect();
+        scalars.extend([Scalar::zero(), Scalar::one(), -Scalar::one()]);
+        let generator = <G1 as CurveGroup>::Affine::generator();
+        for (scalar, product) in scalars.iter().zip(table.batch_mul(&scalars)) {
+            assert_eq!(table.mul(scalar), generator * scalar);
+            assert_eq!(product, generator * scalar);
+        }
+
+        // tables are kept per curve
+        type Secp = ark_secp256k1::Projective;
+        let scalar = ark_secp256k1::Fr::rand(rng);
+        assert_eq!(
+            GeneratorTable::<Secp>::get().mul(&scalar),
+            ark_secp256k1::Affine::generator() * scalar
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Hashed Elgamal for arbitrary byte payloads, e.g. symmetric keys or small blobs.
+//!
+//! The shared secret `y * h` is expanded into a keystream and a MAC key, the payload is XOR-ed
+//! with the keystream and authenticated with the MAC (encrypt-then-MAC). Unlike the exponential
+//! variant, decryption needs no discrete logarithm, but the ciphertexts are not homomorphic.
+use crate::encrypt::constant_time;
+use crate::hash::{constant_time_eq, expand, mac};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::CanonicalSerialize;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+use thiserror::Error as ErrorT;
+use zeroize::{Zeroize, Zeroizing};
+
+const KDF_DOMAIN_SEP: &[u8] = b"fde hashed elgamal";
+const MAC_KEY_SIZE: usize = 32;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid authentication tag")]
+    InvalidTag,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashedCipher<C: CurveGroup> {
+    /// `g^y`
+    pub ephemeral: C::Affine,
+    pub ciphertext: Vec<u8>,
+    pub tag: Vec<u8>,
+}
+
+pub struct HashedElgamal<C, D>(PhantomData<(C, D)>);
+
+impl<C: CurveGroup, D: Digest + Clone> HashedElgamal<C, D> {
+    pub fn encrypt<R: Rng + CryptoRng>(
+        data: &[u8],
+        key: &C::Affine,
+        rng: &mut R,
+    ) -> HashedCipher<C> {
+        let random_nonce = Zeroizing::new(C::ScalarField::rand(rng));
+        Self::encrypt_with_randomness(data, key, &random_nonce)
+    }
+
+    pub fn encrypt_with_randomness(
+        data: &[u8],
+        key: &C::Affine,
+        randomness: &C::ScalarField,
+    ) -> HashedCipher<C> {
+        let ephemeral = (C::Affine::generator() * randomness).into_affine();
+        let shared_secret = (*key * randomness).into_affine();
+        let keys = Self::derive_keys(&ephemeral, &shared_secret, data.len());
+        let ciphertext: Vec<u8> = data.iter().zip(keys.iter()).map(|(m, k)| m ^ k).collect();
+        let tag = mac::<D>(&keys[data.len()..], &ciphertext).to_vec();
+        HashedCipher {
+            ephemeral,
+            ciphertext,
+            tag,
+        }
+    }
+
+    pub fn decrypt(cipher: &HashedCipher<C>, key: &C::ScalarField) -> Result<Vec<u8>, Error> {
+        let mut shared_secret = constant_time::mul::<C>(&cipher.ephemeral, key).into_affine();
+        let len = cipher.ciphertext.len();
+        let keys = Self::derive_keys(&cipher.ephemeral, &shared_secret, len);
+        shared_secret.zeroize();
+        let tag = mac::<D>(&keys[len..], &cipher.ciphertext);
+        if !constant_time_eq(&tag, &cipher.tag) {
+            return Err(Error::InvalidTag);
+        }
+        Ok(cipher
+            .ciphertext
+            .iter()
+            .zip(keys.iter())
+            .map(|(c, k)| c ^ k)
+            .collect())
+    }
+
+    // keystream of `len` bytes followed by the MAC key
+    fn derive_keys(
+        ephemeral: &C::Affine,
+        shared_secret: &C::Affine,
+        len: usize,
+    ) -> Zeroizing<Vec<u8>> {
+        let mut secret = Zeroizing::new(Vec::new());
+        ephemeral
+            .serialize_compressed(&mut *secret)
+            .expect("should not fail");
+        shared_secret
+            .serialize_compressed(&mut *secret)
+            .expect("should not fail");
+        let mut keys = Zeroizing::new(vec![0u8; len + MAC_KEY_SIZE]);
+        expand::<D>(KDF_DOMAIN_SEP, &secret, &mut keys);
+        keys
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::KeyPair;
+    use crate::tests::test_rng;
+    use crate::tests::{TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Hashed = HashedElgamal<G1, TestHash>;
+
+    #[test]
+    fn hashed_elgamal() {
+        let rng = &mut test_rng();
+        let keys = KeyPair::<G1>::generate(rng);
+
+        for data in [&[][..], &[7u8; 32], &[0xab; 100]] {
+            let cipher = Hashed::encrypt(data, keys.pk(), rng);
+            assert_eq!(cipher.ciphertext.len(), data.len());
+            assert_eq!(Hashed::decrypt(&cipher, keys.sk()).unwrap(), data);
+        }
+
+        let data = b"32 byte symmetric encryption key";
+        let cipher = Hashed::encrypt(data, keys.pk(), rng);
+        assert_ne!(&cipher.ciphertext[..], data);
+        // wrong key
+        let other = KeyPair::<G1>::generate(rng);
+        assert_eq!(Hashed::decrypt(&cipher, other.sk()), Err(Error::InvalidTag));
+        // tampered ciphertext
+        let mut tampered = cipher.clone();
+        tampered.ciphertext[3] ^= 1;
+        assert_eq!(
+            Hashed::decrypt(&tampered, keys.sk()),
+            Err(Error::InvalidTag)
+        );
+        let mut tampered = cipher;
+        tampered.ciphertext.push(0);
+        assert_eq!(
+            Hashed::decrypt(&tampered, keys.sk()),
+            Err(Error::InvalidTag)
+        );
+    }
+}
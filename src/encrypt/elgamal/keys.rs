@@ -0,0 +1,139 @@
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::io::{Read, Write};
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use zeroize::Zeroize;
+
+/// Exponential Elgamal key pair `(x, h = x * g)`. The secret key is wiped from memory when the
+/// key pair is dropped.
+pub struct KeyPair<C: CurveGroup> {
+    sk: C::ScalarField,
+    pk: C::Affine,
+}
+
+impl<C: CurveGroup> KeyPair<C> {
+    pub fn generate<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        Self::from_secret_key(C::ScalarField::rand(rng))
+    }
+
+    pub fn from_secret_key(sk: C::ScalarField) -> Self {
+        let pk = (C::Affine::generator() * sk).into_affine();
+        Self { sk, pk }
+    }
+
+    /// The decryption key.
+    pub fn sk(&self) -> &C::ScalarField {
+        &self.sk
+    }
+
+    /// The encryption key.
+    pub fn pk(&self) -> &C::Affine {
+        &self.pk
+    }
+
+    pub fn public_key(&self) -> PublicKey<C> {
+        PublicKey(self.pk)
+    }
+}
+
+impl<C: CurveGroup> Drop for KeyPair<C> {
+    fn drop(&mut self) {
+        self.sk.zeroize();
+    }
+}
+
+/// Exponential Elgamal encryption key, serialized as a compressed point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKey<C: CurveGroup>(pub C::Affine);
+
+impl<C: CurveGroup> From<&KeyPair<C>> for PublicKey<C> {
+    fn from(key_pair: &KeyPair<C>) -> Self {
+        key_pair.public_key()
+    }
+}
+
+impl<C: CurveGroup> CanonicalSerialize for PublicKey<C> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.0.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.0.serialized_size(compress)
+    }
+}
+
+impl<C: CurveGroup> Valid for PublicKey<C> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.0.check()
+    }
+}
+
+impl<C: CurveGroup> CanonicalDeserialize for PublicKey<C> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        C::Affine::deserialize_with_mode(reader, compress, validate).map(Self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: CurveGroup> serde::Serialize for PublicKey<C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::canonical_serde::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: CurveGroup> serde::Deserialize<'de> for PublicKey<C> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::canonical_serde::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{G1Affine, TestCurve};
+    use ark_ec::pairing::Pairing;
+
+    type Keys = KeyPair<<TestCurve as Pairing>::G1>;
+    type Pk = PublicKey<<TestCurve as Pairing>::G1>;
+
+    #[test]
+    fn key_pair() {
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        assert_eq!(
+            *keys.pk(),
+            (G1Affine::generator() * keys.sk()).into_affine()
+        );
+        let restored = Keys::from_secret_key(*keys.sk());
+        assert_eq!(restored.public_key(), Pk::from(&keys));
+
+        let pk = keys.public_key();
+        let mut bytes = Vec::new();
+        pk.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(Pk::deserialize_compressed(&bytes[..]).unwrap(), pk);
+        // not a valid point
+        bytes[47] ^= 1;
+        assert!(Pk::deserialize_compressed(&bytes[..]).is_err());
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&pk).unwrap();
+            assert_eq!(json.len(), 2 * 48 + 2);
+            assert_eq!(serde_json::from_str::<Pk>(&json).unwrap(), pk);
+        }
+    }
+}
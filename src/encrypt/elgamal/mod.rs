@@ -1,20 +1,106 @@
+pub mod blind;
+pub mod dkg;
+pub mod dlog;
+pub mod escrow;
+mod generator;
+pub mod hashed;
+mod keys;
+pub mod packing;
+pub mod proofs;
+pub mod ratchet;
+pub mod reencrypt;
 mod split_scalar;
+pub mod stream;
+pub mod threshold;
 mod utils;
 
-pub use split_scalar::SplitScalar;
+pub use dlog::{BabyStepGiantStep, BruteForce, DiscreteLog, DlogTable, Kangaroo, KangarooSearch};
+pub use generator::GeneratorTable;
+pub use hashed::{HashedCipher, HashedElgamal};
+pub use keys::{KeyPair, PublicKey};
+pub use packing::Packing;
+pub use proofs::{
+    CommitmentEqualityProof, DecryptionProof, PlaintextEqualityProof, PlaintextKnowledgeProof,
+};
+pub use ratchet::KeyRatchet;
+pub use reencrypt::{ProxyReencryption, ReencryptedCipher, ReencryptionKey};
+pub use split_scalar::{SplitEncryption, SplitScalar};
 use utils::shift_scalar;
 
-use super::EncryptionEngine;
+use super::{constant_time, EncryptionEngine};
+use ark_ec::scalar_mul::fixed_base::FixedBase;
 use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::iter::Sum;
 use ark_std::marker::PhantomData;
-use ark_std::ops::{Add, Mul};
-use ark_std::rand::Rng;
+use ark_std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use ark_std::rand::{CryptoRng, Rng};
 use ark_std::{One, UniformRand, Zero};
+use thiserror::Error as ErrorT;
 
 pub const MAX_BITS: usize = 32;
 
+/// Table size of the [`BabyStepGiantStep`] solver used by [`EncryptionEngine::decrypt`], which
+/// balances the baby and giant steps for plaintexts of [`MAX_BITS`] bits.
+pub const DEFAULT_TABLE_BITS: u32 = MAX_BITS as u32 / 2;
+
+/// Smallest batch for which [`EncryptionEngine::encrypt_batch_with_randomness`] precomputes a
+/// window table of the key, the generator always uses the shared [`GeneratorTable`].
+pub const FIXED_BASE_BATCH_SIZE: usize = 32;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum DecryptError {
+    #[error("no plaintext smaller than 2^{bits} matches the ciphertext")]
+    BruteForceExceeded { bits: u32 },
+}
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum CipherError {
+    #[error("ciphertext point is not on the curve or not in the prime order subgroup")]
+    InvalidPoint,
+    #[error("ciphertext point is the identity")]
+    IdentityPoint,
+}
+
 pub struct ExponentialElgamal<C>(pub PhantomData<C>);
 
+/// Discrete logarithm algorithms without a shared precomputation, see [`dlog`] for their
+/// trade-offs. Strategies implement [`DiscreteLog`], so they can be passed to
+/// [`ExponentialElgamal::decrypt_with`] and [`ExponentialElgamal::decrypt_range`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecryptStrategy {
+    BruteForce,
+    /// Builds a fresh [`BabyStepGiantStep`] table of the given size for every cipher.
+    BabyStepGiantStep {
+        table_bits: u32,
+    },
+    Kangaroo,
+}
+
+impl Default for DecryptStrategy {
+    fn default() -> Self {
+        Self::BabyStepGiantStep {
+            table_bits: DEFAULT_TABLE_BITS,
+        }
+    }
+}
+
+impl<C: CurveGroup> DiscreteLog<C> for DecryptStrategy {
+    fn solve(&self, point: C::Affine, bits: u32) -> Option<u64> {
+        match *self {
+            Self::BruteForce => DiscreteLog::<C>::solve(&BruteForce, point, bits),
+            Self::BabyStepGiantStep { table_bits } => {
+                BabyStepGiantStep::<C>::new(table_bits).solve(point, bits)
+            }
+            Self::Kangaroo => DiscreteLog::<C>::solve(&Kangaroo, point, bits),
+        }
+    }
+}
+
 /// Exponential Elgamal encryption scheme ciphertext.
 ///
 /// It contains `c1 = g^y` and `c2 = g^m * h^y` where `g` is a group generator, `h = g^x` is the
@@ -48,34 +134,183 @@ impl<C: CurveGroup> Cipher<C> {
         self.0[1]
     }
 
+    /// Checks that both points are on the curve, in the prime order subgroup and not the identity.
+    ///
+    /// Points outside the subgroup would let a malicious sender learn bits of the decryption key
+    /// from decryptions or proofs, hence this is called by every verification method that takes
+    /// ciphertexts from other parties. Honest ciphertexts contain the identity with negligible
+    /// probability only.
+    pub fn validate(&self) -> Result<(), CipherError> {
+        if self.0.iter().any(|point| point.check().is_err()) {
+            return Err(CipherError::InvalidPoint);
+        }
+        if self.0.iter().any(|point| point.is_zero()) {
+            return Err(CipherError::IdentityPoint);
+        }
+        Ok(())
+    }
+
     pub fn check_encrypted_sum(&self, ciphers: &[Self]) -> bool {
-        let ciphers_sum = ciphers
+        // summed in projective form, comparing against affine points requires no inversion
+        let (c0_sum, c1_sum) = Self::shifted_sum(ciphers);
+        c0_sum == self.c0().into_group() && c1_sum == self.c1().into_group()
+    }
+
+    /// Adds an encryption of zero with fresh randomness, so that the result decrypts to the same
+    /// plaintext but cannot be linked to the original ciphertext without the decryption key.
+    pub fn rerandomize<R: Rng + CryptoRng>(&self, encryption_key: &C::Affine, rng: &mut R) -> Self {
+        let randomness = C::ScalarField::rand(rng);
+        let points = C::normalize_batch(&[
+            GeneratorTable::<C>::get().mul(&randomness) + self.c0(),
+            *encryption_key * randomness + self.c1(),
+        ]);
+        Self([points[0], points[1]])
+    }
+
+    /// Compressed encoding of both points.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.compressed_size());
+        self.serialize_compressed(&mut bytes)
+            .expect("writing to a vector never fails");
+        bytes
+    }
+
+    /// Decodes the compressed encoding of both points, checking that they are in the prime order
+    /// subgroup and that there are no trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        if bytes.len() != Self::zero().compressed_size() {
+            return Err(SerializationError::InvalidData);
+        }
+        Self::deserialize_compressed(bytes)
+    }
+
+    /// Sums the components of split scalar ciphers, each shifted by [`MAX_BITS`] times its index.
+    fn shifted_sum(ciphers: &[Self]) -> (C, C) {
+        ciphers
             .iter()
             .enumerate()
-            .fold(Self::zero(), |acc, (i, c)| {
-                acc + *c * shift_scalar(&C::ScalarField::one(), MAX_BITS * i)
-            });
-        ciphers_sum == *self
+            .fold((C::zero(), C::zero()), |(c0, c1), (i, cipher)| {
+                let shift = shift_scalar(&C::ScalarField::one(), MAX_BITS * i);
+                (c0 + cipher.c0() * shift, c1 + cipher.c1() * shift)
+            })
+    }
+}
+
+impl<C: CurveGroup> CanonicalSerialize for Cipher<C> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.c0().serialize_with_mode(&mut writer, compress)?;
+        self.c1().serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.c0().serialized_size(compress) + self.c1().serialized_size(compress)
+    }
+}
+
+impl<C: CurveGroup> Valid for Cipher<C> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.c0().check()?;
+        self.c1().check()
+    }
+}
+
+impl<C: CurveGroup> CanonicalDeserialize for Cipher<C> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let c0 = C::Affine::deserialize_with_mode(&mut reader, compress, validate)?;
+        let c1 = C::Affine::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self([c0, c1]))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: CurveGroup> serde::Serialize for Cipher<C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::canonical_serde::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: CurveGroup> serde::Deserialize<'de> for Cipher<C> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::canonical_serde::deserialize(deserializer)
     }
 }
 
 impl<C: CurveGroup> Add for Cipher<C> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
-        Self([
-            (self.c0() + rhs.c0()).into_affine(),
-            (self.c1() + rhs.c1()).into_affine(),
-        ])
+        let points = C::normalize_batch(&[self.c0() + rhs.c0(), self.c1() + rhs.c1()]);
+        Self([points[0], points[1]])
+    }
+}
+
+impl<C: CurveGroup> Sub for Cipher<C> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let points = C::normalize_batch(&[
+            self.c0().into_group() - rhs.c0(),
+            self.c1().into_group() - rhs.c1(),
+        ]);
+        Self([points[0], points[1]])
+    }
+}
+
+impl<C: CurveGroup> Neg for Cipher<C> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        let points = C::normalize_batch(&[-self.c0().into_group(), -self.c1().into_group()]);
+        Self([points[0], points[1]])
+    }
+}
+
+impl<C: CurveGroup> AddAssign for Cipher<C> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<C: CurveGroup> SubAssign for Cipher<C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
     }
 }
 
 impl<C: CurveGroup> Mul<C::ScalarField> for Cipher<C> {
     type Output = Self;
     fn mul(self, rhs: C::ScalarField) -> Self::Output {
-        Self([
-            (self.c0() * rhs).into_affine(),
-            (self.c1() * rhs).into_affine(),
-        ])
+        let points = C::normalize_batch(&[self.c0() * rhs, self.c1() * rhs]);
+        Self([points[0], points[1]])
+    }
+}
+
+impl<C: CurveGroup> MulAssign<C::ScalarField> for Cipher<C> {
+    fn mul_assign(&mut self, rhs: C::ScalarField) {
+        *self = *self * rhs;
+    }
+}
+
+/// Sums in projective form, normalizing only the result.
+impl<C: CurveGroup> Sum for Cipher<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let (c0, c1) = iter.fold((C::zero(), C::zero()), |(c0, c1), cipher| {
+            (c0 + cipher.c0(), c1 + cipher.c1())
+        });
+        let points = C::normalize_batch(&[c0, c1]);
+        Self([points[0], points[1]])
+    }
+}
+
+impl<'a, C: CurveGroup> Sum<&'a Self> for Cipher<C> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
     }
 }
 
@@ -84,90 +319,367 @@ impl<C: CurveGroup> EncryptionEngine for ExponentialElgamal<C> {
     type DecryptionKey = C::ScalarField;
     type Cipher = Cipher<C>;
     type PlainText = C::ScalarField;
+    type Randomness = C::ScalarField;
+    type DecryptError = DecryptError;
 
-    fn encrypt<R: Rng>(
+    fn encrypt_returning_randomness<R: Rng + CryptoRng>(
         data: &Self::PlainText,
         key: &Self::EncryptionKey,
         rng: &mut R,
-    ) -> Self::Cipher {
+    ) -> (Self::Cipher, Self::Randomness) {
         let random_nonce = C::ScalarField::rand(rng);
-        Self::encrypt_with_randomness(data, key, &random_nonce)
+        let cipher = Self::encrypt_with_randomness(data, key, &random_nonce);
+        (cipher, random_nonce)
     }
 
     fn encrypt_with_randomness(
         data: &Self::PlainText,
         key: &Self::EncryptionKey,
-        randomness: &Self::PlainText,
+        randomness: &Self::Randomness,
     ) -> Self::Cipher {
+        let generator = GeneratorTable::<C>::get();
         // h^y
         let shared_secret = *key * randomness;
         // g^y
-        let c1 = <C::Affine as AffineRepr>::generator() * randomness;
+        let c1 = generator.mul(randomness);
         // g^m * h^y
-        let c2 = <C::Affine as AffineRepr>::generator() * data + shared_secret;
-        Cipher([c1.into_affine(), c2.into_affine()])
+        let c2 = generator.mul(data) + shared_secret;
+        let points = C::normalize_batch(&[c1, c2]);
+        Cipher([points[0], points[1]])
+    }
+
+    /// Draws all nonces upfront, see [`Self::encrypt_batch_with_randomness`].
+    fn encrypt_batch<R: Rng + CryptoRng>(
+        data: &[Self::PlainText],
+        key: &Self::EncryptionKey,
+        rng: &mut R,
+    ) -> Vec<Self::Cipher> {
+        let randomness: Vec<C::ScalarField> =
+            (0..data.len()).map(|_| C::ScalarField::rand(rng)).collect();
+        Self::encrypt_batch_with_randomness(data, key, &randomness)
+    }
+
+    /// Normalizes all ciphertext points with a single field inversion. Batches of at least
+    /// [`FIXED_BASE_BATCH_SIZE`] ciphers also multiply the key with a window table, which takes a
+    /// single addition per window instead of a double-and-add pass.
+    fn encrypt_batch_with_randomness(
+        data: &[Self::PlainText],
+        key: &Self::EncryptionKey,
+        randomness: &[Self::Randomness],
+    ) -> Vec<Self::Cipher> {
+        let generator = GeneratorTable::<C>::get();
+        let c0s = generator.batch_mul(randomness);
+        let messages = generator.batch_mul(data);
+        let shared_secrets: Vec<C> = if data.len() < FIXED_BASE_BATCH_SIZE {
+            randomness.iter().map(|y| *key * y).collect()
+        } else {
+            let scalar_size = C::ScalarField::MODULUS_BIT_SIZE as usize;
+            let window = FixedBase::get_mul_window_size(data.len());
+            let key_table = FixedBase::get_window_table(scalar_size, window, key.into_group());
+            FixedBase::msm::<C>(scalar_size, window, &key_table, randomness)
+        };
+        let points: Vec<C> = c0s
+            .into_iter()
+            .zip(messages)
+            .zip(shared_secrets)
+            .flat_map(|((c0, message), shared_secret)| [c0, message + shared_secret])
+            .collect();
+        C::normalize_batch(&points)
+            .chunks_exact(2)
+            .map(|cipher| Cipher([cipher[0], cipher[1]]))
+            .collect()
+    }
+
+    /// Solves the discrete logarithm of `m * g` with a fresh [`BabyStepGiantStep`] table, see
+    /// [`ExponentialElgamal::decrypt_with`] for reusing the table across ciphers and
+    /// [`ExponentialElgamal::decrypt_range`] for other bounds. Plaintexts are expected to be
+    /// smaller than `2^MAX_BITS`.
+    fn decrypt(
+        cipher: Self::Cipher,
+        key: &Self::DecryptionKey,
+    ) -> Result<Self::PlainText, Self::DecryptError> {
+        let solver = BabyStepGiantStep::new(DEFAULT_TABLE_BITS);
+        Self::decrypt_with(cipher, key, &solver)
+    }
+}
+
+/// Encryption of the same plaintext to multiple recipients sharing the random nonce, thus the
+/// common `c0 = g^y` is only computed and transmitted once. Reusing the nonce across different
+/// keys is secure for Elgamal as long as the recipients' keys are generated honestly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiCipher<C: CurveGroup> {
+    pub c0: C::Affine,
+    /// `c1 = g^m * h_i^y` for every recipient key `h_i`.
+    pub c1s: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> MultiCipher<C> {
+    pub fn len(&self) -> usize {
+        self.c1s.len()
     }
 
-    fn decrypt(cipher: Self::Cipher, key: &Self::DecryptionKey) -> Self::PlainText {
-        let decrypted_exp = Self::decrypt_exp(cipher, key);
-        Self::brute_force(decrypted_exp)
+    pub fn is_empty(&self) -> bool {
+        self.c1s.is_empty()
+    }
+
+    /// The ciphertext of the recipient at `index`.
+    pub fn cipher(&self, index: usize) -> Option<Cipher<C>> {
+        self.c1s.get(index).map(|c1| Cipher([self.c0, *c1]))
     }
 }
 
 impl<C: CurveGroup> ExponentialElgamal<C> {
-    pub fn decrypt_exp(cipher: Cipher<C>, key: &C::ScalarField) -> C::Affine {
-        let shared_secret = (cipher.c0() * key).into_affine();
-        // AffineRepr has to be converted into a Group element in order to perform subtraction but
-        // I believe this is optimized away in release mode
-        (cipher.c1().into() - shared_secret.into()).into_affine()
+    pub fn encrypt_to_many<R: Rng + CryptoRng>(
+        data: &C::ScalarField,
+        keys: &[C::Affine],
+        rng: &mut R,
+    ) -> MultiCipher<C> {
+        let random_nonce = C::ScalarField::rand(rng);
+        Self::encrypt_to_many_with_randomness(data, keys, &random_nonce)
     }
 
-    pub fn brute_force(decrypted: C::Affine) -> C::ScalarField {
-        let max = C::ScalarField::from(u32::MAX);
-        let mut exponent = C::ScalarField::zero();
+    pub fn encrypt_to_many_with_randomness(
+        data: &C::ScalarField,
+        keys: &[C::Affine],
+        randomness: &C::ScalarField,
+    ) -> MultiCipher<C> {
+        let generator = GeneratorTable::<C>::get();
+        let message = generator.mul(data);
+        let points: Vec<C> = ark_std::iter::once(generator.mul(randomness))
+            .chain(keys.iter().map(|key| message + *key * randomness))
+            .collect();
+        let mut points = C::normalize_batch(&points);
+        let c1s = points.split_off(1);
+        MultiCipher { c0: points[0], c1s }
+    }
 
-        while (<C::Affine as AffineRepr>::generator() * exponent).into_affine() != decrypted
-            && exponent < max
-        {
-            exponent += C::ScalarField::one();
+    /// Computes the plaintext point `m * g = c1 - x * c0` without branching on the decryption key
+    /// `x`, see [`constant_time`].
+    pub fn decrypt_exp(cipher: Cipher<C>, key: &C::ScalarField) -> C::Affine {
+        let mut shared_secret = constant_time::mul::<C>(&cipher.c0(), key);
+        let plaintext_point = (cipher.c1().into_group() - shared_secret).into_affine();
+        shared_secret.zeroize();
+        plaintext_point
+    }
+
+    /// Computes the plaintext points of all ciphers like [`Self::decrypt_exp`], but recodes the
+    /// key once and normalizes all points with a single field inversion.
+    pub fn decrypt_exp_batch(ciphers: &[Cipher<C>], key: &C::ScalarField) -> Vec<C::Affine> {
+        let c0s: Vec<C::Affine> = ciphers.iter().map(Cipher::c0).collect();
+        let mut points = constant_time::mul_batch::<C>(&c0s, key);
+        for (point, cipher) in points.iter_mut().zip(ciphers) {
+            *point = -*point + cipher.c1();
         }
-        exponent
+        // the shared secrets are overwritten in place
+        C::normalize_batch(&points)
+    }
+
+    /// Decrypts a plaintext smaller than `2^MAX_BITS` with the given solver, e.g. a shared
+    /// [`DlogTable`].
+    pub fn decrypt_with<D: DiscreteLog<C>>(
+        cipher: Cipher<C>,
+        key: &C::ScalarField,
+        solver: &D,
+    ) -> Result<C::ScalarField, DecryptError> {
+        Self::decrypt_range(cipher, key, MAX_BITS as u32, solver)
+    }
+
+    /// Decrypts a plaintext smaller than `2^bits`, e.g. with [`DecryptStrategy::Kangaroo`] for
+    /// ranges beyond [`MAX_BITS`], or to give up early on smaller ranges.
+    pub fn decrypt_range<D: DiscreteLog<C>>(
+        cipher: Cipher<C>,
+        key: &C::ScalarField,
+        bits: u32,
+        solver: &D,
+    ) -> Result<C::ScalarField, DecryptError> {
+        solver
+            .solve(Self::decrypt_exp(cipher, key), bits)
+            .map(C::ScalarField::from)
+            .ok_or(DecryptError::BruteForceExceeded { bits })
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::tests::{G1Affine, Scalar, TestCurve, N};
+    use crate::tests::test_rng;
+    use crate::tests::{G1Affine, Scalar, TestCurve, TestHash, N};
     use ark_ec::pairing::Pairing;
     use ark_ec::{AffineRepr, CurveGroup};
-    use ark_std::{test_rng, UniformRand};
+    use ark_std::UniformRand;
 
     type Elgamal = ExponentialElgamal<<TestCurve as Pairing>::G1>;
+    type Keys = KeyPair<<TestCurve as Pairing>::G1>;
 
     #[test]
     fn exponential_elgamal() {
         let rng = &mut test_rng();
-        let decryption_key = Scalar::rand(rng);
-        let encryption_key = (G1Affine::generator() * decryption_key).into_affine();
+        let keys = Keys::generate(rng);
+        let (decryption_key, encryption_key) = (keys.sk(), keys.pk());
 
         // completeness
         let data = Scalar::from(12342526u32);
-        let encrypted = Elgamal::encrypt(&data, &encryption_key, rng);
-        let decrypted = Elgamal::decrypt_exp(encrypted, &decryption_key);
+        let encrypted = Elgamal::encrypt(&data, encryption_key, rng);
+        let decrypted = Elgamal::decrypt_exp(encrypted, decryption_key);
         assert_eq!(decrypted, (G1Affine::generator() * data).into_affine());
         // soundness
         let data = Scalar::from(12342526u32);
-        let invalid_decryption_key = decryption_key + Scalar::from(123u32);
-        let encrypted = Elgamal::encrypt(&data, &encryption_key, rng);
+        let invalid_decryption_key = *decryption_key + Scalar::from(123u32);
+        let encrypted = Elgamal::encrypt(&data, encryption_key, rng);
         let decrypted = Elgamal::decrypt_exp(encrypted, &invalid_decryption_key);
         assert_ne!(decrypted, (G1Affine::generator() * data).into_affine());
+        // returned randomness
+        let (encrypted, randomness) =
+            Elgamal::encrypt_returning_randomness(&data, encryption_key, rng);
+        assert_eq!(
+            encrypted,
+            Elgamal::encrypt_with_randomness(&data, encryption_key, &randomness)
+        );
+        assert_eq!(encrypted.c0(), G1Affine::generator() * randomness);
 
-        // with brute force check
+        // with discrete logarithm
         let data = Scalar::from(12u32);
-        let encrypted = Elgamal::encrypt(&data, &encryption_key, rng);
-        let decrypted = Elgamal::decrypt(encrypted, &decryption_key);
-        assert_eq!(decrypted, data);
+        let encrypted = Elgamal::encrypt(&data, encryption_key, rng);
+        let decrypted = Elgamal::decrypt(encrypted, decryption_key);
+        assert_eq!(decrypted, Ok(data));
+        let encrypted = Elgamal::encrypt(&Scalar::zero(), encryption_key, rng);
+        assert_eq!(
+            Elgamal::decrypt(encrypted, decryption_key),
+            Ok(Scalar::zero())
+        );
+
+        // 32-bit plaintexts with a shared table
+        let solver = BabyStepGiantStep::new(DEFAULT_TABLE_BITS);
+        for data in [12342526u32, u32::MAX - 1, u32::MAX] {
+            let data = Scalar::from(data);
+            let encrypted = Elgamal::encrypt(&data, encryption_key, rng);
+            assert_eq!(
+                Elgamal::decrypt_with(encrypted, decryption_key, &solver),
+                Ok(data)
+            );
+        }
+        let encrypted = Elgamal::encrypt(&Scalar::from(1u64 << 32), encryption_key, rng);
+        assert_eq!(
+            Elgamal::decrypt_with(encrypted, decryption_key, &solver),
+            Err(DecryptError::BruteForceExceeded { bits: 32 })
+        );
+        // configurable bound
+        let encrypted = Elgamal::encrypt(&Scalar::from(1u32 << 16), encryption_key, rng);
+        assert_eq!(
+            Elgamal::decrypt_range(encrypted, decryption_key, 16, &solver),
+            Err(DecryptError::BruteForceExceeded { bits: 16 })
+        );
+        assert_eq!(
+            Elgamal::decrypt_range(encrypted, decryption_key, 17, &solver),
+            Ok(Scalar::from(1u32 << 16))
+        );
+
+        // every strategy
+        let data = Scalar::from(54321u32);
+        let encrypted = Elgamal::encrypt(&data, encryption_key, rng);
+        for strategy in [
+            DecryptStrategy::BruteForce,
+            DecryptStrategy::default(),
+            DecryptStrategy::Kangaroo,
+        ] {
+            assert_eq!(
+                Elgamal::decrypt_range(encrypted, decryption_key, 20, &strategy),
+                Ok(data)
+            );
+        }
+    }
+
+    #[test]
+    fn batch_encryption() {
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let (decryption_key, encryption_key) = (keys.sk(), keys.pk());
+        // with and without window tables
+        for size in [5, FIXED_BASE_BATCH_SIZE + 3] {
+            let data: Vec<Scalar> = (0..size as u32).map(Scalar::from).collect();
+            let randomness: Vec<Scalar> = (0..size).map(|_| Scalar::rand(rng)).collect();
+
+            let ciphers =
+                Elgamal::encrypt_batch_with_randomness(&data, encryption_key, &randomness);
+            assert_eq!(ciphers.len(), data.len());
+            for ((cipher, m), y) in ciphers.into_iter().zip(&data).zip(&randomness) {
+                assert_eq!(
+                    cipher,
+                    Elgamal::encrypt_with_randomness(m, encryption_key, y)
+                );
+                assert_eq!(Elgamal::decrypt(cipher, decryption_key), Ok(*m));
+            }
+
+            let ciphers = Elgamal::encrypt_batch(&data, encryption_key, rng);
+            let points = Elgamal::decrypt_exp_batch(&ciphers, decryption_key);
+            for ((cipher, m), point) in ciphers.into_iter().zip(&data).zip(points) {
+                assert_eq!(Elgamal::decrypt(cipher, decryption_key), Ok(*m));
+                assert_eq!(point, Elgamal::decrypt_exp(cipher, decryption_key));
+            }
+        }
+        assert!(Elgamal::decrypt_exp_batch(&[], decryption_key).is_empty());
+    }
+
+    #[test]
+    fn cipher_serialization() {
+        type G1Cipher = Cipher<<TestCurve as Pairing>::G1>;
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let cipher = Elgamal::encrypt(&Scalar::from(7u8), keys.pk(), rng);
+
+        let bytes = cipher.to_bytes();
+        assert_eq!(bytes.len(), 2 * 48);
+        assert_eq!(G1Cipher::from_bytes(&bytes).unwrap(), cipher);
+        // trailing or missing bytes
+        assert!(G1Cipher::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+        assert!(G1Cipher::from_bytes(&bytes[..48]).is_err());
+        // invalid point
+        let mut invalid = bytes.clone();
+        invalid[95] ^= 1;
+        assert!(G1Cipher::from_bytes(&invalid).is_err());
+
+        let mut uncompressed = Vec::new();
+        cipher.serialize_uncompressed(&mut uncompressed).unwrap();
+        assert_eq!(
+            G1Cipher::deserialize_uncompressed(&uncompressed[..]).unwrap(),
+            cipher
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&cipher).unwrap();
+            assert_eq!(serde_json::from_str::<G1Cipher>(&json).unwrap(), cipher);
+        }
+    }
+
+    #[test]
+    fn cipher_validation() {
+        use ark_ec::short_weierstrass::SWCurveConfig;
+
+        type G1 = <TestCurve as Pairing>::G1;
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let cipher = Elgamal::encrypt(&Scalar::from(7u8), keys.pk(), rng);
+        assert_eq!(cipher.validate(), Ok(()));
+        assert_eq!(
+            Cipher::<G1>::zero().validate(),
+            Err(CipherError::IdentityPoint)
+        );
+
+        // point on the curve, but not in the prime order subgroup
+        let not_in_subgroup = loop {
+            let x = <G1Affine as AffineRepr>::BaseField::rand(rng);
+            if let Some(point) = G1Affine::get_point_from_x_unchecked(x, false) {
+                if !ark_bls12_381::g1::Config::is_in_correct_subgroup_assuming_on_curve(&point) {
+                    break point;
+                }
+            }
+        };
+        let invalid = Cipher::<G1>([not_in_subgroup, cipher.c1()]);
+        assert_eq!(invalid.validate(), Err(CipherError::InvalidPoint));
+        // the decryption of an invalid cipher is not accepted
+        let (point, proof) = DecryptionProof::<G1, TestHash>::new(&invalid, keys.sk(), rng);
+        assert!(!proof.verify(&invalid, &point, keys.pk()));
     }
 
     #[test]
@@ -179,8 +691,8 @@ mod test {
         let rb = Scalar::from(20u8);
         let rc = Scalar::from(200u8);
 
-        let decryption_key = Scalar::from(1234567);
-        let encryption_key = (G1Affine::generator() * decryption_key).into_affine();
+        let keys = Keys::from_secret_key(Scalar::from(1234567));
+        let encryption_key = *keys.pk();
 
         let ea = Elgamal::encrypt_with_randomness(&a, &encryption_key, &ra);
         let eb = Elgamal::encrypt_with_randomness(&b, &encryption_key, &rb);
@@ -211,17 +723,94 @@ mod test {
         );
     }
 
+    #[test]
+    fn homomorphic_operators() {
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let data: Vec<Scalar> = (1..=4u32).map(|m| Scalar::from(m * 100)).collect();
+        let weights: Vec<Scalar> = (1..=4u32).map(Scalar::from).collect();
+        let ciphers: Vec<_> = data
+            .iter()
+            .map(|m| Elgamal::encrypt(m, keys.pk(), rng))
+            .collect();
+
+        // weighted sum
+        let weighted: Cipher<_> = ciphers.iter().zip(&weights).map(|(c, w)| *c * *w).sum();
+        let expected: Scalar = data.iter().zip(&weights).map(|(m, w)| *m * w).sum();
+        assert_eq!(Elgamal::decrypt(weighted, keys.sk()), Ok(expected));
+
+        let mut accumulated = Cipher::zero();
+        for cipher in &ciphers {
+            accumulated += *cipher;
+        }
+        assert_eq!(accumulated, ciphers.iter().sum());
+
+        // difference
+        let difference = ciphers[3] - ciphers[1];
+        assert_eq!(
+            Elgamal::decrypt(difference, keys.sk()),
+            Ok(data[3] - data[1])
+        );
+        assert_eq!(difference, ciphers[3] + -ciphers[1]);
+        accumulated -= ciphers[0];
+        accumulated *= Scalar::from(2u8);
+        assert_eq!(
+            Elgamal::decrypt(accumulated, keys.sk()),
+            Ok((data[1] + data[2] + data[3]) * Scalar::from(2u8))
+        );
+    }
+
+    #[test]
+    fn rerandomization() {
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let data = Scalar::from(1234u32);
+        let cipher = Elgamal::encrypt(&data, keys.pk(), rng);
+
+        let rerandomized = cipher.rerandomize(keys.pk(), rng);
+        assert_ne!(rerandomized.c0(), cipher.c0());
+        assert_ne!(rerandomized.c1(), cipher.c1());
+        assert_eq!(Elgamal::decrypt(rerandomized, keys.sk()), Ok(data));
+        assert_eq!(
+            Elgamal::decrypt_exp(rerandomized, keys.sk()),
+            Elgamal::decrypt_exp(cipher, keys.sk())
+        );
+    }
+
+    #[test]
+    fn multi_recipient_encryption() {
+        let rng = &mut test_rng();
+        let recipients: Vec<Keys> = (0..3).map(|_| Keys::generate(rng)).collect();
+        let keys: Vec<G1Affine> = recipients.iter().map(|keys| *keys.pk()).collect();
+        let data = Scalar::from(777u32);
+
+        let randomness = Scalar::rand(rng);
+        let multi_cipher = Elgamal::encrypt_to_many_with_randomness(&data, &keys, &randomness);
+        assert_eq!(multi_cipher.len(), 3);
+        for (i, keys) in recipients.iter().enumerate() {
+            let cipher = multi_cipher.cipher(i).unwrap();
+            assert_eq!(
+                cipher,
+                Elgamal::encrypt_with_randomness(&data, keys.pk(), &randomness)
+            );
+            assert_eq!(Elgamal::decrypt(cipher, keys.sk()), Ok(data));
+        }
+        assert!(multi_cipher.cipher(3).is_none());
+
+        let multi_cipher = Elgamal::encrypt_to_many(&data, &[], rng);
+        assert!(multi_cipher.is_empty());
+    }
+
     #[test]
     fn split_encryption() {
         let rng = &mut test_rng();
         let scalar = Scalar::rand(rng);
         let split_scalar = SplitScalar::<{ N }, Scalar>::from(scalar);
-        let secret = Scalar::rand(rng);
-        let encryption_key = (G1Affine::generator() * secret).into_affine();
+        let keys = Keys::generate(rng);
 
-        let (ciphers, randomness) = split_scalar.encrypt::<Elgamal, _>(&encryption_key, rng);
+        let (ciphers, randomness) = split_scalar.encrypt::<Elgamal, _>(keys.pk(), rng);
 
-        let cipher = Elgamal::encrypt_with_randomness(&scalar, &encryption_key, &randomness);
+        let cipher = Elgamal::encrypt_with_randomness(&scalar, keys.pk(), &randomness);
 
         assert!(cipher.check_encrypted_sum(&ciphers));
     }
@@ -0,0 +1,179 @@
+//! Radix packing of several small values into a single plaintext scalar, so that a single
+//! exponential Elgamal cipher carries all of them.
+use super::{Cipher, DecryptError, DiscreteLog, ExponentialElgamal};
+use crate::encrypt::EncryptionEngine;
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+use ark_std::rand::{CryptoRng, Rng};
+use thiserror::Error as ErrorT;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("{count} values of {value_bits} bits do not fit into a scalar")]
+    TooWide { value_bits: u32, count: usize },
+    #[error("expected {expected} values, got {got}")]
+    InvalidLength { expected: usize, got: usize },
+    #[error("value at index {0} exceeds the slot size")]
+    ValueTooLarge(usize),
+}
+
+/// Layout of `count` values of `value_bits` bits each, the first value occupies the lowest bits.
+///
+/// The values `v_0, ..., v_{k-1}` of `b` bits each are encoded as `sum v_i * 2^(b * i)`.
+/// Decryption has to solve a discrete logarithm of `b * k` bits, thus the packing trades
+/// ciphertext count for decryption time. Ciphers of packings with the same layout can be added
+/// slot-wise, as long as no slot exceeds `b` bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Packing {
+    value_bits: u32,
+    count: usize,
+}
+
+impl Packing {
+    /// Fails if the packed values could overflow a scalar of `S`, or if a slot is wider than
+    /// 64 bits.
+    pub fn new<S: PrimeField>(value_bits: u32, count: usize) -> Result<Self, Error> {
+        let fits = (value_bits as usize)
+            .checked_mul(count)
+            .is_some_and(|bits| bits < S::MODULUS_BIT_SIZE as usize);
+        if value_bits == 0 || value_bits > u64::BITS || !fits {
+            return Err(Error::TooWide { value_bits, count });
+        }
+        Ok(Self { value_bits, count })
+    }
+
+    pub fn value_bits(&self) -> u32 {
+        self.value_bits
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Bit size of the packed plaintext, i.e. the range passed to the discrete log solver.
+    pub fn total_bits(&self) -> u32 {
+        self.value_bits * self.count as u32
+    }
+
+    pub fn pack<S: PrimeField>(&self, values: &[u64]) -> Result<S, Error> {
+        if values.len() != self.count {
+            return Err(Error::InvalidLength {
+                expected: self.count,
+                got: values.len(),
+            });
+        }
+        if let Some(index) = values
+            .iter()
+            .position(|value| self.value_bits < u64::BITS && value >> self.value_bits != 0)
+        {
+            return Err(Error::ValueTooLarge(index));
+        }
+        let radix = S::from(1u128 << self.value_bits);
+        Ok(values
+            .iter()
+            .rev()
+            .fold(S::zero(), |acc, value| acc * radix + S::from(*value)))
+    }
+
+    /// Splits `packed` into its slots, bits above [`Packing::total_bits`] are ignored.
+    pub fn unpack<S: PrimeField>(&self, packed: &S) -> Vec<u64> {
+        let mask = u64::MAX >> (u64::BITS - self.value_bits);
+        let mut bigint = packed.into_bigint();
+        (0..self.count)
+            .map(|_| {
+                let value = bigint.as_ref()[0] & mask;
+                bigint.divn(self.value_bits);
+                value
+            })
+            .collect()
+    }
+}
+
+impl<C: CurveGroup> ExponentialElgamal<C> {
+    /// Encrypts all `values` in a single cipher according to `packing`.
+    pub fn encrypt_packed<R: Rng + CryptoRng>(
+        values: &[u64],
+        packing: &Packing,
+        key: &C::Affine,
+        rng: &mut R,
+    ) -> Result<Cipher<C>, Error> {
+        let plaintext = packing.pack(values)?;
+        Ok(Self::encrypt(&plaintext, key, rng))
+    }
+
+    /// Decrypts a cipher of [`ExponentialElgamal::encrypt_packed`], or a sum of such ciphers,
+    /// with a discrete log over [`Packing::total_bits`] bits.
+    pub fn decrypt_packed<D: DiscreteLog<C>>(
+        cipher: Cipher<C>,
+        packing: &Packing,
+        key: &C::ScalarField,
+        solver: &D,
+    ) -> Result<Vec<u64>, DecryptError> {
+        let packed = Self::decrypt_range(cipher, key, packing.total_bits(), solver)?;
+        Ok(packing.unpack(&packed))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::{BabyStepGiantStep, KeyPair};
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Elgamal = ExponentialElgamal<G1>;
+
+    #[test]
+    fn packing_layout() {
+        let packing = Packing::new::<Scalar>(16, 4).unwrap();
+        let values = [1, 0xffff, 0, 0x1234];
+        let packed: Scalar = packing.pack(&values).unwrap();
+        assert_eq!(packed, Scalar::from(0x1234_0000_ffff_0001u64));
+        assert_eq!(packing.unpack(&packed), values);
+
+        let packing = Packing::new::<Scalar>(64, 3).unwrap();
+        let values = [u64::MAX, 7, u64::MAX - 1];
+        assert_eq!(
+            packing.unpack(&packing.pack::<Scalar>(&values).unwrap()),
+            values
+        );
+
+        assert_eq!(
+            packing.pack::<Scalar>(&[1, 2]),
+            Err(Error::InvalidLength {
+                expected: 3,
+                got: 2
+            })
+        );
+        let packing = Packing::new::<Scalar>(8, 2).unwrap();
+        assert_eq!(
+            packing.pack::<Scalar>(&[1, 256]),
+            Err(Error::ValueTooLarge(1))
+        );
+        assert!(Packing::new::<Scalar>(64, 4).is_err());
+        assert!(Packing::new::<Scalar>(65, 1).is_err());
+        assert!(Packing::new::<Scalar>(0, 1).is_err());
+    }
+
+    #[test]
+    fn packed_encryption() {
+        let rng = &mut test_rng();
+        let keys = KeyPair::<G1>::generate(rng);
+        let solver = BabyStepGiantStep::<G1>::new(12);
+        let packing = Packing::new::<Scalar>(6, 4).unwrap();
+
+        let cipher = Elgamal::encrypt_packed(&[3, 14, 15, 9], &packing, keys.pk(), rng).unwrap();
+        let other = Elgamal::encrypt_packed(&[2, 7, 1, 8], &packing, keys.pk(), rng).unwrap();
+        assert_eq!(
+            Elgamal::decrypt_packed(cipher, &packing, keys.sk(), &solver),
+            Ok(vec![3, 14, 15, 9])
+        );
+        // slot-wise addition without overflow
+        assert_eq!(
+            Elgamal::decrypt_packed(cipher + other, &packing, keys.sk(), &solver),
+            Ok(vec![5, 21, 16, 17])
+        );
+    }
+}
@@ -0,0 +1,489 @@
+//! Zero-knowledge proofs about exponential Elgamal ciphertexts.
+//!
+//! All proofs are sigma protocols made non-interactive via Fiat-Shamir with the crate's
+//! [`Hasher`], each with its own domain separator.
+use super::{Cipher, ExponentialElgamal};
+use crate::encrypt::EncryptionEngine;
+use crate::equality::Bases;
+use crate::hash::Hasher;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+
+const EQUALITY_DOMAIN_SEP: &[u8] = b"fde elgamal plaintext equality";
+const DECRYPTION_DOMAIN_SEP: &[u8] = b"fde elgamal decryption";
+const COMMITMENT_DOMAIN_SEP: &[u8] = b"fde elgamal commitment equality";
+const KNOWLEDGE_DOMAIN_SEP: &[u8] = b"fde elgamal plaintext knowledge";
+
+/// Proof that two ciphertexts, possibly under different encryption keys, encrypt the same
+/// plaintext, e.g. for checking that every recipient of a multi-recipient encryption gets the
+/// same data.
+pub struct PlaintextEqualityProof<C: CurveGroup, D> {
+    pub challenge: C::ScalarField,
+    pub plaintext_claim: C::ScalarField,
+    pub randomness_claims: [C::ScalarField; 2],
+    _digest: PhantomData<D>,
+}
+
+impl<C, D> PlaintextEqualityProof<C, D>
+where
+    C: CurveGroup,
+    D: Digest,
+{
+    /// Proves that `ciphers[i]` is the encryption of `plaintext` under `keys[i]` with
+    /// `randomness[i]`.
+    pub fn new<R: Rng + CryptoRng>(
+        plaintext: &C::ScalarField,
+        randomness: [C::ScalarField; 2],
+        keys: [C::Affine; 2],
+        ciphers: [Cipher<C>; 2],
+        rng: &mut R,
+    ) -> Self {
+        let rand_plaintext = C::ScalarField::rand(rng);
+        let rand_randomness = [C::ScalarField::rand(rng), C::ScalarField::rand(rng)];
+        let generator = C::Affine::generator();
+        let commitments = [0, 1].map(|i| {
+            [
+                generator * rand_randomness[i],
+                generator * rand_plaintext + keys[i] * rand_randomness[i],
+            ]
+        });
+
+        let challenge = Self::challenge(&keys, &ciphers, &commitments);
+        Self {
+            challenge,
+            plaintext_claim: rand_plaintext - challenge * plaintext,
+            randomness_claims: [0, 1].map(|i| rand_randomness[i] - challenge * randomness[i]),
+            _digest: PhantomData,
+        }
+    }
+
+    pub fn verify(&self, keys: [C::Affine; 2], ciphers: [Cipher<C>; 2]) -> bool {
+        if ciphers.iter().any(|cipher| cipher.validate().is_err()) {
+            return false;
+        }
+        let generator = C::Affine::generator();
+        let commitments = [0, 1].map(|i| {
+            [
+                generator * self.randomness_claims[i] + ciphers[i].c0() * self.challenge,
+                generator * self.plaintext_claim
+                    + keys[i] * self.randomness_claims[i]
+                    + ciphers[i].c1() * self.challenge,
+            ]
+        });
+        Self::challenge(&keys, &ciphers, &commitments) == self.challenge
+    }
+
+    fn challenge(
+        keys: &[C::Affine; 2],
+        ciphers: &[Cipher<C>; 2],
+        commitments: &[[C; 2]; 2],
+    ) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(&EQUALITY_DOMAIN_SEP);
+        for i in 0..2 {
+            hasher.update(&keys[i]);
+            hasher.update(&ciphers[i]);
+            hasher.update(&commitments[i][0]);
+            hasher.update(&commitments[i][1]);
+        }
+        C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+    }
+}
+
+/// Proof that a ciphertext decrypts to a given plaintext point `m * g`, i.e. that the decryption
+/// key `x` of `h = x * g` satisfies `c1 - m * g = x * c0`, without revealing `x`.
+pub struct DecryptionProof<C: CurveGroup, D> {
+    pub challenge: C::ScalarField,
+    pub claim: C::ScalarField,
+    _digest: PhantomData<D>,
+}
+
+impl<C, D> DecryptionProof<C, D>
+where
+    C: CurveGroup,
+    D: Digest,
+{
+    /// Decrypts `cipher` to the plaintext point and proves the correctness of the decryption.
+    pub fn new<R: Rng + CryptoRng>(
+        cipher: &Cipher<C>,
+        decryption_key: &C::ScalarField,
+        rng: &mut R,
+    ) -> (C::Affine, Self) {
+        let generator = C::Affine::generator();
+        let encryption_key = (generator * decryption_key).into_affine();
+        let plaintext_point = ExponentialElgamal::decrypt_exp(*cipher, decryption_key);
+        let rand = C::ScalarField::rand(rng);
+        let commitments = [generator * rand, cipher.c0() * rand];
+
+        let challenge = Self::challenge(cipher, &plaintext_point, &encryption_key, &commitments);
+        let proof = Self {
+            challenge,
+            claim: rand - challenge * decryption_key,
+            _digest: PhantomData,
+        };
+        (plaintext_point, proof)
+    }
+
+    pub fn verify(
+        &self,
+        cipher: &Cipher<C>,
+        plaintext_point: &C::Affine,
+        encryption_key: &C::Affine,
+    ) -> bool {
+        if cipher.validate().is_err() {
+            return false;
+        }
+        // c1 - m * g = x * c0
+        let shared_secret = cipher.c1().into_group() - plaintext_point;
+        let commitments = [
+            C::Affine::generator() * self.claim + *encryption_key * self.challenge,
+            cipher.c0() * self.claim + shared_secret * self.challenge,
+        ];
+        Self::challenge(cipher, plaintext_point, encryption_key, &commitments) == self.challenge
+    }
+
+    /// Same as [`Self::verify`] for a plaintext scalar `m`.
+    pub fn verify_plaintext(
+        &self,
+        cipher: &Cipher<C>,
+        plaintext: &C::ScalarField,
+        encryption_key: &C::Affine,
+    ) -> bool {
+        let plaintext_point = (C::Affine::generator() * plaintext).into_affine();
+        self.verify(cipher, &plaintext_point, encryption_key)
+    }
+
+    fn challenge(
+        cipher: &Cipher<C>,
+        plaintext_point: &C::Affine,
+        encryption_key: &C::Affine,
+        commitments: &[C; 2],
+    ) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(&DECRYPTION_DOMAIN_SEP);
+        hasher.update(cipher);
+        hasher.update(plaintext_point);
+        hasher.update(encryption_key);
+        hasher.update(&commitments[0]);
+        hasher.update(&commitments[1]);
+        C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+    }
+}
+
+/// Proof that a ciphertext encrypts the scalar hidden by a Pedersen-style commitment
+/// `com = value * g + blinding * h`. KZG commitments can be linked via the Lagrange bases as
+/// described in [`crate::equality`].
+pub struct CommitmentEqualityProof<C: CurveGroup, D> {
+    pub challenge: C::ScalarField,
+    pub value_claim: C::ScalarField,
+    pub blinding_claim: C::ScalarField,
+    pub randomness_claim: C::ScalarField,
+    _digest: PhantomData<D>,
+}
+
+impl<C, D> CommitmentEqualityProof<C, D>
+where
+    C: CurveGroup,
+    D: Digest,
+{
+    /// Proves that `cipher` encrypts `value` under `encryption_key` with `randomness` and that
+    /// `commitment = bases.commit(value, blinding)`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<R: Rng + CryptoRng>(
+        value: &C::ScalarField,
+        blinding: &C::ScalarField,
+        randomness: &C::ScalarField,
+        bases: &Bases<C>,
+        encryption_key: &C::Affine,
+        commitment: &C::Affine,
+        cipher: &Cipher<C>,
+        rng: &mut R,
+    ) -> Self {
+        let rand_value = C::ScalarField::rand(rng);
+        let rand_blinding = C::ScalarField::rand(rng);
+        let rand_randomness = C::ScalarField::rand(rng);
+        let generator = C::Affine::generator();
+        let commitments = [
+            bases.commit(&rand_value, &rand_blinding),
+            generator * rand_randomness,
+            generator * rand_value + *encryption_key * rand_randomness,
+        ];
+
+        let challenge = Self::challenge(bases, encryption_key, commitment, cipher, &commitments);
+        Self {
+            challenge,
+            value_claim: rand_value - challenge * value,
+            blinding_claim: rand_blinding - challenge * blinding,
+            randomness_claim: rand_randomness - challenge * randomness,
+            _digest: PhantomData,
+        }
+    }
+
+    pub fn verify(
+        &self,
+        bases: &Bases<C>,
+        encryption_key: &C::Affine,
+        commitment: &C::Affine,
+        cipher: &Cipher<C>,
+    ) -> bool {
+        if cipher.validate().is_err() {
+            return false;
+        }
+        let generator = C::Affine::generator();
+        let commitments = [
+            bases.commit(&self.value_claim, &self.blinding_claim) + *commitment * self.challenge,
+            generator * self.randomness_claim + cipher.c0() * self.challenge,
+            generator * self.value_claim
+                + *encryption_key * self.randomness_claim
+                + cipher.c1() * self.challenge,
+        ];
+        Self::challenge(bases, encryption_key, commitment, cipher, &commitments) == self.challenge
+    }
+
+    fn challenge(
+        bases: &Bases<C>,
+        encryption_key: &C::Affine,
+        commitment: &C::Affine,
+        cipher: &Cipher<C>,
+        commitments: &[C; 3],
+    ) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(&COMMITMENT_DOMAIN_SEP);
+        hasher.update(&bases.g);
+        hasher.update(&bases.h);
+        hasher.update(encryption_key);
+        hasher.update(commitment);
+        hasher.update(cipher);
+        for k in commitments {
+            hasher.update(k);
+        }
+        C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+    }
+}
+
+/// Proof of knowledge of the plaintext `m` and randomness `y` of a ciphertext
+/// `(y * g, m * g + y * h)`, bound to a `context`, e.g. the identity of the sender and the order.
+///
+/// Without it, anyone can copy or rerandomize another party's ciphertext and submit it as their
+/// own. A proof for one context doesn't verify for another, thus replayed ciphertexts are rejected
+/// even if the proof is copied along.
+pub struct PlaintextKnowledgeProof<C: CurveGroup, D> {
+    pub challenge: C::ScalarField,
+    pub plaintext_claim: C::ScalarField,
+    pub randomness_claim: C::ScalarField,
+    _digest: PhantomData<D>,
+}
+
+impl<C, D> PlaintextKnowledgeProof<C, D>
+where
+    C: CurveGroup,
+    D: Digest,
+{
+    /// Proves that `cipher` is the encryption of `plaintext` under `encryption_key` with
+    /// `randomness`.
+    pub fn new<R: Rng + CryptoRng>(
+        plaintext: &C::ScalarField,
+        randomness: &C::ScalarField,
+        encryption_key: &C::Affine,
+        cipher: &Cipher<C>,
+        context: &[u8],
+        rng: &mut R,
+    ) -> Self {
+        let rand_plaintext = C::ScalarField::rand(rng);
+        let rand_randomness = C::ScalarField::rand(rng);
+        let generator = C::Affine::generator();
+        let commitments = [
+            generator * rand_randomness,
+            generator * rand_plaintext + *encryption_key * rand_randomness,
+        ];
+
+        let challenge = Self::challenge(encryption_key, cipher, context, &commitments);
+        Self {
+            challenge,
+            plaintext_claim: rand_plaintext - challenge * plaintext,
+            randomness_claim: rand_randomness - challenge * randomness,
+            _digest: PhantomData,
+        }
+    }
+
+    pub fn verify(&self, encryption_key: &C::Affine, cipher: &Cipher<C>, context: &[u8]) -> bool {
+        if cipher.validate().is_err() {
+            return false;
+        }
+        let generator = C::Affine::generator();
+        let commitments = [
+            generator * self.randomness_claim + cipher.c0() * self.challenge,
+            generator * self.plaintext_claim
+                + *encryption_key * self.randomness_claim
+                + cipher.c1() * self.challenge,
+        ];
+        Self::challenge(encryption_key, cipher, context, &commitments) == self.challenge
+    }
+
+    fn challenge(
+        encryption_key: &C::Affine,
+        cipher: &Cipher<C>,
+        context: &[u8],
+        commitments: &[C; 2],
+    ) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(&KNOWLEDGE_DOMAIN_SEP);
+        hasher.update(&context);
+        hasher.update(encryption_key);
+        hasher.update(cipher);
+        hasher.update(&commitments[0]);
+        hasher.update(&commitments[1]);
+        C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+    }
+}
+
+impl<C: CurveGroup> ExponentialElgamal<C> {
+    /// Encrypts `data` and proves knowledge of the plaintext, see [`PlaintextKnowledgeProof`].
+    pub fn encrypt_with_proof<D: Digest, R: Rng + CryptoRng>(
+        data: &C::ScalarField,
+        key: &C::Affine,
+        context: &[u8],
+        rng: &mut R,
+    ) -> (Cipher<C>, PlaintextKnowledgeProof<C, D>) {
+        let (cipher, randomness) = Self::encrypt_returning_randomness(data, key, rng);
+        let proof = PlaintextKnowledgeProof::new(data, &randomness, key, &cipher, context, rng);
+        (cipher, proof)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::KeyPair;
+    use crate::tests::test_rng;
+    use crate::tests::{G1Affine, Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+    use ark_std::Zero;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Elgamal = ExponentialElgamal<G1>;
+    type Keys = KeyPair<G1>;
+
+    #[test]
+    fn plaintext_equality() {
+        let rng = &mut test_rng();
+        let keys = [*Keys::generate(rng).pk(), *Keys::generate(rng).pk()];
+        let plaintext = Scalar::rand(rng);
+        let (c1, r1) = Elgamal::encrypt_returning_randomness(&plaintext, &keys[0], rng);
+        let (c2, r2) = Elgamal::encrypt_returning_randomness(&plaintext, &keys[1], rng);
+        let ciphers = [c1, c2];
+
+        let proof =
+            PlaintextEqualityProof::<G1, TestHash>::new(&plaintext, [r1, r2], keys, ciphers, rng);
+        assert!(proof.verify(keys, ciphers));
+        // swapped keys
+        assert!(!proof.verify([keys[1], keys[0]], ciphers));
+
+        // different plaintexts
+        let other = plaintext + Scalar::from(1u8);
+        let (c3, r3) = Elgamal::encrypt_returning_randomness(&other, &keys[1], rng);
+        let proof =
+            PlaintextEqualityProof::<G1, TestHash>::new(&plaintext, [r1, r3], keys, [c1, c3], rng);
+        assert!(!proof.verify(keys, [c1, c3]));
+    }
+
+    #[test]
+    fn verifiable_decryption() {
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let plaintext = Scalar::from(424242u32);
+        let cipher = Elgamal::encrypt(&plaintext, keys.pk(), rng);
+
+        let (plaintext_point, proof) =
+            DecryptionProof::<G1, TestHash>::new(&cipher, keys.sk(), rng);
+        assert!(proof.verify(&cipher, &plaintext_point, keys.pk()));
+        assert!(proof.verify_plaintext(&cipher, &plaintext, keys.pk()));
+
+        // wrong plaintext
+        assert!(!proof.verify_plaintext(&cipher, &(plaintext + Scalar::from(1u8)), keys.pk()));
+        // another key
+        let other = Keys::generate(rng);
+        assert!(!proof.verify_plaintext(&cipher, &plaintext, other.pk()));
+        // decryption with the wrong key
+        let (wrong_point, proof) = DecryptionProof::<G1, TestHash>::new(&cipher, other.sk(), rng);
+        assert!(!proof.verify(&cipher, &wrong_point, keys.pk()));
+    }
+
+    #[test]
+    fn commitment_equality() {
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let bases = Bases::<G1>::new(
+            (G1Affine::generator() * Scalar::rand(rng)).into_affine(),
+            (G1Affine::generator() * Scalar::rand(rng)).into_affine(),
+        );
+        let value = Scalar::rand(rng);
+        let blinding = Scalar::rand(rng);
+        let commitment = bases.commit(&value, &blinding).into_affine();
+        let (cipher, randomness) = Elgamal::encrypt_returning_randomness(&value, keys.pk(), rng);
+
+        let proof = CommitmentEqualityProof::<G1, TestHash>::new(
+            &value,
+            &blinding,
+            &randomness,
+            &bases,
+            keys.pk(),
+            &commitment,
+            &cipher,
+            rng,
+        );
+        assert!(proof.verify(&bases, keys.pk(), &commitment, &cipher));
+
+        // the ciphertext encrypts another value
+        let other = Elgamal::encrypt(&(value + Scalar::from(1u8)), keys.pk(), rng);
+        assert!(!proof.verify(&bases, keys.pk(), &commitment, &other));
+        let proof = CommitmentEqualityProof::<G1, TestHash>::new(
+            &value,
+            &blinding,
+            &randomness,
+            &bases,
+            keys.pk(),
+            &commitment,
+            &other,
+            rng,
+        );
+        assert!(!proof.verify(&bases, keys.pk(), &commitment, &other));
+    }
+
+    #[test]
+    fn plaintext_knowledge() {
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let plaintext = Scalar::rand(rng);
+        let (cipher, proof) =
+            Elgamal::encrypt_with_proof::<TestHash, _>(&plaintext, keys.pk(), b"seller 1", rng);
+        assert!(proof.verify(keys.pk(), &cipher, b"seller 1"));
+
+        // replayed by another party or under another key
+        assert!(!proof.verify(keys.pk(), &cipher, b"seller 2"));
+        let other = Keys::generate(rng);
+        assert!(!proof.verify(other.pk(), &cipher, b"seller 1"));
+        // mauled ciphertexts
+        let rerandomized = cipher.rerandomize(keys.pk(), rng);
+        assert!(!proof.verify(keys.pk(), &rerandomized, b"seller 1"));
+        let shifted = cipher
+            + Elgamal::encrypt_with_randomness(&Scalar::from(1u8), keys.pk(), &Scalar::zero());
+        assert!(!proof.verify(keys.pk(), &shifted, b"seller 1"));
+        // proof with wrong witness
+        let (cipher, randomness) =
+            Elgamal::encrypt_returning_randomness(&plaintext, keys.pk(), rng);
+        let proof = PlaintextKnowledgeProof::<G1, TestHash>::new(
+            &(plaintext + Scalar::from(1u8)),
+            &randomness,
+            keys.pk(),
+            &cipher,
+            b"seller 1",
+            rng,
+        );
+        assert!(!proof.verify(keys.pk(), &cipher, b"seller 1"));
+    }
+}
@@ -0,0 +1,118 @@
+//! Per-session Elgamal keys derived from a master secret via a hash-chain ratchet.
+use super::KeyPair;
+use crate::hash::expand;
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_std::marker::PhantomData;
+use digest::Digest;
+use thiserror::Error as ErrorT;
+use zeroize::Zeroizing;
+
+const INIT_DOMAIN_SEP: &[u8] = b"fde key ratchet init";
+const CHAIN_DOMAIN_SEP: &[u8] = b"fde key ratchet chain";
+const SESSION_DOMAIN_SEP: &[u8] = b"fde key ratchet session";
+const CHAIN_KEY_SIZE: usize = 32;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("session {requested} precedes the current session {current}")]
+    SessionInPast { current: u64, requested: u64 },
+}
+
+/// The chain starts at `ck_0 = H("init", master)`. Session `i` uses the key pair with secret
+/// `H("session", ck_i)` reduced to a scalar, then the chain advances to
+/// `ck_{i+1} = H("chain", ck_i)` and `ck_i` is wiped. Both derivations are one-way, thus revealing
+/// a session secret key, e.g. when the seller reveals it upon payment, doesn't expose any other
+/// session, and a compromised ratchet state doesn't expose sessions before it.
+pub struct KeyRatchet<C, D> {
+    chain_key: Zeroizing<[u8; CHAIN_KEY_SIZE]>,
+    session: u64,
+    _marker: PhantomData<(C, D)>,
+}
+
+impl<C: CurveGroup, D: Digest + Clone> KeyRatchet<C, D> {
+    pub fn new(master_secret: &[u8]) -> Self {
+        let mut chain_key = Zeroizing::new([0u8; CHAIN_KEY_SIZE]);
+        expand::<D>(INIT_DOMAIN_SEP, master_secret, &mut *chain_key);
+        Self {
+            chain_key,
+            session: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Index of the session whose key [`Self::next_key`] returns.
+    pub fn session(&self) -> u64 {
+        self.session
+    }
+
+    /// Returns the key pair of the current session and advances the ratchet.
+    pub fn next_key(&mut self) -> KeyPair<C> {
+        let mut wide = Zeroizing::new(vec![
+            0u8;
+            (C::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(8)
+                + 16
+        ]);
+        expand::<D>(SESSION_DOMAIN_SEP, &*self.chain_key, &mut wide);
+        let key_pair = KeyPair::from_secret_key(C::ScalarField::from_le_bytes_mod_order(&wide));
+        self.advance();
+        key_pair
+    }
+
+    /// Advances the ratchet to `session` without deriving the skipped keys, e.g. for recovering
+    /// from a persisted state.
+    pub fn skip_to(&mut self, session: u64) -> Result<(), Error> {
+        if session < self.session {
+            return Err(Error::SessionInPast {
+                current: self.session,
+                requested: session,
+            });
+        }
+        while self.session < session {
+            self.advance();
+        }
+        Ok(())
+    }
+
+    fn advance(&mut self) {
+        let mut next = Zeroizing::new([0u8; CHAIN_KEY_SIZE]);
+        expand::<D>(CHAIN_DOMAIN_SEP, &*self.chain_key, &mut *next);
+        self.chain_key = next;
+        self.session += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Ratchet = KeyRatchet<G1, TestHash>;
+
+    #[test]
+    fn key_ratchet() {
+        let mut ratchet = Ratchet::new(b"master secret");
+        let keys: Vec<_> = (0..4).map(|_| *ratchet.next_key().sk()).collect();
+        assert_eq!(ratchet.session(), 4);
+        for i in 0..keys.len() {
+            assert!(keys[i + 1..].iter().all(|key| *key != keys[i]));
+        }
+
+        // deterministic in the master secret
+        let mut other = Ratchet::new(b"master secret");
+        other.skip_to(2).unwrap();
+        assert_eq!(*other.next_key().sk(), keys[2]);
+        assert_eq!(
+            other.skip_to(1),
+            Err(Error::SessionInPast {
+                current: 3,
+                requested: 1
+            })
+        );
+        other.skip_to(3).unwrap();
+        assert_eq!(*other.next_key().sk(), keys[3]);
+        assert_ne!(*Ratchet::new(b"other secret").next_key().sk(), keys[0]);
+    }
+}
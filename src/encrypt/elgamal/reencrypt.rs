@@ -0,0 +1,164 @@
+//! Single-hop proxy re-encryption of exponential Elgamal ciphertexts, e.g. from a seller to a
+//! buyer via a proxy that learns neither the plaintexts nor the seller's key.
+use super::{Cipher, DecryptError, DiscreteLog, MAX_BITS};
+use crate::encrypt::constant_time;
+use crate::hash::Hasher;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+use zeroize::Zeroize;
+
+const DOMAIN_SEP: &[u8] = b"fde proxy re-encryption";
+
+/// Allows a proxy to re-encrypt ciphers of the delegator to a single delegatee. It must be kept
+/// secret from the delegatee.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReencryptionKey<C: CurveGroup> {
+    /// `x + s`
+    pub key: C::ScalarField,
+    /// `t * g`
+    pub ephemeral: C::Affine,
+}
+
+impl<C: CurveGroup> Drop for ReencryptionKey<C> {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+/// A cipher re-encrypted by the proxy, `cipher.c1()` equals `m * g - s * c0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReencryptedCipher<C: CurveGroup> {
+    pub cipher: Cipher<C>,
+    /// `t * g` of the re-encryption key
+    pub ephemeral: C::Affine,
+}
+
+/// The delegator with key `x` derives a blinding scalar `s = H(t * h', t * g)` from a
+/// Diffie-Hellman exchange with the delegatee's key `h'` and hands the re-encryption key
+/// `(x + s, t * g)` to a proxy. The proxy turns `(c0, c1)` into `(c0, c1 - (x + s) * c0)`, which
+/// only the delegatee can open by recomputing `s` and adding `s * c0`.
+///
+/// Neither the proxy nor the delegatee alone learn `x` or the plaintext of ciphers that were not
+/// re-encrypted, but a proxy colluding with the delegatee recovers `x`. The delegatee's secret key
+/// is not needed to create the re-encryption key.
+pub struct ProxyReencryption<C, D>(PhantomData<(C, D)>);
+
+impl<C: CurveGroup, D: Digest> ProxyReencryption<C, D> {
+    /// Creates the re-encryption key from the delegator's decryption key to the delegatee's
+    /// encryption key.
+    pub fn reencryption_key<R: Rng + CryptoRng>(
+        key: &C::ScalarField,
+        delegatee: &C::Affine,
+        rng: &mut R,
+    ) -> ReencryptionKey<C> {
+        let mut t = C::ScalarField::rand(rng);
+        let ephemeral = (C::Affine::generator() * t).into_affine();
+        let mut shared_secret = (*delegatee * t).into_affine();
+        let mut blinding = Self::blinding(&ephemeral, &shared_secret);
+        let reencryption_key = ReencryptionKey {
+            key: *key + blinding,
+            ephemeral,
+        };
+        t.zeroize();
+        shared_secret.zeroize();
+        blinding.zeroize();
+        reencryption_key
+    }
+
+    /// Re-encrypts `cipher` without learning its plaintext.
+    pub fn reencrypt(cipher: &Cipher<C>, key: &ReencryptionKey<C>) -> ReencryptedCipher<C> {
+        let c1 = cipher.c1().into_group() - cipher.c0() * key.key;
+        ReencryptedCipher {
+            cipher: Cipher([cipher.c0(), c1.into_affine()]),
+            ephemeral: key.ephemeral,
+        }
+    }
+
+    /// Computes the plaintext point `m * g` with the delegatee's decryption key.
+    pub fn decrypt_exp(cipher: &ReencryptedCipher<C>, key: &C::ScalarField) -> C::Affine {
+        let mut shared_secret = constant_time::mul::<C>(&cipher.ephemeral, key).into_affine();
+        let mut blinding = Self::blinding(&cipher.ephemeral, &shared_secret);
+        let plaintext_point = (cipher.cipher.c1()
+            + constant_time::mul::<C>(&cipher.cipher.c0(), &blinding))
+        .into_affine();
+        shared_secret.zeroize();
+        blinding.zeroize();
+        plaintext_point
+    }
+
+    /// Decrypts a plaintext smaller than `2^MAX_BITS` with the delegatee's decryption key, see
+    /// [`super::ExponentialElgamal::decrypt_with`].
+    pub fn decrypt_with<S: DiscreteLog<C>>(
+        cipher: &ReencryptedCipher<C>,
+        key: &C::ScalarField,
+        solver: &S,
+    ) -> Result<C::ScalarField, DecryptError> {
+        let bits = MAX_BITS as u32;
+        solver
+            .solve(Self::decrypt_exp(cipher, key), bits)
+            .map(C::ScalarField::from)
+            .ok_or(DecryptError::BruteForceExceeded { bits })
+    }
+
+    fn blinding(ephemeral: &C::Affine, shared_secret: &C::Affine) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(ephemeral);
+        hasher.update(shared_secret);
+        hasher.next_scalar(DOMAIN_SEP)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::{BabyStepGiantStep, ExponentialElgamal, KeyPair};
+    use crate::encrypt::EncryptionEngine;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Elgamal = ExponentialElgamal<G1>;
+    type Proxy = ProxyReencryption<G1, TestHash>;
+
+    #[test]
+    fn proxy_reencryption() {
+        let rng = &mut test_rng();
+        let seller = KeyPair::<G1>::generate(rng);
+        let buyer = KeyPair::<G1>::generate(rng);
+        let solver = BabyStepGiantStep::<G1>::new(10);
+        let reencryption_key = Proxy::reencryption_key(seller.sk(), buyer.pk(), rng);
+
+        let plaintext = Scalar::from(123456u32);
+        let cipher = Elgamal::encrypt(&plaintext, seller.pk(), rng);
+        let reencrypted = Proxy::reencrypt(&cipher, &reencryption_key);
+        assert_eq!(
+            Proxy::decrypt_with(&reencrypted, buyer.sk(), &solver),
+            Ok(plaintext)
+        );
+        // the re-encrypted cipher opens neither under the seller's key nor as a plain cipher
+        assert_ne!(
+            Elgamal::decrypt_exp(reencrypted.cipher, seller.sk()),
+            Elgamal::decrypt_exp(cipher, seller.sk())
+        );
+        assert_ne!(
+            Elgamal::decrypt_exp(reencrypted.cipher, buyer.sk()),
+            Elgamal::decrypt_exp(cipher, seller.sk())
+        );
+
+        // re-encryption commutes with the homomorphism
+        let other = Elgamal::encrypt(&Scalar::from(4u8), seller.pk(), rng);
+        let mut sum = Proxy::reencrypt(&other, &reencryption_key);
+        sum.cipher += reencrypted.cipher;
+        assert_eq!(
+            Proxy::decrypt_with(&sum, buyer.sk(), &solver),
+            Ok(plaintext + Scalar::from(4u8))
+        );
+
+        let eavesdropper = KeyPair::<G1>::generate(rng);
+        assert!(Proxy::decrypt_with(&reencrypted, eavesdropper.sk(), &solver).is_err());
+    }
+}
@@ -1,9 +1,13 @@
 use super::utils::shift_scalar;
-use super::MAX_BITS;
+use super::{Cipher, DecryptError, DiscreteLog, ExponentialElgamal, MAX_BITS};
+use crate::dleq::Proof as DleqProof;
 use crate::encrypt::EncryptionEngine;
+use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::fields::PrimeField;
 use ark_ff::BigInteger;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::Zero;
+use digest::Digest;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
@@ -25,17 +29,12 @@ impl<const N: usize, S: PrimeField> SplitScalar<N, S> {
         rng: &mut R,
     ) -> ([E::Cipher; N], S)
     where
-        E: EncryptionEngine<PlainText = S>,
+        E: EncryptionEngine<PlainText = S, Randomness = S>,
         E::Cipher: ark_std::fmt::Debug,
-        R: Rng,
+        R: Rng + CryptoRng,
     {
         let rands: Vec<S> = (0..N).map(|_| S::rand(rng)).collect();
-        let ciphers: Vec<E::Cipher> = self
-            .0
-            .iter()
-            .zip(&rands)
-            .map(|(s, r)| E::encrypt_with_randomness(s, encryption_key, r))
-            .collect();
+        let ciphers = E::encrypt_batch_with_randomness(&self.0, encryption_key, &rands);
 
         let shifted_rand_sum = sum_shifted(&rands);
 
@@ -48,6 +47,58 @@ impl<const N: usize, S: PrimeField> SplitScalar<N, S> {
     }
 }
 
+/// Exponential Elgamal encryption of a full scalar split into `N` limbs of [`MAX_BITS`] bits.
+///
+/// The limb ciphers sum up to an encryption of the scalar with randomness `r`, thus a DLEQ proof
+/// of `r` between `(g, c0)` and `(h, c1 - s * g)` shows that the limbs reconstruct the scalar
+/// committed to in `s * g`. It does not show that the limbs are in the brute-forceable range, which
+/// requires range proofs, see [`crate::veck::kzg::elgamal::EncryptionProof`].
+pub struct SplitEncryption<const N: usize, C: CurveGroup, D> {
+    pub ciphers: [Cipher<C>; N],
+    pub proof: DleqProof<C, D>,
+}
+
+impl<const N: usize, C: CurveGroup, D: Digest> SplitEncryption<N, C, D> {
+    pub fn new<R: Rng + CryptoRng>(
+        scalar: &C::ScalarField,
+        encryption_key: &C::Affine,
+        rng: &mut R,
+    ) -> Self {
+        let (ciphers, randomness) = SplitScalar::<N, _>::from(*scalar)
+            .encrypt::<ExponentialElgamal<C>, _>(encryption_key, rng);
+        let proof = DleqProof::new(&randomness, C::Affine::generator(), *encryption_key, rng);
+        Self { ciphers, proof }
+    }
+
+    /// Checks that the limbs reconstruct the scalar of `commitment = s * g`.
+    pub fn verify(&self, commitment: &C::Affine, encryption_key: &C::Affine) -> bool {
+        if self.ciphers.iter().any(|cipher| cipher.validate().is_err()) {
+            return false;
+        }
+        let (c0, c1) = Cipher::shifted_sum(&self.ciphers);
+        self.proof.verify(
+            C::Affine::generator(),
+            c0,
+            *encryption_key,
+            c1 - commitment.into_group(),
+        )
+    }
+
+    /// Decrypts every limb with the given solver and recombines them, fails if any limb is out of
+    /// range.
+    pub fn decrypt<S: DiscreteLog<C>>(
+        &self,
+        key: &C::ScalarField,
+        solver: &S,
+    ) -> Result<C::ScalarField, DecryptError> {
+        let mut limbs = [C::ScalarField::zero(); N];
+        for (limb, cipher) in limbs.iter_mut().zip(&self.ciphers) {
+            *limb = ExponentialElgamal::decrypt_with(*cipher, key, solver)?;
+        }
+        Ok(SplitScalar::new(limbs).reconstruct())
+    }
+}
+
 #[cfg(not(feature = "parallel"))]
 fn sum_shifted<S: PrimeField>(splits: &[S]) -> S {
     splits
@@ -85,12 +136,17 @@ impl<const N: usize, S: PrimeField> From<S> for SplitScalar<N, S> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::encrypt::elgamal::{BabyStepGiantStep, KeyPair, DEFAULT_TABLE_BITS};
+    use crate::tests::test_rng;
+    use crate::tests::TestHash;
     use crate::tests::{G1Affine, Scalar, TestCurve, N};
     use ark_ec::pairing::Pairing;
     use ark_ec::{AffineRepr, CurveGroup};
-    use ark_std::{test_rng, UniformRand, Zero};
+    use ark_std::UniformRand;
 
     type Elgamal = super::super::ExponentialElgamal<<TestCurve as Pairing>::G1>;
+    type Keys = KeyPair<<TestCurve as Pairing>::G1>;
+    type Encryption = SplitEncryption<{ N }, <TestCurve as Pairing>::G1, TestHash>;
 
     #[test]
     fn scalar_splitting() {
@@ -115,7 +171,8 @@ mod test {
     #[test]
     fn encryption() {
         let rng = &mut test_rng();
-        let encryption_pk = (G1Affine::generator() * Scalar::rand(rng)).into_affine();
+        let keys = Keys::generate(rng);
+        let encryption_pk = *keys.pk();
         let scalar = Scalar::rand(rng);
         let split_scalar = SplitScalar::<{ N }, Scalar>::from(scalar);
 
@@ -128,4 +185,26 @@ mod test {
 
         assert!(long_cipher.check_encrypted_sum(&short_ciphers));
     }
+
+    #[test]
+    fn full_scalar_encryption() {
+        let rng = &mut test_rng();
+        let keys = Keys::generate(rng);
+        let (decryption_key, encryption_key) = (keys.sk(), keys.pk());
+        let scalar = Scalar::rand(rng);
+        let commitment = (G1Affine::generator() * scalar).into_affine();
+
+        let encryption = Encryption::new(&scalar, encryption_key, rng);
+        assert!(encryption.verify(&commitment, encryption_key));
+        let solver = BabyStepGiantStep::new(DEFAULT_TABLE_BITS);
+        assert_eq!(encryption.decrypt(decryption_key, &solver), Ok(scalar));
+
+        // limbs of another scalar
+        let other = (G1Affine::generator() * (scalar + Scalar::from(1u8))).into_affine();
+        assert!(!encryption.verify(&other, encryption_key));
+        // tampered limb
+        let mut tampered = Encryption::new(&scalar, encryption_key, rng);
+        tampered.ciphers[0] += encryption.ciphers[1];
+        assert!(!tampered.verify(&commitment, encryption_key));
+    }
 }
@@ -0,0 +1,265 @@
+//! Streaming encryption of large files with bounded memory, see [`StreamEncryption`].
+use super::{
+    Cipher, CipherError, DecryptError, DiscreteLog, ExponentialElgamal, SplitScalar, MAX_BITS,
+};
+use crate::codec;
+use crate::encrypt::EncryptionEngine;
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::Zero;
+use digest::Digest;
+use thiserror::Error as ErrorT;
+use zeroize::Zeroizing;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("chunk size must be positive")]
+    InvalidChunkSize,
+    #[error("{0} limbs of {MAX_BITS} bits cannot hold an encoded element")]
+    TooFewLimbs(usize),
+    #[error("ciphers of chunk {0} don't match the manifest")]
+    HashMismatch(usize),
+    #[error("io error: {0}")]
+    Io(String),
+    #[error("serialization error: {0}")]
+    Serialization(String),
+    #[error(transparent)]
+    Codec(#[from] codec::Error),
+    #[error(transparent)]
+    Cipher(#[from] CipherError),
+    #[error(transparent)]
+    Decrypt(#[from] DecryptError),
+}
+
+impl From<ark_std::io::Error> for Error {
+    fn from(error: ark_std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+impl From<SerializationError> for Error {
+    fn from(error: SerializationError) -> Self {
+        Self::Serialization(error.to_string())
+    }
+}
+
+/// Metadata of a single encrypted chunk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChunkEntry {
+    /// Number of plaintext bytes in the chunk.
+    pub length: usize,
+    /// Hash of the compressed ciphers of the chunk.
+    pub hash: Vec<u8>,
+}
+
+impl ChunkEntry {
+    /// Number of ciphers of the chunk for `N` limbs per element.
+    pub fn ciphers(&self, limbs: usize) -> usize {
+        codec::encoded_len(self.length) * limbs
+    }
+}
+
+/// Plaintext length and hash of the ciphers of every chunk, so that a buyer can check each chunk
+/// before decrypting it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub chunk_size: usize,
+    pub chunks: Vec<ChunkEntry>,
+}
+
+impl Manifest {
+    /// Total number of plaintext bytes.
+    pub fn length(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.length).sum()
+    }
+}
+
+/// Streaming encryption with `N` limbs per encoded element, hashing the ciphers with `D`.
+///
+/// The input is read in chunks of a fixed number of bytes, every chunk is encoded into field
+/// elements by [`codec::encode`], each element is split into `N` limbs of [`MAX_BITS`] bits (see
+/// [`SplitScalar`]) and the limbs are encrypted with exponential Elgamal. The ciphers of a chunk
+/// are written as compressed points before the next chunk is read, thus only a single chunk is
+/// held in memory. Since every chunk carries its own length element, chunks can be decrypted
+/// independently, e.g. for selling a byte range of the file.
+pub struct StreamEncryption<const N: usize, C, D>(PhantomData<(C, D)>);
+
+impl<const N: usize, C: CurveGroup, D: Digest> StreamEncryption<N, C, D> {
+    /// Encrypts everything `reader` yields in chunks of `chunk_size` bytes and writes the ciphers
+    /// to `writer`.
+    pub fn encrypt<I: Read, O: Write, R: Rng + CryptoRng>(
+        mut reader: I,
+        mut writer: O,
+        key: &C::Affine,
+        chunk_size: usize,
+        rng: &mut R,
+    ) -> Result<Manifest, Error> {
+        if chunk_size == 0 {
+            return Err(Error::InvalidChunkSize);
+        }
+        if N * MAX_BITS < 8 * codec::CHUNK_SIZE {
+            return Err(Error::TooFewLimbs(N));
+        }
+        let mut manifest = Manifest {
+            chunk_size,
+            chunks: Vec::new(),
+        };
+        let mut buffer = Zeroizing::new(vec![0u8; chunk_size]);
+        loop {
+            let length = fill(&mut reader, &mut buffer)?;
+            if length == 0 {
+                break;
+            }
+            let limbs: Zeroizing<Vec<C::ScalarField>> = Zeroizing::new(
+                codec::encode::<C::ScalarField>(&buffer[..length])?
+                    .into_iter()
+                    .flat_map(|element| *SplitScalar::<N, _>::from(element).splits())
+                    .collect(),
+            );
+            let ciphers = ExponentialElgamal::<C>::encrypt_batch(&limbs, key, rng);
+            let mut hasher = D::new();
+            let mut bytes = Vec::new();
+            for cipher in &ciphers {
+                bytes.clear();
+                cipher.serialize_compressed(&mut bytes)?;
+                hasher.update(&bytes);
+                writer.write_all(&bytes)?;
+            }
+            manifest.chunks.push(ChunkEntry {
+                length,
+                hash: hasher.finalize().to_vec(),
+            });
+            if length < chunk_size {
+                break;
+            }
+        }
+        writer.flush()?;
+        Ok(manifest)
+    }
+
+    /// Reads the ciphers of every chunk listed in `manifest` from `reader`, checks them against
+    /// their hash, decrypts them with the given solver and writes the plaintext to `writer`.
+    pub fn decrypt<I: Read, O: Write, S: DiscreteLog<C>>(
+        mut reader: I,
+        mut writer: O,
+        manifest: &Manifest,
+        key: &C::ScalarField,
+        solver: &S,
+    ) -> Result<(), Error> {
+        for (index, chunk) in manifest.chunks.iter().enumerate() {
+            let mut hasher = D::new();
+            let mut ciphers = Vec::with_capacity(chunk.ciphers(N));
+            let mut bytes = vec![0u8; Cipher::<C>::default().compressed_size()];
+            for _ in 0..chunk.ciphers(N) {
+                reader.read_exact(&mut bytes)?;
+                hasher.update(&bytes);
+                let cipher = Cipher::<C>::deserialize_compressed(&*bytes)?;
+                cipher.validate()?;
+                ciphers.push(cipher);
+            }
+            if hasher.finalize().as_slice() != chunk.hash.as_slice() {
+                return Err(Error::HashMismatch(index));
+            }
+
+            let mut elements = Zeroizing::new(Vec::with_capacity(ciphers.len() / N));
+            for limb_ciphers in ciphers.chunks_exact(N) {
+                let mut limbs = [C::ScalarField::zero(); N];
+                for (limb, cipher) in limbs.iter_mut().zip(limb_ciphers) {
+                    *limb = ExponentialElgamal::decrypt_with(*cipher, key, solver)?;
+                }
+                elements.push(SplitScalar::new(limbs).reconstruct());
+            }
+            let plaintext = Zeroizing::new(codec::decode(&elements)?);
+            writer.write_all(&plaintext)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+// reads until `buffer` is full or the reader is exhausted
+fn fill<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<usize, Error> {
+    let mut length = 0;
+    while length < buffer.len() {
+        match reader.read(&mut buffer[length..]) {
+            Ok(0) => break,
+            Ok(read) => length += read,
+            Err(error) if error.kind() == ark_std::io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+    Ok(length)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::{BabyStepGiantStep, KeyPair, DEFAULT_TABLE_BITS};
+    use crate::tests::test_rng;
+    use crate::tests::{TestCurve, TestHash, N};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Stream = StreamEncryption<{ N }, G1, TestHash>;
+
+    #[test]
+    fn stream_encryption() {
+        let rng = &mut test_rng();
+        let keys = KeyPair::<G1>::generate(rng);
+        let solver = BabyStepGiantStep::<G1>::new(DEFAULT_TABLE_BITS);
+        let data: Vec<u8> = (0..50).map(|i| (i * 37 + 11) as u8).collect();
+
+        let mut ciphers = Vec::new();
+        let manifest = Stream::encrypt(&*data, &mut ciphers, keys.pk(), 31, rng).unwrap();
+        assert_eq!(manifest.chunks.len(), 2);
+        assert_eq!(manifest.length(), data.len());
+        let expected: usize = manifest.chunks.iter().map(|c| c.ciphers(N)).sum();
+        let cipher_size = Cipher::<G1>::default().compressed_size();
+        assert_eq!(ciphers.len(), expected * cipher_size);
+
+        let mut plaintext = Vec::new();
+        Stream::decrypt(&*ciphers, &mut plaintext, &manifest, keys.sk(), &solver).unwrap();
+        assert_eq!(plaintext, data);
+
+        // the second chunk is checked before decryption
+        let mut tampered = ciphers.clone();
+        let last = tampered.len() - cipher_size;
+        tampered.copy_within(..cipher_size, last);
+        assert_eq!(
+            Stream::decrypt(&*tampered, &mut Vec::new(), &manifest, keys.sk(), &solver),
+            Err(Error::HashMismatch(1))
+        );
+        assert!(Stream::decrypt(
+            &ciphers[1..],
+            &mut Vec::new(),
+            &manifest,
+            keys.sk(),
+            &solver
+        )
+        .is_err());
+
+        // input of a multiple of the chunk size and empty input
+        let manifest = Stream::encrypt(&data[..31], &mut Vec::new(), keys.pk(), 31, rng).unwrap();
+        assert_eq!(manifest.chunks.len(), 1);
+        let mut ciphers = Vec::new();
+        let manifest = Stream::encrypt(&[][..], &mut ciphers, keys.pk(), 31, rng).unwrap();
+        assert!(manifest.chunks.is_empty() && ciphers.is_empty());
+
+        assert_eq!(
+            Stream::encrypt(&*data, &mut Vec::new(), keys.pk(), 0, rng),
+            Err(Error::InvalidChunkSize)
+        );
+        assert_eq!(
+            StreamEncryption::<4, G1, TestHash>::encrypt(
+                &*data,
+                &mut Vec::new(),
+                keys.pk(),
+                31,
+                rng
+            ),
+            Err(Error::TooFewLimbs(4))
+        );
+    }
+}
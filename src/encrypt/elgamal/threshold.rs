@@ -0,0 +1,286 @@
+//! Threshold decryption of exponential Elgamal ciphertexts with a key shared by [`deal`] and
+//! decryption shares combined by [`combine`].
+use super::Cipher;
+use crate::dleq::Proof as DleqProof;
+use crate::encrypt::constant_time;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Field;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::{UniformRand, Zero};
+use digest::Digest;
+use thiserror::Error as ErrorT;
+use zeroize::Zeroize;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid threshold {threshold} for {parties} parties")]
+    InvalidThreshold { threshold: usize, parties: usize },
+    #[error("got {available} decryption shares, but {required} are required")]
+    InsufficientShares { required: usize, available: usize },
+    #[error("party {0} is not a valid share index")]
+    InvalidIndex(u32),
+    #[error("duplicate decryption share of party {0}")]
+    DuplicateShare(u32),
+    #[error("invalid decryption share of party {0}")]
+    InvalidShare(u32),
+}
+
+/// Feldman commitments `a_j * g` to the coefficients of the sharing polynomial `f`, so that every
+/// party can check its share and anyone can compute the public key share `f(i) * g`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationKey<C: CurveGroup> {
+    pub commitments: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> VerificationKey<C> {
+    /// Number of shares required for decryption.
+    pub fn threshold(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// The encryption key of the shared decryption key.
+    pub fn public_key(&self) -> C::Affine {
+        self.commitments[0]
+    }
+
+    /// Returns `f(index) * g` of party `index`.
+    pub fn share_public_key(&self, index: u32) -> C {
+        let x = C::ScalarField::from(index);
+        // Horner's method in the exponent
+        self.commitments
+            .iter()
+            .rev()
+            .fold(C::zero(), |acc, commitment| acc * x + commitment)
+    }
+
+    pub fn verify_share(&self, share: &KeyShare<C>) -> bool {
+        share.index != 0
+            && C::Affine::generator() * share.secret == self.share_public_key(share.index)
+    }
+}
+
+/// Share `f(index)` of the decryption key, wiped from memory when dropped.
+pub struct KeyShare<C: CurveGroup> {
+    pub index: u32,
+    secret: C::ScalarField,
+}
+
+impl<C: CurveGroup> KeyShare<C> {
+    pub(super) fn new(index: u32, secret: C::ScalarField) -> Self {
+        Self { index, secret }
+    }
+
+    pub(super) fn secret(&self) -> &C::ScalarField {
+        &self.secret
+    }
+
+    /// Computes the decryption share of `cipher` and proves its validity.
+    pub fn decrypt<D: Digest, R: Rng + CryptoRng>(
+        &self,
+        cipher: &Cipher<C>,
+        rng: &mut R,
+    ) -> DecryptionShare<C, D> {
+        DecryptionShare {
+            index: self.index,
+            point: constant_time::mul::<C>(&cipher.c0(), &self.secret).into_affine(),
+            proof: DleqProof::new(&self.secret, C::Affine::generator(), cipher.c0(), rng),
+        }
+    }
+}
+
+impl<C: CurveGroup> Drop for KeyShare<C> {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+/// Decryption share `f(index) * c0` with a DLEQ proof against the public key share of the party.
+pub struct DecryptionShare<C: CurveGroup, D> {
+    pub index: u32,
+    pub point: C::Affine,
+    pub proof: DleqProof<C, D>,
+}
+
+impl<C: CurveGroup, D: Digest> DecryptionShare<C, D> {
+    pub fn verify(&self, cipher: &Cipher<C>, verification_key: &VerificationKey<C>) -> bool {
+        self.index != 0
+            && cipher.validate().is_ok()
+            && self.proof.verify(
+                C::Affine::generator(),
+                verification_key.share_public_key(self.index),
+                cipher.c0(),
+                self.point.into_group(),
+            )
+    }
+}
+
+/// Shares a fresh decryption key among `parties` parties such that any `threshold` of them can
+/// decrypt. Party `i` receives the share with index `i + 1`.
+///
+/// The shares are Feldman's verifiable secret sharing of the key `x`, i.e. the evaluations `f(i)`
+/// of a random polynomial `f` of degree `threshold - 1` with `f(0) = x`.
+pub fn deal<C: CurveGroup, R: Rng + CryptoRng>(
+    threshold: usize,
+    parties: usize,
+    rng: &mut R,
+) -> Result<(VerificationKey<C>, Vec<KeyShare<C>>), Error> {
+    share(C::ScalarField::rand(rng), threshold, parties, rng)
+}
+
+/// Shares `secret` with a random polynomial `f` of degree `threshold - 1` such that `f(0) =
+/// secret`.
+pub(super) fn share<C: CurveGroup, R: Rng + CryptoRng>(
+    secret: C::ScalarField,
+    threshold: usize,
+    parties: usize,
+    rng: &mut R,
+) -> Result<(VerificationKey<C>, Vec<KeyShare<C>>), Error> {
+    if threshold == 0 || threshold > parties || u32::try_from(parties).is_err() {
+        return Err(Error::InvalidThreshold { threshold, parties });
+    }
+    let mut coefficients: Vec<C::ScalarField> = ark_std::iter::once(secret)
+        .chain((1..threshold).map(|_| C::ScalarField::rand(rng)))
+        .collect();
+    let commitments: Vec<C> = coefficients
+        .iter()
+        .map(|a| C::Affine::generator() * a)
+        .collect();
+    let shares = (1..=parties as u32)
+        .map(|index| {
+            let x = C::ScalarField::from(index);
+            let secret = coefficients
+                .iter()
+                .rev()
+                .fold(C::ScalarField::zero(), |acc, a| acc * x + a);
+            KeyShare { index, secret }
+        })
+        .collect();
+    coefficients.zeroize();
+
+    let verification_key = VerificationKey {
+        commitments: C::normalize_batch(&commitments),
+    };
+    Ok((verification_key, shares))
+}
+
+/// Verifies the decryption shares and combines the first `threshold` of them into the plaintext
+/// point `m * g`, whose discrete logarithm can be solved with [`super::DiscreteLog`].
+///
+/// Every decryption share `f(i) * c0` comes with a DLEQ proof against the public key share of its
+/// party, and the shares are combined via Lagrange interpolation in the exponent.
+pub fn combine<C: CurveGroup, D: Digest>(
+    cipher: &Cipher<C>,
+    shares: &[DecryptionShare<C, D>],
+    verification_key: &VerificationKey<C>,
+) -> Result<C::Affine, Error> {
+    let threshold = verification_key.threshold();
+    if shares.len() < threshold {
+        return Err(Error::InsufficientShares {
+            required: threshold,
+            available: shares.len(),
+        });
+    }
+    let shares = &shares[..threshold];
+    for (i, share) in shares.iter().enumerate() {
+        if share.index == 0 {
+            return Err(Error::InvalidIndex(share.index));
+        }
+        if shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(Error::DuplicateShare(share.index));
+        }
+        if !share.verify(cipher, verification_key) {
+            return Err(Error::InvalidShare(share.index));
+        }
+    }
+
+    // x * c0 = sum of lambda_i * f(i) * c0 with the Lagrange coefficients at zero
+    let indices: Vec<u32> = shares.iter().map(|share| share.index).collect();
+    let shared_secret = shares.iter().fold(C::zero(), |acc, share| {
+        acc + share.point * lagrange_coefficient::<C::ScalarField>(share.index, &indices)
+    });
+    Ok((cipher.c1().into_group() - shared_secret).into_affine())
+}
+
+/// Lagrange coefficient of `index` for interpolating at zero from the distinct `indices`.
+pub(super) fn lagrange_coefficient<F: Field>(index: u32, indices: &[u32]) -> F {
+    let xi = F::from(index);
+    let (numerator, denominator) = indices.iter().filter(|&&other| other != index).fold(
+        (F::one(), F::one()),
+        |(num, den), &other| {
+            let xj = F::from(other);
+            (num * xj, den * (xj - xi))
+        },
+    );
+    numerator * denominator.inverse().expect("indices are distinct")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::{BabyStepGiantStep, DiscreteLog, ExponentialElgamal};
+    use crate::encrypt::EncryptionEngine;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Elgamal = ExponentialElgamal<G1>;
+
+    #[test]
+    fn threshold_decryption() {
+        let rng = &mut test_rng();
+        assert!(matches!(
+            deal::<G1, _>(4, 3, rng),
+            Err(Error::InvalidThreshold {
+                threshold: 4,
+                parties: 3
+            })
+        ));
+
+        let (verification_key, key_shares) = deal::<G1, _>(3, 5, rng).unwrap();
+        assert_eq!(verification_key.threshold(), 3);
+        assert!(key_shares
+            .iter()
+            .all(|share| verification_key.verify_share(share)));
+
+        let plaintext = Scalar::from(31337u32);
+        let cipher = Elgamal::encrypt(&plaintext, &verification_key.public_key(), rng);
+        let shares: Vec<DecryptionShare<G1, TestHash>> = key_shares
+            .iter()
+            .map(|share| share.decrypt(&cipher, rng))
+            .collect();
+
+        // any 3 parties can decrypt
+        let solver = BabyStepGiantStep::<G1>::new(10);
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let subset: Vec<DecryptionShare<G1, TestHash>> = subset
+                .into_iter()
+                .map(|i| key_shares[i].decrypt(&cipher, rng))
+                .collect();
+            let point = combine(&cipher, &subset, &verification_key).unwrap();
+            assert_eq!(solver.solve(point, 20), Some(31337));
+        }
+
+        assert_eq!(
+            combine(&cipher, &shares[..2], &verification_key),
+            Err(Error::InsufficientShares {
+                required: 3,
+                available: 2
+            })
+        );
+
+        // invalid decryption share
+        let mut shares = shares;
+        shares[1].point = shares[2].point;
+        assert_eq!(
+            combine(&cipher, &shares, &verification_key),
+            Err(Error::InvalidShare(2))
+        );
+        let index = shares[0].index;
+        shares[1].index = index;
+        assert_eq!(
+            combine(&cipher, &shares, &verification_key),
+            Err(Error::DuplicateShare(1))
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Hybrid encryption of arbitrarily large payloads to Elgamal keys.
+use super::chacha20poly1305::{self, KEY_SIZE, NONCE_SIZE, TAG_SIZE};
+use super::{constant_time, EncryptionEngine};
+use crate::hash::expand;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::CanonicalSerialize;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+use zeroize::{Zeroize, Zeroizing};
+
+pub use chacha20poly1305::Error;
+
+const KDF_DOMAIN_SEP: &[u8] = b"fde hybrid encryption";
+const NONCE: [u8; NONCE_SIZE] = [0; NONCE_SIZE];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HybridCipher<C: CurveGroup> {
+    /// `g^y`
+    pub ephemeral: C::Affine,
+    pub ciphertext: Vec<u8>,
+    pub tag: [u8; TAG_SIZE],
+}
+
+/// An ephemeral Diffie-Hellman key exchange `y * h` against the recipient's Elgamal key
+/// encapsulates a fresh session key, under which the payload is encrypted with
+/// [ChaCha20-Poly1305](super::chacha20poly1305). The ephemeral key is bound to the ciphertext as
+/// associated data. Since every session key is used exactly once, the AEAD nonce is fixed.
+pub struct Hybrid<C, D>(PhantomData<(C, D)>);
+
+impl<C: CurveGroup, D: Digest + Clone> Hybrid<C, D> {
+    // returns the session key and the serialized ephemeral key
+    fn derive_key(
+        ephemeral: &C::Affine,
+        shared_secret: &C::Affine,
+    ) -> (Zeroizing<[u8; KEY_SIZE]>, Vec<u8>) {
+        let mut aad = Vec::new();
+        ephemeral
+            .serialize_compressed(&mut aad)
+            .expect("should not fail");
+        let mut secret = Zeroizing::new(aad.clone());
+        shared_secret
+            .serialize_compressed(&mut *secret)
+            .expect("should not fail");
+        let mut session_key = Zeroizing::new([0u8; KEY_SIZE]);
+        expand::<D>(KDF_DOMAIN_SEP, &secret, &mut *session_key);
+        (session_key, aad)
+    }
+}
+
+impl<C: CurveGroup, D: Digest + Clone> EncryptionEngine for Hybrid<C, D> {
+    type EncryptionKey = C::Affine;
+    type DecryptionKey = C::ScalarField;
+    type Cipher = HybridCipher<C>;
+    type PlainText = Vec<u8>;
+    type Randomness = C::ScalarField;
+    type DecryptError = Error;
+
+    fn encrypt_returning_randomness<R: Rng + CryptoRng>(
+        data: &Self::PlainText,
+        key: &Self::EncryptionKey,
+        rng: &mut R,
+    ) -> (Self::Cipher, Self::Randomness) {
+        let random_nonce = C::ScalarField::rand(rng);
+        (
+            Self::encrypt_with_randomness(data, key, &random_nonce),
+            random_nonce,
+        )
+    }
+
+    /// Encrypts `data` under a session key derived from `randomness`, which must never be
+    /// reused.
+    ///
+    /// # Panics
+    ///
+    /// If `data` exceeds the 256 GiB limit of ChaCha20-Poly1305.
+    fn encrypt_with_randomness(
+        data: &Self::PlainText,
+        key: &Self::EncryptionKey,
+        randomness: &Self::Randomness,
+    ) -> Self::Cipher {
+        let ephemeral = (C::Affine::generator() * randomness).into_affine();
+        let shared_secret = (*key * randomness).into_affine();
+        let (session_key, aad) = Self::derive_key(&ephemeral, &shared_secret);
+        let mut ciphertext = data.clone();
+        let tag = chacha20poly1305::seal(&session_key, &NONCE, &aad, &mut ciphertext)
+            .expect("payload too large");
+        HybridCipher {
+            ephemeral,
+            ciphertext,
+            tag,
+        }
+    }
+
+    fn decrypt(
+        cipher: Self::Cipher,
+        key: &Self::DecryptionKey,
+    ) -> Result<Self::PlainText, Self::DecryptError> {
+        let mut shared_secret = constant_time::mul::<C>(&cipher.ephemeral, key).into_affine();
+        let (session_key, aad) = Self::derive_key(&cipher.ephemeral, &shared_secret);
+        shared_secret.zeroize();
+        let mut data = cipher.ciphertext;
+        chacha20poly1305::open(&session_key, &NONCE, &aad, &mut data, &cipher.tag)?;
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::KeyPair;
+    use crate::tests::test_rng;
+    use crate::tests::{TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Engine = Hybrid<G1, TestHash>;
+
+    #[test]
+    fn hybrid_encryption() {
+        let rng = &mut test_rng();
+        let keys = KeyPair::<G1>::generate(rng);
+
+        let large: Vec<u8> = (0..1 << 20).map(|i| i as u8).collect();
+        for data in [vec![], b"short message".to_vec(), large] {
+            let (cipher, randomness) = Engine::encrypt_returning_randomness(&data, keys.pk(), rng);
+            assert_eq!(
+                cipher,
+                Engine::encrypt_with_randomness(&data, keys.pk(), &randomness)
+            );
+            assert_eq!(cipher.ciphertext.len(), data.len());
+            assert_eq!(Engine::decrypt(cipher, keys.sk()), Ok(data));
+        }
+
+        let data = b"some payload".to_vec();
+        let cipher = Engine::encrypt(&data, keys.pk(), rng);
+        let other = KeyPair::<G1>::generate(rng);
+        assert_eq!(
+            Engine::decrypt(cipher.clone(), other.sk()),
+            Err(Error::InvalidTag)
+        );
+        let mut tampered = cipher.clone();
+        tampered.ciphertext[0] ^= 1;
+        assert_eq!(Engine::decrypt(tampered, keys.sk()), Err(Error::InvalidTag));
+        // swapping the ephemeral key changes the session key and the associated data
+        let mut tampered = cipher;
+        tampered.ephemeral = Engine::encrypt(&data, keys.pk(), rng).ephemeral;
+        assert_eq!(Engine::decrypt(tampered, keys.sk()), Err(Error::InvalidTag));
+    }
+}
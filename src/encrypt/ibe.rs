@@ -0,0 +1,236 @@
+//! Boneh-Franklin identity-based encryption of arbitrarily large payloads, where a buyer is
+//! addressed by an identity string, e.g. an email address or ENS name, before having a key.
+use super::chacha20poly1305::{self, KEY_SIZE, NONCE_SIZE, TAG_SIZE};
+use super::EncryptionEngine;
+use crate::hash::{expand, hash_to_curve};
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_serialize::CanonicalSerialize;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+use zeroize::{Zeroize, Zeroizing};
+
+pub use chacha20poly1305::Error;
+
+const IDENTITY_DOMAIN_SEP: &[u8] = b"fde ibe identity";
+const KDF_DOMAIN_SEP: &[u8] = b"fde ibe encryption";
+const NONCE: [u8; NONCE_SIZE] = [0; NONCE_SIZE];
+
+/// The private key generator holding the master secret `s`, which can decrypt every ciphertext.
+pub struct Pkg<P: Pairing, D> {
+    master_secret: P::ScalarField,
+    master_key: P::G1Affine,
+    _digest: PhantomData<D>,
+}
+
+impl<P: Pairing, D> Drop for Pkg<P, D> {
+    fn drop(&mut self) {
+        self.master_secret.zeroize();
+    }
+}
+
+impl<P: Pairing, D: Digest> Pkg<P, D> {
+    pub fn generate<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        let master_secret = P::ScalarField::rand(rng);
+        let master_key = (P::G1Affine::generator() * master_secret).into_affine();
+        Self {
+            master_secret,
+            master_key,
+            _digest: PhantomData,
+        }
+    }
+
+    /// The public key `s * g1`, which together with an identity forms its encryption key.
+    pub fn master_key(&self) -> &P::G1Affine {
+        &self.master_key
+    }
+
+    /// Issues the decryption key of `identity`, which must be handed to its owner only.
+    pub fn extract(&self, identity: &[u8]) -> IdentitySecretKey<P> {
+        let key = (hash_identity::<P, D>(identity) * self.master_secret).into_affine();
+        IdentitySecretKey(key)
+    }
+}
+
+/// Encryption key of an identity under the master key `P = s * g1` of a PKG, with the identity
+/// hashed to `Q = H(id)` in G2.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdentityKey<P: Pairing> {
+    pub master_key: P::G1Affine,
+    pub identity: Vec<u8>,
+}
+
+impl<P: Pairing> IdentityKey<P> {
+    pub fn new(master_key: P::G1Affine, identity: &[u8]) -> Self {
+        Self {
+            master_key,
+            identity: identity.to_vec(),
+        }
+    }
+}
+
+/// Decryption key `s * H(id)` issued by the PKG.
+pub struct IdentitySecretKey<P: Pairing>(P::G2Affine);
+
+impl<P: Pairing> Drop for IdentitySecretKey<P> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<P: Pairing> IdentitySecretKey<P> {
+    /// Checks that the key was issued for `key.identity` under `key.master_key`, i.e.
+    /// `e(g1, d) = e(P, Q)`.
+    pub fn verify<D: Digest>(&self, key: &IdentityKey<P>) -> bool {
+        let hashed = hash_identity::<P, D>(&key.identity);
+        P::pairing(P::G1Affine::generator(), self.0) == P::pairing(key.master_key, hashed)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IbeCipher<P: Pairing> {
+    /// `r * g1`
+    pub ephemeral: P::G1Affine,
+    pub ciphertext: Vec<u8>,
+    pub tag: [u8; TAG_SIZE],
+}
+
+/// Encryption draws `r` and derives a session key from `U = r * g1` and `e(r * P, Q)`, which the
+/// recipient recomputes as `e(U, d)`. The payload is encrypted under the session key with
+/// [ChaCha20-Poly1305](super::chacha20poly1305) like in [`Hybrid`](super::hybrid::Hybrid), with
+/// `U` as associated data.
+pub struct Ibe<P, D>(PhantomData<(P, D)>);
+
+impl<P: Pairing, D: Digest + Clone> Ibe<P, D> {
+    // returns the session key and the serialized ephemeral key
+    fn derive_key(
+        ephemeral: &P::G1Affine,
+        shared_secret: &PairingOutput<P>,
+    ) -> (Zeroizing<[u8; KEY_SIZE]>, Vec<u8>) {
+        let mut aad = Vec::new();
+        ephemeral
+            .serialize_compressed(&mut aad)
+            .expect("should not fail");
+        let mut secret = Zeroizing::new(aad.clone());
+        shared_secret
+            .serialize_compressed(&mut *secret)
+            .expect("should not fail");
+        let mut session_key = Zeroizing::new([0u8; KEY_SIZE]);
+        expand::<D>(KDF_DOMAIN_SEP, &secret, &mut *session_key);
+        (session_key, aad)
+    }
+}
+
+impl<P: Pairing, D: Digest + Clone> EncryptionEngine for Ibe<P, D> {
+    type EncryptionKey = IdentityKey<P>;
+    type DecryptionKey = IdentitySecretKey<P>;
+    type Cipher = IbeCipher<P>;
+    type PlainText = Vec<u8>;
+    type Randomness = P::ScalarField;
+    type DecryptError = Error;
+
+    fn encrypt_returning_randomness<R: Rng + CryptoRng>(
+        data: &Self::PlainText,
+        key: &Self::EncryptionKey,
+        rng: &mut R,
+    ) -> (Self::Cipher, Self::Randomness) {
+        let random_nonce = P::ScalarField::rand(rng);
+        (
+            Self::encrypt_with_randomness(data, key, &random_nonce),
+            random_nonce,
+        )
+    }
+
+    /// Encrypts `data` under a session key derived from `randomness`, which must never be
+    /// reused.
+    ///
+    /// # Panics
+    ///
+    /// If `data` exceeds the 256 GiB limit of ChaCha20-Poly1305.
+    fn encrypt_with_randomness(
+        data: &Self::PlainText,
+        key: &Self::EncryptionKey,
+        randomness: &Self::Randomness,
+    ) -> Self::Cipher {
+        let ephemeral = (P::G1Affine::generator() * randomness).into_affine();
+        let hashed = hash_identity::<P, D>(&key.identity);
+        let mut shared_secret = P::pairing(key.master_key * randomness, hashed);
+        let (session_key, aad) = Self::derive_key(&ephemeral, &shared_secret);
+        shared_secret.zeroize();
+        let mut ciphertext = data.clone();
+        let tag = chacha20poly1305::seal(&session_key, &NONCE, &aad, &mut ciphertext)
+            .expect("payload too large");
+        IbeCipher {
+            ephemeral,
+            ciphertext,
+            tag,
+        }
+    }
+
+    fn decrypt(
+        cipher: Self::Cipher,
+        key: &Self::DecryptionKey,
+    ) -> Result<Self::PlainText, Self::DecryptError> {
+        let mut shared_secret = P::pairing(cipher.ephemeral, key.0);
+        let (session_key, aad) = Self::derive_key(&cipher.ephemeral, &shared_secret);
+        shared_secret.zeroize();
+        let mut data = cipher.ciphertext;
+        chacha20poly1305::open(&session_key, &NONCE, &aad, &mut data, &cipher.tag)?;
+        Ok(data)
+    }
+}
+
+fn hash_identity<P: Pairing, D: Digest>(identity: &[u8]) -> P::G2Affine {
+    let label = [IDENTITY_DOMAIN_SEP, identity].concat();
+    hash_to_curve::<P::G2, D>(&label, 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{TestCurve, TestHash};
+
+    type Engine = Ibe<TestCurve, TestHash>;
+    type TestPkg = Pkg<TestCurve, TestHash>;
+
+    #[test]
+    fn identity_based_encryption() {
+        let rng = &mut test_rng();
+        let pkg = TestPkg::generate(rng);
+        let alice = IdentityKey::<TestCurve>::new(*pkg.master_key(), b"alice@example.com");
+        let alice_sk = pkg.extract(&alice.identity);
+        assert!(alice_sk.verify::<TestHash>(&alice));
+
+        for data in [vec![], b"some payload".to_vec()] {
+            let (cipher, randomness) = Engine::encrypt_returning_randomness(&data, &alice, rng);
+            assert_eq!(
+                cipher,
+                Engine::encrypt_with_randomness(&data, &alice, &randomness)
+            );
+            assert_eq!(Engine::decrypt(cipher, &alice_sk), Ok(data));
+        }
+
+        let data = b"some payload".to_vec();
+        let cipher = Engine::encrypt(&data, &alice, rng);
+        let bob = IdentityKey::<TestCurve>::new(*pkg.master_key(), b"bob.eth");
+        let bob_sk = pkg.extract(&bob.identity);
+        assert!(!bob_sk.verify::<TestHash>(&alice));
+        assert_eq!(
+            Engine::decrypt(cipher.clone(), &bob_sk),
+            Err(Error::InvalidTag)
+        );
+        // a key of another PKG for the same identity
+        let other = TestPkg::generate(rng).extract(&alice.identity);
+        assert!(!other.verify::<TestHash>(&alice));
+        assert_eq!(
+            Engine::decrypt(cipher.clone(), &other),
+            Err(Error::InvalidTag)
+        );
+        let mut tampered = cipher;
+        tampered.ciphertext[0] ^= 1;
+        assert_eq!(Engine::decrypt(tampered, &alice_sk), Err(Error::InvalidTag));
+    }
+}
@@ -1,21 +1,175 @@
+#[cfg(feature = "secp256k1")]
+mod aes;
+pub mod bundle;
+pub mod chacha20poly1305;
+pub mod constant_time;
+#[cfg(feature = "secp256k1")]
+pub mod ecies;
 pub mod elgamal;
+pub mod hybrid;
+pub mod ibe;
+pub mod paillier;
+pub mod timelock;
+#[cfg(feature = "secp256k1")]
+pub mod wallet;
 
-use ark_std::rand::Rng;
+use crate::hash::{expand, mac};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::{CryptoRng, Rng};
+use digest::Digest;
+use zeroize::Zeroizing;
+
+const NONCE_DOMAIN_SEP: &[u8] = b"fde deterministic nonce";
 
 pub trait EncryptionEngine {
     type EncryptionKey;
     type DecryptionKey;
     type Cipher;
     type PlainText;
-    fn encrypt<R: Rng>(
+    /// Encryption nonce, needed by proofs about the ciphertext.
+    type Randomness;
+    /// Reason why a ciphertext could not be decrypted.
+    type DecryptError;
+    fn encrypt<R: Rng + CryptoRng>(
         data: &Self::PlainText,
         key: &Self::EncryptionKey,
         rng: &mut R,
-    ) -> Self::Cipher;
+    ) -> Self::Cipher {
+        Self::encrypt_returning_randomness(data, key, rng).0
+    }
+    /// Encrypts `data` with fresh randomness and returns it alongside the ciphertext.
+    fn encrypt_returning_randomness<R: Rng + CryptoRng>(
+        data: &Self::PlainText,
+        key: &Self::EncryptionKey,
+        rng: &mut R,
+    ) -> (Self::Cipher, Self::Randomness);
     fn encrypt_with_randomness(
         data: &Self::PlainText,
         key: &Self::EncryptionKey,
-        randomness: &Self::PlainText,
+        randomness: &Self::Randomness,
     ) -> Self::Cipher;
-    fn decrypt(cipher: Self::Cipher, key: &Self::DecryptionKey) -> Self::PlainText;
+    /// Encrypts every element of `data` with fresh randomness.
+    fn encrypt_batch<R: Rng + CryptoRng>(
+        data: &[Self::PlainText],
+        key: &Self::EncryptionKey,
+        rng: &mut R,
+    ) -> Vec<Self::Cipher> {
+        data.iter().map(|d| Self::encrypt(d, key, rng)).collect()
+    }
+    /// Encrypts every element of `data` with the corresponding element of `randomness`.
+    fn encrypt_batch_with_randomness(
+        data: &[Self::PlainText],
+        key: &Self::EncryptionKey,
+        randomness: &[Self::Randomness],
+    ) -> Vec<Self::Cipher> {
+        data.iter()
+            .zip(randomness)
+            .map(|(d, r)| Self::encrypt_with_randomness(d, key, r))
+            .collect()
+    }
+    /// Encrypts `data` with randomness derived from a long-term `secret` of the encrypting party
+    /// instead of an RNG, see [`derive_randomness`]. Encrypting the same plaintext to the same key
+    /// within the same `context` yields the same ciphertext, thus the context should be unique
+    /// per message unless linkability is acceptable.
+    fn encrypt_deterministic<D: Digest + Clone>(
+        data: &Self::PlainText,
+        key: &Self::EncryptionKey,
+        secret: &[u8],
+        context: &[u8],
+    ) -> Self::Cipher
+    where
+        Self::PlainText: CanonicalSerialize,
+        Self::EncryptionKey: CanonicalSerialize,
+        Self::Randomness: PrimeField,
+    {
+        let randomness = Zeroizing::new(derive_randomness::<D, _>(secret, data, key, context));
+        Self::encrypt_with_randomness(data, key, &randomness)
+    }
+    fn decrypt(
+        cipher: Self::Cipher,
+        key: &Self::DecryptionKey,
+    ) -> Result<Self::PlainText, Self::DecryptError>;
+}
+
+/// Derives an encryption nonce as `PRF(secret, context, key, data)`, where the PRF is the keyed
+/// hash [`mac`] expanded to 128 bits more than the scalar size before the modular reduction.
+///
+/// A broken RNG of the encrypting party thus cannot leak plaintexts through repeated or
+/// predictable nonces, as long as `secret` itself was generated securely.
+pub fn derive_randomness<D, S>(
+    secret: &[u8],
+    data: &impl CanonicalSerialize,
+    key: &impl CanonicalSerialize,
+    context: &[u8],
+) -> S
+where
+    D: Digest + Clone,
+    S: PrimeField,
+{
+    let mut input = Vec::new();
+    input.extend_from_slice(&(context.len() as u64).to_le_bytes());
+    input.extend_from_slice(context);
+    key.serialize_compressed(&mut input)
+        .expect("should not fail");
+    data.serialize_compressed(&mut input)
+        .expect("should not fail");
+    let input = Zeroizing::new(input);
+    let seed = Zeroizing::new(mac::<D>(secret, &input).to_vec());
+    let mut wide = Zeroizing::new(vec![0u8; (S::MODULUS_BIT_SIZE as usize).div_ceil(8) + 16]);
+    expand::<D>(NONCE_DOMAIN_SEP, &seed, &mut wide);
+    S::from_le_bytes_mod_order(&wide)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::elgamal::{ExponentialElgamal, KeyPair};
+    use crate::encrypt::hybrid::Hybrid;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Elgamal = ExponentialElgamal<G1>;
+
+    #[test]
+    fn deterministic_encryption() {
+        let rng = &mut test_rng();
+        let keys = KeyPair::<G1>::generate(rng);
+        let secret = b"long-term seller secret";
+        let data = Scalar::from(1234u32);
+
+        let cipher = Elgamal::encrypt_deterministic::<TestHash>(&data, keys.pk(), secret, b"a");
+        assert_eq!(
+            cipher,
+            Elgamal::encrypt_deterministic::<TestHash>(&data, keys.pk(), secret, b"a")
+        );
+        assert_eq!(Elgamal::decrypt(cipher, keys.sk()), Ok(data));
+        // any change of the inputs changes the nonce
+        let other_keys = KeyPair::<G1>::generate(rng);
+        for other in [
+            Elgamal::encrypt_deterministic::<TestHash>(&data, keys.pk(), secret, b"b"),
+            Elgamal::encrypt_deterministic::<TestHash>(&data, keys.pk(), b"other secret", b"a"),
+            Elgamal::encrypt_deterministic::<TestHash>(&data, other_keys.pk(), secret, b"a"),
+        ] {
+            assert_ne!(cipher.c0(), other.c0());
+        }
+        let other = Elgamal::encrypt_deterministic::<TestHash>(
+            &(data + Scalar::from(1u8)),
+            keys.pk(),
+            secret,
+            b"a",
+        );
+        assert_ne!(cipher.c0(), other.c0());
+
+        let data = b"payload".to_vec();
+        type Engine = Hybrid<G1, TestHash>;
+        let cipher = Engine::encrypt_deterministic::<TestHash>(&data, keys.pk(), secret, b"a");
+        assert_eq!(
+            cipher,
+            Engine::encrypt_deterministic::<TestHash>(&data, keys.pk(), secret, b"a")
+        );
+        assert_eq!(Engine::decrypt(cipher, keys.sk()), Ok(data));
+    }
 }
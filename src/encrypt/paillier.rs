@@ -0,0 +1,211 @@
+//! Paillier encryption over `Z_{N^2}` with the generator `g = N + 1`.
+//!
+//! Unlike exponential Elgamal, decryption is exact for any plaintext smaller than the modulus
+//! `N`, while the scheme is still additively homomorphic: multiplying ciphertexts adds the
+//! plaintexts modulo `N`. Keys are generated from safe primes `p = 2p' + 1`.
+use super::EncryptionEngine;
+use ark_std::rand::distributions::Distribution;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::{One, Zero};
+use num_bigint::{BigUint, RandomBits};
+use num_integer::Integer;
+use num_prime::nt_funcs::is_prime;
+use thiserror::Error as ErrorT;
+
+/// Smallest supported modulus size, only suitable for tests. Use at least 2048 bits otherwise.
+pub const MIN_MODULUS_BITS: u64 = 64;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid modulus size of {0} bits")]
+    InvalidModulusSize(u64),
+    #[error("ciphertext is not a unit modulo N^2")]
+    InvalidCipher,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicKey {
+    pub n: BigUint,
+    pub n_squared: BigUint,
+}
+
+impl PublicKey {
+    pub fn new(n: BigUint) -> Self {
+        let n_squared = &n * &n;
+        Self { n, n_squared }
+    }
+
+    /// Homomorphic addition of the plaintexts.
+    pub fn add(&self, a: &Cipher, b: &Cipher) -> Cipher {
+        Cipher((&a.0 * &b.0) % &self.n_squared)
+    }
+
+    /// Homomorphic multiplication of the plaintext by `scalar`.
+    pub fn mul(&self, a: &Cipher, scalar: &BigUint) -> Cipher {
+        Cipher(a.0.modpow(scalar, &self.n_squared))
+    }
+
+    fn random_unit<R: Rng + CryptoRng>(&self, rng: &mut R) -> BigUint {
+        let random_bits = RandomBits::new(self.n.bits());
+        loop {
+            let r: BigUint = random_bits.sample(rng);
+            if !r.is_zero() && r < self.n && r.gcd(&self.n).is_one() {
+                return r;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SecretKey {
+    pub public: PublicKey,
+    /// `lcm(p - 1, q - 1)`
+    pub lambda: BigUint,
+    /// `lambda^-1 mod N`
+    pub mu: BigUint,
+}
+
+impl SecretKey {
+    /// Generates a key pair with a modulus of `modulus_bits` bits.
+    pub fn generate<R: Rng + CryptoRng>(modulus_bits: u64, rng: &mut R) -> Result<Self, Error> {
+        if modulus_bits < MIN_MODULUS_BITS || !modulus_bits.is_multiple_of(2) {
+            return Err(Error::InvalidModulusSize(modulus_bits));
+        }
+        let p = safe_prime(modulus_bits / 2, rng);
+        let q = loop {
+            let q = safe_prime(modulus_bits / 2, rng);
+            if q != p {
+                break q;
+            }
+        };
+        let lambda = (&p - BigUint::one()).lcm(&(&q - BigUint::one()));
+        let public = PublicKey::new(p * q);
+        let mu = lambda
+            .modinv(&public.n)
+            .expect("lambda is coprime to N for distinct safe primes");
+        Ok(Self { public, lambda, mu })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cipher(pub BigUint);
+
+pub struct Paillier;
+
+impl EncryptionEngine for Paillier {
+    type EncryptionKey = PublicKey;
+    type DecryptionKey = SecretKey;
+    type Cipher = Cipher;
+    type PlainText = BigUint;
+    type Randomness = BigUint;
+    type DecryptError = Error;
+
+    fn encrypt_returning_randomness<R: Rng + CryptoRng>(
+        data: &Self::PlainText,
+        key: &Self::EncryptionKey,
+        rng: &mut R,
+    ) -> (Self::Cipher, Self::Randomness) {
+        let random = key.random_unit(rng);
+        (Self::encrypt_with_randomness(data, key, &random), random)
+    }
+
+    /// Computes `(1 + m * N) * r^N mod N^2`, i.e. `g^m * r^N` with `g = N + 1`. Plaintexts are
+    /// reduced modulo `N`.
+    fn encrypt_with_randomness(
+        data: &Self::PlainText,
+        key: &Self::EncryptionKey,
+        randomness: &Self::Randomness,
+    ) -> Self::Cipher {
+        let g_m = (BigUint::one() + (data % &key.n) * &key.n) % &key.n_squared;
+        Cipher((g_m * randomness.modpow(&key.n, &key.n_squared)) % &key.n_squared)
+    }
+
+    /// Computes `L(c^lambda mod N^2) * mu mod N` with `L(x) = (x - 1) / N`.
+    fn decrypt(
+        cipher: Self::Cipher,
+        key: &Self::DecryptionKey,
+    ) -> Result<Self::PlainText, Self::DecryptError> {
+        let public = &key.public;
+        if cipher.0 >= public.n_squared || !cipher.0.gcd(&public.n).is_one() {
+            return Err(Error::InvalidCipher);
+        }
+        let x = cipher.0.modpow(&key.lambda, &public.n_squared);
+        let l = (x - BigUint::one()) / &public.n;
+        Ok((l * &key.mu) % &public.n)
+    }
+}
+
+// returns a random safe prime `p = 2p' + 1` of `bits` bits
+fn safe_prime<R: Rng + CryptoRng>(bits: u64, rng: &mut R) -> BigUint {
+    let random_bits = RandomBits::new(bits - 1);
+    loop {
+        let mut candidate: BigUint = random_bits.sample(rng);
+        candidate.set_bit(bits - 2, true);
+        candidate.set_bit(0, true);
+        // 3 divides 2p' + 1 if p' = 1 mod 3
+        if (&candidate % 3u32).is_one() || !is_prime(&candidate, None).probably() {
+            continue;
+        }
+        let p = (candidate << 1) + BigUint::one();
+        if is_prime(&p, None).probably() {
+            return p;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use num_prime::nt_funcs::is_safe_prime;
+
+    #[test]
+    fn paillier() {
+        let rng = &mut test_rng();
+        assert_eq!(
+            SecretKey::generate(MIN_MODULUS_BITS - 2, rng).unwrap_err(),
+            Error::InvalidModulusSize(MIN_MODULUS_BITS - 2)
+        );
+        let p = safe_prime(64, rng);
+        assert_eq!(p.bits(), 64);
+        assert!(is_safe_prime(&p).probably());
+
+        let sk = SecretKey::generate(256, rng).unwrap();
+        let pk = &sk.public;
+        assert!(pk.n.bits() >= 255);
+
+        // exact decryption of large plaintexts
+        let a = BigUint::from(u128::MAX);
+        let max = &pk.n - BigUint::one();
+        for data in [BigUint::zero(), a.clone(), max.clone()] {
+            let (cipher, random) = Paillier::encrypt_returning_randomness(&data, pk, rng);
+            assert_eq!(
+                cipher,
+                Paillier::encrypt_with_randomness(&data, pk, &random)
+            );
+            assert_eq!(Paillier::decrypt(cipher, &sk), Ok(data));
+        }
+
+        // homomorphism
+        let b = BigUint::from(12345u32);
+        let ca = Paillier::encrypt(&a, pk, rng);
+        let cb = Paillier::encrypt(&b, pk, rng);
+        assert_eq!(Paillier::decrypt(pk.add(&ca, &cb), &sk), Ok(&a + &b));
+        assert_eq!(
+            Paillier::decrypt(pk.mul(&ca, &BigUint::from(3u8)), &sk),
+            Ok(&a * 3u8)
+        );
+        // wraps around the modulus
+        let cmax = Paillier::encrypt(&max, pk, rng);
+        assert_eq!(Paillier::decrypt(pk.add(&cmax, &cb), &sk), Ok(&b - 1u8));
+
+        assert_eq!(
+            Paillier::decrypt(Cipher(pk.n.clone()), &sk),
+            Err(Error::InvalidCipher)
+        );
+        assert_eq!(
+            Paillier::decrypt(Cipher(pk.n_squared.clone()), &sk),
+            Err(Error::InvalidCipher)
+        );
+    }
+}
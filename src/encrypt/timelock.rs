@@ -0,0 +1,245 @@
+//! Time-lock puzzles of Rivest, Shamir and Wagner for encrypting payloads, such as decryption
+//! keys, to the future: a seller locks the decryption key for the buyer after payment, such that
+//! the buyer recovers it even if the seller disappears.
+use super::chacha20poly1305::{self, KEY_SIZE, NONCE_SIZE, TAG_SIZE};
+use super::elgamal::KeyPair;
+use crate::hash::expand;
+use ark_ec::CurveGroup;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::distributions::Distribution;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::One;
+use digest::Digest;
+use num_bigint::{BigUint, RandomBits};
+use num_integer::Integer;
+use num_prime::nt_funcs::is_prime;
+use thiserror::Error as ErrorT;
+use zeroize::Zeroizing;
+
+const KDF_DOMAIN_SEP: &[u8] = b"fde timelock";
+const NONCE: [u8; NONCE_SIZE] = [0; NONCE_SIZE];
+
+/// Smallest supported modulus size. Smaller moduli can be factored faster than the squarings are
+/// performed, which reveals the payload early.
+#[cfg(not(test))]
+pub const MIN_MODULUS_BITS: u64 = 2048;
+// keeps prime generation fast in tests
+#[cfg(test)]
+pub const MIN_MODULUS_BITS: u64 = 256;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("invalid modulus size of {0} bits")]
+    InvalidModulusSize(u64),
+    #[error("base is not a unit modulo N")]
+    InvalidBase,
+    #[error(transparent)]
+    Cipher(#[from] chacha20poly1305::Error),
+    #[error("unlocked key does not match the public key")]
+    WrongKey,
+}
+
+/// Payload encrypted under a key derived from `a^(2^T) mod N` for an RSA modulus `N` and a random
+/// base `a`, using [ChaCha20-Poly1305](super::chacha20poly1305).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    pub modulus: BigUint,
+    pub base: BigUint,
+    /// Number `T` of sequential squarings needed to unlock, which should be calibrated to the
+    /// squaring speed of the fastest expected hardware.
+    pub squarings: u64,
+    pub ciphertext: Vec<u8>,
+    pub tag: [u8; TAG_SIZE],
+}
+
+impl Puzzle {
+    /// Locks `data` for `squarings` sequential squarings modulo a fresh RSA modulus of
+    /// `modulus_bits` bits.
+    ///
+    /// Knowing `phi(N)`, the creator computes the solution with a single exponentiation by
+    /// `2^T mod phi(N)`, while anyone else has to perform `T` sequential squarings, which cannot
+    /// be parallelized. The factors of `N` are discarded.
+    ///
+    /// # Panics
+    ///
+    /// If `data` exceeds the 256 GiB limit of ChaCha20-Poly1305.
+    pub fn lock<D: Digest + Clone, R: Rng + CryptoRng>(
+        data: &[u8],
+        squarings: u64,
+        modulus_bits: u64,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        if modulus_bits < MIN_MODULUS_BITS || !modulus_bits.is_multiple_of(2) {
+            return Err(Error::InvalidModulusSize(modulus_bits));
+        }
+        let p = random_prime(modulus_bits / 2, rng);
+        let q = loop {
+            let q = random_prime(modulus_bits / 2, rng);
+            if q != p {
+                break q;
+            }
+        };
+        let phi = (&p - BigUint::one()) * (&q - BigUint::one());
+        let modulus = p * q;
+        let random_bits = RandomBits::new(modulus.bits());
+        let base = loop {
+            let base: BigUint = random_bits.sample(rng);
+            if base > BigUint::one() && base < modulus && base.gcd(&modulus).is_one() {
+                break base;
+            }
+        };
+
+        // shortcut via the order of the group
+        let exponent = BigUint::from(2u8).modpow(&BigUint::from(squarings), &phi);
+        let solution = base.modpow(&exponent, &modulus);
+        let mut puzzle = Self {
+            modulus,
+            base,
+            squarings,
+            ciphertext: data.to_vec(),
+            tag: [0; TAG_SIZE],
+        };
+        let (session_key, aad) = puzzle.derive_key::<D>(&solution);
+        puzzle.tag = chacha20poly1305::seal(&session_key, &NONCE, &aad, &mut puzzle.ciphertext)
+            .expect("payload too large");
+        Ok(puzzle)
+    }
+
+    /// Performs the `T` squarings and decrypts the payload.
+    pub fn unlock<D: Digest + Clone>(&self) -> Result<Vec<u8>, Error> {
+        if self.base <= BigUint::one()
+            || self.base >= self.modulus
+            || !self.base.gcd(&self.modulus).is_one()
+        {
+            return Err(Error::InvalidBase);
+        }
+        let mut solution = self.base.clone();
+        for _ in 0..self.squarings {
+            solution = (&solution * &solution) % &self.modulus;
+        }
+        let (session_key, aad) = self.derive_key::<D>(&solution);
+        let mut data = self.ciphertext.clone();
+        chacha20poly1305::open(&session_key, &NONCE, &aad, &mut data, &self.tag)?;
+        Ok(data)
+    }
+
+    /// Locks the decryption key of `key_pair`. The puzzle does not prove what it contains, see
+    /// [`Self::unlock_key`] for checking the recovered key.
+    pub fn lock_key<C: CurveGroup, D: Digest + Clone, R: Rng + CryptoRng>(
+        key_pair: &KeyPair<C>,
+        squarings: u64,
+        modulus_bits: u64,
+        rng: &mut R,
+    ) -> Result<Self, Error> {
+        let mut data = Zeroizing::new(Vec::new());
+        key_pair
+            .sk()
+            .serialize_compressed(&mut *data)
+            .expect("should not fail");
+        Self::lock::<D, R>(&data, squarings, modulus_bits, rng)
+    }
+
+    /// Unlocks a decryption key and checks it against `public_key`.
+    pub fn unlock_key<C: CurveGroup, D: Digest + Clone>(
+        &self,
+        public_key: &C::Affine,
+    ) -> Result<KeyPair<C>, Error> {
+        let data = Zeroizing::new(self.unlock::<D>()?);
+        let sk = C::ScalarField::deserialize_compressed(&data[..]).map_err(|_| Error::WrongKey)?;
+        let key_pair = KeyPair::from_secret_key(sk);
+        if key_pair.pk() != public_key {
+            return Err(Error::WrongKey);
+        }
+        Ok(key_pair)
+    }
+
+    // returns the session key and the associated data binding the puzzle parameters
+    fn derive_key<D: Digest + Clone>(
+        &self,
+        solution: &BigUint,
+    ) -> (Zeroizing<[u8; KEY_SIZE]>, Vec<u8>) {
+        let mut aad = Vec::new();
+        for number in [&self.modulus, &self.base] {
+            let bytes = number.to_bytes_le();
+            aad.extend((bytes.len() as u64).to_le_bytes());
+            aad.extend(bytes);
+        }
+        aad.extend(self.squarings.to_le_bytes());
+        let mut secret = Zeroizing::new(aad.clone());
+        secret.extend(solution.to_bytes_le());
+        let mut session_key = Zeroizing::new([0u8; KEY_SIZE]);
+        expand::<D>(KDF_DOMAIN_SEP, &secret, &mut *session_key);
+        (session_key, aad)
+    }
+}
+
+// returns a random prime of `bits` bits whose two most significant bits are set, such that the
+// product of two of them has exactly twice as many bits
+fn random_prime<R: Rng + CryptoRng>(bits: u64, rng: &mut R) -> BigUint {
+    let random_bits = RandomBits::new(bits);
+    loop {
+        let mut candidate: BigUint = random_bits.sample(rng);
+        candidate.set_bit(bits - 1, true);
+        candidate.set_bit(bits - 2, true);
+        candidate.set_bit(0, true);
+        if is_prime(&candidate, None).probably() {
+            return candidate;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+
+    type G1 = <TestCurve as Pairing>::G1;
+
+    #[test]
+    fn timelock() {
+        let rng = &mut test_rng();
+        for bits in [MIN_MODULUS_BITS - 2, MIN_MODULUS_BITS + 1] {
+            assert_eq!(
+                Puzzle::lock::<TestHash, _>(b"", 10, bits, rng),
+                Err(Error::InvalidModulusSize(bits))
+            );
+        }
+
+        let data = b"some payload".to_vec();
+        let puzzle = Puzzle::lock::<TestHash, _>(&data, 1000, 256, rng).unwrap();
+        assert_eq!(puzzle.modulus.bits(), 256);
+        assert_eq!(puzzle.unlock::<TestHash>(), Ok(data));
+        for tampered in [
+            Puzzle {
+                squarings: 999,
+                ..puzzle.clone()
+            },
+            Puzzle {
+                base: &puzzle.base + 1u8,
+                ..puzzle.clone()
+            },
+        ] {
+            assert_eq!(
+                tampered.unlock::<TestHash>(),
+                Err(Error::Cipher(chacha20poly1305::Error::InvalidTag))
+            );
+        }
+        let invalid = Puzzle {
+            base: puzzle.modulus.clone(),
+            ..puzzle
+        };
+        assert_eq!(invalid.unlock::<TestHash>(), Err(Error::InvalidBase));
+
+        let key_pair = KeyPair::<G1>::generate(rng);
+        let puzzle = Puzzle::lock_key::<G1, TestHash, _>(&key_pair, 100, 256, rng).unwrap();
+        let unlocked = puzzle.unlock_key::<G1, TestHash>(key_pair.pk()).unwrap();
+        assert_eq!(unlocked.sk(), key_pair.sk());
+        let other = KeyPair::<G1>::generate(rng);
+        assert_eq!(
+            puzzle.unlock_key::<G1, TestHash>(other.pk()).err(),
+            Some(Error::WrongKey)
+        );
+    }
+}
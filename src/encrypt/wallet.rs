@@ -0,0 +1,314 @@
+//! Import and export of secp256k1 encryption keys in the formats of Ethereum and Bitcoin wallets,
+//! and BIP32 hierarchical derivation of per-purchase keys. Secret keys are 32-byte big-endian
+//! scalars and public keys are SEC1 points.
+use super::ecies::{self, public_key_from_bytes, public_key_to_bytes, secret_key_from_bytes};
+use super::elgamal::KeyPair;
+use crate::hash::hmac;
+use crate::hex;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use ark_secp256k1::{Affine, Fr, Projective};
+use sha2::Sha512;
+use sha3::{Digest, Keccak256};
+use thiserror::Error as ErrorT;
+use zeroize::{Zeroize, Zeroizing};
+
+pub const SECRET_KEY_SIZE: usize = 32;
+/// Size of a compressed SEC1 point.
+pub const COMPRESSED_PUBLIC_KEY_SIZE: usize = 33;
+pub const ADDRESS_SIZE: usize = 20;
+/// Offset of hardened child indices.
+pub const HARDENED: u32 = 1 << 31;
+const SEED_KEY: &[u8] = b"Bitcoin seed";
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error(transparent)]
+    Key(#[from] ecies::Error),
+    #[error("invalid derivation path {0:?}")]
+    InvalidPath(String),
+    #[error("child {0} yields an invalid key, use the next index")]
+    InvalidChild(u32),
+    #[error("hardened child {0} cannot be derived from a public key")]
+    HardenedChild(u32),
+}
+
+/// Imports an Elgamal key pair from a wallet secret key.
+pub fn key_pair_from_bytes(bytes: &[u8]) -> Result<KeyPair<Projective>, Error> {
+    Ok(KeyPair::from_secret_key(secret_key_from_bytes(bytes)?))
+}
+
+/// 32-byte big-endian encoding of a secret key as imported by wallets.
+pub fn secret_key_to_bytes(key: &Fr) -> Zeroizing<[u8; SECRET_KEY_SIZE]> {
+    let mut bigint = key.into_bigint();
+    let mut bytes = Zeroizing::new([0u8; SECRET_KEY_SIZE]);
+    let mut be_bytes = bigint.to_bytes_be();
+    bytes.copy_from_slice(&be_bytes);
+    be_bytes.zeroize();
+    bigint.as_mut().zeroize();
+    bytes
+}
+
+/// Compressed SEC1 encoding `0x02 | y_parity || x` of a point.
+pub fn public_key_to_compressed(point: &Affine) -> [u8; COMPRESSED_PUBLIC_KEY_SIZE] {
+    let mut bytes = [0u8; COMPRESSED_PUBLIC_KEY_SIZE];
+    bytes[0] = 0x02 | u8::from(point.y.into_bigint().is_odd());
+    bytes[1..].copy_from_slice(&point.x.into_bigint().to_bytes_be());
+    bytes
+}
+
+/// Ethereum address of a public key, i.e. the last 20 bytes of the Keccak256 hash of its
+/// uncompressed coordinates.
+pub fn address(point: &Affine) -> [u8; ADDRESS_SIZE] {
+    let hash = Keccak256::digest(&public_key_to_bytes(point)[1..]);
+    let mut address = [0u8; ADDRESS_SIZE];
+    address.copy_from_slice(&hash[hash.len() - ADDRESS_SIZE..]);
+    address
+}
+
+/// Ethereum address with the mixed-case checksum of EIP-55, e.g. `0x2c7536E3...`.
+pub fn checksum_address(point: &Affine) -> String {
+    let lowercase = hex::encode(&address(point));
+    let hash = Keccak256::digest(lowercase.as_bytes());
+    let digits = lowercase.chars().enumerate().map(|(i, digit)| {
+        let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+        if nibble >= 8 {
+            digit.to_ascii_uppercase()
+        } else {
+            digit
+        }
+    });
+    "0x".chars().chain(digits).collect()
+}
+
+/// BIP32 extended secret key.
+pub struct ExtendedKey {
+    key: Fr,
+    chain_code: Zeroizing<[u8; 32]>,
+}
+
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl ExtendedKey {
+    /// Derives the master key from a seed of 16 to 64 bytes, e.g. of a BIP39 mnemonic.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, Error> {
+        let output = Zeroizing::new(hmac::<Sha512>(SEED_KEY, seed).to_vec());
+        let key = secret_key_from_bytes(&output[..32])?;
+        Ok(Self {
+            key,
+            chain_code: chain_code(&output),
+        })
+    }
+
+    pub fn secret_key(&self) -> &Fr {
+        &self.key
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn key_pair(&self) -> KeyPair<Projective> {
+        KeyPair::from_secret_key(self.key)
+    }
+
+    pub fn public(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            key: (Affine::generator() * self.key).into_affine(),
+            chain_code: *self.chain_code,
+        }
+    }
+
+    /// Derives the child at `index`, which is hardened from [`HARDENED`] on.
+    pub fn derive_child(&self, index: u32) -> Result<Self, Error> {
+        let mut data = Zeroizing::new(Vec::with_capacity(COMPRESSED_PUBLIC_KEY_SIZE + 4));
+        if index >= HARDENED {
+            data.push(0);
+            data.extend_from_slice(&*secret_key_to_bytes(&self.key));
+        } else {
+            data.extend_from_slice(&public_key_to_compressed(&self.public().key));
+        }
+        data.extend_from_slice(&index.to_be_bytes());
+        let output = Zeroizing::new(hmac::<Sha512>(&*self.chain_code, &data).to_vec());
+        let tweak = secret_key_from_bytes(&output[..32]).map_err(|_| Error::InvalidChild(index))?;
+        let key = tweak + self.key;
+        if key.is_zero() {
+            return Err(Error::InvalidChild(index));
+        }
+        Ok(Self {
+            key,
+            chain_code: chain_code(&output),
+        })
+    }
+
+    /// Derives the key at a path like `m/44'/60'/0'/0/7`, where `'` or `h` marks hardened
+    /// indices.
+    pub fn derive_path(&self, path: &str) -> Result<Self, Error> {
+        let indices = parse_path(path)?;
+        let mut key = Self {
+            key: self.key,
+            chain_code: self.chain_code.clone(),
+        };
+        for index in indices {
+            key = key.derive_child(index)?;
+        }
+        Ok(key)
+    }
+}
+
+/// BIP32 extended public key, from which the non-hardened children are derived.
+///
+/// A buyer derives a fresh key per purchase from a master key, e.g. along
+/// `m/44'/60'/0'/0/<purchase>`. Since the last level is not hardened, a seller holding the
+/// extended public key of `m/44'/60'/0'/0` derives the same encryption keys without any
+/// interaction, while the keys of different purchases cannot be linked without it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    pub key: Affine,
+    pub chain_code: [u8; 32],
+}
+
+impl ExtendedPublicKey {
+    pub fn derive_child(&self, index: u32) -> Result<Self, Error> {
+        if index >= HARDENED {
+            return Err(Error::HardenedChild(index));
+        }
+        let mut data = public_key_to_compressed(&self.key).to_vec();
+        data.extend_from_slice(&index.to_be_bytes());
+        let output = hmac::<Sha512>(&self.chain_code, &data);
+        let tweak = secret_key_from_bytes(&output[..32]).map_err(|_| Error::InvalidChild(index))?;
+        let key = (Affine::generator() * tweak + self.key).into_affine();
+        if key.is_zero() {
+            return Err(Error::InvalidChild(index));
+        }
+        Ok(Self {
+            key,
+            chain_code: *chain_code(&output),
+        })
+    }
+
+    /// Imports an extended public key from a SEC1 public key and a chain code, e.g. as exported
+    /// by a wallet.
+    pub fn from_parts(public_key: &[u8], chain_code: [u8; 32]) -> Result<Self, Error> {
+        Ok(Self {
+            key: public_key_from_bytes(public_key)?,
+            chain_code,
+        })
+    }
+}
+
+fn chain_code(output: &[u8]) -> Zeroizing<[u8; 32]> {
+    let mut chain_code = Zeroizing::new([0u8; 32]);
+    chain_code.copy_from_slice(&output[32..]);
+    chain_code
+}
+
+fn parse_path(path: &str) -> Result<Vec<u32>, Error> {
+    let invalid = || Error::InvalidPath(path.to_string());
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(invalid());
+    }
+    segments
+        .map(|segment| {
+            let (number, offset) = match segment.strip_suffix(['\'', 'h']) {
+                Some(number) => (number, HARDENED),
+                None => (segment, 0),
+            };
+            match number.parse::<u32>() {
+                Ok(index) if index < HARDENED && !number.starts_with('+') => Ok(index + offset),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encrypt::EncryptionEngine;
+    use crate::tests::test_rng;
+
+    type Elgamal = crate::encrypt::elgamal::ExponentialElgamal<Projective>;
+
+    #[test]
+    fn wallet_keys() {
+        // web3.js documentation example
+        let secret =
+            hex::decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+                .unwrap();
+        let keys = key_pair_from_bytes(&secret).unwrap();
+        assert_eq!(secret_key_to_bytes(keys.sk()).as_slice(), secret);
+        assert_eq!(
+            checksum_address(keys.pk()),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+        let compressed = public_key_to_compressed(keys.pk());
+        assert_eq!(public_key_from_bytes(&compressed), Ok(*keys.pk()));
+        assert_eq!(
+            key_pair_from_bytes(&[0u8; 32]).err(),
+            Some(Error::Key(ecies::Error::InvalidSecretKey))
+        );
+
+        // encrypting to the imported key
+        let rng = &mut test_rng();
+        let imported = public_key_from_bytes(&public_key_to_bytes(keys.pk())).unwrap();
+        let cipher = Elgamal::encrypt(&Fr::from(42u8), &imported, rng);
+        assert_eq!(Elgamal::decrypt(cipher, keys.sk()), Ok(Fr::from(42u8)));
+    }
+
+    #[test]
+    fn bip32_derivation() {
+        // test vector 1 of BIP32
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedKey::from_seed(&seed).unwrap();
+        let expected = [
+            (
+                "m",
+                "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            ),
+            (
+                "m/0'",
+                "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+            (
+                "m/0h/1",
+                "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            ),
+        ];
+        for (path, chain_code, key) in expected {
+            let derived = master.derive_path(path).unwrap();
+            assert_eq!(hex::encode(derived.chain_code()), chain_code);
+            assert_eq!(
+                hex::encode(&*secret_key_to_bytes(derived.secret_key())),
+                key
+            );
+        }
+
+        // the seller derives the per-purchase keys from the extended public key
+        let account = master.derive_path("m/44'/60'/0'/0").unwrap();
+        let public = account.public();
+        for purchase in 0..3 {
+            let buyer = account.derive_child(purchase).unwrap().key_pair();
+            assert_eq!(public.derive_child(purchase).unwrap().key, *buyer.pk());
+        }
+        assert_eq!(
+            public.derive_child(HARDENED),
+            Err(Error::HardenedChild(HARDENED))
+        );
+
+        for path in ["", "m/", "0/1", "m/x", "m/2147483648", "m/1''", "m/+1"] {
+            assert_eq!(
+                master.derive_path(path).err(),
+                Some(Error::InvalidPath(path.to_string()))
+            );
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Zero-knowledge proof that two Pedersen-style commitments `com = value * g + blinding * h`,
+//! possibly with different bases, hide the same scalar.
+use crate::hash::Hasher;
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+
+const PROOF_DOMAIN_SEP: &[u8] = b"fde equality proof";
+
+/// Bases of a two-generator commitment `com = value * g + blinding * h`.
+///
+/// A KZG commitment to a polynomial whose evaluations are all fixed except for two (the committed
+/// value and a blinding factor) is also such a commitment, with the bases being the commitments to
+/// the respective Lagrange polynomials. This is how the `f` commitment of a range proof can be
+/// linked to a commitment used elsewhere in a protocol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bases<C: CurveGroup> {
+    pub g: C::Affine,
+    pub h: C::Affine,
+}
+
+impl<C: CurveGroup> Bases<C> {
+    pub fn new(g: C::Affine, h: C::Affine) -> Self {
+        Self { g, h }
+    }
+
+    pub fn commit(&self, value: &C::ScalarField, blinding: &C::ScalarField) -> C {
+        self.g * value + self.h * blinding
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Proof<C: CurveGroup, D> {
+    pub challenge: C::ScalarField,
+    pub value_claim: C::ScalarField,
+    pub blinding_claims: [C::ScalarField; 2],
+    _digest: PhantomData<D>,
+}
+
+impl<C, D> Proof<C, D>
+where
+    C: CurveGroup,
+    D: Digest,
+{
+    /// Proves that `bases[0].commit(value, blindings[0])` and `bases[1].commit(value,
+    /// blindings[1])` hide the same `value`.
+    pub fn new<R: Rng + CryptoRng>(
+        value: &C::ScalarField,
+        blindings: [C::ScalarField; 2],
+        bases: [Bases<C>; 2],
+        rng: &mut R,
+    ) -> Self {
+        let rand_value = C::ScalarField::rand(rng);
+        let rand_blindings = [C::ScalarField::rand(rng), C::ScalarField::rand(rng)];
+        let k1 = bases[0].commit(&rand_value, &rand_blindings[0]);
+        let k2 = bases[1].commit(&rand_value, &rand_blindings[1]);
+        let com1 = bases[0].commit(value, &blindings[0]);
+        let com2 = bases[1].commit(value, &blindings[1]);
+
+        let challenge = Self::challenge(&bases, &k1, &k2, &com1, &com2);
+        let value_claim = rand_value - challenge * value;
+        let blinding_claims = [
+            rand_blindings[0] - challenge * blindings[0],
+            rand_blindings[1] - challenge * blindings[1],
+        ];
+
+        Self {
+            challenge,
+            value_claim,
+            blinding_claims,
+            _digest: PhantomData,
+        }
+    }
+
+    pub fn verify(&self, commitments: [C; 2], bases: [Bases<C>; 2]) -> bool {
+        let k1 = bases[0].commit(&self.value_claim, &self.blinding_claims[0])
+            + commitments[0] * self.challenge;
+        let k2 = bases[1].commit(&self.value_claim, &self.blinding_claims[1])
+            + commitments[1] * self.challenge;
+
+        let challenge = Self::challenge(&bases, &k1, &k2, &commitments[0], &commitments[1]);
+
+        challenge == self.challenge
+    }
+
+    fn challenge(bases: &[Bases<C>; 2], k1: &C, k2: &C, com1: &C, com2: &C) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(&PROOF_DOMAIN_SEP);
+        bases.iter().for_each(|b| {
+            hasher.update(&b.g);
+            hasher.update(&b.h);
+        });
+        hasher.update(k1);
+        hasher.update(k2);
+        hasher.update(com1);
+        hasher.update(com2);
+        C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{G1Affine, Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_std::UniformRand;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type EqualityProof = Proof<G1, TestHash>;
+
+    fn random_bases<R: Rng>(rng: &mut R) -> Bases<G1> {
+        Bases::new(
+            (G1Affine::generator() * Scalar::rand(rng)).into_affine(),
+            (G1Affine::generator() * Scalar::rand(rng)).into_affine(),
+        )
+    }
+
+    #[test]
+    fn completeness() {
+        let rng = &mut test_rng();
+        let bases = [random_bases(rng), random_bases(rng)];
+        let value = Scalar::rand(rng);
+        let blindings = [Scalar::rand(rng), Scalar::rand(rng)];
+        let commitments = [
+            bases[0].commit(&value, &blindings[0]),
+            bases[1].commit(&value, &blindings[1]),
+        ];
+
+        let proof = EqualityProof::new(&value, blindings, bases, rng);
+        assert!(proof.verify(commitments, bases));
+    }
+
+    #[test]
+    fn soundness() {
+        let rng = &mut test_rng();
+        let bases = [random_bases(rng), random_bases(rng)];
+        let value = Scalar::rand(rng);
+        let blindings = [Scalar::rand(rng), Scalar::rand(rng)];
+
+        // commitments hide different values
+        let commitments = [
+            bases[0].commit(&value, &blindings[0]),
+            bases[1].commit(&(value + Scalar::from(1u8)), &blindings[1]),
+        ];
+        let proof = EqualityProof::new(&value, blindings, bases, rng);
+        assert!(!proof.verify(commitments, bases));
+
+        // invalid bases
+        let commitments = [
+            bases[0].commit(&value, &blindings[0]),
+            bases[1].commit(&value, &blindings[1]),
+        ];
+        assert!(!proof.verify(commitments, [bases[1], bases[0]]));
+    }
+}
@@ -1,7 +1,11 @@
+use crate::transcript::Transcript;
+use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::PrimeField;
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate, Write};
 use ark_std::marker::PhantomData;
+use digest::core_api::BlockSizeUser;
 use digest::{Digest, Output};
+use zeroize::Zeroizing;
 
 #[derive(Clone, Debug)]
 pub struct Hasher<D> {
@@ -39,3 +43,234 @@ impl<D: Digest> Hasher<D> {
         S::from_le_bytes_mod_order(&output)
     }
 }
+
+/// Same as [`Hasher`] but the inputs are fed into the digest as they arrive instead of being
+/// buffered, thus it doesn't allocate. It produces exactly the same outputs as [`Hasher`].
+#[derive(Clone)]
+pub struct StreamingHasher<D> {
+    digest: D,
+}
+
+impl<D: Digest> Default for StreamingHasher<D> {
+    fn default() -> Self {
+        Self { digest: D::new() }
+    }
+}
+
+impl<D: Digest + Clone> StreamingHasher<D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update<T: CanonicalSerialize>(&mut self, input: &T) {
+        input
+            .serialize_compressed(DigestWriter(&mut self.digest))
+            .expect("should not fail");
+    }
+
+    pub fn finalize(self) -> Output<D> {
+        self.digest.finalize()
+    }
+
+    pub fn next_scalar<S: PrimeField>(&mut self, label: &[u8]) -> S {
+        Digest::update(&mut self.digest, label);
+        let output = self.digest.clone().finalize();
+        S::from_le_bytes_mod_order(&output)
+    }
+}
+
+/// Digest transcript: labels, messages and challenge labels are absorbed with their lengths as
+/// 8 little-endian bytes in front, thus different sequences of messages never produce the same
+/// input to the digest. Challenges are derived from the digest of everything absorbed so far.
+impl<D: Digest> Transcript for Hasher<D> {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        for bytes in [label, message] {
+            self.data
+                .extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+            self.data.extend_from_slice(bytes);
+        }
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.data
+            .extend_from_slice(&(label.len() as u64).to_le_bytes());
+        self.data.extend_from_slice(label);
+        fill_challenge::<D>(&D::digest(&self.data), dest);
+    }
+}
+
+/// Same as the transcript of [`Hasher`], without allocations.
+impl<D: Digest + Clone> Transcript for StreamingHasher<D> {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        for bytes in [label, message] {
+            Digest::update(&mut self.digest, (bytes.len() as u64).to_le_bytes());
+            Digest::update(&mut self.digest, bytes);
+        }
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        Digest::update(&mut self.digest, (label.len() as u64).to_le_bytes());
+        Digest::update(&mut self.digest, label);
+        fill_challenge::<D>(&self.digest.clone().finalize(), dest);
+    }
+}
+
+// fills `dest` with the blocks `D(seed || i)` for a little-endian `u32` counter `i`
+pub(crate) fn fill_challenge<D: Digest>(seed: &[u8], dest: &mut [u8]) {
+    for (i, chunk) in dest.chunks_mut(<D as Digest>::output_size()).enumerate() {
+        let block = D::new()
+            .chain_update(seed)
+            .chain_update((i as u32).to_le_bytes())
+            .finalize();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+}
+
+/// Derives `output.len()` bytes from `secret` by hashing `domain || len(secret) || secret || i`
+/// for a little-endian `u32` block counter `i`.
+pub fn expand<D: Digest + Clone>(domain: &[u8], secret: &[u8], output: &mut [u8]) {
+    let mut prefix = D::new();
+    Digest::update(&mut prefix, domain);
+    Digest::update(&mut prefix, (secret.len() as u64).to_le_bytes());
+    Digest::update(&mut prefix, secret);
+    for (i, chunk) in output.chunks_mut(<D as Digest>::output_size()).enumerate() {
+        let mut digest = prefix.clone();
+        Digest::update(&mut digest, (i as u32).to_le_bytes());
+        let block = digest.finalize();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+}
+
+/// Keyed hash `D(len(key) || key || len(data) || data)`. Both lengths are hashed before the
+/// data, thus the tag cannot be extended even if `D` is prone to length extension attacks.
+pub fn mac<D: Digest>(key: &[u8], data: &[u8]) -> Output<D> {
+    let mut digest = D::new();
+    Digest::update(&mut digest, (key.len() as u64).to_le_bytes());
+    Digest::update(&mut digest, key);
+    Digest::update(&mut digest, (data.len() as u64).to_le_bytes());
+    Digest::update(&mut digest, data);
+    digest.finalize()
+}
+
+/// HMAC as specified in RFC 2104, for compatibility with external formats. Prefer [`mac`]
+/// otherwise.
+pub fn hmac<D: Digest + BlockSizeUser>(key: &[u8], data: &[u8]) -> Output<D> {
+    let mut padded_key = Zeroizing::new(vec![0u8; D::block_size()]);
+    if key.len() > D::block_size() {
+        let hashed = D::digest(key);
+        padded_key[..hashed.len()].copy_from_slice(&hashed);
+    } else {
+        padded_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = D::new();
+    for byte in padded_key.iter_mut() {
+        *byte ^= 0x36;
+    }
+    Digest::update(&mut inner, &*padded_key);
+    Digest::update(&mut inner, data);
+    let inner = inner.finalize();
+
+    let mut outer = D::new();
+    // 0x36 ^ 0x5c
+    for byte in padded_key.iter_mut() {
+        *byte ^= 0x6a;
+    }
+    Digest::update(&mut outer, &*padded_key);
+    Digest::update(&mut outer, inner);
+    outer.finalize()
+}
+
+/// Compares two byte strings in time depending only on their lengths.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Hashes `label` and `index` to a point in the prime order subgroup with unknown discrete
+/// logarithm by try-and-increment: the hash output is interpreted as a compressed point until it
+/// decodes, then the cofactor is cleared. The number of attempts depends on the input, thus the
+/// input must not be secret.
+pub fn hash_to_curve<G: CurveGroup, D: Digest>(label: &[u8], index: u64) -> G::Affine {
+    let size = G::Affine::generator().compressed_size();
+    for counter in 0u64.. {
+        let mut bytes = Vec::with_capacity(size + <D as Digest>::output_size());
+        for block in 0u64.. {
+            if bytes.len() >= size {
+                break;
+            }
+            let mut digest = D::new();
+            digest.update(label);
+            digest.update(index.to_le_bytes());
+            digest.update(counter.to_le_bytes());
+            digest.update(block.to_le_bytes());
+            bytes.extend_from_slice(&digest.finalize());
+        }
+        if let Ok(point) =
+            G::Affine::deserialize_with_mode(&bytes[..size], Compress::Yes, Validate::No)
+        {
+            let point = point.clear_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+    }
+    unreachable!("hashing to the curve eventually succeeds")
+}
+
+struct DigestWriter<'a, D>(&'a mut D);
+
+impl<D: Digest> Write for DigestWriter<'_, D> {
+    fn write(&mut self, buf: &[u8]) -> ark_std::io::Result<usize> {
+        Digest::update(self.0, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> ark_std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::{G1Affine, Scalar, TestHash};
+    use ark_ec::AffineRepr;
+
+    #[test]
+    fn streaming_hasher_matches_hasher() {
+        let mut hasher = Hasher::<TestHash>::new();
+        let mut streaming = StreamingHasher::<TestHash>::new();
+        hasher.update(&b"domain".as_slice());
+        streaming.update(&b"domain".as_slice());
+        hasher.update(&G1Affine::generator());
+        streaming.update(&G1Affine::generator());
+
+        for label in [b"a".as_slice(), b"b", b"c"] {
+            assert_eq!(
+                hasher.next_scalar::<Scalar>(label),
+                streaming.next_scalar::<Scalar>(label)
+            );
+        }
+        hasher.update(&Scalar::from(7u8));
+        streaming.update(&Scalar::from(7u8));
+        assert_eq!(hasher.finalize(), streaming.finalize());
+    }
+
+    #[test]
+    fn key_derivation() {
+        let mut long = [0u8; 100];
+        let mut short = [0u8; 40];
+        expand::<TestHash>(b"domain", b"secret", &mut long);
+        expand::<TestHash>(b"domain", b"secret", &mut short);
+        assert_eq!(long[..40], short);
+        assert_ne!(long[..32], long[32..64]);
+        expand::<TestHash>(b"domain", b"secreT", &mut short);
+        assert_ne!(long[..40], short);
+
+        let tag = mac::<TestHash>(b"key", b"data");
+        assert!(constant_time_eq(&tag, &mac::<TestHash>(b"key", b"data")));
+        assert!(!constant_time_eq(&tag, &mac::<TestHash>(b"kez", b"data")));
+        assert!(!constant_time_eq(&tag, &mac::<TestHash>(b"keyd", b"ata")));
+        assert!(!constant_time_eq(&tag, &tag[..31]));
+    }
+}
@@ -0,0 +1,54 @@
+//! Minimal hex encoding used for interoperating with text based formats (trusted setup files,
+//! Ethereum tooling).
+use thiserror::Error as ErrorT;
+
+#[derive(ErrorT, Debug, PartialEq)]
+pub enum Error {
+    #[error("hex string has odd length {0}")]
+    OddLength(usize),
+    #[error("invalid hex character {0:?}")]
+    InvalidCharacter(char),
+}
+
+/// Encodes `bytes` as a lowercase hex string without a `0x` prefix.
+pub fn encode(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut result = String::with_capacity(2 * bytes.len());
+    for byte in bytes {
+        result.push(DIGITS[(byte >> 4) as usize] as char);
+        result.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    result
+}
+
+/// Decodes a hex string with an optional `0x` prefix, accepting both lower and uppercase digits.
+pub fn decode(input: &str) -> Result<Vec<u8>, Error> {
+    let input = input.strip_prefix("0x").unwrap_or(input);
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::OddLength(input.len()));
+    }
+    let digits = input
+        .chars()
+        .map(|c| c.to_digit(16).ok_or(Error::InvalidCharacter(c)))
+        .collect::<Result<Vec<u32>, Error>>()?;
+    Ok(digits
+        .chunks_exact(2)
+        .map(|pair| (pair[0] << 4 | pair[1]) as u8)
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hex_roundtrip() {
+        let bytes = [0x00, 0x01, 0xab, 0xff, 0x7f];
+        assert_eq!(encode(&bytes), "0001abff7f");
+        assert_eq!(decode("0001abff7f").unwrap(), bytes);
+        assert_eq!(decode("0x0001ABFF7F").unwrap(), bytes);
+        assert_eq!(decode("").unwrap(), Vec::<u8>::new());
+        assert_eq!(decode("abc"), Err(Error::OddLength(3)));
+        assert_eq!(decode("0g"), Err(Error::InvalidCharacter('g')));
+    }
+}
@@ -3,13 +3,21 @@
 #![deny(unused_crate_dependencies)]
 
 pub mod adaptor_sig;
+#[cfg(feature = "serde")]
+mod canonical_serde;
+pub mod codec;
 pub mod commit;
+pub mod curves;
 pub mod dleq;
 pub mod encrypt;
+pub mod equality;
 pub mod hash;
+pub mod hex;
 pub mod range_proof;
+pub mod sum;
 #[cfg(test)]
 mod tests;
+pub mod transcript;
 pub mod veck;
 
 use thiserror::Error;
@@ -18,6 +26,26 @@ use thiserror::Error;
 pub enum Error {
     #[error("couldn't generate valid FFT domain of size {0}")]
     InvalidFftDomain(usize),
+    #[error("setup has {available} powers, but {required} are required")]
+    InsufficientPowers { required: usize, available: usize },
+    #[error("setup powers are not consistent powers of the same secret")]
+    InconsistentPowers,
+    #[error("opening points must be distinct")]
+    RepeatedOpeningPoint,
+    #[error("invalid degree bound {0}")]
+    InvalidDegreeBound(usize),
+    #[error("polynomial of degree {degree} exceeds the degree bound {bound}")]
+    DegreeBoundExceeded { degree: usize, bound: usize },
+    #[error("rows of the matrix have different lengths")]
+    RaggedMatrix,
+    #[error("invalid window size of {0} bits")]
+    InvalidWindowSize(usize),
+    #[error("index {0} is out of bounds")]
+    IndexOutOfBounds(usize),
+    #[error("expected {expected} elements, got {actual}")]
+    LengthMismatch { expected: usize, actual: usize },
+    #[error(transparent)]
+    Codec(#[from] codec::Error),
     #[error(transparent)]
     RangeProof(#[from] range_proof::Error),
     #[error(transparent)]
@@ -0,0 +1,178 @@
+use super::{Error, RangeProof};
+use crate::commit::kzg::Powers;
+use crate::hash::Hasher;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM as Msm};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::{One, UniformRand};
+use digest::Digest;
+
+// a single KZG opening `commitment(point) = value` with witness `proof`
+#[derive(Clone, Copy, Debug)]
+struct Opening<C: Pairing> {
+    proof: C::G1Affine,
+    commitment: C::G1,
+    point: C::ScalarField,
+    value: C::ScalarField,
+}
+
+/// Collection of range proofs whose pairing checks are deferred and performed at once.
+///
+/// [`Self::push`] runs every check of [`RangeProof::verify`] that doesn't involve pairings and
+/// records the two KZG openings of the proof. [`Self::finalize`] then folds all recorded openings
+/// into a single pairing product using random 128 bit weights, so verifying thousands of proofs
+/// (possibly of different bit sizes, collected across sessions) costs two pairings in total.
+///
+/// If the final check fails, it is not known which proof is invalid. Proofs can be verified
+/// individually in that case.
+#[derive(Clone, Debug)]
+pub struct Archive<C: Pairing, D> {
+    openings: Vec<Opening<C>>,
+    proofs: Vec<RangeProof<C, D>>,
+}
+
+impl<C: Pairing, D> Default for Archive<C, D> {
+    fn default() -> Self {
+        Self {
+            openings: Vec::new(),
+            proofs: Vec::new(),
+        }
+    }
+}
+
+impl<C: Pairing, D: Digest> Archive<C, D> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a proof for bit size `n` to the archive.
+    ///
+    /// Returns an error if the proof is malformed or fails any of the checks that can be done
+    /// without pairings, in which case the archive is left unchanged.
+    pub fn push(&mut self, proof: RangeProof<C, D>, n: usize) -> Result<(), CrateError> {
+        proof.validate()?;
+        let domain = GeneralEvaluationDomain::<C::ScalarField>::new(n)
+            .ok_or(CrateError::InvalidFftDomain(n))?;
+        let (tau, rho, aggregation_challenge) = proof.challenges(n, &domain, Hasher::<D>::new());
+
+        let mut openings = Vec::with_capacity(2);
+        proof.check(
+            &domain,
+            tau,
+            rho,
+            aggregation_challenge,
+            |proof, commitment, point, value| {
+                openings.push(Opening {
+                    proof,
+                    commitment,
+                    point,
+                    value,
+                });
+                true
+            },
+        )?;
+
+        self.openings.append(&mut openings);
+        self.proofs.push(proof);
+        Ok(())
+    }
+
+    /// Returns the archived proofs in insertion order.
+    pub fn proofs(&self) -> &[RangeProof<C, D>] {
+        &self.proofs
+    }
+
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+
+    /// Performs the deferred pairing checks of every archived proof in one batch.
+    ///
+    /// With random weights `r_i`, this checks that
+    /// `e(sum(r_i * (com_i - value_i * g1 + point_i * proof_i)), g2)` equals
+    /// `e(sum(r_i * proof_i), tau * g2)`, which holds for all openings if it holds for a random
+    /// combination of them.
+    pub fn finalize<R: Rng + CryptoRng>(
+        &self,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<(), CrateError> {
+        let weights: Vec<C::ScalarField> = self
+            .openings
+            .iter()
+            .map(|_| u128::rand(rng).into())
+            .collect();
+
+        let mut value_sum = C::ScalarField::from(0u8);
+        let mut lhs_bases = Vec::with_capacity(2 * self.openings.len());
+        let mut lhs_scalars = Vec::with_capacity(2 * self.openings.len());
+        for (opening, weight) in self.openings.iter().zip(&weights) {
+            value_sum += *weight * opening.value;
+            lhs_bases.push(opening.commitment);
+            lhs_scalars.push(*weight);
+            lhs_bases.push(opening.proof.into_group());
+            lhs_scalars.push(*weight * opening.point);
+        }
+        let lhs_bases = C::G1::normalize_batch(&lhs_bases);
+        let lhs =
+            C::G1::msm_unchecked(&lhs_bases, &lhs_scalars) - C::G1Affine::generator() * value_sum;
+
+        let witnesses: Vec<C::G1Affine> = self.openings.iter().map(|o| o.proof).collect();
+        let rhs = C::G1::msm_unchecked(&witnesses, &weights);
+
+        let g1 = C::G1::normalize_batch(&[lhs, -rhs]);
+        let check = C::multi_pairing(g1, [C::G2Affine::generator(), powers.g2_tau()]);
+        if check.0.is_one() {
+            Ok(())
+        } else {
+            Err(Error::ArchiveCheckFailed.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{G1Affine, Scalar, TestCurve, TestHash};
+
+    type Proof = RangeProof<TestCurve, TestHash>;
+
+    #[test]
+    fn archive_of_range_proofs() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * 16);
+
+        let mut archive = Archive::<TestCurve, TestHash>::new();
+        assert!(archive.finalize(&powers, rng).is_ok());
+        for (z, n) in [(3u32, 4), (255, 8), (40000, 16), (0, 8)] {
+            let proof = Proof::new(Scalar::from(z), n, &powers, rng).unwrap();
+            archive.push(proof, n).unwrap();
+        }
+        assert_eq!(archive.len(), 4);
+        assert!(archive.finalize(&powers, rng).is_ok());
+
+        // a proof that only fails the pairing checks is detected at the end
+        let mut proof = Proof::new(Scalar::from(7u32), 8, &powers, rng).unwrap();
+        proof.proofs.shifted = (proof.proofs.shifted + G1Affine::generator()).into_affine();
+        assert!(proof.verify(8, &powers).is_err());
+        archive.push(proof, 8).unwrap();
+        assert_eq!(
+            archive.finalize(&powers, rng),
+            Err(CrateError::RangeProof(Error::ArchiveCheckFailed))
+        );
+
+        // a proof for another bit size is rejected right away
+        let mut archive = Archive::<TestCurve, TestHash>::new();
+        let proof = Proof::new(Scalar::from(7u32), 8, &powers, rng).unwrap();
+        assert!(archive.push(proof, 16).is_err());
+        assert!(archive.is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+use super::RangeProof;
+use crate::commit::kzg::Powers;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ff::{BigInteger, PrimeField};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+
+/// Result of [`RangeProof::check_witness`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WitnessReport {
+    /// Number of bits the proof would cover, i.e. `n` rounded up to the next power of two.
+    pub bits: usize,
+    /// Positions of the set bits of `z` that are out of range (`>= bits`).
+    pub violating_bits: Vec<usize>,
+    /// Number of G1 powers needed to commit to the prover's polynomials.
+    pub required_powers: usize,
+    /// Number of G1 powers available in the SRS.
+    pub available_powers: usize,
+}
+
+impl WitnessReport {
+    pub fn in_range(&self) -> bool {
+        self.violating_bits.is_empty()
+    }
+
+    pub fn srs_is_large_enough(&self) -> bool {
+        self.available_powers >= self.required_powers
+    }
+
+    /// Returns `true` if proving would succeed.
+    pub fn is_valid(&self) -> bool {
+        self.in_range() && self.srs_is_large_enough()
+    }
+}
+
+impl<C: Pairing, D> RangeProof<C, D> {
+    /// Checks whether a proof of `0 <= z < 2^n` can be generated with the given `powers` without
+    /// actually running the (relatively expensive) prover.
+    pub fn check_witness(
+        z: C::ScalarField,
+        n: usize,
+        powers: &Powers<C>,
+    ) -> Result<WitnessReport, CrateError> {
+        let bits = GeneralEvaluationDomain::<C::ScalarField>::new(n)
+            .ok_or(CrateError::InvalidFftDomain(n))?
+            .size();
+        let violating_bits = z
+            .into_bigint()
+            .to_bits_le()
+            .into_iter()
+            .enumerate()
+            .skip(bits)
+            .filter_map(|(i, bit)| bit.then_some(i))
+            .collect();
+        // the quotient polynomial has degree 4n - 3 and the opening proofs need tau * G2
+        let available_powers = if powers.g2.len() < 2 {
+            0
+        } else {
+            powers.g1.len()
+        };
+
+        Ok(WitnessReport {
+            bits,
+            violating_bits,
+            required_powers: 4 * bits - 2,
+            available_powers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_std::UniformRand;
+
+    type Proof = RangeProof<TestCurve, TestHash>;
+
+    #[test]
+    fn witness_diagnostics() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 30);
+
+        let report = Proof::check_witness(Scalar::from(255u32), 8, &powers).unwrap();
+        assert!(report.is_valid());
+        assert!(Proof::new(Scalar::from(255u32), 8, &powers, rng).is_ok());
+
+        let report = Proof::check_witness(Scalar::from(0b1010_0000_0001u32), 8, &powers).unwrap();
+        assert_eq!(report.violating_bits, vec![9, 11]);
+        assert!(report.srs_is_large_enough());
+        assert!(!report.is_valid());
+
+        // n is rounded up to 16 bits which requires 62 powers
+        let report = Proof::check_witness(Scalar::from(1u32 << 12), 12, &powers).unwrap();
+        assert_eq!(report.bits, 16);
+        assert!(report.in_range());
+        assert_eq!(report.required_powers, 62);
+        assert!(!report.srs_is_large_enough());
+    }
+}
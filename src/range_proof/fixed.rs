@@ -0,0 +1,160 @@
+use super::{Error, RangeProof};
+use crate::commit::kzg::Powers;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_std::rand::{CryptoRng, Rng};
+use digest::Digest;
+
+/// Range proof whose bit width is part of its type, i.e. it proves that `0 <= z < 2^N`.
+///
+/// [`RangeProof::verify`] takes the bit width as a runtime argument which has to match the one
+/// used by the prover. Here `N` is fixed by the type, so a proof for a 32 bit value cannot be
+/// passed to code expecting a 64 bit proof. Since [`RangeProof`] silently rounds the bit width up
+/// to the next power of two, `N` is required to be a power of two, otherwise the code doesn't
+/// compile.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedWidthRangeProof<C: Pairing, D, const N: usize> {
+    proof: RangeProof<C, D>,
+}
+
+impl<C: Pairing, D: Digest, const N: usize> FixedWidthRangeProof<C, D, N> {
+    const VALID_WIDTH: () = assert!(N.is_power_of_two(), "bit width must be a power of two");
+
+    pub fn new<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let () = Self::VALID_WIDTH;
+        let proof = RangeProof::new(z, N, powers, rng)?;
+        Ok(Self { proof })
+    }
+
+    /// Same as [`Self::new`] but the blinding factor of the `f` commitment is provided by the
+    /// caller, see [`RangeProof::new_with_blinding`].
+    pub fn new_with_blinding<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        r: C::ScalarField,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let () = Self::VALID_WIDTH;
+        let proof = RangeProof::new_with_blinding(z, r, N, powers, rng)?;
+        Ok(Self { proof })
+    }
+
+    /// Wraps a proof that is claimed to be generated for bit width `N`. This is not checked, but
+    /// a proof generated for another width will fail verification.
+    pub fn from_proof(proof: RangeProof<C, D>) -> Self {
+        let () = Self::VALID_WIDTH;
+        Self { proof }
+    }
+
+    pub fn verify(&self, powers: &Powers<C>) -> Result<(), CrateError> {
+        self.proof.verify(N, powers)
+    }
+
+    pub fn proof(&self) -> &RangeProof<C, D> {
+        &self.proof
+    }
+
+    pub fn into_inner(self) -> RangeProof<C, D> {
+        self.proof
+    }
+}
+
+/// Proof that a value fits into a `u32`, i.e. `0 <= z < 2^32`.
+pub type U32RangeProof<C, D> = FixedWidthRangeProof<C, D, 32>;
+/// Proof that a value fits into a `u64`, i.e. `0 <= z < 2^64`.
+pub type U64RangeProof<C, D> = FixedWidthRangeProof<C, D, 64>;
+/// Proof that a value fits into a `u128`, i.e. `0 <= z < 2^128`.
+pub type U128RangeProof<C, D> = FixedWidthRangeProof<C, D, 128>;
+
+impl<C: Pairing, D: Digest> RangeProof<C, D> {
+    /// Proves that the committed value is in `[0, 2^32)`.
+    pub fn prove_u32<R: Rng + CryptoRng>(
+        value: u32,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<U32RangeProof<C, D>, CrateError> {
+        U32RangeProof::new(C::ScalarField::from(value), powers, rng)
+    }
+
+    /// Proves that the committed value is in `[0, 2^64)`.
+    pub fn prove_u64<R: Rng + CryptoRng>(
+        value: u64,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<U64RangeProof<C, D>, CrateError> {
+        U64RangeProof::new(C::ScalarField::from(value), powers, rng)
+    }
+
+    /// Proves that the committed value is in `[0, 2^128)`.
+    ///
+    /// Returns [`Error::InputOutOfBounds`] if the scalar field is too small to represent every
+    /// `u128` without wrapping around.
+    pub fn prove_u128<R: Rng + CryptoRng>(
+        value: u128,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<U128RangeProof<C, D>, CrateError> {
+        if C::ScalarField::MODULUS_BIT_SIZE <= 128 {
+            return Err(Error::InputOutOfBounds.into());
+        }
+        U128RangeProof::new(C::ScalarField::from(value), powers, rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::range_proof::Error;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_std::UniformRand;
+
+    type RangeProof8 = FixedWidthRangeProof<TestCurve, TestHash, 8>;
+    type RangeProof16 = FixedWidthRangeProof<TestCurve, TestHash, 16>;
+
+    #[test]
+    fn fixed_width_range_proof() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * 16);
+
+        let proof = RangeProof8::new(Scalar::from(255u32), &powers, rng).unwrap();
+        assert!(proof.verify(&powers).is_ok());
+        assert_eq!(
+            RangeProof8::new(Scalar::from(256u32), &powers, rng).unwrap_err(),
+            CrateError::RangeProof(Error::InputOutOfBounds)
+        );
+
+        // a proof for another width can only be smuggled in explicitly and it is rejected
+        let proof = RangeProof16::from_proof(proof.into_inner());
+        assert!(proof.verify(&powers).is_err());
+
+        // domain sizes that do not fit into a byte
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * 256);
+        let z = Scalar::from(u128::MAX) * Scalar::from(u64::MAX);
+        let proof = FixedWidthRangeProof::<TestCurve, TestHash, 256>::new(z, &powers, rng).unwrap();
+        assert!(proof.verify(&powers).is_ok());
+    }
+
+    #[test]
+    fn range_proofs_from_primitive_integers() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * 128);
+
+        type Proof = RangeProof<TestCurve, TestHash>;
+        let proof = Proof::prove_u32(u32::MAX, &powers, rng).unwrap();
+        assert!(proof.verify(&powers).is_ok());
+        assert!(proof.proof().verify(32, &powers).is_ok());
+        let proof = Proof::prove_u64(u64::MAX, &powers, rng).unwrap();
+        assert!(proof.verify(&powers).is_ok());
+        let proof = Proof::prove_u128(u128::MAX, &powers, rng).unwrap();
+        assert!(proof.verify(&powers).is_ok());
+        assert!(proof.proof().verify(64, &powers).is_err());
+    }
+}
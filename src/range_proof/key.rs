@@ -0,0 +1,91 @@
+use super::RangeProof;
+use crate::commit::kzg::Powers;
+use crate::hash::StreamingHasher;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use digest::Digest;
+
+/// Precomputed parameters for verifying range proofs of bit size `n`.
+///
+/// Together with [`RangeProof::verify_with_key`] this provides a verification path that works
+/// exclusively with fixed-size data and a [`StreamingHasher`], so it can run in constrained
+/// environments like zkVM guests or embedded devices. The only heap allocations left are the ones
+/// performed internally by the arkworks pairing implementation.
+#[derive(Clone, Copy, Debug)]
+pub struct VerifierKey<C: Pairing> {
+    pub n: usize,
+    pub domain: GeneralEvaluationDomain<C::ScalarField>,
+    pub g2_tau: C::G2Affine,
+}
+
+impl<C: Pairing> VerifierKey<C> {
+    pub fn new(n: usize, g2_tau: C::G2Affine) -> Result<Self, CrateError> {
+        let domain = GeneralEvaluationDomain::<C::ScalarField>::new(n)
+            .ok_or(CrateError::InvalidFftDomain(n))?;
+        Ok(Self { n, domain, g2_tau })
+    }
+
+    pub fn from_powers(n: usize, powers: &Powers<C>) -> Result<Self, CrateError> {
+        Self::new(n, powers.g2_tau())
+    }
+}
+
+impl<C: Pairing, D: Digest + Clone> RangeProof<C, D> {
+    /// Same as [`Self::verify`] but without heap allocations, see [`VerifierKey`].
+    pub fn verify_with_key(&self, key: &VerifierKey<C>) -> Result<(), CrateError> {
+        self.validate()?;
+
+        let (tau, rho, aggregation_challenge) =
+            self.challenges(key.n, &key.domain, StreamingHasher::<D>::new());
+        self.check(
+            &key.domain,
+            tau,
+            rho,
+            aggregation_challenge,
+            |proof, com, point, value| super::kzg_check::<C>(proof, com, point, value, key.g2_tau),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::range_proof::Error;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_std::UniformRand;
+
+    const LOG_2_UPPER_BOUND: usize = 8;
+
+    #[test]
+    fn verify_with_precomputed_key() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let key = VerifierKey::from_powers(LOG_2_UPPER_BOUND, &powers).unwrap();
+
+        let mut proof = RangeProof::<TestCurve, TestHash>::new(
+            Scalar::from(42u8),
+            LOG_2_UPPER_BOUND,
+            &powers,
+            rng,
+        )
+        .unwrap();
+        assert!(proof.verify_with_key(&key).is_ok());
+
+        // key for a different bit size
+        let other_key = VerifierKey::from_powers(2 * LOG_2_UPPER_BOUND, &powers).unwrap();
+        assert!(proof.verify_with_key(&other_key).is_err());
+
+        proof.evaluations.g_omega += Scalar::from(1u8);
+        assert_eq!(
+            proof.verify_with_key(&key),
+            proof.verify(LOG_2_UPPER_BOUND, &powers)
+        );
+        assert_eq!(
+            proof.verify_with_key(&key),
+            Err(CrateError::RangeProof(Error::ExpectedZeroPolynomial))
+        );
+    }
+}
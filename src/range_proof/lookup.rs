@@ -0,0 +1,780 @@
+use super::poly::{self, SecretPolynomial};
+use super::{kzg_check, Error, Proofs};
+use crate::commit::kzg::{Kzg, Powers};
+use crate::equality::Bases;
+use crate::hash::Hasher;
+use crate::transcript::Transcript;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::{One, UniformRand, Zero};
+use digest::Digest;
+use zeroize::{Zeroize, Zeroizing};
+
+const PROOF_DOMAIN_SEP: &[u8] = b"fde lookup range proof";
+const COMMITMENT_LABELS: [&[u8]; 4] = [b"f", b"g", b"h1", b"h2"];
+
+/// Largest supported chunk size, i.e. tables have at most `2^16` entries.
+pub const MAX_CHUNK_BITS: usize = 16;
+
+// the quotient has degree 2N + 7 which requires a coset of size 4N >= 3N + 8
+const MIN_DOMAIN_SIZE: usize = 8;
+
+/// Precommitted table `{0, 1, .., 2^c - 1}` of `c` bit chunks used by [`LookupRangeProof`].
+///
+/// The table is interpolated over an evaluation domain of size `N` which is large enough for both
+/// the table and `max_chunks` chunks, padding the table with its largest entry. It only depends on
+/// the setup, thus it is computed once and shared by every proof using the same chunk size.
+#[derive(Clone, Debug)]
+pub struct LookupTable<C: Pairing> {
+    pub chunk_bits: usize,
+    pub domain: GeneralEvaluationDomain<C::ScalarField>,
+    pub poly: DensePolynomial<C::ScalarField>,
+    pub commitment: C::G1Affine,
+}
+
+impl<C: Pairing> LookupTable<C> {
+    pub fn new(
+        chunk_bits: usize,
+        max_chunks: usize,
+        powers: &Powers<C>,
+    ) -> Result<Self, CrateError> {
+        if chunk_bits == 0 || chunk_bits > MAX_CHUNK_BITS {
+            return Err(Error::UnsupportedBitSize(chunk_bits).into());
+        }
+        let size = (1 << chunk_bits).max(max_chunks + 1).max(MIN_DOMAIN_SIZE);
+        let domain = GeneralEvaluationDomain::<C::ScalarField>::new(size)
+            .ok_or(CrateError::InvalidFftDomain(size))?;
+        let evaluations: Vec<C::ScalarField> = table_values(chunk_bits, domain.size())
+            .map(C::ScalarField::from)
+            .collect();
+        let poly = DensePolynomial::from_coefficients_vec(domain.ifft(&evaluations));
+        let commitment = powers.commit_g1(&poly)?.into_affine();
+        Ok(Self {
+            chunk_bits,
+            domain,
+            poly,
+            commitment,
+        })
+    }
+
+    /// Maximum number of chunks a proved value may consist of.
+    pub fn max_chunks(&self) -> usize {
+        self.domain.size() - 1
+    }
+
+    /// Number of G1 powers required for proving, the quotient polynomial has degree `2N + 7`.
+    pub fn required_powers(&self) -> usize {
+        2 * self.domain.size() + 8
+    }
+
+    /// Returns the bases `g` and `h` such that the `f` commitment of a proof equals
+    /// `z * g + r * h`, see [`RangeProof::commitment_bases`](super::RangeProof::commitment_bases).
+    pub fn commitment_bases(&self, powers: &Powers<C>) -> Result<Bases<C::G1>, CrateError> {
+        let zero = C::ScalarField::zero();
+        let one = C::ScalarField::one();
+        let g = powers.commit_g1(&poly::f(&self.domain, one, zero))?;
+        let h = powers.commit_g1(&poly::f(&self.domain, zero, one))?;
+        let bases = C::G1::normalize_batch(&[g, h]);
+        Ok(Bases::new(bases[0], bases[1]))
+    }
+
+    // number of chunks of a value with `n` bits
+    fn chunks(&self, n: usize) -> Result<usize, CrateError> {
+        let chunks = n / self.chunk_bits;
+        if n == 0
+            || !n.is_multiple_of(self.chunk_bits)
+            || chunks > self.max_chunks()
+            || n >= C::ScalarField::MODULUS_BIT_SIZE as usize
+        {
+            return Err(Error::UnsupportedBitSize(n).into());
+        }
+        Ok(chunks)
+    }
+}
+
+// table entries in domain order, padded with the largest entry
+fn table_values(chunk_bits: usize, size: usize) -> impl Iterator<Item = u64> {
+    let max = (1u64 << chunk_bits) - 1;
+    (0..size as u64).map(move |i| i.min(max))
+}
+
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LookupEvaluations<S: CanonicalSerialize + CanonicalDeserialize> {
+    pub f: S,
+    pub g: S,
+    pub g_omega: S,
+    pub t: S,
+    pub t_omega: S,
+    pub h1: S,
+    pub h1_omega: S,
+    pub h2: S,
+    pub h2_omega: S,
+    pub product: S,
+    pub product_omega: S,
+    pub q: S,
+}
+
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LookupCommitments<C: Pairing> {
+    pub f: C::G1Affine,
+    pub g: C::G1Affine,
+    pub h1: C::G1Affine,
+    pub h2: C::G1Affine,
+    pub product: C::G1Affine,
+    pub q: C::G1Affine,
+}
+
+/// Range proof that checks `c` bit chunks of the value against a precommitted [`LookupTable`],
+/// i.e. it proves that `0 <= z < 2^n` where `n` is a multiple of `c`.
+///
+/// Instead of decomposing `z` into `n` bits like [`RangeProof`](super::RangeProof), the prover
+/// writes `z = sum(chunk_i * 2^(c * i))` and shows with a plookup argument that every chunk is a
+/// table entry. The polynomials are defined over a domain with about `max(2^c, n / c)` elements,
+/// e.g. 32 for 4 bit chunks of a 64 bit value, compared to the `2n` and `4n` degree polynomials of
+/// the bitwise proof. The price is a larger proof and the one-time table commitment.
+///
+/// With evaluation domain `H = {1, w, .., w^(N-1)}` the polynomials are
+/// - `f` with `f(1) = z` and `f(w) = r`, just like in the bitwise proof,
+/// - `g` with `g(w^i) = sum_{j >= i}(chunk_j * 2^(c * (j - i)))`, thus the chunks are
+///   `g(X) - 2^c * g(wX)` on `H` and `g(w^k) = 0` for the number of chunks `k`,
+/// - `h1`, `h2` and the grand product `product` of the plookup argument, see
+///   [the paper](https://eprint.iacr.org/2020/315),
+/// - and the quotient `q` of the combined constraints divided by `X^N - 1`.
+///
+/// Every polynomial but `f` is blinded by a random multiple of `X^N - 1`.
+#[derive(Clone, Copy, Debug)]
+pub struct LookupRangeProof<C: Pairing, D> {
+    pub evaluations: LookupEvaluations<C::ScalarField>,
+    pub commitments: LookupCommitments<C>,
+    pub proofs: Proofs<C>,
+    _digest: PhantomData<D>,
+}
+
+impl<C: Pairing, D: Digest> LookupRangeProof<C, D> {
+    // prove 0 <= z < 2^n
+    pub fn new<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        n: usize,
+        table: &LookupTable<C>,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let r = C::ScalarField::rand(rng);
+        Self::new_with_blinding(z, r, n, table, powers, rng)
+    }
+
+    /// Same as [`Self::new`] but the blinding factor of the `f` commitment is provided by the
+    /// caller, see [`LookupTable::commitment_bases`].
+    pub fn new_with_blinding<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        r: C::ScalarField,
+        n: usize,
+        table: &LookupTable<C>,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let k = table.chunks(n)?;
+        if z.into_bigint().num_bits() as usize > n {
+            return Err(Error::InputOutOfBounds.into());
+        }
+
+        let domain = &table.domain;
+        let size = domain.size();
+        let c = table.chunk_bits;
+        let shift = C::ScalarField::from(1u64 << c);
+        let z = Zeroizing::new(z);
+        let r = Zeroizing::new(r);
+
+        // chunks of z on the first N - 1 rows, the remaining rows are zero
+        let mut bits = z.into_bigint().to_bits_le();
+        let mut chunks = vec![0u64; size - 1];
+        for (i, chunk) in chunks.iter_mut().take(k).enumerate() {
+            *chunk = bits[i * c..(i + 1) * c]
+                .iter()
+                .rev()
+                .fold(0, |acc, &bit| (acc << 1) | bit as u64);
+        }
+        bits.zeroize();
+
+        // accumulated chunks g_i = chunk_i + 2^c * g_(i+1)
+        let mut g_evals = vec![C::ScalarField::zero(); size];
+        for i in (0..k).rev() {
+            g_evals[i] = C::ScalarField::from(chunks[i]) + shift * g_evals[i + 1];
+        }
+
+        // chunks and table sorted together
+        let mut sorted: Vec<u64> = chunks
+            .iter()
+            .copied()
+            .chain(table_values(c, size))
+            .collect();
+        sorted.sort_unstable();
+        let mut sorted_evals: Vec<C::ScalarField> =
+            sorted.iter().copied().map(C::ScalarField::from).collect();
+        let t_evals: Vec<C::ScalarField> =
+            table_values(c, size).map(C::ScalarField::from).collect();
+
+        let f_poly = SecretPolynomial(poly::f(domain, *z, *r));
+        let g_poly = SecretPolynomial(blind(domain, &g_evals, rng));
+        let h1_poly = SecretPolynomial(blind(domain, &sorted_evals[..size], rng));
+        let h2_poly = SecretPolynomial(blind(domain, &sorted_evals[size - 1..], rng));
+
+        let commitments_1 = C::G1::normalize_batch(&[
+            powers.commit_g1(&*f_poly)?,
+            powers.commit_g1(&*g_poly)?,
+            powers.commit_g1(&*h1_poly)?,
+            powers.commit_g1(&*h2_poly)?,
+        ]);
+
+        let mut transcript = Hasher::<D>::new();
+        transcript.append(b"domain separator", &PROOF_DOMAIN_SEP);
+        transcript.append(b"n", &n.to_le_bytes());
+        transcript.append(b"chunk bits", &c.to_le_bytes());
+        transcript.append(b"table", &table.commitment);
+        for (label, commitment) in COMMITMENT_LABELS.into_iter().zip(&commitments_1) {
+            transcript.append(label, commitment);
+        }
+        let beta: C::ScalarField = transcript.challenge_scalar(b"beta");
+        let gamma: C::ScalarField = transcript.challenge_scalar(b"gamma");
+
+        // grand product of the plookup argument
+        let one_plus_beta = C::ScalarField::one() + beta;
+        let gamma_beta = gamma * one_plus_beta;
+        let mut denominators: Vec<C::ScalarField> = (0..size - 1)
+            .map(|i| {
+                (gamma_beta + sorted_evals[i] + beta * sorted_evals[i + 1])
+                    * (gamma_beta + sorted_evals[size - 1 + i] + beta * sorted_evals[size + i])
+            })
+            .collect();
+        ark_ff::batch_inversion(&mut denominators);
+        let mut product_evals = vec![C::ScalarField::one(); size];
+        for i in 0..size - 1 {
+            let chunk = g_evals[i] - shift * g_evals[i + 1];
+            product_evals[i + 1] = product_evals[i]
+                * one_plus_beta
+                * (gamma + chunk)
+                * (gamma_beta + t_evals[i] + beta * t_evals[i + 1])
+                * denominators[i];
+        }
+        let product_poly = SecretPolynomial(blind(domain, &product_evals, rng));
+        let product_commitment = powers.commit_g1(&*product_poly)?.into_affine();
+
+        chunks.zeroize();
+        sorted.zeroize();
+        g_evals.zeroize();
+        sorted_evals.zeroize();
+        product_evals.zeroize();
+
+        transcript.append(b"product", &product_commitment);
+        let alpha: C::ScalarField = transcript.challenge_scalar(b"alpha");
+
+        let polys = Polys {
+            f: &f_poly,
+            g: &g_poly,
+            t: &table.poly,
+            h1: &h1_poly,
+            h2: &h2_poly,
+            product: &product_poly,
+        };
+        let q_poly = SecretPolynomial(quotient(domain, k, c, &polys, beta, gamma, alpha)?);
+        let q_commitment = powers.commit_g1(&*q_poly)?.into_affine();
+
+        transcript.append(b"q", &q_commitment);
+        let zeta: C::ScalarField = transcript.challenge_scalar(b"zeta");
+        let zeta_omega = zeta * domain.group_gen();
+
+        let evaluations = LookupEvaluations {
+            f: f_poly.evaluate(&zeta),
+            g: g_poly.evaluate(&zeta),
+            g_omega: g_poly.evaluate(&zeta_omega),
+            t: table.poly.evaluate(&zeta),
+            t_omega: table.poly.evaluate(&zeta_omega),
+            h1: h1_poly.evaluate(&zeta),
+            h1_omega: h1_poly.evaluate(&zeta_omega),
+            h2: h2_poly.evaluate(&zeta),
+            h2_omega: h2_poly.evaluate(&zeta_omega),
+            product: product_poly.evaluate(&zeta),
+            product_omega: product_poly.evaluate(&zeta_omega),
+            q: q_poly.evaluate(&zeta),
+        };
+        transcript.append(b"evaluations", &evaluations);
+        let nu: C::ScalarField = transcript.challenge_scalar(b"aggregation_challenge");
+
+        let opened_at_zeta = [
+            &*f_poly,
+            &*g_poly,
+            &table.poly,
+            &*h1_poly,
+            &*h2_poly,
+            &*product_poly,
+            &*q_poly,
+        ];
+        let aggregate_poly = SecretPolynomial(aggregate(&opened_at_zeta, nu));
+        let aggregate_witness = SecretPolynomial(Kzg::<C>::witness(&aggregate_poly, zeta));
+        let opened_at_zeta_omega = [&*g_poly, &table.poly, &*h1_poly, &*h2_poly, &*product_poly];
+        let shifted_poly = SecretPolynomial(aggregate(&opened_at_zeta_omega, nu));
+        let shifted_witness = SecretPolynomial(Kzg::<C>::witness(&shifted_poly, zeta_omega));
+        let proofs = C::G1::normalize_batch(&[
+            powers.commit_g1(&*aggregate_witness)?,
+            powers.commit_g1(&*shifted_witness)?,
+        ]);
+
+        Ok(Self {
+            evaluations,
+            commitments: LookupCommitments {
+                f: commitments_1[0],
+                g: commitments_1[1],
+                h1: commitments_1[2],
+                h2: commitments_1[3],
+                product: product_commitment,
+                q: q_commitment,
+            },
+            proofs: Proofs {
+                aggregate: proofs[0],
+                shifted: proofs[1],
+            },
+            _digest: PhantomData,
+        })
+    }
+
+    pub fn verify(
+        &self,
+        n: usize,
+        table: &LookupTable<C>,
+        powers: &Powers<C>,
+    ) -> Result<(), CrateError> {
+        let k = table.chunks(n)?;
+        let coms = &self.commitments;
+        let points = [
+            coms.f,
+            coms.g,
+            coms.h1,
+            coms.h2,
+            coms.product,
+            coms.q,
+            self.proofs.aggregate,
+            self.proofs.shifted,
+        ];
+        if !points.iter().all(|point| point.check().is_ok()) {
+            return Err(Error::InvalidPoint.into());
+        }
+
+        let domain = &table.domain;
+        let c = table.chunk_bits;
+        let mut transcript = Hasher::<D>::new();
+        transcript.append(b"domain separator", &PROOF_DOMAIN_SEP);
+        transcript.append(b"n", &n.to_le_bytes());
+        transcript.append(b"chunk bits", &c.to_le_bytes());
+        transcript.append(b"table", &table.commitment);
+        for (label, commitment) in COMMITMENT_LABELS
+            .into_iter()
+            .zip(&[coms.f, coms.g, coms.h1, coms.h2])
+        {
+            transcript.append(label, commitment);
+        }
+        let beta: C::ScalarField = transcript.challenge_scalar(b"beta");
+        let gamma: C::ScalarField = transcript.challenge_scalar(b"gamma");
+        transcript.append(b"product", &coms.product);
+        let alpha: C::ScalarField = transcript.challenge_scalar(b"alpha");
+        transcript.append(b"q", &coms.q);
+        let zeta: C::ScalarField = transcript.challenge_scalar(b"zeta");
+        transcript.append(b"evaluations", &self.evaluations);
+        let nu: C::ScalarField = transcript.challenge_scalar(b"aggregation_challenge");
+
+        let evals = &self.evaluations;
+        let constraints = Constraints::new(domain, c, beta, gamma, alpha);
+        let vanishing = domain.evaluate_vanishing_polynomial(zeta);
+        let lagrange = |i: usize| {
+            let omega_i = domain.element(i);
+            omega_i * vanishing / (C::ScalarField::from(domain.size() as u64) * (zeta - omega_i))
+        };
+        let combined = constraints.combine(
+            [lagrange(0), lagrange(k), lagrange(domain.size() - 1)],
+            zeta,
+            &Evals {
+                f: evals.f,
+                g: evals.g,
+                g_omega: evals.g_omega,
+                t: evals.t,
+                t_omega: evals.t_omega,
+                h1: evals.h1,
+                h1_omega: evals.h1_omega,
+                h2: evals.h2,
+                h2_omega: evals.h2_omega,
+                product: evals.product,
+                product_omega: evals.product_omega,
+            },
+        );
+        if combined != evals.q * vanishing {
+            return Err(Error::ExpectedZeroPolynomial.into());
+        }
+
+        let g2_tau = powers.g2_tau();
+        let at_zeta = [
+            coms.f,
+            coms.g,
+            table.commitment,
+            coms.h1,
+            coms.h2,
+            coms.product,
+            coms.q,
+        ]
+        .map(|com| com.into_group());
+        let at_zeta_values = [
+            evals.f,
+            evals.g,
+            evals.t,
+            evals.h1,
+            evals.h2,
+            evals.product,
+            evals.q,
+        ];
+        let aggregate_check = kzg_check::<C>(
+            self.proofs.aggregate,
+            super::utils::aggregate(&at_zeta, nu),
+            zeta,
+            super::utils::aggregate(&at_zeta_values, nu),
+            g2_tau,
+        );
+
+        let at_zeta_omega =
+            [coms.g, table.commitment, coms.h1, coms.h2, coms.product].map(|com| com.into_group());
+        let at_zeta_omega_values = [
+            evals.g_omega,
+            evals.t_omega,
+            evals.h1_omega,
+            evals.h2_omega,
+            evals.product_omega,
+        ];
+        let shifted_check = kzg_check::<C>(
+            self.proofs.shifted,
+            super::utils::aggregate(&at_zeta_omega, nu),
+            zeta * domain.group_gen(),
+            super::utils::aggregate(&at_zeta_omega_values, nu),
+            g2_tau,
+        );
+
+        if !aggregate_check {
+            Err(Error::AggregateWitnessCheckFailed.into())
+        } else if !shifted_check {
+            Err(Error::ShiftedWitnessCheckFailed.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// interpolates the evaluations over the domain and adds a random multiple of X^N - 1 which leaves
+// the evaluations on the domain intact, but hides the evaluations at two points
+fn blind<S: PrimeField, R: Rng + CryptoRng>(
+    domain: &GeneralEvaluationDomain<S>,
+    evaluations: &[S],
+    rng: &mut R,
+) -> DensePolynomial<S> {
+    let poly = DensePolynomial::from_coefficients_vec(domain.ifft(evaluations));
+    let blinding =
+        DensePolynomial::from_coefficients_vec(vec![S::rand(rng), S::rand(rng), S::rand(rng)]);
+    &poly + &blinding.mul_by_vanishing_poly(*domain)
+}
+
+fn aggregate<S: PrimeField>(polys: &[&DensePolynomial<S>], by: S) -> DensePolynomial<S> {
+    let mut acc = S::one();
+    let mut result = DensePolynomial::zero();
+    for poly in polys {
+        result += (acc, *poly);
+        acc *= by;
+    }
+    result
+}
+
+struct Polys<'a, S: PrimeField> {
+    f: &'a DensePolynomial<S>,
+    g: &'a DensePolynomial<S>,
+    t: &'a DensePolynomial<S>,
+    h1: &'a DensePolynomial<S>,
+    h2: &'a DensePolynomial<S>,
+    product: &'a DensePolynomial<S>,
+}
+
+// evaluations of the polynomials at x and x * w
+struct Evals<S> {
+    f: S,
+    g: S,
+    g_omega: S,
+    t: S,
+    t_omega: S,
+    h1: S,
+    h1_omega: S,
+    h2: S,
+    h2_omega: S,
+    product: S,
+    product_omega: S,
+}
+
+// Constraints that hold on every point of the domain:
+// 1. L_0(X) * (g(X) - f(X)) = 0, i.e. g(1) = z
+// 2. L_k(X) * g(X) = 0, i.e. there are at most k chunks
+// 3. L_0(X) * (product(X) - 1) = 0
+// 4. (X - w^(N-1)) * (product(X) * (1 + beta) * (gamma + chunk(X)) * (gamma * (1 + beta) + t(X) +
+//    beta * t(wX)) - product(wX) * (gamma * (1 + beta) + h1(X) + beta * h1(wX)) * (gamma * (1 +
+//    beta) + h2(X) + beta * h2(wX))) = 0 where chunk(X) = g(X) - 2^c * g(wX)
+// 5. L_(N-1)(X) * (h1(X) - h2(wX)) = 0
+// 6. L_(N-1)(X) * (product(X) - 1) = 0
+struct Constraints<S> {
+    last: S,
+    shift: S,
+    beta: S,
+    gamma: S,
+    alpha: S,
+}
+
+impl<S: PrimeField> Constraints<S> {
+    fn new(domain: &GeneralEvaluationDomain<S>, c: usize, beta: S, gamma: S, alpha: S) -> Self {
+        Self {
+            last: domain.element(domain.size() - 1),
+            shift: S::from(1u64 << c),
+            beta,
+            gamma,
+            alpha,
+        }
+    }
+
+    // lagrange contains L_0(x), L_k(x) and L_(N-1)(x)
+    fn combine(&self, lagrange: [S; 3], x: S, evals: &Evals<S>) -> S {
+        let one = S::one();
+        let one_plus_beta = one + self.beta;
+        let gamma_beta = self.gamma * one_plus_beta;
+        let chunk = evals.g - self.shift * evals.g_omega;
+
+        let c1 = lagrange[0] * (evals.g - evals.f);
+        let c2 = lagrange[1] * evals.g;
+        let c3 = lagrange[0] * (evals.product - one);
+        let c4 = (x - self.last)
+            * (evals.product
+                * one_plus_beta
+                * (self.gamma + chunk)
+                * (gamma_beta + evals.t + self.beta * evals.t_omega)
+                - evals.product_omega
+                    * (gamma_beta + evals.h1 + self.beta * evals.h1_omega)
+                    * (gamma_beta + evals.h2 + self.beta * evals.h2_omega));
+        let c5 = lagrange[2] * (evals.h1 - evals.h2_omega);
+        let c6 = lagrange[2] * (evals.product - one);
+
+        let mut result = c6;
+        for constraint in [c5, c4, c3, c2, c1] {
+            result = result * self.alpha + constraint;
+        }
+        result
+    }
+}
+
+// computes the combined constraints divided by X^N - 1 on a coset of the 4N sized domain
+fn quotient<S: PrimeField>(
+    domain: &GeneralEvaluationDomain<S>,
+    k: usize,
+    c: usize,
+    polys: &Polys<S>,
+    beta: S,
+    gamma: S,
+    alpha: S,
+) -> Result<DensePolynomial<S>, CrateError> {
+    let size = domain.size();
+    let coset = GeneralEvaluationDomain::<S>::new(4 * size)
+        .and_then(|domain_4n| domain_4n.get_coset(S::GENERATOR))
+        .ok_or(CrateError::InvalidFftDomain(4 * size))?;
+
+    let mut f = coset.fft(polys.f);
+    let mut g = coset.fft(polys.g);
+    let t = coset.fft(polys.t);
+    let mut h1 = coset.fft(polys.h1);
+    let mut h2 = coset.fft(polys.h2);
+    let mut product = coset.fft(polys.product);
+
+    let rows = [
+        domain.element(0),
+        domain.element(k),
+        domain.element(size - 1),
+    ];
+    let mut inverses: Vec<S> = coset
+        .elements()
+        .flat_map(|x| {
+            [
+                x - rows[0],
+                x - rows[1],
+                x - rows[2],
+                x.pow([size as u64]) - S::one(),
+            ]
+        })
+        .collect();
+    ark_ff::batch_inversion(&mut inverses);
+
+    let constraints = Constraints::new(domain, c, beta, gamma, alpha);
+    let size_inv = S::from(size as u64).inverse().expect("size is non-zero");
+    let coset_size = coset.size();
+    let q_evals: Vec<S> = coset
+        .elements()
+        .zip(inverses.chunks_exact(4))
+        .enumerate()
+        .map(|(i, (x, inv))| {
+            // the vanishing polynomial is the inverse of inv[3]
+            let vanishing_over_size = size_inv * (S::one() / inv[3]);
+            let lagrange = [
+                rows[0] * vanishing_over_size * inv[0],
+                rows[1] * vanishing_over_size * inv[1],
+                rows[2] * vanishing_over_size * inv[2],
+            ];
+            let j = (i + 4) % coset_size;
+            let evals = Evals {
+                f: f[i],
+                g: g[i],
+                g_omega: g[j],
+                t: t[i],
+                t_omega: t[j],
+                h1: h1[i],
+                h1_omega: h1[j],
+                h2: h2[i],
+                h2_omega: h2[j],
+                product: product[i],
+                product_omega: product[j],
+            };
+            constraints.combine(lagrange, x, &evals) * inv[3]
+        })
+        .collect();
+
+    f.zeroize();
+    g.zeroize();
+    h1.zeroize();
+    h2.zeroize();
+    product.zeroize();
+
+    let q_poly = DensePolynomial::from_coefficients_vec(coset.ifft(&q_evals));
+    let max_degree = 2 * size + 7;
+    if q_poly.degree() > max_degree {
+        return Err(Error::QuotientNotDivisible {
+            degree: q_poly.degree(),
+            max_degree,
+        }
+        .into());
+    }
+    Ok(q_poly)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+
+    type Proof = LookupRangeProof<TestCurve, TestHash>;
+
+    #[test]
+    fn lookup_range_proof_success() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 2 * 32 + 8);
+        let table = LookupTable::new(4, 16, &powers).unwrap();
+        assert_eq!(table.domain.size(), 32);
+        assert_eq!(table.required_powers(), powers.g1.len());
+
+        for z in [0, 1, 255, 1 << 40, u64::MAX] {
+            let proof = Proof::new(Scalar::from(z), 64, &table, &powers, rng).unwrap();
+            assert!(proof.verify(64, &table, &powers).is_ok());
+        }
+
+        // fewer chunks than the table allows
+        let proof = Proof::new(Scalar::from(200u32), 8, &table, &powers, rng).unwrap();
+        assert!(proof.verify(8, &table, &powers).is_ok());
+        assert!(proof.verify(12, &table, &powers).is_err());
+    }
+
+    #[test]
+    fn lookup_range_proof_commitment() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 2 * 16 + 8);
+        let table = LookupTable::new(2, 8, &powers).unwrap();
+
+        let z = Scalar::from(1000u32);
+        let r = Scalar::rand(rng);
+        let proof = Proof::new_with_blinding(z, r, 16, &table, &powers, rng).unwrap();
+        assert!(proof.verify(16, &table, &powers).is_ok());
+        assert_eq!(
+            proof.commitments.f,
+            table
+                .commitment_bases(&powers)
+                .unwrap()
+                .commit(&z, &r)
+                .into_affine()
+        );
+    }
+
+    #[test]
+    fn lookup_range_proof_failures() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 2 * 32 + 8);
+        let table = LookupTable::new(4, 16, &powers).unwrap();
+
+        assert_eq!(
+            Proof::new(Scalar::from(256u32), 8, &table, &powers, rng).unwrap_err(),
+            CrateError::RangeProof(Error::InputOutOfBounds)
+        );
+        // not a multiple of the chunk size or too many chunks
+        for n in [0, 6, 128] {
+            assert_eq!(
+                Proof::new(Scalar::from(1u32), n, &table, &powers, rng).unwrap_err(),
+                CrateError::RangeProof(Error::UnsupportedBitSize(n))
+            );
+        }
+
+        let proof = Proof::new(Scalar::from(77u32), 8, &table, &powers, rng).unwrap();
+        let mut invalid = proof.clone();
+        invalid.evaluations.g += Scalar::one();
+        assert_eq!(
+            invalid.verify(8, &table, &powers),
+            Err(CrateError::RangeProof(Error::ExpectedZeroPolynomial))
+        );
+
+        // a table with another chunk size
+        let other = LookupTable::new(2, 16, &powers).unwrap();
+        assert!(proof.verify(8, &other, &powers).is_err());
+    }
+
+    #[test]
+    fn lookup_quotient_of_invalid_witness_fails() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 2 * 16 + 8);
+        let table = LookupTable::new(2, 8, &powers).unwrap();
+        let domain = &table.domain;
+        let size = domain.size();
+
+        // the accumulator encodes a chunk 4 which is not a table entry and there is no valid
+        // grand product
+        let mut g_evals = vec![Scalar::zero(); size];
+        g_evals[0] = Scalar::from(4u8);
+        let g_poly = blind(domain, &g_evals, rng);
+        let f_poly = poly::f(domain, Scalar::from(4u8), Scalar::rand(rng));
+        let zero = blind(domain, &vec![Scalar::zero(); size], rng);
+        let polys = Polys {
+            f: &f_poly,
+            g: &g_poly,
+            t: &table.poly,
+            h1: &zero,
+            h2: &zero,
+            product: &zero,
+        };
+        let (beta, gamma, alpha) = (Scalar::rand(rng), Scalar::rand(rng), Scalar::rand(rng));
+        assert!(matches!(
+            quotient(domain, 1, 2, &polys, beta, gamma, alpha),
+            Err(CrateError::RangeProof(Error::QuotientNotDivisible { .. }))
+        ));
+    }
+}
@@ -7,20 +7,51 @@
 //!
 //! This implementation is a modernized/updated version of the code found
 //! [here](https://github.com/roynalnaruto/range_proof).
+mod archive;
+mod diagnostics;
+mod fixed;
+mod key;
+mod lookup;
+mod pedersen;
 mod poly;
+mod positive;
+mod signed;
+#[cfg(feature = "bn254")]
+pub mod solidity;
 mod utils;
-
-use crate::commit::kzg::{Kzg, Powers};
+mod validation;
+
+pub use archive::Archive;
+pub use diagnostics::WitnessReport;
+pub use fixed::{FixedWidthRangeProof, U128RangeProof, U32RangeProof, U64RangeProof};
+pub use key::VerifierKey;
+pub use lookup::{
+    LookupCommitments, LookupEvaluations, LookupRangeProof, LookupTable, MAX_CHUNK_BITS,
+};
+pub use pedersen::PedersenRangeProof;
+pub use positive::PositiveRangeProof;
+pub use signed::SignedRangeProof;
+
+use crate::commit::kzg::{Kzg, Powers, PreparedPowers};
+use crate::equality::Bases;
 use crate::hash::Hasher;
+use crate::transcript::Transcript;
 use crate::Error as CrateError;
 use ark_ec::pairing::Pairing;
 use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
-use ark_std::UniformRand;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::{One, UniformRand, Zero};
 use digest::Digest;
+use poly::SecretPolynomial;
 use thiserror::Error as ErrorT;
+use zeroize::Zeroizing;
 
 #[derive(ErrorT, Debug, PartialEq)]
 pub enum Error {
@@ -32,25 +63,53 @@ pub enum Error {
     InputOutOfBounds,
     #[error("polynomial is nonzero")]
     ExpectedZeroPolynomial,
+    #[error("f commitment does not match the expected commitment")]
+    CommitmentMismatch,
+    #[error("proof contains a point that is not on the curve or not in the prime order subgroup")]
+    InvalidPoint,
+    #[error("proof contains the point at infinity")]
+    IdentityPoint,
+    #[error("proof contains the same point more than once")]
+    RepeatedPoint,
+    #[error("input value is zero")]
+    ZeroInput,
+    #[error("non-zero proof is invalid")]
+    NonZeroCheckFailed,
+    #[error("quotient polynomial has degree {degree}, expected at most {max_degree}")]
+    QuotientNotDivisible { degree: usize, max_degree: usize },
+    #[error("bit size {0} is not supported")]
+    UnsupportedBitSize(usize),
+    #[error("pedersen commitment does not hide the range proven value")]
+    PedersenLinkFailed,
+    #[error("batched pairing check of the archived proofs failed")]
+    ArchiveCheckFailed,
 }
 
 const PROOF_DOMAIN_SEP: &[u8] = b"fde range proof";
 
-#[derive(Clone, Copy, Debug)]
-pub struct Evaluations<S> {
+/// Size of a [compressed](RangeProof::compressed_bytes) range proof over BLS12-381 in bytes: three
+/// 32 byte scalars and five 48 byte G1 points.
+pub const BLS12_381_PROOF_SIZE: usize = 3 * 32 + 5 * 48;
+
+/// Size of a [compressed](RangeProof::compressed_bytes) range proof over BN254 in bytes: three 32
+/// byte scalars and five 32 byte G1 points.
+pub const BN254_PROOF_SIZE: usize = 3 * 32 + 5 * 32;
+
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Evaluations<S: CanonicalSerialize + CanonicalDeserialize> {
     pub g: S,
     pub g_omega: S,
     pub w_cap: S,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Commitments<C: Pairing> {
     pub f: C::G1Affine,
     pub g: C::G1Affine,
     pub q: C::G1Affine,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Proofs<C: Pairing> {
     pub aggregate: C::G1Affine,
     pub shifted: C::G1Affine,
@@ -64,47 +123,147 @@ pub struct RangeProof<C: Pairing, D> {
     _digest: PhantomData<D>,
 }
 
+impl<C: Pairing, D> CanonicalSerialize for RangeProof<C, D> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.evaluations
+            .serialize_with_mode(&mut writer, compress)?;
+        self.commitments
+            .serialize_with_mode(&mut writer, compress)?;
+        self.proofs.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.evaluations.serialized_size(compress)
+            + self.commitments.serialized_size(compress)
+            + self.proofs.serialized_size(compress)
+    }
+}
+
+impl<C: Pairing, D: Sync> Valid for RangeProof<C, D> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.evaluations.check()?;
+        self.commitments.check()?;
+        self.proofs.check()
+    }
+}
+
+/// Deserializing with [`Validate::Yes`] checks that every point is on the curve and in the prime
+/// order subgroup, which is the recommended way of receiving proofs from untrusted parties.
+impl<C: Pairing, D: Send + Sync> CanonicalDeserialize for RangeProof<C, D> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let evaluations = Evaluations::deserialize_with_mode(&mut reader, compress, validate)?;
+        let commitments = Commitments::deserialize_with_mode(&mut reader, compress, validate)?;
+        let proofs = Proofs::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self {
+            evaluations,
+            commitments,
+            proofs,
+            _digest: PhantomData,
+        })
+    }
+}
+
 impl<C: Pairing, D: Digest> RangeProof<C, D> {
     // prove 0 <= z < 2^n
-    pub fn new<R: Rng>(
+    pub fn new<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        n: usize,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let r = C::ScalarField::rand(rng);
+        Self::new_with_blinding(z, r, n, powers, rng)
+    }
+
+    /// Same as [`Self::new`] but the blinding factor `r` of the `f` commitment is provided by the
+    /// caller.
+    ///
+    /// The `f` commitment equals `z * g + r * h` where `g` and `h` are the bases returned by
+    /// [`Self::commitment_bases`], thus knowing `r` allows the prover to link `f` to other
+    /// commitments hiding `z`, e.g. via an [`equality`](crate::equality) proof.
+    pub fn new_with_blinding<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        r: C::ScalarField,
+        n: usize,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        Self::prove(z, r, n, powers, Hasher::<D>::new(), rng)
+    }
+
+    /// Same as [`Self::new`] but the Fiat-Shamir challenges are derived from a `transcript` that
+    /// the caller may have already seeded with context from the outer protocol (e.g. session id,
+    /// buyer key, payment hash). The default transcript is a [`Hasher`] over `D`.
+    ///
+    /// This binds the proof to the outer protocol, thus it cannot be replayed in another session.
+    /// Such proofs should be checked via [`Self::verify_with_transcript`] using an identically
+    /// seeded transcript.
+    pub fn new_with_transcript<T: Transcript, R: Rng + CryptoRng>(
         z: C::ScalarField,
         n: usize,
         powers: &Powers<C>,
+        transcript: T,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let r = C::ScalarField::rand(rng);
+        Self::prove(z, r, n, powers, transcript, rng)
+    }
+
+    // The blinding scalars and every polynomial derived from z are wiped from memory before
+    // returning, the caller is responsible for its own copies of z and r.
+    fn prove<T: Transcript, R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        r: C::ScalarField,
+        n: usize,
+        powers: &Powers<C>,
+        mut transcript: T,
         rng: &mut R,
     ) -> Result<Self, CrateError> {
         let domain = GeneralEvaluationDomain::<C::ScalarField>::new(n)
             .ok_or(CrateError::InvalidFftDomain(n))?;
-        let domain_2n = GeneralEvaluationDomain::<C::ScalarField>::new(2 * n)
-            .ok_or(CrateError::InvalidFftDomain(2 * n))?;
+
+        // the quotient polynomial is only well-defined if z fits into `domain.size()` bits
+        if z.into_bigint().num_bits() as usize > domain.size() {
+            return Err(Error::InputOutOfBounds.into());
+        }
+
+        // secrets are wiped from memory once they go out of scope
+        let z = Zeroizing::new(z);
+        let r = Zeroizing::new(r);
 
         // random scalars
-        let r = C::ScalarField::rand(rng);
-        let alpha = C::ScalarField::rand(rng);
-        let beta = C::ScalarField::rand(rng);
+        let alpha = Zeroizing::new(C::ScalarField::rand(rng));
+        let beta = Zeroizing::new(C::ScalarField::rand(rng));
 
         // compute f and g polynomials and their commitments
-        let f_poly = poly::f(&domain, z, r);
-        let g_poly = poly::g(&domain, z, alpha, beta);
-        let f_commitment = powers.commit_g1(&f_poly);
-        let g_commitment = powers.commit_g1(&g_poly);
+        let f_poly = SecretPolynomial(poly::f(&domain, *z, *r));
+        let g_poly = SecretPolynomial(poly::g(&domain, *z, *alpha, *beta));
+        let commitments_1 =
+            C::G1::normalize_batch(&[powers.commit_g1(&*f_poly)?, powers.commit_g1(&*g_poly)?]);
 
         // compute challenges
-        let mut hasher = Hasher::<D>::new();
-        hasher.update(&PROOF_DOMAIN_SEP);
-        hasher.update(&n.to_le_bytes());
-        hasher.update(&domain.group_gen());
-        hasher.update(&f_commitment);
-        hasher.update(&g_commitment);
+        transcript.append(b"domain separator", &PROOF_DOMAIN_SEP);
+        transcript.append_u64(b"n", n as u64);
+        transcript.append_scalar(b"omega", &domain.group_gen());
+        transcript.append_point(b"f", &commitments_1[0]);
+        transcript.append_point(b"g", &commitments_1[1]);
 
-        let tau = hasher.next_scalar(b"tau");
-        let rho = hasher.next_scalar(b"rho");
-        let aggregation_challenge = hasher.next_scalar(b"aggregation_challenge");
+        let tau: C::ScalarField = transcript.challenge_scalar(b"tau");
+        let rho: C::ScalarField = transcript.challenge_scalar(b"rho");
+        let aggregation_challenge: C::ScalarField =
+            transcript.challenge_scalar(b"aggregation_challenge");
 
         // aggregate w1, w2 and w3 to compute quotient polynomial
-        let (w1_poly, w2_poly) = poly::w1_w2(&domain, &f_poly, &g_poly)?;
-        let w3_poly = poly::w3(&domain, &domain_2n, &g_poly)?;
-        let q_poly = poly::quotient(&domain, &w1_poly, &w2_poly, &w3_poly, tau)?;
-        let q_commitment = powers.commit_g1(&q_poly);
+        let q_poly = SecretPolynomial(poly::quotient(&domain, &f_poly, &g_poly, tau)?);
+        let q_commitment = powers.commit_g1(&*q_poly)?;
 
         let rho_omega = rho * domain.group_gen();
         // evaluate g at rho
@@ -113,18 +272,19 @@ impl<C: Pairing, D: Digest> RangeProof<C, D> {
         let g_omega_eval = g_poly.evaluate(&rho_omega);
 
         // compute evaluation of w_cap at ρ
-        let w_cap_poly = poly::w_cap(&domain, &f_poly, &q_poly, rho);
+        let w_cap_poly = SecretPolynomial(poly::w_cap(&domain, &f_poly, &q_poly, rho));
         let w_cap_eval = w_cap_poly.evaluate(&rho);
 
         // compute witness for g(X) at ρw
-        let shifted_witness_poly = Kzg::<C>::witness(&g_poly, rho_omega);
-        let shifted_proof = powers.commit_g1(&shifted_witness_poly);
+        let shifted_witness_poly = SecretPolynomial(Kzg::<C>::witness(&g_poly, rho_omega));
+        let shifted_proof = powers.commit_g1(&*shifted_witness_poly)?;
 
         // compute aggregate witness for
         // g(X) at ρ, f(X) at ρ, w_cap(X) at ρ
-        let aggregate_witness_poly =
-            Kzg::<C>::aggregate_witness(&[g_poly, w_cap_poly], rho, aggregation_challenge);
-        let aggregate_proof = powers.commit_g1(&aggregate_witness_poly);
+        // (same as `Kzg::aggregate_witness` without moving the polynomials)
+        let aggregate_poly = SecretPolynomial(&*g_poly + &(&*w_cap_poly * aggregation_challenge));
+        let aggregate_witness_poly = SecretPolynomial(Kzg::<C>::witness(&aggregate_poly, rho));
+        let aggregate_proof = powers.commit_g1(&*aggregate_witness_poly)?;
 
         let evaluations = Evaluations {
             g: g_eval,
@@ -132,15 +292,17 @@ impl<C: Pairing, D: Digest> RangeProof<C, D> {
             w_cap: w_cap_eval,
         };
 
+        let points = C::G1::normalize_batch(&[q_commitment, aggregate_proof, shifted_proof]);
+
         let commitments = Commitments {
-            f: f_commitment.into_affine(),
-            g: g_commitment.into_affine(),
-            q: q_commitment.into_affine(),
+            f: commitments_1[0],
+            g: commitments_1[1],
+            q: points[0],
         };
 
         let proofs = Proofs {
-            aggregate: aggregate_proof.into_affine(),
-            shifted: shifted_proof.into_affine(),
+            aggregate: points[1],
+            shifted: points[2],
         };
 
         Ok(Self {
@@ -151,28 +313,162 @@ impl<C: Pairing, D: Digest> RangeProof<C, D> {
         })
     }
 
+    /// Same as [`Self::new_with_blinding`] but the proof is bound to an `f_commitment` that the
+    /// caller has already published.
+    ///
+    /// Returns an error if `f_commitment` is not a commitment to `z` with blinding factor `r`.
+    /// Such proofs should be checked via [`Self::verify_with_commitment`].
+    pub fn new_with_commitment<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        r: C::ScalarField,
+        f_commitment: C::G1Affine,
+        n: usize,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let bases = Self::commitment_bases(n, powers)?;
+        if bases.commit(&z, &r).into_affine() != f_commitment {
+            return Err(Error::CommitmentMismatch.into());
+        }
+        Self::new_with_blinding(z, r, n, powers, rng)
+    }
+
+    /// Returns the bases `g` and `h` such that the `f` commitment of a proof with bit size `n`
+    /// equals `z * g + r * h`.
+    pub fn commitment_bases(n: usize, powers: &Powers<C>) -> Result<Bases<C::G1>, CrateError> {
+        let domain = GeneralEvaluationDomain::<C::ScalarField>::new(n)
+            .ok_or(CrateError::InvalidFftDomain(n))?;
+        let zero = C::ScalarField::zero();
+        let one = C::ScalarField::one();
+        let g = powers.commit_g1(&poly::f(&domain, one, zero))?;
+        let h = powers.commit_g1(&poly::f(&domain, zero, one))?;
+        let bases = C::G1::normalize_batch(&[g, h]);
+        Ok(Bases::new(bases[0], bases[1]))
+    }
+
+    /// Returns the size of [`Self::compressed_bytes`] for curve `C`.
+    pub fn compressed_size() -> usize {
+        3 * C::ScalarField::zero().compressed_size()
+            + 5 * C::G1Affine::generator().compressed_size()
+    }
+
+    /// Canonical encoding of the proof with compressed points and fixed-width scalars, i.e. the
+    /// output is always [`Self::compressed_size`] bytes long.
+    pub fn compressed_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::compressed_size());
+        self.serialize_compressed(&mut bytes)
+            .expect("should not fail");
+        bytes
+    }
+
+    /// Inverse of [`Self::compressed_bytes`] that also checks that every point is valid.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, SerializationError>
+    where
+        D: Send + Sync,
+    {
+        if bytes.len() != Self::compressed_size() {
+            return Err(SerializationError::InvalidData);
+        }
+        Self::deserialize_compressed(bytes)
+    }
+
+    /// Verifies the proof and checks that it was generated for the given `f_commitment`.
+    pub fn verify_with_commitment(
+        &self,
+        f_commitment: C::G1Affine,
+        n: usize,
+        powers: &Powers<C>,
+    ) -> Result<(), CrateError> {
+        if self.commitments.f != f_commitment {
+            return Err(Error::CommitmentMismatch.into());
+        }
+        self.verify(n, powers)
+    }
+
     pub fn verify(&self, n: usize, powers: &Powers<C>) -> Result<(), CrateError> {
+        self.verify_with_transcript(n, powers, Hasher::<D>::new())
+    }
+
+    /// Verifies a proof generated via [`Self::new_with_transcript`].
+    pub fn verify_with_transcript<T: Transcript>(
+        &self,
+        n: usize,
+        powers: &Powers<C>,
+        transcript: T,
+    ) -> Result<(), CrateError> {
+        self.validate()?;
         let domain = GeneralEvaluationDomain::<C::ScalarField>::new(n)
             .ok_or(CrateError::InvalidFftDomain(n))?;
+        let (tau, rho, aggregation_challenge) = self.challenges(n, &domain, transcript);
+        let g2_tau = powers.g2_tau();
+        self.check(
+            &domain,
+            tau,
+            rho,
+            aggregation_challenge,
+            |proof, com, point, value| kzg_check::<C>(proof, com, point, value, g2_tau),
+        )
+    }
 
-        let mut hasher = Hasher::<D>::new();
-        hasher.update(&PROOF_DOMAIN_SEP);
-        hasher.update(&n.to_le_bytes());
-        hasher.update(&domain.group_gen());
-        hasher.update(&self.commitments.f);
-        hasher.update(&self.commitments.g);
+    /// Same as [`Self::verify`] but the G2 elements of the setup are already in prepared form,
+    /// which saves the Miller loop preprocessing when verifying many proofs.
+    pub fn verify_with_prepared(
+        &self,
+        n: usize,
+        prepared: &PreparedPowers<C>,
+    ) -> Result<(), CrateError> {
+        self.validate()?;
+        let domain = GeneralEvaluationDomain::<C::ScalarField>::new(n)
+            .ok_or(CrateError::InvalidFftDomain(n))?;
+        let (tau, rho, aggregation_challenge) = self.challenges(n, &domain, Hasher::<D>::new());
+        self.check(
+            &domain,
+            tau,
+            rho,
+            aggregation_challenge,
+            |proof, com, point, value| {
+                Kzg::<C>::verify_prepared(proof, com, point, value, prepared)
+            },
+        )
+    }
 
-        let tau = hasher.next_scalar(b"tau");
-        let rho = hasher.next_scalar(b"rho");
-        let aggregation_challenge: C::ScalarField = hasher.next_scalar(b"aggregation_challenge");
+    fn challenges<T: Transcript>(
+        &self,
+        n: usize,
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+        mut transcript: T,
+    ) -> (C::ScalarField, C::ScalarField, C::ScalarField) {
+        transcript.append(b"domain separator", &PROOF_DOMAIN_SEP);
+        transcript.append_u64(b"n", n as u64);
+        transcript.append_scalar(b"omega", &domain.group_gen());
+        transcript.append_point(b"f", &self.commitments.f);
+        transcript.append_point(b"g", &self.commitments.g);
+
+        let tau = transcript.challenge_scalar(b"tau");
+        let rho = transcript.challenge_scalar(b"rho");
+        let aggregation_challenge = transcript.challenge_scalar(b"aggregation_challenge");
+        (tau, rho, aggregation_challenge)
+    }
 
+    // the checks that follow the challenge computation, `kzg_check` verifies a single opening
+    fn check<K>(
+        &self,
+        domain: &GeneralEvaluationDomain<C::ScalarField>,
+        tau: C::ScalarField,
+        rho: C::ScalarField,
+        aggregation_challenge: C::ScalarField,
+        mut kzg_check: K,
+    ) -> Result<(), CrateError>
+    where
+        K: FnMut(C::G1Affine, C::G1, C::ScalarField, C::ScalarField) -> bool,
+    {
         // calculate w_cap_commitment
         let w_cap_commitment =
             utils::w_cap::<C::G1>(domain.size(), self.commitments.f, self.commitments.q, rho);
 
         // calculate w2(ρ) and w3(ρ)
         let sum = utils::w1_w2_w3_evals_sum(
-            &domain,
+            domain,
             self.evaluations.g,
             self.evaluations.g_omega,
             rho,
@@ -195,22 +491,20 @@ impl<C: Pairing, D: Digest> RangeProof<C, D> {
             &[self.evaluations.g, self.evaluations.w_cap],
             aggregation_challenge,
         );
-        let aggregation_kzg_check = Kzg::verify_scalar(
+        let aggregation_kzg_check = kzg_check(
             self.proofs.aggregate,
-            aggregate_poly_commitment.into_affine(),
+            aggregate_poly_commitment,
             rho,
             aggregate_value,
-            powers,
         );
 
         // check shifted witness commitment
         let rho_omega = rho * domain.group_gen();
-        let shifted_kzg_check = Kzg::verify_scalar(
+        let shifted_kzg_check = kzg_check(
             self.proofs.shifted,
-            self.commitments.g,
+            self.commitments.g.into_group(),
             rho_omega,
             self.evaluations.g_omega,
-            powers,
         );
 
         if !aggregation_kzg_check {
@@ -223,13 +517,27 @@ impl<C: Pairing, D: Digest> RangeProof<C, D> {
     }
 }
 
+// Same as `Kzg::verify_scalar` but only requires `tau * G2` instead of the full setup.
+fn kzg_check<C: Pairing>(
+    proof: C::G1Affine,
+    commitment: C::G1,
+    point: C::ScalarField,
+    value: C::ScalarField,
+    g2_tau: C::G2Affine,
+) -> bool {
+    let com_over_g_value = commitment - C::G1Affine::generator() * value;
+    let g_tau_over_g_point = g2_tau.into_group() - C::G2Affine::generator() * point;
+    Kzg::<C>::pairing_check(com_over_g_value, proof.into_group(), g_tau_over_g_point)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::commit::kzg::Powers;
+    use crate::tests::test_rng;
     use crate::tests::{Scalar, TestCurve, TestHash};
     use crate::Error as CrateError;
-    use ark_std::{test_rng, UniformRand};
+    use ark_std::UniformRand;
 
     const LOG_2_UPPER_BOUND: usize = 8; // 2^8
 
@@ -251,6 +559,361 @@ mod test {
         assert!(proof.verify(LOG_2_UPPER_BOUND, &powers).is_ok());
     }
 
+    #[test]
+    fn range_proof_with_prepared_powers() {
+        use crate::tests::G1Affine;
+
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let prepared = PreparedPowers::from(&powers);
+
+        let mut proof = RangeProof::<TestCurve, TestHash>::new(
+            Scalar::from(200u32),
+            LOG_2_UPPER_BOUND,
+            &powers,
+            rng,
+        )
+        .unwrap();
+        assert!(proof
+            .verify_with_prepared(LOG_2_UPPER_BOUND, &prepared)
+            .is_ok());
+
+        proof.proofs.shifted = (proof.proofs.shifted + G1Affine::generator()).into_affine();
+        assert_eq!(
+            proof.verify_with_prepared(LOG_2_UPPER_BOUND, &prepared),
+            Err(CrateError::RangeProof(Error::ShiftedWitnessCheckFailed))
+        );
+    }
+
+    #[test]
+    fn range_proof_linked_to_pedersen_commitment() {
+        use crate::equality::{Bases, Proof as EqualityProof};
+        use crate::tests::G1Affine;
+        use ark_ec::{AffineRepr, CurveGroup};
+
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        let z = Scalar::from(123u32);
+        let r = Scalar::rand(rng);
+        let proof = RangeProof::<TestCurve, TestHash>::new_with_blinding(
+            z,
+            r,
+            LOG_2_UPPER_BOUND,
+            &powers,
+            rng,
+        )
+        .unwrap();
+        assert!(proof.verify(LOG_2_UPPER_BOUND, &powers).is_ok());
+
+        // the same value committed elsewhere with different bases
+        let range_bases =
+            RangeProof::<TestCurve, TestHash>::commitment_bases(LOG_2_UPPER_BOUND, &powers)
+                .unwrap();
+        let other_bases = Bases::new(
+            G1Affine::generator(),
+            (G1Affine::generator() * Scalar::rand(rng)).into_affine(),
+        );
+        let other_r = Scalar::rand(rng);
+        let other_commitment = other_bases.commit(&z, &other_r);
+        assert_eq!(
+            range_bases.commit(&z, &r).into_affine(),
+            proof.commitments.f
+        );
+
+        let equality = EqualityProof::<<TestCurve as Pairing>::G1, TestHash>::new(
+            &z,
+            [r, other_r],
+            [range_bases, other_bases],
+            rng,
+        );
+        assert!(equality.verify(
+            [proof.commitments.f.into_group(), other_commitment],
+            [range_bases, other_bases]
+        ));
+    }
+
+    #[test]
+    fn range_proof_with_external_commitment() {
+        use ark_ec::{AffineRepr, CurveGroup};
+
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let bases = RangeProof::<TestCurve, TestHash>::commitment_bases(LOG_2_UPPER_BOUND, &powers)
+            .unwrap();
+
+        let z = Scalar::from(77u32);
+        let r = Scalar::rand(rng);
+        let published = bases.commit(&z, &r).into_affine();
+
+        let proof = RangeProof::<TestCurve, TestHash>::new_with_commitment(
+            z,
+            r,
+            published,
+            LOG_2_UPPER_BOUND,
+            &powers,
+            rng,
+        )
+        .unwrap();
+        assert!(proof
+            .verify_with_commitment(published, LOG_2_UPPER_BOUND, &powers)
+            .is_ok());
+
+        // proof is not bound to another commitment
+        let other = (published + bases.h.into_group()).into_affine();
+        assert_eq!(
+            proof.verify_with_commitment(other, LOG_2_UPPER_BOUND, &powers),
+            Err(CrateError::RangeProof(Error::CommitmentMismatch))
+        );
+
+        // prover cannot bind the proof to a commitment of a different value
+        assert_eq!(
+            RangeProof::<TestCurve, TestHash>::new_with_commitment(
+                z + Scalar::from(1u8),
+                r,
+                published,
+                LOG_2_UPPER_BOUND,
+                &powers,
+                rng,
+            )
+            .unwrap_err(),
+            CrateError::RangeProof(Error::CommitmentMismatch)
+        );
+    }
+
+    #[test]
+    fn range_proof_bound_to_transcript() {
+        use crate::tests::G1Affine;
+        use crate::transcript::PoseidonTranscript;
+        use ark_ec::AffineRepr;
+        use ark_ff::{BigInteger, PrimeField};
+
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        let session = |id: u64| {
+            let mut transcript = Hasher::<TestHash>::new();
+            transcript.append(b"session id", &id);
+            transcript
+        };
+
+        let z = Scalar::from(200u32);
+        let proof = RangeProof::<TestCurve, TestHash>::new_with_transcript(
+            z,
+            LOG_2_UPPER_BOUND,
+            &powers,
+            session(1),
+            rng,
+        )
+        .unwrap();
+        assert!(proof
+            .verify_with_transcript(LOG_2_UPPER_BOUND, &powers, session(1))
+            .is_ok());
+        // replayed in another session
+        assert!(proof
+            .verify_with_transcript(LOG_2_UPPER_BOUND, &powers, session(2))
+            .is_err());
+        // verified without the outer context
+        assert!(proof.verify(LOG_2_UPPER_BOUND, &powers).is_err());
+
+        let poseidon = |id: u64| {
+            let mut transcript =
+                PoseidonTranscript::new(&PoseidonTranscript::default_config(), b"fde test");
+            transcript.append_field_elements(b"session id", &[Scalar::from(id)]);
+            transcript
+        };
+        let proof = RangeProof::<TestCurve, TestHash>::new_with_transcript(
+            z,
+            LOG_2_UPPER_BOUND,
+            &powers,
+            poseidon(1),
+            rng,
+        )
+        .unwrap();
+        assert!(proof
+            .verify_with_transcript(LOG_2_UPPER_BOUND, &powers, poseidon(1))
+            .is_ok());
+        assert!(proof
+            .verify_with_transcript(LOG_2_UPPER_BOUND, &powers, poseidon(2))
+            .is_err());
+
+        // the commitments are absorbed as coordinates in 31-byte limbs over the scalar field
+        let coordinates = |point: &G1Affine| {
+            let (x, y) = point.xy().unwrap();
+            let mut elements = Vec::new();
+            for coordinate in [x, y] {
+                let bytes = coordinate.into_bigint().to_bytes_le();
+                elements.push(Scalar::from_le_bytes_mod_order(&bytes[..31]));
+                elements.push(Scalar::from_le_bytes_mod_order(&bytes[31..]));
+            }
+            elements.push(Scalar::from(false));
+            elements
+        };
+        let domain = GeneralEvaluationDomain::<Scalar>::new(LOG_2_UPPER_BOUND).unwrap();
+        let mut expected = poseidon(1);
+        let mut domain_sep = Vec::new();
+        PROOF_DOMAIN_SEP
+            .serialize_compressed(&mut domain_sep)
+            .unwrap();
+        expected.append_message(b"domain separator", &domain_sep);
+        expected.append_field_elements(b"n", &[Scalar::from(LOG_2_UPPER_BOUND as u64)]);
+        expected.append_field_elements(b"omega", &[domain.group_gen()]);
+        expected.append_field_elements(b"f", &coordinates(&proof.commitments.f));
+        expected.append_field_elements(b"g", &coordinates(&proof.commitments.g));
+        let challenges = (
+            expected.challenge_scalar(b"tau"),
+            expected.challenge_scalar(b"rho"),
+            expected.challenge_scalar(b"aggregation_challenge"),
+        );
+        assert_eq!(
+            proof.challenges(LOG_2_UPPER_BOUND, &domain, poseidon(1)),
+            challenges
+        );
+    }
+
+    #[cfg(feature = "merlin")]
+    #[test]
+    fn range_proof_bound_to_merlin_transcript() {
+        use crate::transcript::MerlinTranscript;
+
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        let z = Scalar::from(200u32);
+        let merlin = |id: u64| {
+            let mut transcript = MerlinTranscript::new(b"fde test");
+            transcript.append(b"session id", &id);
+            transcript
+        };
+        let proof = RangeProof::<TestCurve, TestHash>::new_with_transcript(
+            z,
+            LOG_2_UPPER_BOUND,
+            &powers,
+            merlin(1),
+            rng,
+        )
+        .unwrap();
+        assert!(proof
+            .verify_with_transcript(LOG_2_UPPER_BOUND, &powers, merlin(1))
+            .is_ok());
+        assert!(proof
+            .verify_with_transcript(LOG_2_UPPER_BOUND, &powers, merlin(2))
+            .is_err());
+        assert!(proof.verify(LOG_2_UPPER_BOUND, &powers).is_err());
+    }
+
+    #[test]
+    fn range_proof_with_invalid_points_fails() {
+        use crate::tests::G1Affine;
+        use ark_ec::short_weierstrass::SWCurveConfig;
+        use ark_ff::{Field, UniformRand};
+
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        let z = Scalar::from(10u32);
+        let proof =
+            RangeProof::<TestCurve, TestHash>::new(z, LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+        assert!(proof.validate().is_ok());
+
+        // point that is not on the curve
+        let mut invalid = proof.clone();
+        invalid.commitments.q = G1Affine::new_unchecked(
+            invalid.commitments.q.x + <G1Affine as AffineRepr>::BaseField::ONE,
+            invalid.commitments.q.y,
+        );
+        assert_eq!(
+            invalid.verify(LOG_2_UPPER_BOUND, &powers),
+            Err(CrateError::RangeProof(Error::InvalidPoint))
+        );
+
+        // point on the curve, but not in the prime order subgroup
+        let not_in_subgroup = loop {
+            let x = <G1Affine as AffineRepr>::BaseField::rand(rng);
+            if let Some(point) = G1Affine::get_point_from_x_unchecked(x, false) {
+                if !ark_bls12_381::g1::Config::is_in_correct_subgroup_assuming_on_curve(&point) {
+                    break point;
+                }
+            }
+        };
+        let mut invalid = proof.clone();
+        invalid.proofs.shifted = not_in_subgroup;
+        assert_eq!(
+            invalid.verify(LOG_2_UPPER_BOUND, &powers),
+            Err(CrateError::RangeProof(Error::InvalidPoint))
+        );
+    }
+
+    #[test]
+    fn range_proof_checked_deserialization() {
+        use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        let z = Scalar::from(10u32);
+        let proof =
+            RangeProof::<TestCurve, TestHash>::new(z, LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        let deserialized =
+            RangeProof::<TestCurve, TestHash>::deserialize_compressed(bytes.as_slice()).unwrap();
+        assert!(deserialized.verify(LOG_2_UPPER_BOUND, &powers).is_ok());
+
+        // flipping a bit in a compressed point either makes it invalid or changes the proof
+        let len = bytes.len();
+        bytes[len - 1] ^= 1;
+        if let Ok(tampered) =
+            RangeProof::<TestCurve, TestHash>::deserialize_compressed(bytes.as_slice())
+        {
+            assert!(tampered.verify(LOG_2_UPPER_BOUND, &powers).is_err());
+        }
+    }
+
+    #[test]
+    fn range_proof_compressed_encoding() {
+        use ark_bn254::{Bn254, Fr};
+
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let proof = RangeProof::<TestCurve, TestHash>::new(
+            Scalar::from(3u8),
+            LOG_2_UPPER_BOUND,
+            &powers,
+            rng,
+        )
+        .unwrap();
+
+        let bytes = proof.compressed_bytes();
+        assert_eq!(bytes.len(), BLS12_381_PROOF_SIZE);
+        assert_eq!(
+            RangeProof::<TestCurve, TestHash>::compressed_size(),
+            BLS12_381_PROOF_SIZE
+        );
+        let decoded = RangeProof::<TestCurve, TestHash>::from_compressed_bytes(&bytes).unwrap();
+        assert_eq!(decoded.compressed_bytes(), bytes);
+        assert!(decoded.verify(LOG_2_UPPER_BOUND, &powers).is_ok());
+        assert!(RangeProof::<TestCurve, TestHash>::from_compressed_bytes(&bytes[1..]).is_err());
+
+        let powers = Powers::<Bn254>::unsafe_setup(Fr::rand(rng), 4 * LOG_2_UPPER_BOUND);
+        let proof =
+            RangeProof::<Bn254, TestHash>::new(Fr::from(3u8), LOG_2_UPPER_BOUND, &powers, rng)
+                .unwrap();
+        assert_eq!(proof.compressed_bytes().len(), BN254_PROOF_SIZE);
+        assert_eq!(
+            RangeProof::<Bn254, TestHash>::compressed_size(),
+            BN254_PROOF_SIZE
+        );
+    }
+
     #[test]
     fn range_proof_with_invalid_size_fails() {
         // KZG setup simulation
@@ -277,7 +940,7 @@ mod test {
         let z = Scalar::from(256u32);
         assert_eq!(
             RangeProof::<TestCurve, TestHash>::new(z, LOG_2_UPPER_BOUND, &powers, rng).unwrap_err(),
-            CrateError::RangeProof(Error::ExpectedZeroPolynomial)
+            CrateError::RangeProof(Error::InputOutOfBounds)
         );
     }
 
@@ -291,7 +954,21 @@ mod test {
         let z = Scalar::from(300u32);
         assert_eq!(
             RangeProof::<TestCurve, TestHash>::new(z, LOG_2_UPPER_BOUND, &powers, rng).unwrap_err(),
-            CrateError::RangeProof(Error::ExpectedZeroPolynomial)
+            CrateError::RangeProof(Error::InputOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn range_proof_with_negative_z_fails() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        // -1 is the largest field element, it is rejected before any polynomial is built
+        let z = -Scalar::from(1u32);
+        assert_eq!(
+            RangeProof::<TestCurve, TestHash>::new(z, LOG_2_UPPER_BOUND, &powers, rng).unwrap_err(),
+            CrateError::RangeProof(Error::InputOutOfBounds)
         );
     }
 }
@@ -0,0 +1,101 @@
+use super::{Error, RangeProof};
+use crate::commit::kzg::Powers;
+use crate::equality::{Bases, Proof as EqualityProof};
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+
+/// Range proof for a value hidden in a Pedersen commitment `com = z * g + r * h` with arbitrary
+/// bases, i.e. it proves that `com` hides some `0 <= z < 2^n`.
+///
+/// The proof consists of an ordinary [`RangeProof`] with a fresh blinding factor and an
+/// [`equality`](crate::equality) proof showing that its `f` commitment (which is itself a
+/// commitment over the bases returned by [`RangeProof::commitment_bases`]) and `com` hide the same
+/// value. Thus, protocols that already use Pedersen commitments can prove ranges without exposing
+/// any KZG commitment to the value itself.
+#[derive(Clone, Debug)]
+pub struct PedersenRangeProof<C: Pairing, D> {
+    pub proof: RangeProof<C, D>,
+    pub link: EqualityProof<C::G1, D>,
+}
+
+impl<C: Pairing, D: Digest> PedersenRangeProof<C, D> {
+    /// Proves that `bases.commit(z, r)` hides a value in `[0, 2^n)`.
+    pub fn new<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        r: C::ScalarField,
+        bases: Bases<C::G1>,
+        n: usize,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let f_blinding = C::ScalarField::rand(rng);
+        let proof = RangeProof::new_with_blinding(z, f_blinding, n, powers, rng)?;
+        let range_bases = RangeProof::<C, D>::commitment_bases(n, powers)?;
+        let link = EqualityProof::new(&z, [f_blinding, r], [range_bases, bases], rng);
+        Ok(Self { proof, link })
+    }
+
+    /// Verifies that `commitment` (over `bases`) hides a value in `[0, 2^n)`.
+    pub fn verify(
+        &self,
+        commitment: C::G1Affine,
+        bases: Bases<C::G1>,
+        n: usize,
+        powers: &Powers<C>,
+    ) -> Result<(), CrateError> {
+        self.proof.verify(n, powers)?;
+        let range_bases = RangeProof::<C, D>::commitment_bases(n, powers)?;
+        let commitments = [self.proof.commitments.f.into(), commitment.into()];
+        if self.link.verify(commitments, [range_bases, bases]) {
+            Ok(())
+        } else {
+            Err(Error::PedersenLinkFailed.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{G1Affine, Scalar, TestCurve, TestHash};
+    use ark_ec::{AffineRepr, CurveGroup};
+
+    const LOG_2_UPPER_BOUND: usize = 8;
+
+    type G1 = <TestCurve as Pairing>::G1;
+    type Proof = PedersenRangeProof<TestCurve, TestHash>;
+
+    #[test]
+    fn range_proof_over_pedersen_commitment() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let bases = Bases::<G1>::new(
+            (G1Affine::generator() * Scalar::rand(rng)).into_affine(),
+            (G1Affine::generator() * Scalar::rand(rng)).into_affine(),
+        );
+
+        let z = Scalar::from(201u32);
+        let r = Scalar::rand(rng);
+        let commitment = bases.commit(&z, &r).into_affine();
+        let proof = Proof::new(z, r, bases, LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+        assert!(proof
+            .verify(commitment, bases, LOG_2_UPPER_BOUND, &powers)
+            .is_ok());
+
+        // commitment to another value
+        let other = bases.commit(&(z + Scalar::from(1u8)), &r).into_affine();
+        assert_eq!(
+            proof.verify(other, bases, LOG_2_UPPER_BOUND, &powers),
+            Err(CrateError::RangeProof(Error::PedersenLinkFailed))
+        );
+
+        // value out of range
+        let z = Scalar::from(256u32);
+        assert!(Proof::new(z, r, bases, LOG_2_UPPER_BOUND, &powers, rng).is_err());
+    }
+}
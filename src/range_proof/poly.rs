@@ -2,12 +2,36 @@ use super::Error;
 use crate::Error as CrateError;
 use ark_ff::{BigInteger, PrimeField};
 use ark_poly::univariate::DensePolynomial;
-use ark_poly::{DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial};
+use ark_std::ops::Deref;
+#[cfg(test)]
 use ark_std::Zero;
+use zeroize::Zeroize;
+
+/// Polynomial derived from the prover's secrets whose coefficients are wiped from memory when it
+/// is dropped.
+pub struct SecretPolynomial<S: PrimeField>(pub DensePolynomial<S>);
+
+impl<S: PrimeField> Deref for SecretPolynomial<S> {
+    type Target = DensePolynomial<S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S: PrimeField> Drop for SecretPolynomial<S> {
+    fn drop(&mut self) {
+        self.0.coeffs.zeroize();
+    }
+}
 
 pub fn f<S: PrimeField>(domain: &GeneralEvaluationDomain<S>, z: S, r: S) -> DensePolynomial<S> {
     // f is a linear polynomial: f(1) = z
-    DensePolynomial::from_coefficients_vec(domain.ifft(&[z, r]))
+    let mut evaluations = [z, r];
+    let coeffs = domain.ifft(&evaluations);
+    evaluations.zeroize();
+    DensePolynomial::from_coefficients_vec(coeffs)
 }
 
 pub fn g<S: PrimeField>(
@@ -18,7 +42,8 @@ pub fn g<S: PrimeField>(
 ) -> DensePolynomial<S> {
     // get bits for z -> consider only the first `n` bits
     let size = domain.size();
-    let z_bits = &z.into_bigint().to_bits_le()[0..size];
+    let mut z_bits = z.into_bigint().to_bits_le();
+    z_bits.truncate(size);
     let mut evaluations: Vec<S> = vec![S::zero(); size];
 
     // take the first evaluation point, i.e. (n-1)th bit of z
@@ -36,7 +61,7 @@ pub fn g<S: PrimeField>(
         });
 
     // compute g
-    let g_poly = DensePolynomial::from_coefficients_vec(domain.ifft(&evaluations));
+    let mut g_poly = DensePolynomial::from_coefficients_vec(domain.ifft(&evaluations));
 
     // extended domain
     let domain_ext = GeneralEvaluationDomain::<S>::new(size + 1).expect("valid domain");
@@ -48,10 +73,18 @@ pub fn g<S: PrimeField>(
     let mut g_evals = domain_ext.fft(&g_poly);
     g_evals[1] = alpha;
     g_evals[3] = beta;
+    let coeffs = domain_ext.ifft(&g_evals);
 
-    DensePolynomial::from_coefficients_vec(domain_ext.ifft(&g_evals))
+    // intermediate values encode the bits of z
+    z_bits.zeroize();
+    evaluations.zeroize();
+    g_poly.coeffs.zeroize();
+    g_evals.zeroize();
+
+    DensePolynomial::from_coefficients_vec(coeffs)
 }
 
+#[cfg(test)]
 pub fn w1_w2<S: PrimeField>(
     domain: &GeneralEvaluationDomain<S>,
     f_poly: &DensePolynomial<S>,
@@ -83,6 +116,7 @@ pub fn w1_w2<S: PrimeField>(
     Ok((w1_poly, w2_poly))
 }
 
+#[cfg(test)]
 pub fn w3<S: PrimeField>(
     domain: &GeneralEvaluationDomain<S>,
     domain_2n: &GeneralEvaluationDomain<S>,
@@ -140,7 +174,75 @@ pub fn w_cap<S: PrimeField>(
     &(f_poly * &rho_poly_1) + &(q_poly * &rho_poly_2)
 }
 
+/// Computes the quotient polynomial `q = (w1 + tau * w2 + tau^2 * w3) / (X^n - 1)` in evaluation
+/// form.
+///
+/// The terms are evaluated on a coset of the `4n` sized domain where neither `X^n - 1` nor the
+/// linear divisors of `w1` and `w2` vanish, thus every division is a simple field division and
+/// only one forward FFT per input polynomial and a single inverse FFT are needed.
+///
+/// If the linear combination is not divisible by `X^n - 1` (i.e. the committed value is out of
+/// range), the interpolated evaluations don't belong to a polynomial of degree `4n - 3` and
+/// [`Error::QuotientNotDivisible`] is returned.
 pub fn quotient<S: PrimeField>(
+    domain: &GeneralEvaluationDomain<S>,
+    f_poly: &DensePolynomial<S>,
+    g_poly: &DensePolynomial<S>,
+    tau: S,
+) -> Result<DensePolynomial<S>, CrateError> {
+    // deg(q) = deg(w2) - n = 4n - 3, hence 4n evaluations are enough
+    let size = domain.size();
+    let coset = GeneralEvaluationDomain::<S>::new(4 * size)
+        .and_then(|domain_4n| domain_4n.get_coset(S::GENERATOR))
+        .ok_or(CrateError::InvalidFftDomain(4 * size))?;
+
+    let f_evals = coset.fft(f_poly);
+    let g_evals = coset.fft(g_poly);
+
+    // the coset elements x_i are multiplied by omega if i is shifted by 4
+    let w_n_minus_1 = domain.group_gen_inv();
+    let one = S::one();
+    let two = S::from(2u8);
+    let mut inverses: Vec<S> = coset
+        .elements()
+        .flat_map(|x| [x - one, x - w_n_minus_1, x.pow([size as u64]) - one])
+        .collect();
+    ark_ff::batch_inversion(&mut inverses);
+
+    let q_evals: Vec<S> = coset
+        .elements()
+        .zip(inverses.chunks_exact(3))
+        .enumerate()
+        .map(|(i, (x, inv))| {
+            let g = g_evals[i];
+            let g_omega = g_evals[(i + 4) % coset.size()];
+            // w1 / (X^n - 1) = (g - f) / (X - 1)
+            let w1 = (g - f_evals[i]) * inv[0];
+            // w2 / (X^n - 1) = g * (1 - g) / (X - w^(n-1))
+            let w2 = g * (one - g) * inv[1];
+            // w3 / (X^n - 1)
+            let part_a = g - two * g_omega;
+            let part_b = one - part_a;
+            let w3 = part_a * part_b * (x - w_n_minus_1) * inv[2];
+            w1 + tau * (w2 + tau * w3)
+        })
+        .collect();
+
+    let q_poly = DensePolynomial::from_coefficients_vec(coset.ifft(&q_evals));
+    let max_degree = 4 * size - 3;
+    if q_poly.degree() > max_degree {
+        return Err(Error::QuotientNotDivisible {
+            degree: q_poly.degree(),
+            max_degree,
+        }
+        .into());
+    }
+    Ok(q_poly)
+}
+
+// coefficient form reference implementation of `quotient`
+#[cfg(test)]
+pub fn quotient_coeff<S: PrimeField>(
     domain: &GeneralEvaluationDomain<S>,
     w1_poly: &DensePolynomial<S>,
     w2_poly: &DensePolynomial<S>,
@@ -164,13 +266,14 @@ pub fn quotient<S: PrimeField>(
 #[cfg(test)]
 mod test {
     use crate::commit::kzg::Powers;
+    use crate::tests::test_rng;
     use crate::tests::{Scalar, TestCurve};
     use ark_ec::pairing::Pairing;
     use ark_ec::CurveGroup;
     use ark_ff::{Field, PrimeField};
     use ark_poly::univariate::DensePolynomial;
     use ark_poly::{DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial};
-    use ark_std::{test_rng, UniformRand};
+    use ark_std::UniformRand;
     use ark_std::{One, Zero};
 
     fn w2_w3_parts<S: PrimeField>(
@@ -350,13 +453,13 @@ mod test {
         let g_poly = super::g(&domain, z, alpha, beta);
         let (w1_poly, w2_poly) = super::w1_w2(&domain, &f_poly, &g_poly).unwrap();
         let w3_poly = super::w3(&domain, &domain_2n, &g_poly).unwrap();
-        let q_poly = super::quotient(&domain, &w1_poly, &w2_poly, &w3_poly, t).unwrap();
+        let q_poly = super::quotient_coeff(&domain, &w1_poly, &w2_poly, &w3_poly, t).unwrap();
         let w_cap_poly = super::w_cap(&domain, &f_poly, &q_poly, rho);
 
         // compute commitments
-        let f_commitment = powers.commit_g1(&f_poly).into_affine();
-        let q_commitment = powers.commit_g1(&q_poly).into_affine();
-        let w_cap_commitment_expected = powers.commit_g1(&w_cap_poly);
+        let f_commitment = powers.commit_g1(&f_poly).unwrap().into_affine();
+        let q_commitment = powers.commit_g1(&q_poly).unwrap().into_affine();
+        let w_cap_commitment_expected = powers.commit_g1(&w_cap_poly).unwrap();
 
         // calculate w_cap commitment fact that commitment scheme is additively homomorphic
         let w_cap_commitment_calculated = super::super::utils::w_cap::<<TestCurve as Pairing>::G1>(
@@ -376,6 +479,47 @@ mod test {
         assert_eq!(sum, w_cap_eval);
     }
 
+    #[test]
+    fn coset_quotient_matches_coefficient_form() {
+        let rng = &mut test_rng();
+        for n in [8usize, 16, 32] {
+            let domain = GeneralEvaluationDomain::<Scalar>::new(n).unwrap();
+            let domain_2n = GeneralEvaluationDomain::<Scalar>::new(2 * n).unwrap();
+            let z = Scalar::from(173u8);
+            let f_poly = super::f(&domain, z, Scalar::rand(rng));
+            let g_poly = super::g(&domain, z, Scalar::rand(rng), Scalar::rand(rng));
+            let tau = Scalar::rand(rng);
+
+            let (w1_poly, w2_poly) = super::w1_w2(&domain, &f_poly, &g_poly).unwrap();
+            let w3_poly = super::w3(&domain, &domain_2n, &g_poly).unwrap();
+            let expected =
+                super::quotient_coeff(&domain, &w1_poly, &w2_poly, &w3_poly, tau).unwrap();
+
+            assert_eq!(
+                super::quotient(&domain, &f_poly, &g_poly, tau).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn quotient_of_out_of_range_value_fails() {
+        let rng = &mut test_rng();
+        let n = 8usize;
+        let domain = GeneralEvaluationDomain::<Scalar>::new(n).unwrap();
+        // g only encodes the lowest n bits of z, so g(1) != f(1)
+        let z = Scalar::from(256u32 + 3);
+        let f_poly = super::f(&domain, z, Scalar::rand(rng));
+        let g_poly = super::g(&domain, z, Scalar::rand(rng), Scalar::rand(rng));
+
+        let err = super::quotient(&domain, &f_poly, &g_poly, Scalar::rand(rng)).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::RangeProof(super::Error::QuotientNotDivisible { max_degree, .. })
+                if max_degree == 4 * n - 3
+        ));
+    }
+
     #[test]
     fn compute_w1_part_success() {
         let rng = &mut test_rng();
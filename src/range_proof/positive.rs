@@ -0,0 +1,140 @@
+use super::{Error, RangeProof};
+use crate::commit::kzg::Powers;
+use crate::equality::Bases;
+use crate::hash::Hasher;
+use crate::transcript::Transcript;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::CurveGroup;
+use ark_ff::Field;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+
+const PROOF_DOMAIN_SEP: &[u8] = b"fde non-zero proof";
+
+/// Range proof for strictly positive values, i.e. it proves that `0 < z < 2^n`.
+///
+/// Besides an ordinary [`RangeProof`], the prover shows that `z` has a multiplicative inverse `w`.
+/// Writing the `f` commitment as `z * g + r * h` (see [`RangeProof::commitment_bases`]), the
+/// prover knows `w = 1 / z` and `s = -r / z` such that `w * f + s * h = g`, which is proven with a
+/// Schnorr-style proof of representation. If `z = 0`, then `f = r * h` and the prover would have to
+/// know the discrete logarithm of `g` with respect to `h`.
+#[derive(Clone, Copy, Debug)]
+pub struct PositiveRangeProof<C: Pairing, D> {
+    pub proof: RangeProof<C, D>,
+    pub challenge: C::ScalarField,
+    pub inverse_claims: [C::ScalarField; 2],
+}
+
+impl<C: Pairing, D: Digest> PositiveRangeProof<C, D> {
+    // prove 0 < z < 2^n
+    pub fn new<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        n: usize,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let r = C::ScalarField::rand(rng);
+        Self::new_with_blinding(z, r, n, powers, rng)
+    }
+
+    /// Same as [`Self::new`] but the blinding factor of the `f` commitment is provided by the
+    /// caller.
+    pub fn new_with_blinding<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        r: C::ScalarField,
+        n: usize,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let w = z.inverse().ok_or(Error::ZeroInput)?;
+        let s = -r * w;
+        let proof = RangeProof::new_with_blinding(z, r, n, powers, rng)?;
+        let bases = RangeProof::<C, D>::commitment_bases(n, powers)?;
+
+        let rand = [C::ScalarField::rand(rng), C::ScalarField::rand(rng)];
+        let k = proof.commitments.f * rand[0] + bases.h * rand[1];
+        let challenge = Self::challenge(&bases, &proof, &k);
+        let inverse_claims = [rand[0] - challenge * w, rand[1] - challenge * s];
+
+        Ok(Self {
+            proof,
+            challenge,
+            inverse_claims,
+        })
+    }
+
+    pub fn verify(&self, n: usize, powers: &Powers<C>) -> Result<(), CrateError> {
+        self.proof.verify(n, powers)?;
+        let bases = RangeProof::<C, D>::commitment_bases(n, powers)?;
+        let k = self.proof.commitments.f * self.inverse_claims[0]
+            + bases.h * self.inverse_claims[1]
+            + bases.g * self.challenge;
+
+        if Self::challenge(&bases, &self.proof, &k) == self.challenge {
+            Ok(())
+        } else {
+            Err(Error::NonZeroCheckFailed.into())
+        }
+    }
+
+    fn challenge(bases: &Bases<C::G1>, proof: &RangeProof<C, D>, k: &C::G1) -> C::ScalarField {
+        let mut transcript = Hasher::<D>::new();
+        transcript.append(b"domain separator", &PROOF_DOMAIN_SEP);
+        transcript.append(b"g", &bases.g);
+        transcript.append(b"h", &bases.h);
+        transcript.append(b"commitments", &proof.commitments);
+        transcript.append(b"proofs", &proof.proofs);
+        transcript.append(b"k", &k.into_affine());
+        transcript.challenge_scalar(b"challenge")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+
+    const LOG_2_UPPER_BOUND: usize = 8;
+
+    type Proof = PositiveRangeProof<TestCurve, TestHash>;
+
+    #[test]
+    fn positive_range_proof() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        for z in [1u32, 2, 255] {
+            let proof = Proof::new(Scalar::from(z), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+            assert!(proof.verify(LOG_2_UPPER_BOUND, &powers).is_ok());
+        }
+
+        assert_eq!(
+            Proof::new(Scalar::from(0u32), LOG_2_UPPER_BOUND, &powers, rng).unwrap_err(),
+            CrateError::RangeProof(Error::ZeroInput)
+        );
+        assert_eq!(
+            Proof::new(Scalar::from(256u32), LOG_2_UPPER_BOUND, &powers, rng).unwrap_err(),
+            CrateError::RangeProof(Error::InputOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn positive_range_proof_for_zero_fails() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        // a valid range proof for zero combined with a non-zero proof of another value
+        let mut proof = Proof::new(Scalar::from(1u32), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+        proof.proof = RangeProof::new(Scalar::from(0u32), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+        assert!(proof.proof.verify(LOG_2_UPPER_BOUND, &powers).is_ok());
+        assert_eq!(
+            proof.verify(LOG_2_UPPER_BOUND, &powers),
+            Err(CrateError::RangeProof(Error::NonZeroCheckFailed))
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use super::RangeProof;
+use crate::commit::kzg::Powers;
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+
+/// Range proof for signed values, i.e. it proves that `-2^(n-1) <= z < 2^(n-1)`.
+///
+/// Negative values are represented by their additive inverse in the scalar field (e.g.
+/// `Scalar::from(-5i64)`). Internally, the proof is an ordinary [`RangeProof`] for the offset
+/// value `z + 2^(n-1)` which is in `[0, 2^n)` if and only if `z` is in the signed range. Just like
+/// [`RangeProof`], `n` is rounded up to the next power of two.
+#[derive(Clone, Copy, Debug)]
+pub struct SignedRangeProof<C: Pairing, D> {
+    pub proof: RangeProof<C, D>,
+}
+
+impl<C: Pairing, D: Digest> SignedRangeProof<C, D> {
+    // prove -2^(n-1) <= z < 2^(n-1)
+    pub fn new<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        n: usize,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let r = C::ScalarField::rand(rng);
+        Self::new_with_blinding(z, r, n, powers, rng)
+    }
+
+    /// Same as [`Self::new`] but the blinding factor of the `f` commitment is provided by the
+    /// caller.
+    pub fn new_with_blinding<R: Rng + CryptoRng>(
+        z: C::ScalarField,
+        r: C::ScalarField,
+        n: usize,
+        powers: &Powers<C>,
+        rng: &mut R,
+    ) -> Result<Self, CrateError> {
+        let offset = offset::<C::ScalarField>(n)?;
+        let proof = RangeProof::new_with_blinding(z + offset, r, n, powers, rng)?;
+        Ok(Self { proof })
+    }
+
+    pub fn verify(&self, n: usize, powers: &Powers<C>) -> Result<(), CrateError> {
+        self.proof.verify(n, powers)
+    }
+
+    /// Returns the commitment `z * g + r * h` to the signed value itself, where `g` and `h` are
+    /// the bases returned by [`RangeProof::commitment_bases`].
+    ///
+    /// The `f` commitment of the inner proof commits to the offset value, so the offset is
+    /// subtracted homomorphically.
+    pub fn commitment(&self, n: usize, powers: &Powers<C>) -> Result<C::G1Affine, CrateError> {
+        let offset = offset::<C::ScalarField>(n)?;
+        let bases = RangeProof::<C, D>::commitment_bases(n, powers)?;
+        Ok((self.proof.commitments.f.into_group() - bases.g * offset).into_affine())
+    }
+}
+
+/// Returns `2^(n-1)` where `n` is the size of the evaluation domain used by the range proof.
+fn offset<S: PrimeField>(n: usize) -> Result<S, CrateError> {
+    let size = GeneralEvaluationDomain::<S>::new(n)
+        .ok_or(CrateError::InvalidFftDomain(n))?
+        .size();
+    Ok(S::from(2u8).pow([size as u64 - 1]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::range_proof::Error;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+
+    const LOG_2_UPPER_BOUND: usize = 8; // -2^7 <= z < 2^7
+
+    type SignedProof = SignedRangeProof<TestCurve, TestHash>;
+
+    #[test]
+    fn signed_range_proof_success() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        for z in [-128i64, -17, 0, 5, 127] {
+            let proof = SignedProof::new(Scalar::from(z), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+            assert!(proof.verify(LOG_2_UPPER_BOUND, &powers).is_ok());
+        }
+    }
+
+    #[test]
+    fn signed_range_proof_commitment() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        let z = Scalar::from(-42i64);
+        let r = Scalar::rand(rng);
+        let proof = SignedProof::new_with_blinding(z, r, LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+        let bases = RangeProof::<TestCurve, TestHash>::commitment_bases(LOG_2_UPPER_BOUND, &powers)
+            .unwrap();
+        assert_eq!(
+            proof.commitment(LOG_2_UPPER_BOUND, &powers).unwrap(),
+            bases.commit(&z, &r).into_affine()
+        );
+    }
+
+    #[test]
+    fn signed_range_proof_out_of_bounds_fails() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        for z in [-129i64, 128] {
+            assert_eq!(
+                SignedProof::new(Scalar::from(z), LOG_2_UPPER_BOUND, &powers, rng).unwrap_err(),
+                CrateError::RangeProof(Error::InputOutOfBounds)
+            );
+        }
+    }
+}
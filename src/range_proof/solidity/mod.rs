@@ -0,0 +1,400 @@
+//! Solidity verifier generation for range proofs instantiated over BN254, thus disputes about
+//! committed values can be settled on Ethereum.
+use super::{RangeProof, PROOF_DOMAIN_SEP};
+use crate::commit::kzg::Powers;
+use crate::hash::fill_challenge;
+use crate::transcript::Transcript;
+use crate::Error as CrateError;
+use ark_bn254::{Bn254, Fr, G1Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_serialize::CanonicalSerialize;
+use ark_std::rand::{CryptoRng, Rng};
+use sha3::{Digest, Keccak256};
+
+pub type Bn254RangeProof = RangeProof<Bn254, Keccak256>;
+
+/// Signature of the verifier function of the generated contract.
+pub const VERIFY_SIGNATURE: &str = "verifyProof(uint256[13])";
+
+/// Number of 32 byte words in the encoded proof.
+pub const PROOF_WORDS: usize = 13;
+
+const TEMPLATE: &str = include_str!("verifier.sol");
+
+/// Fiat-Shamir transcript recomputed by the generated contracts with the `keccak256` opcode.
+///
+/// Labels are ignored and messages are concatenated without their lengths, each challenge is the
+/// little-endian Keccak256 digest of everything absorbed so far followed by the challenge label.
+/// Unlike the default [`Hasher`](crate::hash::Hasher) transcript, this encoding is ambiguous for
+/// messages of variable length. It is only sound for the fixed sequence of messages of a range
+/// proof and must not be used outside of [`prove`] and [`verify`].
+#[derive(Clone, Default)]
+pub struct LegacyTranscript {
+    digest: Keccak256,
+}
+
+impl Transcript for LegacyTranscript {
+    fn append_message(&mut self, _label: &'static [u8], message: &[u8]) {
+        self.digest.update(message);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.digest.update(label);
+        fill_challenge::<Keccak256>(&self.digest.clone().finalize(), dest);
+    }
+
+    fn challenge_scalar<F: PrimeField>(&mut self, label: &'static [u8]) -> F {
+        self.digest.update(label);
+        F::from_le_bytes_mod_order(&self.digest.clone().finalize())
+    }
+}
+
+/// Proves `0 <= z < 2^n` such that the proof is accepted by [`verifier_contract`], i.e. with the
+/// challenges of a [`LegacyTranscript`].
+pub fn prove<R: Rng + CryptoRng>(
+    z: Fr,
+    n: usize,
+    powers: &Powers<Bn254>,
+    rng: &mut R,
+) -> Result<Bn254RangeProof, CrateError> {
+    Bn254RangeProof::new_with_transcript(z, n, powers, LegacyTranscript::default(), rng)
+}
+
+/// Verifies a proof generated via [`prove`] with the same checks as the generated contract.
+pub fn verify(proof: &Bn254RangeProof, n: usize, powers: &Powers<Bn254>) -> Result<(), CrateError> {
+    proof.verify_with_transcript(n, powers, LegacyTranscript::default())
+}
+
+/// Returns the source of a `RangeProofVerifier{n}` contract that accepts proofs of
+/// `0 <= z < 2^n` generated with the given `powers`, where `n` is rounded up to a power of two.
+///
+/// The generated contract only provides the bit size and the trusted setup as constants to the
+/// abstract `RangeProofVerifier` in `contracts/RangeProofVerifier.sol`, which re-implements
+/// [`RangeProof::verify`] on top of the curve operations of `contracts/BN254.sol`. Since it
+/// recomputes the Fiat-Shamir challenges with the `keccak256` opcode, proofs must be generated
+/// via [`prove`].
+pub fn verifier_contract(n: usize, powers: &Powers<Bn254>) -> Result<String, CrateError> {
+    let domain = GeneralEvaluationDomain::<Fr>::new(n).ok_or(CrateError::InvalidFftDomain(n))?;
+
+    let mut prefix = Vec::new();
+    // same encoding as in the `LegacyTranscript` of the prover
+    (&PROOF_DOMAIN_SEP)
+        .serialize_compressed(&mut prefix)
+        .expect("should not fail");
+    n.to_le_bytes()
+        .serialize_compressed(&mut prefix)
+        .expect("should not fail");
+    domain
+        .group_gen()
+        .serialize_compressed(&mut prefix)
+        .expect("should not fail");
+
+    let tau_g2 = powers.g2_tau();
+    let replacements = [
+        ("{{DOMAIN_SIZE}}", domain.size().to_string()),
+        (
+            "{{LOG_DOMAIN_SIZE}}",
+            domain.log_size_of_group().to_string(),
+        ),
+        ("{{OMEGA}}", uint(domain.group_gen())),
+        ("{{OMEGA_LAST}}", uint(domain.group_gen_inv())),
+        ("{{TAU_G2_X_IM}}", uint(tau_g2.x.c1)),
+        ("{{TAU_G2_X_RE}}", uint(tau_g2.x.c0)),
+        ("{{TAU_G2_Y_IM}}", uint(tau_g2.y.c1)),
+        ("{{TAU_G2_Y_RE}}", uint(tau_g2.y.c0)),
+        ("{{TRANSCRIPT_PREFIX}}", hex(&prefix)),
+    ];
+
+    Ok(replacements
+        .iter()
+        .fold(TEMPLATE.to_string(), |source, (placeholder, value)| {
+            source.replace(placeholder, value)
+        }))
+}
+
+/// Encodes the proof as the big-endian `uint256[13]` argument of the contract's `verifyProof`
+/// function: `[g(rho), g(rho * omega), w_cap(rho), f, g, q, aggregate, shifted]`, where G1 points
+/// take two words each. The point at infinity is encoded as `(0, 0)`.
+pub fn encode_proof(proof: &Bn254RangeProof) -> [[u8; 32]; PROOF_WORDS] {
+    let mut words = [[0u8; 32]; PROOF_WORDS];
+    words[0] = word(proof.evaluations.g);
+    words[1] = word(proof.evaluations.g_omega);
+    words[2] = word(proof.evaluations.w_cap);
+    let points = [
+        proof.commitments.f,
+        proof.commitments.g,
+        proof.commitments.q,
+        proof.proofs.aggregate,
+        proof.proofs.shifted,
+    ];
+    for (i, point) in points.iter().enumerate() {
+        let (x, y) = point_words(point);
+        words[3 + 2 * i] = x;
+        words[4 + 2 * i] = y;
+    }
+    words
+}
+
+/// Returns the full calldata of a `verifyProof` call, i.e. the function selector followed by the
+/// [encoded proof](encode_proof).
+pub fn calldata(proof: &Bn254RangeProof) -> Vec<u8> {
+    let mut data = Keccak256::digest(VERIFY_SIGNATURE.as_bytes())[..4].to_vec();
+    encode_proof(proof)
+        .iter()
+        .for_each(|word| data.extend_from_slice(word));
+    data
+}
+
+fn point_words(point: &G1Affine) -> ([u8; 32], [u8; 32]) {
+    point
+        .xy()
+        .map(|(x, y)| (word(*x), word(*y)))
+        .unwrap_or_default()
+}
+
+fn word<F: PrimeField>(element: F) -> [u8; 32] {
+    let bytes = element.into_bigint().to_bytes_be();
+    let mut word = [0u8; 32];
+    word[32 - bytes.len()..].copy_from_slice(&bytes);
+    word
+}
+
+fn uint<F: PrimeField>(element: F) -> String {
+    format!("0x{}", hex(&word(element)))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::test_rng;
+    use ark_bn254::{Fq, G1Projective, G2Affine};
+    use ark_ec::pairing::Pairing;
+    use ark_ec::CurveGroup;
+    use ark_ff::{Field, One, Zero};
+    use ark_std::UniformRand;
+
+    const LOG_2_UPPER_BOUND: usize = 8;
+
+    // Executes the steps of the generated contract on the calldata words, mirroring its integer
+    // arithmetic as closely as possible.
+    fn contract_model(words: &[[u8; 32]; PROOF_WORDS], n: usize, powers: &Powers<Bn254>) -> bool {
+        let domain = GeneralEvaluationDomain::<Fr>::new(n).unwrap();
+        let scalar = |i: usize| Fr::from_be_bytes_mod_order(&words[i]);
+        let point = |i: usize| {
+            let (x, y) = (
+                Fq::from_be_bytes_mod_order(&words[i]),
+                Fq::from_be_bytes_mod_order(&words[i + 1]),
+            );
+            if x.is_zero() && y.is_zero() {
+                G1Affine::zero()
+            } else {
+                G1Affine::new_unchecked(x, y)
+            }
+        };
+        // compressed point encoding computed on the raw words
+        let compress = |i: usize| {
+            if words[i] == [0u8; 32] && words[i + 1] == [0u8; 32] {
+                let mut encoded = [0u8; 32];
+                encoded[31] = 0x40;
+                return encoded;
+            }
+            let mut encoded = words[i];
+            encoded.reverse();
+            let half_q = word(-Fq::one() / Fq::from(2u8));
+            if words[i + 1] > half_q {
+                encoded[31] |= 0x80;
+            }
+            encoded
+        };
+
+        let contract = verifier_contract(n, powers).unwrap();
+        let prefix_hex = contract
+            .split("return hex\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap();
+        let mut transcript: Vec<u8> = (0..prefix_hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&prefix_hex[i..i + 2], 16).unwrap())
+            .collect();
+        transcript.extend_from_slice(&compress(3));
+        transcript.extend_from_slice(&compress(5));
+        let mut challenge = |label: &[u8]| {
+            transcript.extend_from_slice(label);
+            let mut digest = Keccak256::digest(&transcript).to_vec();
+            digest.reverse();
+            Fr::from_be_bytes_mod_order(&digest)
+        };
+        let tau = challenge(b"tau");
+        let rho = challenge(b"rho");
+        let aggregation = challenge(b"aggregation_challenge");
+
+        let rho_n = (0..domain.log_size_of_group()).fold(rho, |acc, _| acc.square());
+        let rel2 = rho_n - Fr::one();
+        let d1 = rho - Fr::one();
+        let d2 = rho - domain.group_gen_inv();
+        let inv = (d1 * d2).inverse().unwrap();
+        let rel1 = rel2 * inv * d2;
+        let inv_shift = inv * d1;
+
+        let (g, g_omega, w_cap) = (scalar(0), scalar(1), scalar(2));
+        let w1 = g * rel1;
+        let w2 = g * (Fr::one() - g) * rel2 * inv_shift;
+        let a = g - Fr::from(2u8) * g_omega;
+        let w3 = a * (Fr::one() - a) * d2;
+        if w1 + tau * (w2 + tau * w3) != w_cap {
+            return false;
+        }
+
+        let w_cap_commitment = point(3) * rel1 + point(7) * rel2;
+        let commitment = point(5).into_group() + w_cap_commitment * aggregation;
+        let value = g + w_cap * aggregation;
+
+        let kzg_check = |commitment: G1Projective, value: Fr, at: Fr, proof: G1Affine| {
+            let lhs = commitment - G1Affine::generator() * value + proof * at;
+            Bn254::multi_pairing(
+                [lhs.into_affine(), (-proof.into_group()).into_affine()],
+                [G2Affine::generator(), powers.g2_tau()],
+            )
+            .is_zero()
+        };
+        kzg_check(commitment, value, rho, point(9))
+            && kzg_check(
+                point(5).into_group(),
+                g_omega,
+                rho * domain.group_gen(),
+                point(11),
+            )
+    }
+
+    #[test]
+    fn contract_is_generated_for_the_setup() {
+        let rng = &mut test_rng();
+        let tau = Fr::rand(rng);
+        let powers = Powers::<Bn254>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        let contract = verifier_contract(LOG_2_UPPER_BOUND, &powers).unwrap();
+        assert!(!contract.contains("{{"));
+        assert!(contract.contains("[0, 2^8)"));
+        assert!(contract.contains(&uint(powers.g2_tau().x.c1)));
+        // 8 bytes of length, 15 bytes of domain separator, 8 bytes of n and 32 bytes of omega
+        assert!(contract.contains(&format!("hex\"0f00000000000000{}", hex(b"fde range proof"))));
+    }
+
+    // Regenerates the verifier and a proof for the Foundry tests in `contracts/test`, set
+    // `FDE_UPDATE_FIXTURES` to write the file instead of comparing it.
+    #[test]
+    fn foundry_fixture_is_up_to_date() {
+        let rng = &mut test_rng();
+        let powers = Powers::<Bn254>::unsafe_setup(Fr::rand(rng), 4 * LOG_2_UPPER_BOUND);
+        let proof = prove(Fr::from(100u8), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+        assert!(verify(&proof, LOG_2_UPPER_BOUND, &powers).is_ok());
+
+        // the first element determines the type of the array literal
+        let words = encode_proof(&proof)
+            .iter()
+            .enumerate()
+            .map(|(i, word)| match i {
+                0 => format!("uint256(0x{})", hex(word)),
+                _ => format!("0x{}", hex(word)),
+            })
+            .collect::<Vec<_>>()
+            .join(",\n            ");
+        let fixture = format!(
+            "{}\n/// @notice A proof of 0 <= 100 < 2^8 generated by the Rust prover for the verifier above.\n\
+             library RangeProofFixture {{\n    \
+             function proof() internal pure returns (uint256[13] memory) {{\n        \
+             return [\n            {words}\n        ];\n    }}\n}}\n",
+            verifier_contract(LOG_2_UPPER_BOUND, &powers).unwrap(),
+        );
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/contracts/test/RangeProofFixture.sol"
+        );
+        if std::env::var_os("FDE_UPDATE_FIXTURES").is_some() {
+            std::fs::write(path, &fixture).unwrap();
+        }
+        assert_eq!(std::fs::read_to_string(path).unwrap(), fixture);
+    }
+
+    #[test]
+    fn calldata_encoding() {
+        let rng = &mut test_rng();
+        let tau = Fr::rand(rng);
+        let powers = Powers::<Bn254>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let proof = prove(Fr::from(100u8), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+
+        let data = calldata(&proof);
+        assert_eq!(data.len(), 4 + 32 * PROOF_WORDS);
+        // keccak256("verifyProof(uint256[13])")[..4]
+        assert_eq!(
+            data[..4],
+            Keccak256::digest(VERIFY_SIGNATURE.as_bytes())[..4]
+        );
+        assert_eq!(data[4..36], word(proof.evaluations.g));
+        let f = proof.commitments.f;
+        assert_eq!(data[100..132], word(f.x));
+        assert_eq!(data[132..164], word(f.y));
+    }
+
+    #[test]
+    fn contract_model_accepts_valid_proofs() {
+        let rng = &mut test_rng();
+        let tau = Fr::rand(rng);
+        let powers = Powers::<Bn254>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+
+        for z in [0u32, 1, 100, 255] {
+            let proof = prove(Fr::from(z), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+            assert!(verify(&proof, LOG_2_UPPER_BOUND, &powers).is_ok());
+            assert!(contract_model(
+                &encode_proof(&proof),
+                LOG_2_UPPER_BOUND,
+                &powers
+            ));
+        }
+    }
+
+    #[test]
+    fn contract_model_rejects_invalid_proofs() {
+        let rng = &mut test_rng();
+        let tau = Fr::rand(rng);
+        let powers = Powers::<Bn254>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let proof = prove(Fr::from(42u8), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+
+        // tampered evaluation
+        let mut words = encode_proof(&proof);
+        words[1] = word(proof.evaluations.g_omega + Fr::one());
+        assert!(!contract_model(&words, LOG_2_UPPER_BOUND, &powers));
+
+        // swapped witnesses
+        let mut words = encode_proof(&proof);
+        words.swap(9, 11);
+        words.swap(10, 12);
+        assert!(!contract_model(&words, LOG_2_UPPER_BOUND, &powers));
+
+        // different bit size
+        assert!(!contract_model(
+            &encode_proof(&proof),
+            2 * LOG_2_UPPER_BOUND,
+            &powers
+        ));
+
+        // proofs with the challenges of the default transcript
+        assert!(proof.verify(LOG_2_UPPER_BOUND, &powers).is_err());
+        let proof = Bn254RangeProof::new(Fr::from(42u8), LOG_2_UPPER_BOUND, &powers, rng).unwrap();
+        assert!(!contract_model(
+            &encode_proof(&proof),
+            LOG_2_UPPER_BOUND,
+            &powers
+        ));
+    }
+}
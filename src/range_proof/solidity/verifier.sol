@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.13;
+
+import { RangeProofVerifier } from "contracts/RangeProofVerifier.sol";
+import { Types } from "contracts/Types.sol";
+
+/// @notice Verifies `fde` range proofs over BN254 proving that the value committed to in `f` lies
+/// in [0, 2^{{DOMAIN_SIZE}}). Generated by `fde::range_proof::solidity`, do not edit by hand.
+contract RangeProofVerifier{{DOMAIN_SIZE}} is RangeProofVerifier {
+    function logDomainSize() internal pure override returns (uint256) {
+        return {{LOG_DOMAIN_SIZE}};
+    }
+
+    function omega() internal pure override returns (uint256, uint256) {
+        return ({{OMEGA}}, {{OMEGA_LAST}});
+    }
+
+    // tau * G2 from the trusted setup, coordinates are encoded as `x0 * i + x1`
+    function tauG2() internal pure override returns (Types.G2Point memory) {
+        return Types.G2Point({
+            x0: {{TAU_G2_X_IM}},
+            x1: {{TAU_G2_X_RE}},
+            y0: {{TAU_G2_Y_IM}},
+            y1: {{TAU_G2_Y_RE}}
+        });
+    }
+
+    function transcriptPrefix() internal pure override returns (bytes memory) {
+        return hex"{{TRANSCRIPT_PREFIX}}";
+    }
+}
@@ -6,9 +6,8 @@ use ark_std::Zero;
 
 // returns (rho^n - 1) / (rho - 1) and (rho^n - 1)
 pub fn rho_relations<S: PrimeField>(size: usize, rho: S) -> (S, S) {
-    let n_as_ref = S::from(size as u8).into_bigint();
     let one = S::one();
-    let rho_n_minus_1 = rho.pow(n_as_ref) - one;
+    let rho_n_minus_1 = rho.pow([size as u64]) - one;
     let rho_n_minus_1_by_rho_minus_1 = rho_n_minus_1 / (rho - one);
 
     (rho_n_minus_1_by_rho_minus_1, rho_n_minus_1)
@@ -31,7 +30,8 @@ pub fn w1_w2_w3_evals_sum<S: PrimeField>(
     let (rho_n_minus_1_by_rho_minus_1, rho_n_minus_1) = rho_relations(domain.size(), rho);
     let one = S::one();
     let two = S::from(2u8);
-    let w_n_minus_1 = domain.elements().last().unwrap();
+    // omega^{n - 1} = omega^{-1}
+    let w_n_minus_1 = domain.group_gen_inv();
     // w1_part
     let w1_eval = g_eval * rho_n_minus_1_by_rho_minus_1;
     // w2
@@ -0,0 +1,91 @@
+use super::{Error, RangeProof};
+use crate::Error as CrateError;
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_serialize::Valid;
+use digest::Digest;
+
+impl<C: Pairing, D: Digest> RangeProof<C, D> {
+    /// Checks that every point in the proof is on the curve and in the prime order subgroup and
+    /// that the proof is not degenerate.
+    ///
+    /// Proofs received from untrusted parties may contain invalid points that could break the
+    /// soundness of the pairing checks, hence this is called by every verification method.
+    ///
+    /// An honest prover blinds every commitment and witness, so none of them is the point at
+    /// infinity and no two of them are equal, except with negligible probability. Such proofs are
+    /// rejected before any pairing is computed, which prevents a malicious party from probing the
+    /// verifier with crafted trivial proofs.
+    pub fn validate(&self) -> Result<(), CrateError> {
+        let points = self.points();
+        if !points.iter().all(|point| point.check().is_ok()) {
+            return Err(Error::InvalidPoint.into());
+        }
+        if points.iter().any(|point| point.is_zero()) {
+            return Err(Error::IdentityPoint.into());
+        }
+        for (i, point) in points.iter().enumerate() {
+            if points[i + 1..].contains(point) {
+                return Err(Error::RepeatedPoint.into());
+            }
+        }
+        Ok(())
+    }
+
+    fn points(&self) -> [C::G1Affine; 5] {
+        [
+            self.commitments.f,
+            self.commitments.g,
+            self.commitments.q,
+            self.proofs.aggregate,
+            self.proofs.shifted,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commit::kzg::Powers;
+    use crate::tests::test_rng;
+    use crate::tests::{G1Affine, Scalar, TestCurve, TestHash};
+    use ark_std::UniformRand;
+
+    const LOG_2_UPPER_BOUND: usize = 8;
+
+    #[test]
+    fn degenerate_proofs_are_rejected() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let proof = RangeProof::<TestCurve, TestHash>::new(
+            Scalar::from(17u32),
+            LOG_2_UPPER_BOUND,
+            &powers,
+            rng,
+        )
+        .unwrap();
+        assert!(proof.validate().is_ok());
+
+        let mut invalid = proof.clone();
+        invalid.proofs.aggregate = G1Affine::zero();
+        assert_eq!(
+            invalid.verify(LOG_2_UPPER_BOUND, &powers),
+            Err(CrateError::RangeProof(Error::IdentityPoint))
+        );
+
+        let mut invalid = proof.clone();
+        invalid.commitments.q = invalid.commitments.f;
+        assert_eq!(
+            invalid.verify(LOG_2_UPPER_BOUND, &powers),
+            Err(CrateError::RangeProof(Error::RepeatedPoint))
+        );
+
+        let mut invalid = proof.clone();
+        invalid.proofs.shifted = invalid.proofs.aggregate;
+        assert_eq!(
+            invalid.verify(LOG_2_UPPER_BOUND, &powers),
+            Err(CrateError::RangeProof(Error::RepeatedPoint))
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Zero-knowledge proof that a set of Pedersen-style commitments hides values summing up to a
+//! public total.
+use crate::equality::Bases;
+use crate::hash::Hasher;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::PrimeField;
+use ark_std::marker::PhantomData;
+use ark_std::rand::{CryptoRng, Rng};
+use ark_std::UniformRand;
+use digest::Digest;
+
+const PROOF_DOMAIN_SEP: &[u8] = b"fde sum proof";
+
+/// Proof of knowledge of the discrete logarithm of the excess `sum(com_i) - total * g` with
+/// respect to `h`, which equals `sum(r_i) * h` for commitments `com_i = z_i * g + r_i * h` if and
+/// only if `sum(z_i) = total`.
+///
+/// Since the `f` commitments of range proofs with the same bit size share their bases (see
+/// [`RangeProof::commitment_bases`](crate::range_proof::RangeProof::commitment_bases)), this proof
+/// can be combined with per-value range proofs for balance-conservation statements.
+#[derive(Clone, Debug)]
+pub struct Proof<C: CurveGroup, D> {
+    pub challenge: C::ScalarField,
+    pub claim: C::ScalarField,
+    _digest: PhantomData<D>,
+}
+
+impl<C, D> Proof<C, D>
+where
+    C: CurveGroup,
+    D: Digest,
+{
+    /// Proves that the commitments generated with the given `blindings` sum up to `total`.
+    pub fn new<R: Rng + CryptoRng>(
+        blindings: &[C::ScalarField],
+        total: &C::ScalarField,
+        bases: Bases<C>,
+        rng: &mut R,
+    ) -> Self {
+        let blinding_sum: C::ScalarField = blindings.iter().sum();
+        let excess = bases.h * blinding_sum;
+
+        let rand = C::ScalarField::rand(rng);
+        let k = bases.h * rand;
+
+        let challenge = Self::challenge(&bases, total, &excess, &k);
+        let claim = rand - challenge * blinding_sum;
+
+        Self {
+            challenge,
+            claim,
+            _digest: PhantomData,
+        }
+    }
+
+    pub fn verify(
+        &self,
+        commitments: &[C::Affine],
+        total: &C::ScalarField,
+        bases: Bases<C>,
+    ) -> bool {
+        let commitment_sum: C = commitments.iter().map(|c| c.into_group()).sum();
+        let excess = commitment_sum - bases.g * total;
+        let k = bases.h * self.claim + excess * self.challenge;
+
+        let challenge = Self::challenge(&bases, total, &excess, &k);
+
+        challenge == self.challenge
+    }
+
+    fn challenge(bases: &Bases<C>, total: &C::ScalarField, excess: &C, k: &C) -> C::ScalarField {
+        let mut hasher = Hasher::<D>::new();
+        hasher.update(&PROOF_DOMAIN_SEP);
+        hasher.update(&bases.g);
+        hasher.update(&bases.h);
+        hasher.update(total);
+        hasher.update(excess);
+        hasher.update(k);
+        C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commit::kzg::Powers;
+    use crate::range_proof::RangeProof;
+    use crate::tests::test_rng;
+    use crate::tests::{Scalar, TestCurve, TestHash};
+    use ark_ec::pairing::Pairing;
+    use ark_std::UniformRand;
+
+    const LOG_2_UPPER_BOUND: usize = 8;
+
+    type SumProof = Proof<<TestCurve as Pairing>::G1, TestHash>;
+
+    #[test]
+    fn sum_of_range_proven_values() {
+        let rng = &mut test_rng();
+        let tau = Scalar::rand(rng);
+        let powers = Powers::<TestCurve>::unsafe_setup(tau, 4 * LOG_2_UPPER_BOUND);
+        let bases = RangeProof::<TestCurve, TestHash>::commitment_bases(LOG_2_UPPER_BOUND, &powers)
+            .unwrap();
+
+        let values = [12u32, 200, 43].map(Scalar::from);
+        let blindings: Vec<Scalar> = (0..values.len()).map(|_| Scalar::rand(rng)).collect();
+        let commitments: Vec<_> = values
+            .iter()
+            .zip(&blindings)
+            .map(|(&z, &r)| {
+                let proof = RangeProof::<TestCurve, TestHash>::new_with_blinding(
+                    z,
+                    r,
+                    LOG_2_UPPER_BOUND,
+                    &powers,
+                    rng,
+                )
+                .unwrap();
+                assert!(proof.verify(LOG_2_UPPER_BOUND, &powers).is_ok());
+                proof.commitments.f
+            })
+            .collect();
+
+        let total = Scalar::from(255u32);
+        let proof = SumProof::new(&blindings, &total, bases, rng);
+        assert!(proof.verify(&commitments, &total, bases));
+
+        // invalid total
+        assert!(!proof.verify(&commitments, &Scalar::from(254u32), bases));
+        // missing commitment
+        assert!(!proof.verify(&commitments[1..], &total, bases));
+        // proof generated for an invalid total
+        let proof = SumProof::new(&blindings, &Scalar::from(256u32), bases, rng);
+        assert!(!proof.verify(&commitments, &Scalar::from(256u32), bases));
+    }
+}
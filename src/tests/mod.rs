@@ -2,7 +2,9 @@ pub use ark_bls12_381::{Bls12_381 as TestCurve, G1Affine};
 use ark_ec::pairing::Pairing;
 use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
+use ark_std::rand::{rngs::StdRng, SeedableRng};
 use criterion as _;
+use serde_json as _;
 pub use sha3::Keccak256 as TestHash;
 
 pub const N: usize = Scalar::MODULUS_BIT_SIZE as usize / crate::encrypt::elgamal::MAX_BITS + 1;
@@ -10,6 +12,16 @@ pub const N: usize = Scalar::MODULUS_BIT_SIZE as usize / crate::encrypt::elgamal
 pub type Scalar = <TestCurve as Pairing>::ScalarField;
 pub type UniPoly = DensePolynomial<Scalar>;
 
+/// Deterministic RNG seeded like [`ark_std::test_rng`], but satisfying the `CryptoRng` bound of
+/// key, nonce and proof generation.
+pub fn test_rng() -> StdRng {
+    let seed = [
+        1, 0, 0, 0, 23, 0, 0, 0, 200, 1, 0, 0, 210, 30, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0,
+    ];
+    StdRng::from_seed(seed)
+}
+
 /*
 pub type Elgamal = crate::encrypt::elgamal::ExponentialElgamal<<BlsCurve as Pairing>::G1>;
 pub type ElgamalEncryptionProof = crate::veck::kzg_elgamal::EncryptionProof<{ N }, BlsCurve, Keccak256>;
@@ -0,0 +1,368 @@
+//! Fiat-Shamir transcripts shared by the non-interactive protocols of the crate. A [`Transcript`]
+//! absorbs labeled prover messages and squeezes challenges bound to everything absorbed so far,
+//! see [`PoseidonTranscript`], the digest transcripts of [`Hasher`](crate::hash::Hasher) and
+//! [`StreamingHasher`](crate::hash::StreamingHasher) and the `MerlinTranscript` of the `merlin`
+//! feature.
+use ark_crypto_primitives::sponge::poseidon::{
+    find_poseidon_ark_and_mds, PoseidonConfig, PoseidonSponge,
+};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_serialize::CanonicalSerialize;
+use ark_std::sync::Arc;
+use ark_std::Zero;
+#[cfg(feature = "std")]
+use ark_std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+};
+#[cfg(feature = "std")]
+use std::sync::{OnceLock, RwLock};
+
+// x^5 S-box with 8 full and 57 partial rounds, i.e. 128-bit security for width 3 over fields of
+// about 255 bits
+const POSEIDON_ALPHA: u64 = 5;
+const POSEIDON_FULL_ROUNDS: usize = 8;
+const POSEIDON_PARTIAL_ROUNDS: usize = 57;
+const POSEIDON_RATE: usize = 2;
+
+#[cfg(feature = "std")]
+type ConfigCache = RwLock<BTreeMap<TypeId, Arc<dyn Any + Send + Sync>>>;
+
+#[cfg(feature = "std")]
+static POSEIDON_CONFIGS: OnceLock<ConfigCache> = OnceLock::new();
+
+pub trait Transcript {
+    /// Absorbs `message` under `label`.
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+
+    /// Absorbs the compressed serialization of `value` under `label`.
+    fn append<T: CanonicalSerialize>(&mut self, label: &'static [u8], value: &T) {
+        let mut message = Vec::new();
+        value
+            .serialize_compressed(&mut message)
+            .expect("should not fail");
+        self.append_message(label, &message);
+    }
+
+    /// Absorbs the curve point `point` under `label`, by default in its compressed serialization.
+    fn append_point<A: AffineRepr>(&mut self, label: &'static [u8], point: &A) {
+        self.append(label, point);
+    }
+
+    /// Absorbs the field element `scalar` under `label`, by default in its serialization.
+    fn append_scalar<S: PrimeField>(&mut self, label: &'static [u8], scalar: &S) {
+        self.append(label, scalar);
+    }
+
+    /// Absorbs `value` under `label`, by default as 8 little-endian bytes.
+    fn append_u64(&mut self, label: &'static [u8], value: u64) {
+        self.append(label, &value.to_le_bytes());
+    }
+
+    /// Fills `dest` with challenge bytes bound to `label` and everything absorbed so far.
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]);
+
+    /// Squeezes a challenge scalar from 64 challenge bytes, thus its bias is negligible.
+    fn challenge_scalar<F: PrimeField>(&mut self, label: &'static [u8]) -> F {
+        let mut bytes = [0u8; 64];
+        self.challenge_bytes(label, &mut bytes);
+        F::from_le_bytes_mod_order(&bytes)
+    }
+}
+
+/// [Merlin](https://merlin.cool) transcript of the `merlin` crate, which absorbs labels together
+/// with the lengths of the messages, thus the encoding is unambiguous.
+#[cfg(feature = "merlin")]
+pub type MerlinTranscript = merlin::Transcript;
+
+#[cfg(feature = "merlin")]
+impl Transcript for merlin::Transcript {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        merlin::Transcript::append_message(self, label, message);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        merlin::Transcript::challenge_bytes(self, label, dest);
+    }
+}
+
+/// Transcript over a Poseidon sponge, whose challenges in `F` can be recomputed cheaply inside
+/// SNARK circuits over `F`.
+///
+/// Byte messages and labels are absorbed in the length-prefixed packing of [`Absorb`] for byte
+/// slices, and field elements via [`Self::append_field_elements`] without conversion. Points are
+/// absorbed as their affine coordinates followed by the infinity flag, and integers as single
+/// field elements. Elements of other prime fields are absorbed natively if their modulus does not
+/// exceed the one of `F`, and as little-endian limbs of `(F::MODULUS_BIT_SIZE - 1) / 8` bytes
+/// otherwise. Challenge scalars in `F` are squeezed as native field elements, any other challenges
+/// are derived from squeezed bits.
+#[derive(Clone)]
+pub struct PoseidonTranscript<F: PrimeField> {
+    sponge: PoseidonSponge<F>,
+}
+
+impl<F: PrimeField + Absorb> PoseidonTranscript<F> {
+    /// Starts a transcript for the protocol `label`.
+    pub fn new(config: &PoseidonConfig<F>, label: &'static [u8]) -> Self {
+        let mut transcript = Self {
+            sponge: PoseidonSponge::new(config),
+        };
+        transcript.append_message(b"dom-sep", label);
+        transcript
+    }
+
+    /// Width 3 configuration with the `x^5` S-box and round constants generated as in the
+    /// Poseidon reference implementation. With the `std` feature, the constants are generated once
+    /// per field and shared by all later calls, otherwise they are generated on every call.
+    ///
+    /// # Panics
+    ///
+    /// If `x^5` is not a permutation of `F`, i.e. `p = 1 mod 5`.
+    #[cfg(feature = "std")]
+    pub fn default_config() -> Arc<PoseidonConfig<F>> {
+        let cache = POSEIDON_CONFIGS.get_or_init(Default::default);
+        let cached = cache
+            .read()
+            .expect("config cache poisoned")
+            .get(&TypeId::of::<F>())
+            .cloned();
+        let config = match cached {
+            Some(config) => config,
+            None => cache
+                .write()
+                .expect("config cache poisoned")
+                .entry(TypeId::of::<F>())
+                .or_insert_with(|| Arc::new(Self::generate_config()))
+                .clone(),
+        };
+        config.downcast().expect("keyed by the field type")
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn default_config() -> Arc<PoseidonConfig<F>> {
+        Arc::new(Self::generate_config())
+    }
+
+    fn generate_config() -> PoseidonConfig<F> {
+        let modulus_mod_alpha = F::MODULUS.to_bytes_be().iter().fold(0u64, |acc, &byte| {
+            (acc * 256 + byte as u64) % POSEIDON_ALPHA
+        });
+        assert_ne!(
+            modulus_mod_alpha, 1,
+            "x^5 is not a permutation of the field"
+        );
+        let (ark, mds) = find_poseidon_ark_and_mds::<F>(
+            F::MODULUS_BIT_SIZE as u64,
+            POSEIDON_RATE,
+            POSEIDON_FULL_ROUNDS as u64,
+            POSEIDON_PARTIAL_ROUNDS as u64,
+            0,
+        );
+        PoseidonConfig::new(
+            POSEIDON_FULL_ROUNDS,
+            POSEIDON_PARTIAL_ROUNDS,
+            POSEIDON_ALPHA,
+            mds,
+            ark,
+            POSEIDON_RATE,
+            1,
+        )
+    }
+
+    /// Absorbs native field elements under `label`.
+    pub fn append_field_elements(&mut self, label: &'static [u8], elements: &[F]) {
+        self.sponge.absorb(&label);
+        self.sponge.absorb(&elements);
+    }
+
+    // maps an element of any prime field to elements of `F`, see the type documentation
+    fn to_native<B: PrimeField>(element: &B) -> Vec<F> {
+        let bytes = element.into_bigint().to_bytes_le();
+        if B::MODULUS_BIT_SIZE < F::MODULUS_BIT_SIZE
+            || B::MODULUS.to_bytes_le() == F::MODULUS.to_bytes_le()
+        {
+            return vec![F::from_le_bytes_mod_order(&bytes)];
+        }
+        let limb_size = (F::MODULUS_BIT_SIZE as usize - 1) / 8;
+        bytes
+            .chunks(limb_size)
+            .map(F::from_le_bytes_mod_order)
+            .collect()
+    }
+}
+
+impl<F: PrimeField + Absorb> Transcript for PoseidonTranscript<F> {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.sponge.absorb(&label);
+        self.sponge.absorb(&message);
+    }
+
+    fn append_point<A: AffineRepr>(&mut self, label: &'static [u8], point: &A) {
+        let zero = A::BaseField::zero();
+        let (x, y) = point.xy().unwrap_or((&zero, &zero));
+        let mut elements: Vec<F> = x
+            .to_base_prime_field_elements()
+            .chain(y.to_base_prime_field_elements())
+            .flat_map(|element| Self::to_native(&element))
+            .collect();
+        elements.push(F::from(point.is_zero()));
+        self.append_field_elements(label, &elements);
+    }
+
+    fn append_scalar<S: PrimeField>(&mut self, label: &'static [u8], scalar: &S) {
+        self.append_field_elements(label, &Self::to_native(scalar));
+    }
+
+    fn append_u64(&mut self, label: &'static [u8], value: u64) {
+        self.append_field_elements(label, &[F::from(value)]);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.sponge.absorb(&label);
+        dest.copy_from_slice(&self.sponge.squeeze_bytes(dest.len()));
+    }
+
+    fn challenge_scalar<S: PrimeField>(&mut self, label: &'static [u8]) -> S {
+        self.sponge.absorb(&label);
+        self.sponge.squeeze_field_elements::<S>(1)[0]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hash::{Hasher, StreamingHasher};
+    use crate::tests::test_rng;
+    use crate::tests::{G1Affine, Scalar, TestHash};
+    use ark_crypto_primitives::sponge::FieldBasedCryptographicSponge;
+    use ark_ec::AffineRepr;
+    use ark_std::UniformRand;
+
+    #[cfg(feature = "merlin")]
+    #[test]
+    fn merlin_transcript() {
+        use crate::hex;
+
+        // test vector of the merlin crate, through the `Transcript` trait
+        let mut transcript = MerlinTranscript::new(b"test protocol");
+        Transcript::append_message(&mut transcript, b"some label", b"some data");
+        let mut challenge = [0u8; 32];
+        Transcript::challenge_bytes(&mut transcript, b"challenge", &mut challenge);
+        assert_eq!(
+            hex::encode(&challenge),
+            "d5a21972d0d5fe320c0d263fac7fffb8145aa640af6e9bca177c03c7efcf0615"
+        );
+
+        // challenges depend on labels and order
+        let challenge = |first: &'static [u8], second: &'static [u8]| {
+            let mut transcript = MerlinTranscript::new(b"test protocol");
+            Transcript::append_message(&mut transcript, first, &[7u8; 1000]);
+            transcript.append(second, &42u64);
+            transcript.challenge_scalar::<Scalar>(b"challenge")
+        };
+        assert_eq!(challenge(b"a", b"b"), challenge(b"a", b"b"));
+        assert_ne!(challenge(b"a", b"b"), challenge(b"b", b"a"));
+    }
+
+    #[test]
+    fn digest_transcripts() {
+        // digest transcripts agree with each other
+        let data = vec![7u8; 1000];
+        let mut streaming = StreamingHasher::<TestHash>::new();
+        let mut transcript = Hasher::<TestHash>::new();
+        streaming.append(b"data", &data);
+        transcript.append(b"data", &data);
+        assert_eq!(
+            streaming.challenge_scalar::<Scalar>(b"challenge"),
+            transcript.challenge_scalar::<Scalar>(b"challenge")
+        );
+        let mut bytes = [[0u8; 80]; 2];
+        streaming.challenge_bytes(b"bytes", &mut bytes[0]);
+        transcript.challenge_bytes(b"bytes", &mut bytes[1]);
+        assert_eq!(bytes[0], bytes[1]);
+        assert_ne!(bytes[0][..32], bytes[0][32..64]);
+
+        // the digest transcripts separate messages and labels by their lengths
+        let challenge = |messages: [(&'static [u8], &[u8]); 2]| {
+            let mut transcript = Hasher::<TestHash>::new();
+            for (label, message) in messages {
+                transcript.append_message(label, message);
+            }
+            transcript.challenge_scalar::<Scalar>(b"challenge")
+        };
+        let expected = challenge([(b"m", b"a"), (b"m", b"bc")]);
+        assert_eq!(challenge([(b"m", b"a"), (b"m", b"bc")]), expected);
+        assert_ne!(challenge([(b"m", b"ab"), (b"m", b"c")]), expected);
+        assert_ne!(challenge([(b"m", b"a"), (b"n", b"bc")]), expected);
+        assert_ne!(challenge([(b"m", b"a"), (b"mb", b"c")]), expected);
+    }
+
+    #[test]
+    fn poseidon_transcript() {
+        let rng = &mut test_rng();
+        let config = PoseidonTranscript::<Scalar>::default_config();
+        #[cfg(feature = "std")]
+        assert!(Arc::ptr_eq(
+            &config,
+            &PoseidonTranscript::<Scalar>::default_config()
+        ));
+        let elements = [Scalar::rand(rng), Scalar::rand(rng)];
+        let point = G1Affine::generator();
+        let mut transcript = PoseidonTranscript::new(&config, b"test protocol");
+        transcript.append_field_elements(b"elements", &elements);
+        transcript.append_point(b"point", &point);
+        transcript.append_point(b"infinity", &G1Affine::zero());
+        transcript.append_scalar(b"scalar", &elements[0]);
+        transcript.append_u64(b"integer", 42);
+        transcript.append(b"bytes", &point);
+        let challenge: Scalar = transcript.challenge_scalar(b"challenge");
+
+        // the encoding a circuit has to follow, coordinates of the 381-bit base field are split
+        // into 31-byte limbs
+        let limbs = |coordinate: &<G1Affine as AffineRepr>::BaseField| {
+            let bytes = coordinate.into_bigint().to_bytes_le();
+            [&bytes[..31], &bytes[31..]].map(Scalar::from_le_bytes_mod_order)
+        };
+        let (x, y) = point.xy().unwrap();
+        let [x_0, x_1] = limbs(x);
+        let [y_0, y_1] = limbs(y);
+        let zero = Scalar::from(0u8);
+        let mut point_bytes = Vec::new();
+        point.serialize_compressed(&mut point_bytes).unwrap();
+        let mut sponge = PoseidonSponge::new(&config);
+        sponge.absorb(&b"dom-sep".as_slice());
+        sponge.absorb(&b"test protocol".as_slice());
+        sponge.absorb(&b"elements".as_slice());
+        sponge.absorb(&elements.as_slice());
+        sponge.absorb(&b"point".as_slice());
+        sponge.absorb(&[x_0, x_1, y_0, y_1, zero].as_slice());
+        sponge.absorb(&b"infinity".as_slice());
+        sponge.absorb(&[zero, zero, zero, zero, Scalar::from(1u8)].as_slice());
+        sponge.absorb(&b"scalar".as_slice());
+        sponge.absorb(&[elements[0]].as_slice());
+        sponge.absorb(&b"integer".as_slice());
+        sponge.absorb(&[Scalar::from(42u8)].as_slice());
+        sponge.absorb(&b"bytes".as_slice());
+        sponge.absorb(&point_bytes.as_slice());
+        sponge.absorb(&b"challenge".as_slice());
+        assert_eq!(sponge.squeeze_native_field_elements(1)[0], challenge);
+
+        // bound to labels and previous challenges
+        let mut other = PoseidonTranscript::new(&config, b"test protocol");
+        other.append_field_elements(b"other", &elements);
+        other.append_point(b"point", &point);
+        assert_ne!(other.challenge_scalar::<Scalar>(b"challenge"), challenge);
+        assert_ne!(
+            transcript.challenge_scalar::<Scalar>(b"challenge"),
+            challenge
+        );
+
+        // non-native challenges
+        let mut bytes = [0u8; 80];
+        transcript.challenge_bytes(b"bytes", &mut bytes);
+        assert_ne!(bytes[..40], bytes[40..]);
+        let _: ark_secp256k1::Fr = transcript.challenge_scalar(b"foreign");
+    }
+}
@@ -1,10 +1,11 @@
 use crate::commit::kzg::Powers;
-use crate::encrypt::elgamal::{Cipher, ExponentialElgamal as Elgamal, SplitScalar, MAX_BITS};
+use crate::encrypt::elgamal::{
+    Cipher, CipherError, ExponentialElgamal as Elgamal, SplitScalar, MAX_BITS,
+};
 use crate::encrypt::EncryptionEngine;
 use crate::range_proof::RangeProof;
 use ark_ec::pairing::Pairing;
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
 use digest::Digest;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -39,7 +40,7 @@ impl<const N: usize, C: Pairing, D: Clone + Digest> Default for EncryptionProof<
 }
 
 impl<const N: usize, C: Pairing, D: Clone + Digest + Send + Sync> EncryptionProof<N, C, D> {
-    pub fn new<R: Rng + Send + Sync>(
+    pub fn new<R: Rng + CryptoRng + Send + Sync>(
         evaluations: &[C::ScalarField],
         encryption_pk: &<Elgamal<C::G1> as EncryptionEngine>::EncryptionKey,
         powers: &Powers<C>,
@@ -61,7 +62,7 @@ impl<const N: usize, C: Pairing, D: Clone + Digest + Send + Sync> EncryptionProo
         proof
     }
 
-    fn append<R: Rng>(
+    fn append<R: Rng + CryptoRng>(
         mut self,
         eval: &C::ScalarField,
         encryption_pk: &<Elgamal<C::G1> as EncryptionEngine>::EncryptionKey,
@@ -78,8 +79,8 @@ impl<const N: usize, C: Pairing, D: Clone + Digest + Send + Sync> EncryptionProo
             encryption_pk,
             &rand,
         );
-        self.random_encryption_points
-            .push((C::G1Affine::generator() * rand).into_affine());
+        // the first component of the cipher is `rand * g`
+        self.random_encryption_points.push(cipher.c0());
         self.ciphers.push(cipher);
         self.short_ciphers.push(sc);
         self.range_proofs.push(rp);
@@ -120,6 +121,14 @@ impl<const N: usize, C: Pairing, D: Clone + Digest + Send + Sync> EncryptionProo
         }
     }
 
+    /// Checks every cipher and short cipher via [`Cipher::validate`].
+    pub fn validate(&self) -> Result<(), CipherError> {
+        self.ciphers
+            .iter()
+            .chain(self.short_ciphers.iter().flatten())
+            .try_for_each(Cipher::validate)
+    }
+
     /// Checks that the sum of split scalars evaluate to the encrypted value via the homomorphic
     /// properties of Elgamal encryption.
     pub fn verify_split_scalars(&self) -> bool {
@@ -169,9 +178,10 @@ impl<const N: usize, C: Pairing, D: Clone + Digest + Send + Sync> EncryptionProo
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::encrypt::elgamal::KeyPair;
+    use crate::tests::test_rng;
     use crate::tests::*;
-    use ark_ec::Group;
-    use ark_std::{test_rng, UniformRand};
+    use ark_std::UniformRand;
 
     const DATA_SIZE: usize = 16;
 
@@ -183,11 +193,11 @@ mod test {
         let tau = Scalar::rand(rng); // "secret" tau
         let powers = Powers::<TestCurve>::unsafe_setup(tau, (DATA_SIZE + 1).max(MAX_BITS * 4)); // generate powers of tau size DATA_SIZE
 
-        let encryption_sk = Scalar::rand(rng);
-        let encryption_pk = (<TestCurve as Pairing>::G1::generator() * encryption_sk).into_affine();
+        let encryption_keys = KeyPair::<<TestCurve as Pairing>::G1>::generate(rng);
 
         let data: Vec<Scalar> = (0..DATA_SIZE).map(|_| Scalar::rand(rng)).collect();
-        let mut encryption_proof = ElgamalEncryptionProof::new(&data, &encryption_pk, &powers, rng);
+        let mut encryption_proof =
+            ElgamalEncryptionProof::new(&data, encryption_keys.pk(), &powers, rng);
 
         assert!(encryption_proof.verify_split_scalars());
         assert!(encryption_proof.verify_range_proofs(&powers));
@@ -3,6 +3,7 @@ pub use encryption::EncryptionProof;
 
 use crate::commit::kzg::{Kzg, Powers};
 use crate::dleq::Proof as DleqProof;
+use crate::encrypt::elgamal::CipherError;
 use crate::hash::Hasher;
 use crate::Error as CrateError;
 use ark_ec::pairing::Pairing;
@@ -13,7 +14,7 @@ use ark_poly::univariate::DensePolynomial;
 use ark_poly::EvaluationDomain;
 use ark_poly::Polynomial;
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
 use digest::Digest;
 
 use thiserror::Error as ErrorT;
@@ -30,6 +31,8 @@ pub enum Error {
     InvalidSplitScalars,
     #[error("invalid range proofs")]
     InvalidRangeProofs,
+    #[error(transparent)]
+    InvalidCipher(#[from] CipherError),
 }
 
 pub struct Proof<const N: usize, C: Pairing, D: Clone + Digest> {
@@ -47,7 +50,7 @@ where
     C: Pairing,
     D: Digest + Clone + Send + Sync,
 {
-    pub fn new<R: Rng>(
+    pub fn new<R: Rng + CryptoRng>(
         f_poly: &DensePolynomial<C::ScalarField>,
         f_s_poly: &DensePolynomial<C::ScalarField>,
         encryption_sk: &C::ScalarField,
@@ -68,8 +71,8 @@ where
         // challenge and KZG proof
         let challenge = C::ScalarField::from_le_bytes_mod_order(&hasher.finalize());
         let challenge_eval = f_s_poly.evaluate(&challenge);
-        let challenge_opening_proof = Kzg::proof(f_s_poly, challenge, challenge_eval, powers);
-        let challenge_eval_commitment = (C::G1Affine::generator() * challenge_eval).into_affine();
+        let challenge_opening_proof = Kzg::proof(f_s_poly, challenge, challenge_eval, powers)?;
+        let challenge_eval_commitment = C::G1Affine::generator() * challenge_eval;
 
         // NOTE According to the docs this should always return Some((q, rem)), so unwrap is fine
         // https://docs.rs/ark-poly/latest/src/ark_poly/polynomial/univariate/dense.rs.html#144
@@ -78,7 +81,7 @@ where
             .unwrap()
             .0;
         // subset polynomial KZG commitment
-        let com_f_q_poly = powers.commit_g1(&f_q_poly).into();
+        let com_f_q_poly = powers.commit_g1(&f_q_poly)?;
 
         // DLEQ proof
         let lagrange_evaluations = &domain.evaluate_all_lagrange_coefficients(challenge);
@@ -87,19 +90,15 @@ where
             lagrange_evaluations,
         );
 
-        let dleq_proof = DleqProof::new(
-            encryption_sk,
-            q_point.into_affine(),
-            C::G1Affine::generator(),
-            rng,
-        );
+        let points = C::G1::normalize_batch(&[challenge_eval_commitment, com_f_q_poly, q_point]);
+        let dleq_proof = DleqProof::new(encryption_sk, points[2], C::G1Affine::generator(), rng);
 
         Ok(Self {
             encryption_proof,
-            challenge_eval_commitment,
+            challenge_eval_commitment: points[0],
             challenge_opening_proof,
             dleq_proof,
-            com_f_q_poly,
+            com_f_q_poly: points[1],
             _poly: PhantomData,
             _digest: PhantomData,
         })
@@ -112,6 +111,7 @@ where
         encryption_pk: C::G1Affine,
         powers: &Powers<C>,
     ) -> Result<(), CrateError> {
+        self.encryption_proof.validate().map_err(Error::from)?;
         let mut hasher = Hasher::<D>::new();
         let c1_points: Vec<C::G1Affine> = self
             .encryption_proof
@@ -130,7 +130,7 @@ where
 
         // polynomial division check via vanishing polynomial
         let vanishing_poly = DensePolynomial::from(domain.vanishing_polynomial());
-        let com_vanishing_poly = powers.commit_g2(&vanishing_poly);
+        let com_vanishing_poly = powers.commit_g2(&vanishing_poly)?;
         let subset_pairing_check = Kzg::<C>::pairing_check(
             com_f_poly - com_f_s_poly,
             self.com_f_q_poly.into_group(),
@@ -184,11 +184,11 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::encrypt::elgamal::MAX_BITS;
+    use crate::encrypt::elgamal::{KeyPair, MAX_BITS};
+    use crate::tests::test_rng;
     use crate::tests::*;
-    use ark_ec::Group;
     use ark_poly::Evaluations;
-    use ark_std::{test_rng, UniformRand};
+    use ark_std::UniformRand;
 
     const DATA_SIZE: usize = 16;
     const SUBSET_SIZE: usize = 8;
@@ -204,12 +204,12 @@ mod test {
         let powers = Powers::<TestCurve>::unsafe_setup(tau, (DATA_SIZE + 1).max(MAX_BITS * 4)); // generate powers of tau size DATA_SIZE
 
         // Server's (elphemeral?) encryption key for this session
-        let encryption_sk = Scalar::rand(rng);
-        let encryption_pk = (<TestCurve as Pairing>::G1::generator() * encryption_sk).into_affine();
+        let encryption_keys = KeyPair::<<TestCurve as Pairing>::G1>::generate(rng);
 
         // Generate random data and public inputs (encrypted data, etc)
         let data: Vec<Scalar> = (0..DATA_SIZE).map(|_| Scalar::rand(rng)).collect();
-        let encryption_proof = ElgamalEncryptionProof::new(&data, &encryption_pk, &powers, rng);
+        let encryption_proof =
+            ElgamalEncryptionProof::new(&data, encryption_keys.pk(), &powers, rng);
 
         assert!(encryption_proof.verify_range_proofs(&powers));
 
@@ -220,7 +220,7 @@ mod test {
         // This is performed only once by the server
         let evaluations = Evaluations::from_vec_and_domain(data, domain);
         let f_poly: UniPoly = evaluations.interpolate_by_ref();
-        let com_f_poly = powers.commit_g1(&f_poly);
+        let com_f_poly = powers.commit_g1(&f_poly).unwrap();
 
         // get subdomain with size suitable for interpolating a polynomial with SUBSET_SIZE
         // coefficients
@@ -229,21 +229,21 @@ mod test {
         let subset_evaluations =
             crate::veck::subset_evals(&evaluations, &subset_indices, subdomain);
         let f_s_poly: UniPoly = subset_evaluations.interpolate_by_ref();
-        let com_f_s_poly = powers.commit_g1(&f_s_poly);
+        let com_f_s_poly = powers.commit_g1(&f_s_poly).unwrap();
 
         let sub_encryption_proof = encryption_proof.subset(&subset_indices);
 
         let proof = KzgElgamalProof::new(
             &f_poly,
             &f_s_poly,
-            &encryption_sk,
+            encryption_keys.sk(),
             sub_encryption_proof,
             &powers,
             rng,
         )
         .unwrap();
         assert!(proof
-            .verify(com_f_poly, com_f_s_poly, encryption_pk, &powers)
+            .verify(com_f_poly, com_f_s_poly, *encryption_keys.pk(), &powers)
             .is_ok());
     }
 }
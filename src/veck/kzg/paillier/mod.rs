@@ -14,7 +14,7 @@ use ark_ff::fields::PrimeField;
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
 use ark_std::marker::PhantomData;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
 use ark_std::One;
 use digest::Digest;
 use num_bigint::BigUint;
@@ -45,7 +45,7 @@ pub struct Proof<C: Pairing, D> {
 
 impl<C: Pairing, D: Digest> Proof<C, D> {
     #[allow(clippy::too_many_arguments)]
-    pub fn new<R: Rng>(
+    pub fn new<R: Rng + CryptoRng>(
         values: &[BigUint],
         f_poly: &DensePolynomial<C::ScalarField>,
         f_s_poly: &DensePolynomial<C::ScalarField>,
@@ -182,10 +182,11 @@ impl<C: Pairing, D: Digest> Proof<C, D> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::tests::test_rng;
     use crate::tests::*;
     use ark_ff::BigInteger;
     use ark_poly::Evaluations;
-    use ark_std::{test_rng, UniformRand};
+    use ark_std::UniformRand;
 
     const DATA_SIZE: usize = 16;
     const SUBSET_SIZE: usize = 16;
@@ -1,6 +1,6 @@
 use super::N_BITS;
 use ark_std::rand::distributions::Distribution;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
 use num_bigint::{BigUint, RandomBits};
 
 pub struct RandomParameters {
@@ -10,7 +10,7 @@ pub struct RandomParameters {
 }
 
 impl RandomParameters {
-    pub fn new<R: Rng>(size: usize, rng: &mut R) -> Self {
+    pub fn new<R: Rng + CryptoRng>(size: usize, rng: &mut R) -> Self {
         let mut u_vec = Vec::with_capacity(size);
         let mut s_vec = Vec::with_capacity(size);
         let mut r_vec = Vec::with_capacity(size);
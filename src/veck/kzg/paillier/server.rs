@@ -1,6 +1,6 @@
 use super::N_BITS;
 use ark_std::rand::distributions::Distribution;
-use ark_std::rand::Rng;
+use ark_std::rand::{CryptoRng, Rng};
 use ark_std::One;
 use num_bigint::{BigUint, RandomBits};
 use num_integer::Integer;
@@ -17,7 +17,7 @@ pub struct Server {
 }
 
 impl Server {
-    pub fn new<R: Rng>(rng: &mut R) -> Self {
+    pub fn new<R: Rng + CryptoRng>(rng: &mut R) -> Self {
         // generate small enough primes so that their product fits
         // "N_BITS" number of bits
         let (p, q) = primes(N_BITS >> 1, rng);
@@ -50,7 +50,7 @@ impl Server {
     }
 }
 
-fn primes<R: Rng>(n_bits: u64, rng: &mut R) -> (BigUint, BigUint) {
+fn primes<R: Rng + CryptoRng>(n_bits: u64, rng: &mut R) -> (BigUint, BigUint) {
     let random_bits = RandomBits::new(n_bits);
     let target_p: BigUint = random_bits.sample(rng);
     let target_q: BigUint = random_bits.sample(rng);
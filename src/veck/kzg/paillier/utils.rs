@@ -66,8 +66,8 @@ mod test {
     use super::super::server::Server;
     use super::super::N_BITS;
     use super::*;
+    use crate::tests::test_rng;
     use ark_std::rand::distributions::Distribution;
-    use ark_std::test_rng;
     use num_bigint::RandomBits;
 
     #[test]
@@ -97,7 +97,8 @@ fn subset_evals_zero_pad<S: FftField>(
 mod test {
     use super::*;
     use crate::tests::{BlsCurve, Scalar};
-    use ark_std::{test_rng, UniformRand};
+    use ark_std::UniformRand;
+    use crate::tests::test_rng;
 
     const DATA_SIZE: usize = 4;
     const SUBSET_SIZE: usize = 2;